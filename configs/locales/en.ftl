@@ -31,3 +31,18 @@ error-internal = An internal server error occurred. Please try again later.
 ## Code: 5001
 error-database = A database error occurred: { $message }
 
+## Code: 5003
+error-service_unavailable = The service is temporarily unavailable. Please retry later.
+
+## Code: 4012
+error-precondition_failed = The { $resource } has been modified by someone else (current version: { $version }). Reload it and try again.
+
+## Code: 4028
+error-precondition_required = This request requires an If-Match header carrying the resource ETag.
+
+## Code: 4109
+error-reference_conflict = The record is still referenced by other records.
+
+## Code: 4209
+error-record_not_updated = The record was changed or removed by someone else. Reload it and try again.
+
@@ -0,0 +1,93 @@
+# 财务健康报告的说明文字（手工维护，不由 ftl-codegen 生成）
+
+health-ratio-roe = Return on equity
+health-ratio-roa = Return on assets
+health-ratio-roic = Return on invested capital
+health-ratio-gross_margin = Gross margin
+health-ratio-operating_margin = Operating margin
+health-ratio-net_margin = Net margin
+health-ratio-debt_to_equity = Debt to equity
+health-ratio-debt_to_assets = Liabilities to assets
+health-ratio-current_ratio = Current ratio
+health-ratio-quick_ratio = Quick ratio
+health-ratio-asset_turnover = Asset turnover
+health-ratio-inventory_turnover = Inventory turnover
+health-ratio-receivables_turnover = Receivables turnover
+
+health-unavailable = Not enough data to compute this score.
+health-missing = Missing inputs: { $items }.
+health-component-missing = Not enough data.
+
+health-piotroski_f-healthy = F-Score { $score } of 9: strong and improving fundamentals.
+health-piotroski_f-caution = F-Score { $score } of 9: mixed fundamentals.
+health-piotroski_f-risk = F-Score { $score } of 9: weak or deteriorating fundamentals.
+health-piotroski_f-roa_positive = Return on assets is { $value }, { $passed ->
+        [true] positive
+       *[false] not positive
+    }.
+health-piotroski_f-cfo_positive = Operating cash flow to assets is { $value }, { $passed ->
+        [true] positive
+       *[false] not positive
+    }.
+health-piotroski_f-roa_improved = Return on assets { $passed ->
+        [true] improved
+       *[false] did not improve
+    } to { $value }.
+health-piotroski_f-accruals = Operating cash flow { $passed ->
+        [true] exceeds
+       *[false] does not exceed
+    } net income (cash flow to assets { $value }).
+health-piotroski_f-leverage_decreased = Long-term debt to assets { $passed ->
+        [true] did not increase
+       *[false] increased
+    } ({ $value }).
+health-piotroski_f-current_ratio_improved = Current ratio { $passed ->
+        [true] improved
+       *[false] did not improve
+    } to { $value }.
+health-piotroski_f-no_dilution = Shares outstanding { $passed ->
+        [true] did not increase
+       *[false] increased
+    } (change { $value }).
+health-piotroski_f-gross_margin_improved = Gross margin { $passed ->
+        [true] improved
+       *[false] did not improve
+    } to { $value }.
+health-piotroski_f-asset_turnover_improved = Asset turnover { $passed ->
+        [true] improved
+       *[false] did not improve
+    } to { $value }.
+
+health-altman_z-healthy = Z-Score { $score } is in the safe zone ({ $model ->
+        [public] above 2.99
+       *[private] book-value model, above 2.9
+    }).
+health-altman_z-caution = Z-Score { $score } is in the grey zone ({ $model ->
+        [public] 1.81 to 2.99
+       *[private] book-value model, 1.23 to 2.9
+    }).
+health-altman_z-risk = Z-Score { $score } is in the distress zone ({ $model ->
+        [public] below 1.81
+       *[private] book-value model, below 1.23
+    }).
+health-altman_z-working_capital = Working capital to total assets: { $value }.
+health-altman_z-retained_earnings = Retained earnings to total assets: { $value }.
+health-altman_z-ebit = EBIT to total assets: { $value }.
+health-altman_z-equity_to_liabilities = { $model ->
+        [public] Market value
+       *[private] Book value
+    } of equity to total liabilities: { $value }.
+health-altman_z-sales = Sales to total assets: { $value }.
+
+health-beneish_m-healthy = M-Score { $score } is below -2.22: earnings manipulation is unlikely.
+health-beneish_m-caution = M-Score { $score } is between -2.22 and -1.78: some signs of earnings manipulation.
+health-beneish_m-risk = M-Score { $score } is above -1.78: earnings manipulation is likely.
+health-beneish_m-missing = Not enough data, a neutral value of 1 was used.
+health-beneish_m-dsri = Days sales in receivables index: { $value }, above 1 means receivables grew faster than sales.
+health-beneish_m-gmi = Gross margin index: { $value }, above 1 means margins deteriorated.
+health-beneish_m-aqi = Asset quality index: { $value }, above 1 means more assets with uncertain future benefit.
+health-beneish_m-sgi = Sales growth index: { $value }.
+health-beneish_m-depi = Depreciation index: { $value }, above 1 means depreciation slowed down.
+health-beneish_m-sgai = Selling and administrative expense index: { $value }.
+health-beneish_m-lvgi = Leverage index: { $value }, above 1 means leverage increased.
+health-beneish_m-tata = Total accruals to total assets: { $value }, higher means earnings are less backed by cash.
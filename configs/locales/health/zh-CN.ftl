@@ -0,0 +1,93 @@
+# 财务健康报告的说明文字（手工维护，不由 ftl-codegen 生成）
+
+health-ratio-roe = 净资产收益率
+health-ratio-roa = 总资产收益率
+health-ratio-roic = 投入资本回报率
+health-ratio-gross_margin = 毛利率
+health-ratio-operating_margin = 营业利润率
+health-ratio-net_margin = 净利率
+health-ratio-debt_to_equity = 有息负债权益比
+health-ratio-debt_to_assets = 资产负债率
+health-ratio-current_ratio = 流动比率
+health-ratio-quick_ratio = 速动比率
+health-ratio-asset_turnover = 总资产周转率
+health-ratio-inventory_turnover = 存货周转率
+health-ratio-receivables_turnover = 应收账款周转率
+
+health-unavailable = 数据不足，无法计算该评分。
+health-missing = 缺少输入：{ $items }。
+health-component-missing = 数据不足。
+
+health-piotroski_f-healthy = F-Score 为 { $score }（满分 9）：基本面强劲且在改善。
+health-piotroski_f-caution = F-Score 为 { $score }（满分 9）：基本面好坏参半。
+health-piotroski_f-risk = F-Score 为 { $score }（满分 9）：基本面较弱或在恶化。
+health-piotroski_f-roa_positive = 总资产收益率为 { $value }，{ $passed ->
+        [true] 为正
+       *[false] 不为正
+    }。
+health-piotroski_f-cfo_positive = 经营现金流与总资产之比为 { $value }，{ $passed ->
+        [true] 为正
+       *[false] 不为正
+    }。
+health-piotroski_f-roa_improved = 总资产收益率{ $passed ->
+        [true] 提高
+       *[false] 未提高
+    }，为 { $value }。
+health-piotroski_f-accruals = 经营现金流{ $passed ->
+        [true] 高于
+       *[false] 不高于
+    }净利润（经营现金流与总资产之比 { $value }）。
+health-piotroski_f-leverage_decreased = 长期借款与总资产之比{ $passed ->
+        [true] 未上升
+       *[false] 上升
+    }（{ $value }）。
+health-piotroski_f-current_ratio_improved = 流动比率{ $passed ->
+        [true] 提高
+       *[false] 未提高
+    }，为 { $value }。
+health-piotroski_f-no_dilution = 总股本{ $passed ->
+        [true] 未增加
+       *[false] 增加
+    }（变动 { $value }）。
+health-piotroski_f-gross_margin_improved = 毛利率{ $passed ->
+        [true] 提高
+       *[false] 未提高
+    }，为 { $value }。
+health-piotroski_f-asset_turnover_improved = 总资产周转率{ $passed ->
+        [true] 提高
+       *[false] 未提高
+    }，为 { $value }。
+
+health-altman_z-healthy = Z-Score 为 { $score }，处于安全区（{ $model ->
+        [public] 高于 2.99
+       *[private] 账面价值模型，高于 2.9
+    }）。
+health-altman_z-caution = Z-Score 为 { $score }，处于灰色区（{ $model ->
+        [public] 1.81 至 2.99
+       *[private] 账面价值模型，1.23 至 2.9
+    }）。
+health-altman_z-risk = Z-Score 为 { $score }，处于困境区（{ $model ->
+        [public] 低于 1.81
+       *[private] 账面价值模型，低于 1.23
+    }）。
+health-altman_z-working_capital = 营运资本与总资产之比：{ $value }。
+health-altman_z-retained_earnings = 未分配利润与总资产之比：{ $value }。
+health-altman_z-ebit = 息税前利润与总资产之比：{ $value }。
+health-altman_z-equity_to_liabilities = { $model ->
+        [public] 股权市值
+       *[private] 股东权益账面价值
+    }与总负债之比：{ $value }。
+health-altman_z-sales = 营业收入与总资产之比：{ $value }。
+
+health-beneish_m-healthy = M-Score 为 { $score }，低于 -2.22：盈余操纵的可能性低。
+health-beneish_m-caution = M-Score 为 { $score }，介于 -2.22 与 -1.78 之间：存在一定的盈余操纵迹象。
+health-beneish_m-risk = M-Score 为 { $score }，高于 -1.78：存在盈余操纵的可能。
+health-beneish_m-missing = 数据不足，按中性值 1 计算。
+health-beneish_m-dsri = 应收账款周转天数指数：{ $value }，大于 1 表示应收账款增长快于收入。
+health-beneish_m-gmi = 毛利率指数：{ $value }，大于 1 表示毛利率下降。
+health-beneish_m-aqi = 资产质量指数：{ $value }，大于 1 表示未来收益不确定的资产占比上升。
+health-beneish_m-sgi = 营业收入增长指数：{ $value }。
+health-beneish_m-depi = 折旧率指数：{ $value }，大于 1 表示折旧放缓。
+health-beneish_m-sgai = 销售及管理费用指数：{ $value }。
+health-beneish_m-lvgi = 杠杆指数：{ $value }，大于 1 表示杠杆上升。
+health-beneish_m-tata = 应计项目与总资产之比：{ $value }，越高表示利润的现金支撑越弱。
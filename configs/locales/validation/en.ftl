@@ -0,0 +1,67 @@
+# 字段级校验消息（手工维护，不由 ftl-codegen 生成）
+
+validation-required = This field is required.
+validation-required_for_asset_type = This field is required for asset type { $asset_type }.
+validation-asset_type = Unknown asset type "{ $value }", expected one of: { $expected }.
+validation-currency_code = Must be an alphanumeric currency code.
+validation-positive = Must be greater than 0.
+validation-non_negative = Must not be negative.
+validation-date = Must be a date in YYYY-MM-DD format.
+validation-date_order = Must not be earlier than { $other }.
+validation-json_object = Must be a JSON object.
+validation-option_right = Must be either "call" or "put".
+validation-sort_field = Unknown sort field "{ $value }", expected one of: { $expected }.
+validation-sort_order = Sort direction must be "asc" or "desc", got "{ $value }".
+validation-alias_source = Must contain only lowercase letters, digits and underscores.
+validation-identifier = Not a valid { $kind } identifier.
+validation-date_span = Range must not exceed { $max } days.
+validation-calendar_coverage = Holidays are only available for { $years }.
+validation-resample_scan = Resampling to { $interval } would scan more than { $max } bars, narrow start/end or lower limit.
+validation-timezone = Unknown IANA timezone "{ $value }".
+validation-trading_hours = Sessions must be HH:MM ranges (24:00 allowed) with close after open and must not overlap.
+validation-import_path = Must be a relative path inside the import directory.
+validation-ts_format = Unknown timestamp format "{ $value }", expected auto, rfc3339, unix_s, unix_ms, unix_us, unix_ns or a strftime pattern.
+validation-file_format = Cannot infer the file format from the extension, specify csv or parquet.
+validation-import_open = Cannot open the file: { $error }
+validation-import_read = Failed to read the file: { $error }
+validation-import_csv_header = Invalid CSV header: { $error }
+validation-import_parquet = Invalid Parquet file: { $error }
+validation-import_columns = Missing column(s): { $columns }.
+validation-import_record = Unreadable row: { $error }
+validation-number = Must be a finite number, got "{ $value }".
+validation-integer = Must be an integer, got "{ $value }".
+validation-timestamp = Not a valid timestamp: "{ $value }".
+validation-timestamp_range = Timestamp { $value } is out of range.
+validation-local_time = { $value } does not exist in timezone { $timezone }.
+validation-bar_high = Must not be lower than the open, close or low price.
+validation-bar_low = Must not be higher than the open or close price.
+validation-instrument_source = Specify either instrument_id or exchange together with columns.symbol.
+validation-book_levels = Levels must be [price, qty] pairs with price greater than 0 and qty not negative.
+validation-required_for_action = This field is required for { $kind }.
+validation-split_ratio = Must be greater than 1 for a split and less than 1 for a reverse split.
+validation-metric_name = Must start with a lowercase letter and contain only lowercase letters, digits and underscores.
+validation-unknown_metric = Unknown metric "{ $value }", register it in the metric catalogue first.
+validation-line_item = Unknown line item "{ $value }" for { $statement_type } statements.
+validation-type = Invalid type or format.
+validation-json_syntax = The request body is not valid JSON.
+validation-reference_missing = A referenced record does not exist.
+validation-json_content_type = Expected a request with Content-Type: application/json.
+validation-path_param = Must be a valid { $expected }, got "{ $value }".
+validation-body = The request body could not be read.
+validation-body_too_large = The request body is too large.
+validation-multipart = Malformed multipart/form-data body.
+validation-import_format = Cannot determine the data format, pass format=csv or format=ndjson.
+validation-import_rows = Must contain at most { $max } data rows.
+validation-import_duplicate = Duplicate of row { $row }.
+validation-statement_mismatch = Must have the same period_end and currency as row { $row } of the same statement.
+validation-statement_skipped = Statement skipped because rows { $rows } are invalid.
+validation-invalid = Invalid value.
+validation-length = Length must be between { $min } and { $max }.
+validation-length_min = Length must be at least { $min }.
+validation-length_max = Length must be at most { $max }.
+validation-length_equal = Length must be exactly { $equal }.
+validation-range = Must be between { $min } and { $max }.
+validation-range_min = Must be at least { $min }.
+validation-range_max = Must be at most { $max }.
+validation-email = Must be a valid email address.
+validation-url = Must be a valid URL.
@@ -0,0 +1,67 @@
+# 字段级校验消息（手工维护，不由 ftl-codegen 生成）
+
+validation-required = 该字段为必填项。
+validation-required_for_asset_type = 资产类型 { $asset_type } 必须提供该字段。
+validation-asset_type = 未知的资产类型 "{ $value }"，可选值：{ $expected }。
+validation-currency_code = 必须为字母或数字组成的币种代码。
+validation-positive = 必须大于 0。
+validation-non_negative = 不能为负数。
+validation-date = 必须为 YYYY-MM-DD 格式的日期。
+validation-date_order = 不能早于 { $other }。
+validation-json_object = 必须为 JSON 对象。
+validation-option_right = 必须为 "call" 或 "put"。
+validation-sort_field = 未知的排序字段 "{ $value }"，可选值：{ $expected }。
+validation-sort_order = 排序方向必须为 "asc" 或 "desc"，实际为 "{ $value }"。
+validation-alias_source = 只能包含小写字母、数字和下划线。
+validation-identifier = 不是合法的 { $kind } 标识符。
+validation-date_span = 范围不能超过 { $max } 天。
+validation-calendar_coverage = 休市安排仅收录 { $years } 年。
+validation-resample_scan = 重采样为 { $interval } 需要读取超过 { $max } 根 K 线，请缩小 start/end 或减小 limit。
+validation-timezone = 未知的 IANA 时区 "{ $value }"。
+validation-trading_hours = 交易时段须为 HH:MM 区间（允许 24:00），收盘晚于开盘且互不重叠。
+validation-import_path = 必须为导入目录内的相对路径。
+validation-ts_format = 未知的时间格式 "{ $value }"，可选 auto、rfc3339、unix_s、unix_ms、unix_us、unix_ns 或 strftime 格式。
+validation-file_format = 无法根据扩展名推断文件格式，请指定 csv 或 parquet。
+validation-import_open = 无法打开文件：{ $error }
+validation-import_read = 读取文件失败：{ $error }
+validation-import_csv_header = CSV 表头无效：{ $error }
+validation-import_parquet = Parquet 文件无效：{ $error }
+validation-import_columns = 缺少列：{ $columns }。
+validation-import_record = 无法读取该行：{ $error }
+validation-number = 必须为有限的数值，实际为 "{ $value }"。
+validation-integer = 必须为整数，实际为 "{ $value }"。
+validation-timestamp = 无效的时间："{ $value }"。
+validation-timestamp_range = 时间戳 { $value } 超出范围。
+validation-local_time = { $value } 在时区 { $timezone } 中不存在。
+validation-bar_high = 不能低于开盘价、收盘价或最低价。
+validation-bar_low = 不能高于开盘价或收盘价。
+validation-instrument_source = 须指定 instrument_id，或同时指定 exchange 与 columns.symbol。
+validation-book_levels = 档位须为 [价格, 数量]，价格大于 0，数量不能为负。
+validation-required_for_action = 类型 { $kind } 必须提供该字段。
+validation-split_ratio = 拆股比例须大于 1，合股比例须小于 1。
+validation-metric_name = 须以小写字母开头，只能包含小写字母、数字和下划线。
+validation-unknown_metric = 未知指标 "{ $value }"，请先在指标目录中登记。
+validation-line_item = { $statement_type } 报表中没有科目 "{ $value }"。
+validation-type = 类型或格式不正确。
+validation-json_syntax = 请求体不是合法的 JSON。
+validation-reference_missing = 引用的记录不存在。
+validation-json_content_type = 请求头 Content-Type 须为 application/json。
+validation-path_param = 须为合法的 { $expected }，实际为 "{ $value }"。
+validation-body = 无法读取请求体。
+validation-body_too_large = 请求体过大。
+validation-multipart = multipart/form-data 请求体格式错误。
+validation-import_format = 无法确定数据格式，请传入 format=csv 或 format=ndjson。
+validation-import_rows = 数据行不能超过 { $max } 行。
+validation-import_duplicate = 与第 { $row } 行重复。
+validation-statement_mismatch = period_end 与 currency 必须与同一报表的第 { $row } 行一致。
+validation-statement_skipped = 第 { $rows } 行无效，整份报表未导入。
+validation-invalid = 取值无效。
+validation-length = 长度必须在 { $min } 到 { $max } 之间。
+validation-length_min = 长度不能小于 { $min }。
+validation-length_max = 长度不能大于 { $max }。
+validation-length_equal = 长度必须为 { $equal }。
+validation-range = 取值必须在 { $min } 到 { $max } 之间。
+validation-range_min = 取值不能小于 { $min }。
+validation-range_max = 取值不能大于 { $max }。
+validation-email = 必须为合法的邮箱地址。
+validation-url = 必须为合法的 URL。
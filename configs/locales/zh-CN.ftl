@@ -31,3 +31,18 @@ error-internal = 服务器内部错误，请稍后再试。
 ## Code: 5001
 error-database = 数据库错误: { $message }
 
+## Code: 5003
+error-service_unavailable = 服务暂时不可用，请稍后重试。
+
+## Code: 4012
+error-precondition_failed = 资源 { $resource } 已被他人修改（当前版本：{ $version }），请重新获取后再试。
+
+## Code: 4028
+error-precondition_required = 该请求需要携带包含资源 ETag 的 If-Match 请求头。
+
+## Code: 4109
+error-reference_conflict = 记录仍被其他数据引用。
+
+## Code: 4209
+error-record_not_updated = 记录已被他人修改或删除，请重新获取后再试。
+
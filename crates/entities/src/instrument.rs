@@ -1,19 +1,177 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
 
+use super::sea_orm_active_enums::AssetType;
+use super::sea_orm_active_enums::InstrumentStatus;
 use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue::Set;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
 #[sea_orm(table_name = "instrument")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub exchange: String,
+    pub exchange_id: i32,
     pub symbol: String,
-    pub asset_type: String,
+    pub asset_type: AssetType,
     pub name: String,
+    pub base_currency: Option<String>,
+    pub quote_currency: Option<String>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub tick_size: Option<f64>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub lot_size: Option<f64>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub min_notional: Option<f64>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub contract_multiplier: Option<f64>,
+    pub listing_date: Option<Date>,
+    pub delisting_date: Option<Date>,
+    pub status: InstrumentStatus,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub metadata: Json,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub created_by: Option<String>,
+    /// 乐观锁版本号，每次更新加 1
+    pub version: i32,
+    /// 中文名称的拼音（`首字母 全拼`），保存时由名称生成，供搜索使用
+    pub name_pinyin: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::exchange::Entity",
+        from = "Column::ExchangeId",
+        to = "super::exchange::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Exchange,
+    #[sea_orm(has_many = "super::corporate_action::Entity")]
+    CorporateAction,
+    #[sea_orm(has_many = "super::data_quality_issue::Entity")]
+    DataQualityIssue,
+    #[sea_orm(has_many = "super::feature_metric::Entity")]
+    FeatureMetric,
+    #[sea_orm(has_many = "super::financial_statement::Entity")]
+    FinancialStatement,
+    #[sea_orm(has_many = "super::instrument_alias::Entity")]
+    InstrumentAlias,
+    #[sea_orm(has_many = "super::instrument_history::Entity")]
+    InstrumentHistory,
+    #[sea_orm(has_many = "super::kline::Entity")]
+    Kline,
+    #[sea_orm(has_many = "super::order_book::Entity")]
+    OrderBook,
+    #[sea_orm(has_many = "super::trade::Entity")]
+    Trade,
+}
+
+impl Related<super::exchange::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Exchange.def()
+    }
+}
+
+impl Related<super::corporate_action::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CorporateAction.def()
+    }
+}
+
+impl Related<super::data_quality_issue::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DataQualityIssue.def()
+    }
+}
+
+impl Related<super::feature_metric::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FeatureMetric.def()
+    }
+}
+
+impl Related<super::financial_statement::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FinancialStatement.def()
+    }
+}
+
+impl Related<super::instrument_alias::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InstrumentAlias.def()
+    }
+}
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::instrument_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::InstrumentHistory.def()
+    }
+}
+
+impl Related<super::kline::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Kline.def()
+    }
+}
+
+impl Related<super::order_book::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OrderBook.def()
+    }
+}
+
+impl Related<super::trade::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trade.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// 写入时维护审计字段：插入时补齐 `created_at`、`version`，每次保存刷新 `updated_at` 及 `name_pinyin`
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let now = chrono::Utc::now().fixed_offset();
+        if insert && self.created_at.is_not_set() {
+            self.created_at = Set(now);
+        }
+        if insert && self.version.is_not_set() {
+            self.version = Set(1);
+        }
+        self.updated_at = Set(now);
+        if let Some(name) = self.name.try_as_ref() {
+            self.name_pinyin = Set(name_pinyin(name));
+        }
+        Ok(self)
+    }
+}
+
+/// 名称中含汉字时返回 `首字母 全拼`（小写，其余字母数字原样保留），如 `贵州茅台` -> `gzmt guizhoumaotai`
+pub fn name_pinyin(name: &str) -> Option<String> {
+    use pinyin::ToPinyin;
+
+    let mut initials = String::new();
+    let mut full = String::new();
+    let mut has_han = false;
+    for (ch, pinyin) in name.chars().zip(name.to_pinyin()) {
+        match pinyin {
+            Some(pinyin) => {
+                has_han = true;
+                initials.push_str(pinyin.first_letter());
+                full.push_str(pinyin.plain());
+            }
+            None if ch.is_alphanumeric() => {
+                initials.extend(ch.to_lowercase());
+                full.extend(ch.to_lowercase());
+            }
+            None => {}
+        }
+    }
+    has_han.then(|| format!("{initials} {full}"))
+}
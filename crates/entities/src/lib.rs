@@ -2,4 +2,17 @@
 
 pub mod prelude;
 
+pub mod corporate_action;
+pub mod data_quality_issue;
+pub mod exchange;
+pub mod feature_metric;
+pub mod financial_statement;
+pub mod financial_statement_item;
 pub mod instrument;
+pub mod instrument_alias;
+pub mod instrument_history;
+pub mod kline;
+pub mod metric_definition;
+pub mod order_book;
+pub mod sea_orm_active_enums;
+pub mod trade;
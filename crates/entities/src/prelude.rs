@@ -1,3 +1,15 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
 
+pub use super::corporate_action::Entity as CorporateAction;
+pub use super::data_quality_issue::Entity as DataQualityIssue;
+pub use super::exchange::Entity as Exchange;
+pub use super::feature_metric::Entity as FeatureMetric;
+pub use super::financial_statement::Entity as FinancialStatement;
+pub use super::financial_statement_item::Entity as FinancialStatementItem;
 pub use super::instrument::Entity as Instrument;
+pub use super::instrument_alias::Entity as InstrumentAlias;
+pub use super::instrument_history::Entity as InstrumentHistory;
+pub use super::kline::Entity as Kline;
+pub use super::metric_definition::Entity as MetricDefinition;
+pub use super::order_book::Entity as OrderBook;
+pub use super::trade::Entity as Trade;
@@ -1,6 +1,21 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20261018_000001_create_exchange_table;
+mod m20261018_000002_link_instrument_exchange;
+mod m20261018_000003_add_instrument_contract_spec;
+mod m20261018_000004_normalize_instrument_asset_type;
+mod m20261018_000005_add_instrument_indexes;
+mod m20261018_000006_add_instrument_audit_and_history;
+mod m20261018_000007_add_instrument_version;
+mod m20261018_000008_create_instrument_alias_table;
+mod m20261018_000009_add_instrument_search;
+mod m20261018_000010_create_kline_table;
+mod m20261018_000011_create_data_quality_issue_table;
+mod m20261018_000012_create_trade_and_order_book_tables;
+mod m20261018_000013_create_corporate_action_table;
+mod m20261018_000014_create_feature_metric_tables;
+mod m20261018_000015_create_financial_statement_tables;
 
 pub struct Migrator;
 
@@ -9,6 +24,21 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261018_000001_create_exchange_table::Migration),
+            Box::new(m20261018_000002_link_instrument_exchange::Migration),
+            Box::new(m20261018_000003_add_instrument_contract_spec::Migration),
+            Box::new(m20261018_000004_normalize_instrument_asset_type::Migration),
+            Box::new(m20261018_000005_add_instrument_indexes::Migration),
+            Box::new(m20261018_000006_add_instrument_audit_and_history::Migration),
+            Box::new(m20261018_000007_add_instrument_version::Migration),
+            Box::new(m20261018_000008_create_instrument_alias_table::Migration),
+            Box::new(m20261018_000009_add_instrument_search::Migration),
+            Box::new(m20261018_000010_create_kline_table::Migration),
+            Box::new(m20261018_000011_create_data_quality_issue_table::Migration),
+            Box::new(m20261018_000012_create_trade_and_order_book_tables::Migration),
+            Box::new(m20261018_000013_create_corporate_action_table::Migration),
+            Box::new(m20261018_000014_create_feature_metric_tables::Migration),
+            Box::new(m20261018_000015_create_financial_statement_tables::Migration),
         ]
     }
 }
@@ -0,0 +1,45 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Exchange::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Exchange::Id))
+                    .col(string_len_uniq(Exchange::Code, 32))
+                    .col(string(Exchange::Name))
+                    .col(string_len(Exchange::Timezone, 64).default("UTC"))
+                    .col(string_len_null(Exchange::Country, 64))
+                    // 资产类别列表，如 ["stock", "etf"]
+                    .col(json_binary(Exchange::AssetClasses))
+                    // 交易时段列表，如 [{"open": "09:30", "close": "11:30"}]
+                    .col(json_binary(Exchange::TradingHours))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Exchange::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub(crate) enum Exchange {
+    Table,
+    Id,
+    Code,
+    Name,
+    Timezone,
+    Country,
+    AssetClasses,
+    TradingHours,
+}
@@ -0,0 +1,135 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+use crate::m20261018_000001_create_exchange_table::Exchange;
+
+/// 将 `instrument.exchange` 自由文本列迁移为指向 `exchange` 表的外键 `exchange_id`。
+///
+/// SQLite 不支持给已有表追加外键，因此统一采用“建新表 → 拷贝数据 → 删旧表 → 改名”的方式重建 `instrument`。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        // 1. 把已有的交易所字符串（去空白、转大写后）去重写入 exchange 表
+        let empty_json = json_literal(backend, "[]");
+        db.execute_unprepared(&format!(
+            "INSERT INTO exchange (code, name, timezone, asset_classes, trading_hours) \
+             SELECT DISTINCT UPPER(TRIM(i.exchange)), UPPER(TRIM(i.exchange)), 'UTC', {empty_json}, {empty_json} \
+             FROM instrument i \
+             WHERE NOT EXISTS (SELECT 1 FROM exchange e WHERE e.code = UPPER(TRIM(i.exchange)))"
+        ))
+        .await?;
+
+        // 2. 以外键形式重建 instrument 表
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstrumentNew::Table)
+                    .col(pk_auto(Instrument::Id))
+                    .col(integer(Instrument::ExchangeId))
+                    .col(string(Instrument::Symbol))
+                    .col(string(Instrument::AssetType))
+                    .col(string_null(Instrument::Name))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_instrument_exchange")
+                            .from(InstrumentNew::Table, Instrument::ExchangeId)
+                            .to(Exchange::Table, Exchange::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            "INSERT INTO instrument_new (id, exchange_id, symbol, asset_type, name) \
+             SELECT i.id, e.id, i.symbol, i.asset_type, i.name \
+             FROM instrument i JOIN exchange e ON e.code = UPPER(TRIM(i.exchange))",
+        )
+        .await?;
+
+        swap_tables(manager).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstrumentNew::Table)
+                    .col(pk_auto(Instrument::Id))
+                    .col(string(Instrument::Exchange))
+                    .col(string(Instrument::Symbol))
+                    .col(string(Instrument::AssetType))
+                    .col(string_null(Instrument::Name))
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            "INSERT INTO instrument_new (id, exchange, symbol, asset_type, name) \
+             SELECT i.id, e.code, i.symbol, i.asset_type, i.name \
+             FROM instrument i JOIN exchange e ON e.id = i.exchange_id",
+        )
+        .await?;
+
+        swap_tables(manager).await
+    }
+}
+
+/// 用 instrument_new 替换 instrument，并修正 Postgres 自增序列
+async fn swap_tables(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    manager
+        .drop_table(Table::drop().table(Instrument::Table).to_owned())
+        .await?;
+    manager
+        .rename_table(
+            Table::rename()
+                .table(InstrumentNew::Table, Instrument::Table)
+                .to_owned(),
+        )
+        .await?;
+
+    // 显式写入 id 后 Postgres 的序列不会前进，需要手动对齐到 MAX(id)
+    if manager.get_database_backend() == DbBackend::Postgres {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "SELECT setval(pg_get_serial_sequence('instrument', 'id'), \
+                 COALESCE((SELECT MAX(id) FROM instrument), 0) + 1, false)",
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// 生成 JSON 字面量：Postgres 需要显式转换为 jsonb，SQLite 以文本存储
+fn json_literal(backend: DbBackend, value: &str) -> String {
+    match backend {
+        DbBackend::Postgres => format!("'{value}'::jsonb"),
+        _ => format!("'{value}'"),
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+    Exchange,
+    ExchangeId,
+    Symbol,
+    AssetType,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum InstrumentNew {
+    Table,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 为 `instrument` 增加合约规格、生命周期状态以及扩展元数据列。
+///
+/// SQLite 的 ALTER TABLE 一次只能增加一列，因此逐列执行。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = [
+            string_len_null(Instrument::BaseCurrency, 10),
+            string_len_null(Instrument::QuoteCurrency, 10),
+            double_null(Instrument::TickSize),
+            double_null(Instrument::LotSize),
+            double_null(Instrument::MinNotional),
+            double_null(Instrument::ContractMultiplier),
+            date_null(Instrument::ListingDate),
+            date_null(Instrument::DelistingDate),
+            // active | suspended | delisted
+            string_len(Instrument::Status, 16).default("active").to_owned(),
+            // Postgres 下为 JSONB，SQLite 下以文本存储
+            json_binary(Instrument::Metadata).default("{}").to_owned(),
+        ];
+
+        for mut column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Instrument::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = [
+            Instrument::BaseCurrency,
+            Instrument::QuoteCurrency,
+            Instrument::TickSize,
+            Instrument::LotSize,
+            Instrument::MinNotional,
+            Instrument::ContractMultiplier,
+            Instrument::ListingDate,
+            Instrument::DelistingDate,
+            Instrument::Status,
+            Instrument::Metadata,
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Instrument::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    BaseCurrency,
+    QuoteCurrency,
+    TickSize,
+    LotSize,
+    MinNotional,
+    ContractMultiplier,
+    ListingDate,
+    DelistingDate,
+    Status,
+    Metadata,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+/// `instrument.asset_type` 取值（与 `entities::sea_orm_active_enums::AssetType` 保持一致）
+const ASSET_TYPES: &[&str] = &[
+    "stock", "etf", "crypto_spot", "crypto_perp", "future", "option", "forex", "index", "bond",
+];
+
+/// 历史自由文本取值到枚举取值的映射
+const ALIASES: &[(&str, &str)] = &[
+    ("equity", "stock"),
+    ("crypto", "crypto_spot"),
+    ("spot", "crypto_spot"),
+    ("perp", "crypto_perp"),
+    ("perpetual", "crypto_perp"),
+    ("futures", "future"),
+    ("options", "option"),
+    ("fx", "forex"),
+];
+
+/// 将 `instrument.asset_type` 的历史取值规范化为 `AssetType` 枚举取值。
+///
+/// 无法识别的取值会让迁移失败，需人工修正数据后重试，避免实体加载时反序列化出错。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared("UPDATE instrument SET asset_type = LOWER(TRIM(asset_type))")
+            .await?;
+        for (alias, value) in ALIASES {
+            db.execute_unprepared(&format!(
+                "UPDATE instrument SET asset_type = '{value}' WHERE asset_type = '{alias}'"
+            ))
+            .await?;
+        }
+
+        let known = ASSET_TYPES
+            .iter()
+            .map(|v| format!("'{v}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let unknown = db
+            .query_all(Statement::from_string(
+                manager.get_database_backend(),
+                format!("SELECT DISTINCT asset_type FROM instrument WHERE asset_type NOT IN ({known})"),
+            ))
+            .await?
+            .iter()
+            .filter_map(|row| row.try_get::<String>("", "asset_type").ok())
+            .collect::<Vec<_>>();
+
+        if !unknown.is_empty() {
+            return Err(DbErr::Migration(format!(
+                "instrument.asset_type contains unknown values {unknown:?}, expected one of {ASSET_TYPES:?}"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // 规范化后的取值仍是合法字符串，无需回滚
+        Ok(())
+    }
+}
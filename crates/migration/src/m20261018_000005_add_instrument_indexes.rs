@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+/// 为 `instrument` 增加 (exchange_id, symbol) 唯一索引以及 symbol、asset_type 查询索引。
+///
+/// 已存在的重复 (exchange_id, symbol) 在建索引前合并：保留 ID 最小（最早创建）的一条，
+/// 删除其余记录并逐条记录告警日志，便于事后核对被删除的数据。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 同一 (exchange_id, symbol) 中 ID 不是最小的记录
+const DUPLICATES: &str = "FROM instrument WHERE id NOT IN \
+    (SELECT MIN(id) FROM instrument GROUP BY exchange_id, symbol)";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let duplicates = db
+            .query_all(Statement::from_string(
+                manager.get_database_backend(),
+                format!("SELECT id, exchange_id, symbol {DUPLICATES}"),
+            ))
+            .await?;
+        for row in &duplicates {
+            let id = row.try_get::<i32>("", "id")?;
+            let exchange_id = row.try_get::<i32>("", "exchange_id")?;
+            let symbol = row.try_get::<String>("", "symbol")?;
+            tracing::warn!(id, exchange_id, %symbol, "Removing duplicated instrument before creating unique index");
+        }
+        if !duplicates.is_empty() {
+            db.execute_unprepared(&format!("DELETE {DUPLICATES}")).await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_exchange_symbol")
+                    .table(Instrument::Table)
+                    .col(Instrument::ExchangeId)
+                    .col(Instrument::Symbol)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_symbol")
+                    .table(Instrument::Table)
+                    .col(Instrument::Symbol)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_asset_type")
+                    .table(Instrument::Table)
+                    .col(Instrument::AssetType)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for name in [
+            "idx_instrument_asset_type",
+            "idx_instrument_symbol",
+            "idx_instrument_exchange_symbol",
+        ] {
+            manager
+                .drop_index(Index::drop().name(name).table(Instrument::Table).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    ExchangeId,
+    Symbol,
+    AssetType,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Migrator, MigratorTrait};
+    use sea_orm_migration::sea_orm::{ConnectOptions, ConnectionTrait, Database, Statement};
+
+    #[async_std::test]
+    async fn test_duplicates_are_merged_before_unique_index() {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1).min_connections(1).sqlx_logging(false);
+        let db = Database::connect(opt).await.unwrap();
+        Migrator::up(&db, Some(5)).await.unwrap();
+
+        db.execute_unprepared(
+            "INSERT INTO exchange (id, code, name, asset_classes, trading_hours) VALUES (1, 'SSE', 'SSE', '[]', '[]');
+             INSERT INTO instrument (id, exchange_id, symbol, asset_type) VALUES
+                (1, 1, '600000', 'stock'), (2, 1, '600519', 'stock'), (3, 1, '600000', 'stock');",
+        )
+        .await
+        .unwrap();
+        Migrator::up(&db, Some(1)).await.unwrap();
+
+        let ids = db
+            .query_all(Statement::from_string(db.get_database_backend(), "SELECT id FROM instrument ORDER BY id"))
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.try_get::<i32>("", "id").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(db
+            .execute_unprepared("INSERT INTO instrument (exchange_id, symbol, asset_type) VALUES (1, '600000', 'stock')")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// 为 `instrument` 增加审计列（创建 / 更新 / 软删除时间、创建人），并新建字段级变更历史表 `instrument_history`。
+///
+/// - SQLite 的 ADD COLUMN 不允许非常量默认值，时间列先以纪元时间占位，再回填为迁移时间。
+/// - (exchange_id, symbol) 唯一索引改为只约束未删除的记录，删除后可重新创建同名标的。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let backend = manager.get_database_backend();
+        let now_default = match backend {
+            DbBackend::Sqlite => Expr::val("1970-01-01T00:00:00+00:00"),
+            _ => Expr::current_timestamp(),
+        };
+
+        let columns = [
+            timestamp_with_time_zone(Instrument::CreatedAt).default(now_default.clone()).to_owned(),
+            timestamp_with_time_zone(Instrument::UpdatedAt).default(now_default).to_owned(),
+            timestamp_with_time_zone_null(Instrument::DeletedAt),
+            string_len_null(Instrument::CreatedBy, 64),
+        ];
+        for mut column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Instrument::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        if backend == DbBackend::Sqlite {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "UPDATE instrument SET \
+                     created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'), \
+                     updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')",
+                )
+                .await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_instrument_exchange_symbol")
+                    .table(Instrument::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_exchange_symbol")
+                    .table(Instrument::Table)
+                    .col(Instrument::ExchangeId)
+                    .col(Instrument::Symbol)
+                    .unique()
+                    .and_where(Expr::col(Instrument::DeletedAt).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstrumentHistory::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InstrumentHistory::Id))
+                    .col(integer(InstrumentHistory::InstrumentId))
+                    .col(string_len(InstrumentHistory::Field, 64))
+                    // 变更前后的值，以 JSON 保存以兼容不同类型的列
+                    .col(json_binary_null(InstrumentHistory::OldValue))
+                    .col(json_binary_null(InstrumentHistory::NewValue))
+                    .col(timestamp_with_time_zone(InstrumentHistory::ChangedAt))
+                    .col(string_len_null(InstrumentHistory::ChangedBy, 64))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_instrument_history_instrument")
+                            .from(InstrumentHistory::Table, InstrumentHistory::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_history_lookup")
+                    .table(InstrumentHistory::Table)
+                    .col(InstrumentHistory::InstrumentId)
+                    .col(InstrumentHistory::Field)
+                    .col(InstrumentHistory::ChangedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InstrumentHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_instrument_exchange_symbol")
+                    .table(Instrument::Table)
+                    .to_owned(),
+            )
+            .await?;
+        // 恢复全量唯一索引前需清理已软删除的记录，否则可能与同名的新记录冲突
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM instrument WHERE deleted_at IS NOT NULL")
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_exchange_symbol")
+                    .table(Instrument::Table)
+                    .col(Instrument::ExchangeId)
+                    .col(Instrument::Symbol)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            Instrument::CreatedAt,
+            Instrument::UpdatedAt,
+            Instrument::DeletedAt,
+            Instrument::CreatedBy,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Instrument::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+    ExchangeId,
+    Symbol,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+    CreatedBy,
+}
+
+#[derive(DeriveIden)]
+enum InstrumentHistory {
+    Table,
+    Id,
+    InstrumentId,
+    Field,
+    OldValue,
+    NewValue,
+    ChangedAt,
+    ChangedBy,
+}
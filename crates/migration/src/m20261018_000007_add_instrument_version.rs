@@ -0,0 +1,36 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 为 `instrument` 增加乐观锁版本号 `version`，每次更新加 1，已有记录从 1 开始。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Instrument::Table)
+                    .add_column(integer(Instrument::Version).default(1))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Instrument::Table)
+                    .drop_column(Instrument::Version)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Version,
+}
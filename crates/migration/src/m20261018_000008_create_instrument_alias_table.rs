@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 新建标的别名表 `instrument_alias`：同一标的在不同来源下的标识（ISIN / FIGI / CUSIP / 数据商代码等）。
+///
+/// (source, value) 全局唯一，保证任一来源的标识只能解析到一个标的。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstrumentAlias::Table)
+                    .if_not_exists()
+                    .col(pk_auto(InstrumentAlias::Id))
+                    .col(integer(InstrumentAlias::InstrumentId))
+                    // 来源，小写，如 isin、figi、cusip、bloomberg
+                    .col(string_len(InstrumentAlias::Source, 32))
+                    .col(string_len(InstrumentAlias::Value, 64))
+                    .col(timestamp_with_time_zone(InstrumentAlias::CreatedAt))
+                    .col(string_len_null(InstrumentAlias::CreatedBy, 64))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_instrument_alias_instrument")
+                            .from(InstrumentAlias::Table, InstrumentAlias::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_alias_source_value")
+                    .table(InstrumentAlias::Table)
+                    .col(InstrumentAlias::Source)
+                    .col(InstrumentAlias::Value)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_instrument_alias_instrument")
+                    .table(InstrumentAlias::Table)
+                    .col(InstrumentAlias::InstrumentId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InstrumentAlias::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum InstrumentAlias {
+    Table,
+    Id,
+    InstrumentId,
+    Source,
+    Value,
+    CreatedAt,
+    CreatedBy,
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// 为标的搜索增加 `instrument.name_pinyin`（中文名称的拼音首字母与全拼，由应用写入）。
+///
+/// Postgres 下尝试启用 `pg_trgm` 并为 symbol / 名称 / 拼音 / 别名建立三元组索引；
+/// 扩展不可用（未安装或权限不足）时跳过，搜索退化为应用内索引。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// 三元组索引：(索引名, 表, 索引表达式)
+const TRIGRAM_INDEXES: &[(&str, &str, &str)] = &[
+    ("idx_instrument_symbol_trgm", "instrument", "lower(symbol)"),
+    ("idx_instrument_name_trgm", "instrument", "lower(name)"),
+    ("idx_instrument_name_pinyin_trgm", "instrument", "name_pinyin"),
+    ("idx_instrument_alias_value_trgm", "instrument_alias", "lower(value)"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Instrument::Table)
+                    .add_column(string_len_null(Instrument::NamePinyin, 255))
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let conn = manager.get_connection();
+        conn.execute_unprepared(
+            "DO $$ BEGIN \
+                 CREATE EXTENSION IF NOT EXISTS pg_trgm; \
+             EXCEPTION WHEN OTHERS THEN \
+                 RAISE NOTICE 'pg_trgm unavailable, instrument search uses the in-process index'; \
+             END $$",
+        )
+        .await?;
+        for (name, table, expr) in TRIGRAM_INDEXES {
+            conn.execute_unprepared(&format!(
+                "DO $$ BEGIN \
+                     IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_trgm') THEN \
+                         CREATE INDEX IF NOT EXISTS {name} ON {table} USING gin ({expr} gin_trgm_ops); \
+                     END IF; \
+                 END $$"
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Postgres {
+            for (name, _, _) in TRIGRAM_INDEXES {
+                manager
+                    .get_connection()
+                    .execute_unprepared(&format!("DROP INDEX IF EXISTS {name}"))
+                    .await?;
+            }
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Instrument::Table)
+                    .drop_column(Instrument::NamePinyin)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    NamePinyin,
+}
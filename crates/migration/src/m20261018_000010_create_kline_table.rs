@@ -0,0 +1,113 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// 新建多周期 K 线表 `kline`，主键为 (instrument_id, interval, ts)。
+///
+/// 取代 README 中按周期分表（`kline_1m` / `kline_1d`）的设计：各周期共用一张表，按 `interval` 区分。
+/// Postgres 下若可用 TimescaleDB 则转换为按 `ts` 分块的 hypertable；否则（含 SQLite）保留普通表，
+/// 依靠主键及 (interval, ts) 索引支持按时间范围查询。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Kline::Table)
+                    .if_not_exists()
+                    .col(integer(Kline::InstrumentId))
+                    // 周期，如 1m、1h、1d、1M
+                    .col(string_len(Kline::Interval, 8))
+                    // 周期开始时间
+                    .col(timestamp_with_time_zone(Kline::Ts))
+                    .col(double(Kline::Open))
+                    .col(double(Kline::High))
+                    .col(double(Kline::Low))
+                    .col(double(Kline::Close))
+                    .col(double(Kline::Volume))
+                    .col(double_null(Kline::QuoteVolume))
+                    .col(big_integer_null(Kline::TradeCount))
+                    .primary_key(
+                        Index::create()
+                            .col(Kline::InstrumentId)
+                            .col(Kline::Interval)
+                            .col(Kline::Ts),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_kline_instrument")
+                            .from(Kline::Table, Kline::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_kline_interval_ts")
+                    .table(Kline::Table)
+                    .col(Kline::Interval)
+                    .col(Kline::Ts)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        // 已安装的扩展直接使用；未安装时仅在可用且有权限时启用，失败则保留普通表
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DO $$ BEGIN \
+                     IF NOT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') \
+                        AND EXISTS (SELECT 1 FROM pg_available_extensions WHERE name = 'timescaledb') THEN \
+                         BEGIN \
+                             CREATE EXTENSION IF NOT EXISTS timescaledb; \
+                         EXCEPTION WHEN OTHERS THEN \
+                             RAISE NOTICE 'timescaledb unavailable, kline stays a plain table'; \
+                         END; \
+                     END IF; \
+                     IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') THEN \
+                         PERFORM create_hypertable('kline', 'ts', \
+                             chunk_time_interval => INTERVAL '7 days', \
+                             if_not_exists => TRUE, migrate_data => TRUE); \
+                     END IF; \
+                 END $$",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Kline::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Kline {
+    Table,
+    InstrumentId,
+    Interval,
+    Ts,
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+    QuoteVolume,
+    TradeCount,
+}
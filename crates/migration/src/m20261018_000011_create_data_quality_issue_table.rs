@@ -0,0 +1,78 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 新建行情数据质量问题表 `data_quality_issue`：缺失 K 线、同一周期内的重复 K 线、OHLC 不自洽、
+/// 连续零成交量和价格跳变。
+///
+/// 每次检查按 (标的, 周期, 检查区间) 整体替换区间内的问题，表中始终是最近一次检查的结果。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DataQualityIssue::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DataQualityIssue::Id))
+                    .col(integer(DataQualityIssue::InstrumentId))
+                    .col(string_len(DataQualityIssue::Interval, 8))
+                    // 问题类型，如 missing_bars、price_spike
+                    .col(string_len(DataQualityIssue::Kind, 24))
+                    // 首根 / 末根受影响 K 线（或缺失周期）的开始时间
+                    .col(timestamp_with_time_zone(DataQualityIssue::StartTs))
+                    .col(timestamp_with_time_zone(DataQualityIssue::EndTs))
+                    .col(integer(DataQualityIssue::BarCount))
+                    .col(json_null(DataQualityIssue::Detail))
+                    .col(timestamp_with_time_zone(DataQualityIssue::DetectedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_data_quality_issue_instrument")
+                            .from(DataQualityIssue::Table, DataQualityIssue::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_quality_issue_instrument_interval_start")
+                    .table(DataQualityIssue::Table)
+                    .col(DataQualityIssue::InstrumentId)
+                    .col(DataQualityIssue::Interval)
+                    .col(DataQualityIssue::StartTs)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DataQualityIssue::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DataQualityIssue {
+    Table,
+    Id,
+    InstrumentId,
+    Interval,
+    Kind,
+    StartTs,
+    EndTs,
+    BarCount,
+    Detail,
+    DetectedAt,
+}
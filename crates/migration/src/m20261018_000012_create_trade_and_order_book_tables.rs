@@ -0,0 +1,134 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// 新建逐笔成交表 `trade` 与盘口表 `order_book`。
+///
+/// 两张表的主键都以 (instrument_id, ts, ...) 开头，按时间范围读取时走主键索引；
+/// 盘口档位以 JSON 数组 `[[price, qty], ...]` 保存（Postgres 为 JSONB），快照为完整档位，增量中数量为 0 表示删除该档。
+/// 与 `kline` 相同，Postgres 下 TimescaleDB 可用时转换为 hypertable，分块按 1 天。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Trade::Table)
+                    .if_not_exists()
+                    .col(integer(Trade::InstrumentId))
+                    .col(timestamp_with_time_zone(Trade::Ts))
+                    // 交易所成交 ID，没有时由采集端按顺序分配
+                    .col(big_integer(Trade::TradeId))
+                    .col(double(Trade::Price))
+                    .col(double(Trade::Qty))
+                    // 主动方 buy / sell，未知为 NULL
+                    .col(string_len_null(Trade::Side, 4))
+                    .primary_key(
+                        Index::create()
+                            .col(Trade::InstrumentId)
+                            .col(Trade::Ts)
+                            .col(Trade::TradeId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_trade_instrument")
+                            .from(Trade::Table, Trade::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrderBook::Table)
+                    .if_not_exists()
+                    .col(integer(OrderBook::InstrumentId))
+                    .col(timestamp_with_time_zone(OrderBook::Ts))
+                    // 交易所的更新序号，用于校验增量是否连续
+                    .col(big_integer(OrderBook::Sequence))
+                    // snapshot / delta
+                    .col(string_len(OrderBook::Kind, 8))
+                    .col(json_binary(OrderBook::Bids))
+                    .col(json_binary(OrderBook::Asks))
+                    .primary_key(
+                        Index::create()
+                            .col(OrderBook::InstrumentId)
+                            .col(OrderBook::Ts)
+                            .col(OrderBook::Sequence),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_book_instrument")
+                            .from(OrderBook::Table, OrderBook::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        // 扩展已由 kline 迁移尝试启用，这里只在已启用时转换
+        for table in ["trade", "order_book"] {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "DO $$ BEGIN \
+                         IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') THEN \
+                             PERFORM create_hypertable('{table}', 'ts', \
+                                 chunk_time_interval => INTERVAL '1 day', \
+                                 if_not_exists => TRUE, migrate_data => TRUE); \
+                         END IF; \
+                     END $$"
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OrderBook::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Trade::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
+enum Trade {
+    Table,
+    InstrumentId,
+    Ts,
+    TradeId,
+    Price,
+    Qty,
+    Side,
+}
+
+#[derive(DeriveIden)]
+enum OrderBook {
+    Table,
+    InstrumentId,
+    Ts,
+    Sequence,
+    Kind,
+    Bids,
+    Asks,
+}
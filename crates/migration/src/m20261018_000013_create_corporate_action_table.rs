@@ -0,0 +1,88 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 新建公司行动表 `corporate_action`：拆股、合股、现金分红、送转股、配股与更名，按除权除息日计算复权因子。
+///
+/// 同一标的同一除权日同一类型只有一条；比例、金额等字段按类型选填，含义见实体定义。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CorporateAction::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CorporateAction::Id))
+                    .col(integer(CorporateAction::InstrumentId))
+                    // split / reverse_split / cash_dividend / stock_dividend / rights_issue / symbol_change
+                    .col(string_len(CorporateAction::Kind, 20))
+                    // 除权除息日（交易所本地日期）
+                    .col(date(CorporateAction::ExDate))
+                    .col(date_null(CorporateAction::RecordDate))
+                    .col(date_null(CorporateAction::PayDate))
+                    .col(double_null(CorporateAction::Ratio))
+                    .col(double_null(CorporateAction::Cash))
+                    .col(double_null(CorporateAction::SubscriptionPrice))
+                    .col(string_len_null(CorporateAction::OldSymbol, 50))
+                    .col(string_len_null(CorporateAction::NewSymbol, 50))
+                    .col(text_null(CorporateAction::Note))
+                    .col(timestamp_with_time_zone(CorporateAction::CreatedAt))
+                    .col(timestamp_with_time_zone(CorporateAction::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_corporate_action_instrument")
+                            .from(CorporateAction::Table, CorporateAction::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_corporate_action_instrument_ex_date_kind")
+                    .table(CorporateAction::Table)
+                    .col(CorporateAction::InstrumentId)
+                    .col(CorporateAction::ExDate)
+                    .col(CorporateAction::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CorporateAction::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum CorporateAction {
+    Table,
+    Id,
+    InstrumentId,
+    Kind,
+    ExDate,
+    RecordDate,
+    PayDate,
+    Ratio,
+    Cash,
+    SubscriptionPrice,
+    OldSymbol,
+    NewSymbol,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
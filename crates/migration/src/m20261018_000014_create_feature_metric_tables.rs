@@ -0,0 +1,138 @@
+use sea_orm_migration::{prelude::*, schema::*};
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// 新建指标目录 `metric_definition` 与指标时间序列表 `feature_metric`。
+///
+/// `feature_metric` 在 README 设计的基础上增加 `known_at`（数值公布 / 可获得的时间）并纳入主键：
+/// 财报更正等重述写入新的 `known_at`，原值保留，按时点查询时只取当时已公布的最新值，避免引入未来数据。
+/// 与 `kline` 相同，Postgres 下 TimescaleDB 可用时转换为 hypertable，分块按 7 天。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MetricDefinition::Table)
+                    .if_not_exists()
+                    .col(string_len(MetricDefinition::Name, 100).primary_key())
+                    .col(string_len_null(MetricDefinition::Unit, 30))
+                    // daily / weekly / monthly / quarterly / annual / irregular
+                    .col(string_len(MetricDefinition::Frequency, 10))
+                    .col(string_len_null(MetricDefinition::Source, 100))
+                    .col(text_null(MetricDefinition::Description))
+                    .col(timestamp_with_time_zone(MetricDefinition::CreatedAt))
+                    .col(timestamp_with_time_zone(MetricDefinition::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(FeatureMetric::Table)
+                    .if_not_exists()
+                    .col(integer(FeatureMetric::InstrumentId))
+                    .col(string_len(FeatureMetric::MetricName, 100))
+                    // 数值所属的时间（如报告期末、交易日）
+                    .col(timestamp_with_time_zone(FeatureMetric::Ts))
+                    // 数值公布的时间，重述时为更正公布的时间
+                    .col(timestamp_with_time_zone(FeatureMetric::KnownAt))
+                    .col(double_null(FeatureMetric::Value))
+                    .col(json_binary_null(FeatureMetric::Detail))
+                    .primary_key(
+                        Index::create()
+                            .col(FeatureMetric::InstrumentId)
+                            .col(FeatureMetric::MetricName)
+                            .col(FeatureMetric::Ts)
+                            .col(FeatureMetric::KnownAt),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_feature_metric_instrument")
+                            .from(FeatureMetric::Table, FeatureMetric::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_feature_metric_definition")
+                            .from(FeatureMetric::Table, FeatureMetric::MetricName)
+                            .to(MetricDefinition::Table, MetricDefinition::Name)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // 截面查询按指标和时间筛选全部标的
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_feature_metric_name_ts")
+                    .table(FeatureMetric::Table)
+                    .col(FeatureMetric::MetricName)
+                    .col(FeatureMetric::Ts)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        // 扩展已由 kline 迁移尝试启用，这里只在已启用时转换
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DO $$ BEGIN \
+                     IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') THEN \
+                         PERFORM create_hypertable('feature_metric', 'ts', \
+                             chunk_time_interval => INTERVAL '7 days', \
+                             if_not_exists => TRUE, migrate_data => TRUE); \
+                     END IF; \
+                 END $$",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FeatureMetric::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MetricDefinition::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum MetricDefinition {
+    Table,
+    Name,
+    Unit,
+    Frequency,
+    Source,
+    Description,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum FeatureMetric {
+    Table,
+    InstrumentId,
+    MetricName,
+    Ts,
+    KnownAt,
+    Value,
+    Detail,
+}
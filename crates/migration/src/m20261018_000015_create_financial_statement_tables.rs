@@ -0,0 +1,121 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// 新建财务报表表 `financial_statement` 与科目表 `financial_statement_item`。
+///
+/// 每次公布（含更正、重述）都是一个新版本，原有版本保留，按公告日即可还原某一天已知的报表；
+/// 同一标的、报表类型、财年和报告期下版本号唯一。科目代码取自应用内的标准科目表。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FinancialStatement::Table)
+                    .if_not_exists()
+                    .col(pk_auto(FinancialStatement::Id))
+                    .col(integer(FinancialStatement::InstrumentId))
+                    // income / balance_sheet / cash_flow
+                    .col(string_len(FinancialStatement::StatementType, 20))
+                    .col(integer(FinancialStatement::FiscalYear))
+                    // q1 / q2 / q3 / q4 / h1 / h2 / fy
+                    .col(string_len(FinancialStatement::FiscalPeriod, 4))
+                    .col(date(FinancialStatement::PeriodEnd))
+                    .col(string_len(FinancialStatement::Currency, 10))
+                    // 公告日，该日起视为已知
+                    .col(date(FinancialStatement::AnnounceDate))
+                    .col(integer(FinancialStatement::Version))
+                    .col(string_len_null(FinancialStatement::Source, 100))
+                    .col(timestamp_with_time_zone(FinancialStatement::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_financial_statement_instrument")
+                            .from(FinancialStatement::Table, FinancialStatement::InstrumentId)
+                            .to(Instrument::Table, Instrument::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_financial_statement_period_version")
+                    .table(FinancialStatement::Table)
+                    .col(FinancialStatement::InstrumentId)
+                    .col(FinancialStatement::StatementType)
+                    .col(FinancialStatement::FiscalYear)
+                    .col(FinancialStatement::FiscalPeriod)
+                    .col(FinancialStatement::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(FinancialStatementItem::Table)
+                    .if_not_exists()
+                    .col(integer(FinancialStatementItem::StatementId))
+                    .col(string_len(FinancialStatementItem::ItemCode, 50))
+                    .col(double(FinancialStatementItem::Value))
+                    .primary_key(
+                        Index::create()
+                            .col(FinancialStatementItem::StatementId)
+                            .col(FinancialStatementItem::ItemCode),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_financial_statement_item_statement")
+                            .from(FinancialStatementItem::Table, FinancialStatementItem::StatementId)
+                            .to(FinancialStatement::Table, FinancialStatement::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FinancialStatementItem::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(FinancialStatement::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Instrument {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum FinancialStatement {
+    Table,
+    Id,
+    InstrumentId,
+    StatementType,
+    FiscalYear,
+    FiscalPeriod,
+    PeriodEnd,
+    Currency,
+    AnnounceDate,
+    Version,
+    Source,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum FinancialStatementItem {
+    Table,
+    StatementId,
+    ItemCode,
+    Value,
+}
@@ -0,0 +1,90 @@
+
+/// 交易日历接口，路径中的交易所可以是 ID 或代码
+use crate::api::extract::{ValidatedPath, ValidatedQuery};
+use crate::dto::calendar::{
+    CalendarStatus, CalendarStatusQuery, SessionRangeQuery, SessionResponse, TradingDaysQuery,
+};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::calendar::CalendarService;
+use axum::{
+    Json,
+    extract::State,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(status, sessions, trading_days),
+    tags((name = "calendar", description = "交易日历与交易时段"))
+)]
+pub struct CalendarApi;
+
+/// 查询交易所在某一时刻是否开市，以及下一次开盘 / 收盘时间
+///
+/// 时段包含午休（如 SSE 上午、下午两个时段），休市日与半天交易取自 `configs/calendars/<代码>.toml`，
+/// `covered_years` 为已收录休市安排的年份。
+/// 查询参数中的 `+` 需编码为 `%2B`。
+#[utoipa::path(
+    get,
+    path = "/exchanges/{id}/calendar",
+    tag = "calendar",
+    params(("id" = String, Path, description = "交易所 ID 或代码"), CalendarStatusQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<CalendarStatus>),
+        (status = 422, description = "查询参数格式错误", body = ErrorResponse),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+    )
+)]
+pub async fn status(
+    State(service): State<Arc<CalendarService>>,
+    ValidatedPath(exchange): ValidatedPath<String>,
+    ValidatedQuery(query): ValidatedQuery<CalendarStatusQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.status(&exchange, query.at).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 查询时间范围内的交易时段
+#[utoipa::path(
+    get,
+    path = "/exchanges/{id}/calendar/sessions",
+    tag = "calendar",
+    params(("id" = String, Path, description = "交易所 ID 或代码"), SessionRangeQuery),
+    responses(
+        (status = 200, description = "按时间排序的交易时段", body = APIResponse<Vec<SessionResponse>>),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+        (status = 422, description = "范围颠倒、超过 366 天或超出已收录的年份", body = ErrorResponse),
+    )
+)]
+pub async fn sessions(
+    State(service): State<Arc<CalendarService>>,
+    ValidatedPath(exchange): ValidatedPath<String>,
+    ValidatedQuery(query): ValidatedQuery<SessionRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.sessions(&exchange, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 查询日期范围内的交易日
+#[utoipa::path(
+    get,
+    path = "/exchanges/{id}/calendar/days",
+    tag = "calendar",
+    params(("id" = String, Path, description = "交易所 ID 或代码"), TradingDaysQuery),
+    responses(
+        (status = 200, description = "交易日列表", body = APIResponse<Vec<NaiveDate>>),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+        (status = 422, description = "范围颠倒、超过 366 天或超出已收录的年份", body = ErrorResponse),
+    )
+)]
+pub async fn trading_days(
+    State(service): State<Arc<CalendarService>>,
+    ValidatedPath(exchange): ValidatedPath<String>,
+    ValidatedQuery(query): ValidatedQuery<TradingDaysQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.trading_days(&exchange, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
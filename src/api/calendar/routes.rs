@@ -0,0 +1,14 @@
+use crate::api::calendar::handler;
+use crate::service::calendar::CalendarService;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+// 与交易所接口共用 `{id}` 参数名（axum 要求同一位置的参数同名），取值可以是 ID 或代码
+pub fn routes(service: Arc<CalendarService>) -> Router {
+    Router::new()
+        .route("/exchanges/{id}/calendar", get(handler::status))
+        .route("/exchanges/{id}/calendar/sessions", get(handler::sessions))
+        .route("/exchanges/{id}/calendar/days", get(handler::trading_days))
+        .with_state(service)
+}
@@ -0,0 +1,141 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::corporate_action::{
+    AdjustmentFactorResponse, CorporateActionFilter, CorporateActionRequest,
+    CorporateActionResponse,
+};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::corporate_action::CorporateActionService;
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(timeline, create, replace, delete, factors),
+    tags((name = "corporate-action", description = "公司行动与复权"))
+)]
+pub struct CorporateActionApi;
+
+/// 查询标的的公司行动时间线
+///
+/// 按除权日升序，`start` / `end` 按除权日过滤（含两端）。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/corporate-actions",
+    tag = "corporate-action",
+    params(("id" = i32, Path, description = "标的 ID"), CorporateActionFilter),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<CorporateActionResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn timeline(
+    State(service): State<Arc<CorporateActionService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(filter): ValidatedQuery<CorporateActionFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.timeline(id, filter).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 新增公司行动
+///
+/// 各类型需要的字段见请求体说明；同一除权日同一类型只能有一条。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/corporate-actions",
+    tag = "corporate-action",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = CorporateActionRequest,
+    responses(
+        (status = 201, description = "添加成功", body = APIResponse<CorporateActionResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 409, description = "同一除权日已有同类型的公司行动", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn create(
+    State(service): State<Arc<CorporateActionService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<CorporateActionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create(id, req).await?;
+    Ok((StatusCode::CREATED, Json(APIResponse::success(response))))
+}
+
+/// 整体替换公司行动
+#[utoipa::path(
+    put,
+    path = "/instruments/{id}/corporate-actions/{action_id}",
+    tag = "corporate-action",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("action_id" = i32, Path, description = "公司行动 ID"),
+    ),
+    request_body = CorporateActionRequest,
+    responses(
+        (status = 200, description = "替换成功", body = APIResponse<CorporateActionResponse>),
+        (status = 404, description = "公司行动不存在", body = ErrorResponse),
+        (status = 409, description = "同一除权日已有同类型的公司行动", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn replace(
+    State(service): State<Arc<CorporateActionService>>,
+    ValidatedPath((id, action_id)): ValidatedPath<(i32, i32)>,
+    ValidatedJson(req): ValidatedJson<CorporateActionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.replace(id, action_id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 删除公司行动
+#[utoipa::path(
+    delete,
+    path = "/instruments/{id}/corporate-actions/{action_id}",
+    tag = "corporate-action",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("action_id" = i32, Path, description = "公司行动 ID"),
+    ),
+    responses(
+        (status = 204, description = "删除成功"),
+        (status = 404, description = "公司行动不存在", body = ErrorResponse),
+    )
+)]
+pub async fn delete(
+    State(service): State<Arc<CorporateActionService>>,
+    ValidatedPath((id, action_id)): ValidatedPath<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete(id, action_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 查询标的的复权因子
+///
+/// 每个除权日一条，同一天的多项行动合并计算：`ratio = 除权参考价 / 前收盘价`。
+/// 现金分红与配股需要除权日前的收盘价（取自库中日线或 1m K 线），缺失时该日不复权。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/adjustment-factors",
+    tag = "corporate-action",
+    params(("id" = i32, Path, description = "标的 ID")),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<AdjustmentFactorResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+    )
+)]
+pub async fn factors(
+    State(service): State<Arc<CorporateActionService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.factors(id).await?;
+    Ok(Json(APIResponse::success(response)))
+}
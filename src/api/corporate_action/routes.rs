@@ -0,0 +1,19 @@
+use crate::api::corporate_action::handler;
+use crate::service::corporate_action::CorporateActionService;
+use axum::Router;
+use axum::routing::{get, put};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<CorporateActionService>) -> Router {
+    Router::new()
+        .route(
+            "/instruments/{id}/corporate-actions",
+            get(handler::timeline).post(handler::create),
+        )
+        .route(
+            "/instruments/{id}/corporate-actions/{action_id}",
+            put(handler::replace).delete(handler::delete),
+        )
+        .route("/instruments/{id}/adjustment-factors", get(handler::factors))
+        .with_state(service)
+}
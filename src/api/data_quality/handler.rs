@@ -0,0 +1,94 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::data_quality::{
+    BackfillQuery, BackfillRange, DataQualityCheckRequest, DataQualityFilter,
+    DataQualityIssueResponse, DataQualityReport,
+};
+use crate::dto::pagination::{PageParams, PageResponse};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::data_quality::DataQualityService;
+use axum::{
+    Json,
+    extract::State,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(check, list, backfill),
+    tags((name = "data-quality", description = "行情数据质量"))
+)]
+pub struct DataQualityApi;
+
+/// 检查标的的 K 线数据质量
+///
+/// 按交易所交易日历找出缺失的 K 线，并检测同一周期内的重复 K 线、OHLC 不自洽、连续零成交量和价格跳变。
+/// 结果替换检查区间内已保存的问题，返回各类问题的条数，明细通过列表接口查询。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/data-quality/check",
+    tag = "data-quality",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = DataQualityCheckRequest,
+    responses(
+        (status = 200, description = "检查完成", body = APIResponse<DataQualityReport>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "区间或阈值校验失败，或区间超出交易所日历已收录的年份", body = ErrorResponse),
+    )
+)]
+pub async fn check(
+    State(service): State<Arc<DataQualityService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<DataQualityCheckRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.check(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 分页查询最近一次检查发现的问题
+///
+/// 可排序字段：`start_ts`（默认升序）、`kind`、`bar_count`、`detected_at`。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/data-quality",
+    tag = "data-quality",
+    params(("id" = i32, Path, description = "标的 ID"), DataQualityFilter, PageParams),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<PageResponse<DataQualityIssueResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn list(
+    State(service): State<Arc<DataQualityService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(filter): ValidatedQuery<DataQualityFilter>,
+    ValidatedQuery(params): ValidatedQuery<PageParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.list(id, filter, params).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 查询需要补数的区间
+///
+/// 由最近一次检查发现的缺失 K 线生成，区间为 `[start, end)`；补数后重新检查即可清除对应问题。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/data-quality/backfill",
+    tag = "data-quality",
+    params(("id" = i32, Path, description = "标的 ID"), BackfillQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<BackfillRange>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+    )
+)]
+pub async fn backfill(
+    State(service): State<Arc<DataQualityService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<BackfillQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.backfill_ranges(id, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
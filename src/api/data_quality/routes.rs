@@ -0,0 +1,13 @@
+use crate::api::data_quality::handler;
+use crate::service::data_quality::DataQualityService;
+use axum::Router;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<DataQualityService>) -> Router {
+    Router::new()
+        .route("/instruments/{id}/data-quality", get(handler::list))
+        .route("/instruments/{id}/data-quality/check", post(handler::check))
+        .route("/instruments/{id}/data-quality/backfill", get(handler::backfill))
+        .with_state(service)
+}
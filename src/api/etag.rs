@@ -0,0 +1,74 @@
+//! 基于版本号的 ETag 与条件请求头（`If-Match` / `If-None-Match`）的提取器
+//!
+//! ETag 取资源的 `version`，形如 `"3"`；标签的解析与比较规则见 [`crate::core::etag`]。
+
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderValue, header, request::Parts},
+};
+
+use crate::core::etag::ETags;
+use crate::error::code::AppError;
+
+/// 由版本号生成 ETag 头的值
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{version}\"")).expect("ETag is always a valid header value")
+}
+
+fn parse_header(parts: &Parts, name: header::HeaderName) -> Result<Option<ETags>, AppError> {
+    let Some(value) = parts.headers.get(&name) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(ETags::parse)
+        .map(Some)
+        .ok_or_else(|| AppError::BadRequest { message: format!("Malformed {name} header") })
+}
+
+/// 必填的 `If-Match` 请求头，缺失时返回 428
+#[derive(Debug, Clone)]
+pub struct IfMatch(pub ETags);
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parse_header(parts, header::IF_MATCH)?
+            .map(Self)
+            .ok_or(AppError::PreconditionRequired)
+    }
+}
+
+/// 可选的 `If-None-Match` 请求头
+#[derive(Debug, Clone)]
+pub struct IfNoneMatch(pub Option<ETags>);
+
+impl IfNoneMatch {
+    /// 客户端缓存的版本与当前版本一致时返回 `true`，可直接响应 304
+    pub fn is_fresh(&self, version: i32) -> bool {
+        self.0.as_ref().is_some_and(|tags| tags.matches_weak(version))
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IfNoneMatch {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parse_header(parts, header::IF_NONE_MATCH).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_none_match_compares_weakly() {
+        let tags = ETags::parse("W/\"1\", \"2\"").unwrap();
+        assert!(IfNoneMatch(Some(tags.clone())).is_fresh(1));
+        assert!(!IfNoneMatch(Some(tags)).is_fresh(3));
+        assert!(!IfNoneMatch(None).is_fresh(1));
+    }
+}
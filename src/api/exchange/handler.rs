@@ -0,0 +1,127 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::dto::exchange::{CreateExchangeRequest, ExchangeResponse, UpdateExchangeRequest};
+use crate::error::code::AppError;
+use crate::service::exchange::ExchangeService;
+use axum::{
+    Json,
+    extract::State,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create, get_all, get_by_id, update, delete),
+    tags((name = "exchange", description = "交易所"))
+)]
+pub struct ExchangeApi;
+
+/// 创建交易所
+#[utoipa::path(
+    post,
+    path = "/exchanges",
+    tag = "exchange",
+    request_body = CreateExchangeRequest,
+    responses(
+        (status = 201, description = "创建成功", body = APIResponse<ExchangeResponse>),
+        (status = 409, description = "交易所代码已存在", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn create(
+    State(service): State<Arc<ExchangeService>>,
+    ValidatedJson(req): ValidatedJson<CreateExchangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create(req).await?;
+    Ok((axum::http::StatusCode::CREATED, Json(APIResponse::success(response))))
+}
+
+/// 根据 ID 获取交易所
+#[utoipa::path(
+    get,
+    path = "/exchanges/{id}",
+    tag = "exchange",
+    params(("id" = i32, Path, description = "交易所 ID")),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<ExchangeResponse>),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+    )
+)]
+pub async fn get_by_id(
+    State(service): State<Arc<ExchangeService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.get_by_id(id).await?;
+    let response = response.ok_or(AppError::NotFound {
+        resource: "Exchange".to_string(),
+        identifier: Some(id.to_string()),
+    })?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 获取全部交易所
+#[utoipa::path(
+    get,
+    path = "/exchanges",
+    tag = "exchange",
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<ExchangeResponse>>),
+    )
+)]
+pub async fn get_all(
+    State(service): State<Arc<ExchangeService>>,
+) -> Result<impl IntoResponse, AppError> {
+    let responses = service.get_all().await?;
+    Ok(Json(APIResponse::success(responses)))
+}
+
+/// 更新交易所
+#[utoipa::path(
+    put,
+    path = "/exchanges/{id}",
+    tag = "exchange",
+    params(("id" = i32, Path, description = "交易所 ID")),
+    request_body = UpdateExchangeRequest,
+    responses(
+        (status = 200, description = "更新成功", body = APIResponse<ExchangeResponse>),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+        (status = 409, description = "交易所代码已存在", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn update(
+    State(service): State<Arc<ExchangeService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<UpdateExchangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.update(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 删除交易所
+#[utoipa::path(
+    delete,
+    path = "/exchanges/{id}",
+    tag = "exchange",
+    params(("id" = i32, Path, description = "交易所 ID")),
+    responses(
+        (status = 204, description = "删除成功"),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+        (status = 409, description = "仍有交易标的引用该交易所", body = ErrorResponse),
+    )
+)]
+pub async fn delete(
+    State(service): State<Arc<ExchangeService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let deleted = service.delete(id).await?;
+    if !deleted {
+        return Err(AppError::NotFound {
+            resource: "Exchange".to_string(),
+            identifier: Some(id.to_string()),
+        });
+    }
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,15 @@
+use crate::api::exchange::handler;
+use crate::service::exchange::ExchangeService;
+use axum::Router;
+use axum::routing::{delete, get, post, put};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<ExchangeService>) -> Router {
+    Router::new()
+        .route("/exchanges", post(handler::create))
+        .route("/exchanges", get(handler::get_all))
+        .route("/exchanges/{id}", get(handler::get_by_id))
+        .route("/exchanges/{id}", put(handler::update))
+        .route("/exchanges/{id}", delete(handler::delete))
+        .with_state(service)
+}
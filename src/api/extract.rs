@@ -0,0 +1,339 @@
+use std::error::Error as StdError;
+use std::io::{self, Read};
+
+use axum::{
+    Json, RequestExt,
+    body::Bytes,
+    extract::{
+        FromRequest, FromRequestParts, Path, Query, RawPathParams, Request,
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection},
+    },
+    http::{StatusCode, header, request::Parts},
+};
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use http_body_util::LengthLimitError;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::dto::import::DataFormat;
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+
+/// 导入接口允许的最大请求体
+pub const MAX_IMPORT_BYTES: usize = 32 * 1024 * 1024;
+
+/// 反序列化 JSON 请求体并执行 `validator` 校验
+///
+/// 类型错误与校验失败一样返回带字段级错误的 `AppError::Validation`，定位到具体字段；
+/// 请求体不是合法的 JSON 时返回 `AppError::BadRequest`。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await.map_err(json_rejection)?;
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
+
+/// 反序列化查询字符串并执行 `validator` 校验，错误处理同 [`ValidatedJson`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| deserialize_errors(&rejection, "query"))?;
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
+
+/// 解析路径参数，失败时返回以参数名为字段的 `AppError::Validation`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(Self(value)),
+            Err(rejection) => {
+                // 元组形式的参数只报告下标，借助原始参数换成参数名
+                let names = RawPathParams::from_request_parts(parts, state)
+                    .await
+                    .map(|params| params.iter().map(|(key, _)| key.to_string()).collect())
+                    .unwrap_or_default();
+                Err(path_rejection(rejection, names))
+            }
+        }
+    }
+}
+
+fn json_rejection(rejection: JsonRejection) -> AppError {
+    match &rejection {
+        JsonRejection::JsonDataError(_) => deserialize_errors(&rejection, "body"),
+        JsonRejection::JsonSyntaxError(_) => AppError::bad_request("validation-json_syntax"),
+        JsonRejection::MissingJsonContentType(_) => AppError::bad_request("validation-json_content_type"),
+        JsonRejection::BytesRejection(rejection) => body_error("body", rejection.status()),
+        _ => AppError::bad_request("validation-invalid"),
+    }
+}
+
+
+/// serde 反序列化错误转换为字段错误；无法定位字段时记在 `fallback` 上
+///
+/// 缺失字段被 serde 报告在父级路径上，这里补上字段名并使用 `validation-required`。
+fn deserialize_errors(rejection: &dyn StdError, fallback: &str) -> AppError {
+    let (path, message) = deserialize_error_source(rejection).unwrap_or_default();
+    let join = |name: &str| match path.as_str() {
+        "" | "." => name.to_string(),
+        parent => format!("{parent}.{name}"),
+    };
+
+    let mut errors = FieldErrors::new();
+    match message.strip_prefix("missing field `").and_then(|rest| rest.split('`').next()) {
+        Some(name) => errors.add(join(name), "validation-required", TranslateArgs::new()),
+        None => {
+            let field = if matches!(path.as_str(), "" | ".") { fallback.to_string() } else { path };
+            errors.add(field, "validation-type", TranslateArgs::new());
+        }
+    }
+    errors.into()
+}
+
+/// 沿错误链找到 `serde_path_to_error` 的错误，返回 (字段路径, 原始消息)
+fn deserialize_error_source(rejection: &dyn StdError) -> Option<(String, String)> {
+    let mut source = rejection.source();
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            return Some((err.path().to_string(), err.inner().to_string()));
+        }
+        if let Some(err) = err.downcast_ref::<serde_path_to_error::Error<serde::de::value::Error>>() {
+            return Some((err.path().to_string(), err.inner().to_string()));
+        }
+        source = err.source();
+    }
+    None
+}
+
+fn path_rejection(rejection: PathRejection, names: Vec<String>) -> AppError {
+    let PathRejection::FailedToDeserializePathParams(err) = &rejection else {
+        tracing::error!(%rejection, "Path parameters missing from the route");
+        return AppError::Internal;
+    };
+    let first = || names.first().cloned().unwrap_or_else(|| "path".to_string());
+    let (field, key, args) = match err.kind() {
+        ErrorKind::ParseErrorAtKey { key, value, expected_type } => {
+            (key.clone(), "validation-path_param", path_param_args(value, expected_type))
+        }
+        ErrorKind::ParseErrorAtIndex { index, value, expected_type } => {
+            let field = names.get(*index).cloned().unwrap_or_else(|| format!("path[{index}]"));
+            (field, "validation-path_param", path_param_args(value, expected_type))
+        }
+        ErrorKind::ParseError { value, expected_type } => {
+            (first(), "validation-path_param", path_param_args(value, expected_type))
+        }
+        ErrorKind::InvalidUtf8InPathParam { key } | ErrorKind::DeserializeError { key, .. } => {
+            (key.clone(), "validation-invalid", TranslateArgs::new())
+        }
+        _ => {
+            tracing::error!(%rejection, "Path parameters do not match the handler");
+            return AppError::Internal;
+        }
+    };
+    let mut errors = FieldErrors::new();
+    errors.add(field, key, args);
+    errors.into()
+}
+
+fn path_param_args(value: &str, expected_type: &str) -> TranslateArgs {
+    TranslateArgs::new().add("value", value).add("expected", expected_type)
+}
+
+/// 请求体读取失败（过大或连接中断）
+fn body_error(field: &str, status: StatusCode) -> AppError {
+    let key = if status == StatusCode::PAYLOAD_TOO_LARGE {
+        "validation-body_too_large"
+    } else {
+        "validation-body"
+    };
+    let mut errors = FieldErrors::new();
+    errors.add(field, key, TranslateArgs::new());
+    errors.into()
+}
+
+/// 流式读取请求体时出错，超过 `DefaultBodyLimit` 时为 413
+fn read_error(field: &str, err: &(dyn StdError + 'static)) -> AppError {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if err.is::<LengthLimitError>() {
+            return body_error(field, StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        source = err.source();
+    }
+    body_error(field, StatusCode::BAD_REQUEST)
+}
+
+fn multipart_error(err: multer::Error) -> AppError {
+    match err {
+        multer::Error::StreamReadFailed(err) => read_error("file", err.as_ref()),
+        err => {
+            tracing::debug!(%err, "Rejected multipart body");
+            let mut errors = FieldErrors::new();
+            errors.add("body", "validation-multipart", TranslateArgs::new());
+            errors.into()
+        }
+    }
+}
+
+/// 批量导入的数据：`multipart/form-data` 中名为 `file` 的文件字段，或直接作为请求体上传
+///
+/// 数据按块流式读取，不会整体缓冲在内存中；总大小仍受路由上的 `DefaultBodyLimit` 限制。
+/// `format` 为根据 Content-Type / 文件名推断出的格式，无法推断时为 `None`。
+pub struct ImportPayload {
+    pub format: Option<DataFormat>,
+    pub body: BoxStream<'static, Result<Bytes, AppError>>,
+}
+
+impl ImportPayload {
+    /// 查询参数中的格式优先，其次为推断出的格式
+    pub fn resolve_format(&self, format: Option<DataFormat>) -> Result<DataFormat, AppError> {
+        format.or(self.format).ok_or_else(|| {
+            let mut errors = FieldErrors::new();
+            errors.add("format", "validation-import_format", TranslateArgs::new());
+            errors.into()
+        })
+    }
+
+    /// 转换为阻塞读取的 `Read`，需在 `spawn_blocking` 中使用
+    ///
+    /// 读取失败时返回的 `io::Error` 内含原始的 `AppError`，可用 [`crate::service::tabular::read_failure`] 取回。
+    pub fn into_reader(self) -> impl Read + Send + 'static {
+        let body = self.body.map_err(io::Error::other);
+        SyncIoBridge::new(StreamReader::new(body))
+    }
+
+    /// 读取全部数据，用于需要整体解析的导入
+    pub async fn bytes(mut self) -> Result<Vec<u8>, AppError> {
+        let mut data = Vec::new();
+        while let Some(chunk) = self.body.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        Ok(data)
+    }
+}
+
+impl<S> FromRequest<S> for ImportPayload
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let stream = req.into_limited_body().into_data_stream();
+
+        if !content_type.starts_with("multipart/form-data") {
+            let body = stream.map_err(|err| read_error("body", &err)).boxed();
+            return Ok(Self { format: DataFormat::from_mime(&content_type), body });
+        }
+
+        let boundary = multer::parse_boundary(&content_type).map_err(multipart_error)?;
+        let mut multipart = multer::Multipart::new(stream, boundary);
+        while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+            if field.name() != Some("file") {
+                continue;
+            }
+            let format = field
+                .content_type()
+                .and_then(|mime| DataFormat::from_mime(mime.as_ref()))
+                .or_else(|| field.file_name().and_then(DataFormat::from_filename));
+            return Ok(Self { format, body: field.map_err(multipart_error).boxed() });
+        }
+        let mut errors = FieldErrors::new();
+        errors.add("file", "validation-required", TranslateArgs::new());
+        Err(errors.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, routing::get};
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize, Validate)]
+    struct Payload {
+        name: String,
+        page: u32,
+    }
+
+    fn field_names(err: AppError) -> Vec<String> {
+        match err {
+            AppError::Validation { errors: Some(errors) } => {
+                let mut names: Vec<String> = errors.into_keys().collect();
+                names.sort();
+                names
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    async fn json_error(body: &str) -> AppError {
+        let req = Request::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        ValidatedJson::<Payload>::from_request(req, &()).await.unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn test_json_rejections() {
+        assert_eq!(field_names(json_error(r#"{"page": 1}"#).await), vec!["name"]);
+        assert_eq!(field_names(json_error(r#"{"name": "x", "page": "one"}"#).await), vec!["page"]);
+        assert!(matches!(json_error("{").await, AppError::BadRequest { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_path_rejection_names_the_parameter() {
+        async fn handler(ValidatedPath((_, _)): ValidatedPath<(i32, i32)>) {}
+        let app = Router::new().route("/items/{id}/aliases/{alias_id}", get(handler));
+
+        let req = Request::builder().uri("/items/1/aliases/x").body(Body::empty()).unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["errors"]["alias_id"].is_string());
+    }
+}
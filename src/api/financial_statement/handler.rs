@@ -0,0 +1,102 @@
+use crate::api::extract::{ImportPayload, ValidatedPath, ValidatedQuery};
+use crate::dto::financial_statement::{
+    FinancialStatementQuery, FinancialStatementResponse, LineItemQuery, LineItemResponse,
+};
+use crate::dto::import::{ImportQuery, ImportReport};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::financial_statement::FinancialStatementService;
+use axum::{
+    Json,
+    extract::State,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(line_items, statements, import),
+    tags((name = "financial-statement", description = "财务报表"))
+)]
+pub struct FinancialStatementApi;
+
+/// 标准科目表
+#[utoipa::path(
+    get,
+    path = "/financial-statements/line-items",
+    tag = "financial-statement",
+    params(LineItemQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<LineItemResponse>>),
+    )
+)]
+pub async fn line_items(
+    State(service): State<Arc<FinancialStatementService>>,
+    ValidatedQuery(query): ValidatedQuery<LineItemQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(APIResponse::success(service.line_items(query))))
+}
+
+/// 查询标的的财务报表
+///
+/// 默认每个报告期只返回最新版本；指定 `as_of` 时只使用该日及之前公告的版本，即当天实际可见的报表。
+/// `all_versions=true` 返回每个报告期的全部版本，可用于查看重述历史。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/financial-statements",
+    tag = "financial-statement",
+    params(("id" = i32, Path, description = "标的 ID"), FinancialStatementQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<FinancialStatementResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn statements(
+    State(service): State<Arc<FinancialStatementService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<FinancialStatementQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.statements(id, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 导入财务报表
+///
+/// 交换格式见 `docs/financial_statements.md`：NDJSON 每行一份报表，CSV 每行一个科目。
+/// 公告日和内容与已有版本相同的报表跳过，其余写入为新版本。`total` / `failed` 按数据行计，
+/// `created`（报告期的首个版本）/ `updated`（重述）/ `unchanged` 按报表计。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/financial-statements:import",
+    tag = "financial-statement",
+    params(("id" = i32, Path, description = "标的 ID"), ImportQuery),
+    request_body(
+        content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+            (String = "multipart/form-data"),
+        ),
+        description = "待导入的数据",
+    ),
+    responses(
+        (status = 200, description = "导入完成（含失败行明细）", body = APIResponse<ImportReport>),
+        (status = 400, description = "表头错误或请求体读取失败", body = ErrorResponse),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 409, description = "并发导入分配到相同版本号，重试即可", body = ErrorResponse),
+        (status = 413, description = "请求体过大"),
+        (status = 422, description = "无法确定数据格式、缺少文件或行数超限", body = ErrorResponse),
+    )
+)]
+pub async fn import(
+    State(service): State<Arc<FinancialStatementService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<ImportQuery>,
+    payload: ImportPayload,
+) -> Result<impl IntoResponse, AppError> {
+    let format = payload.resolve_format(query.format)?;
+    let data = payload.bytes().await?;
+    let report = service.import(id, format, &data, query.dry_run).await?;
+    Ok(Json(APIResponse::success(report)))
+}
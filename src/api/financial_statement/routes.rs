@@ -0,0 +1,18 @@
+use crate::api::extract::MAX_IMPORT_BYTES;
+use crate::api::financial_statement::handler;
+use crate::service::financial_statement::FinancialStatementService;
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<FinancialStatementService>) -> Router {
+    Router::new()
+        .route("/financial-statements/line-items", get(handler::line_items))
+        .route("/instruments/{id}/financial-statements", get(handler::statements))
+        .route(
+            "/instruments/{id}/financial-statements:import",
+            post(handler::import).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+        )
+        .with_state(service)
+}
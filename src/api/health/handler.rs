@@ -0,0 +1,66 @@
+use crate::api::extract::{ValidatedPath, ValidatedQuery};
+use crate::core::context::current_locale;
+use crate::dto::health::{HealthQuery, HealthRefreshResponse, HealthReport};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::health::HealthService;
+use axum::{
+    Json,
+    extract::State,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(report, refresh),
+    tags((name = "financial-health", description = "财务健康诊断"))
+)]
+pub struct HealthApi;
+
+/// 财务健康报告
+///
+/// 基于年报计算财务比率与 Piotroski F、Altman Z、Beneish M 评分，同比项使用上一财年的年报。
+/// 指定 `as_of` 时只使用该日及之前公告的报表版本。结论与各组成项的解释按请求语言（`Accept-Language`）返回。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/health",
+    tag = "financial-health",
+    params(("id" = i32, Path, description = "标的 ID"), HealthQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<HealthReport>),
+        (status = 404, description = "标的不存在或没有该财年的年报", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn report(
+    State(service): State<Arc<HealthService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<HealthQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.report(id, query, &current_locale()).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 重新计算并写入指标
+///
+/// 按公告日回放全部年报，把每个知识日期可见的比率与评分写入 `feature_metric`（`ts` 为报告期末，`known_at` 为公告日），
+/// 之后可通过 `/instruments/{id}/metrics` 按时点读取、通过 `/metrics/{name}` 查询截面。导入或重述年报后调用。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/health:refresh",
+    tag = "financial-health",
+    params(("id" = i32, Path, description = "标的 ID")),
+    responses(
+        (status = 200, description = "计算完成", body = APIResponse<HealthRefreshResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+    )
+)]
+pub async fn refresh(
+    State(service): State<Arc<HealthService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.refresh(id).await?;
+    Ok(Json(APIResponse::success(response)))
+}
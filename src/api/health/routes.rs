@@ -0,0 +1,12 @@
+use crate::api::health::handler;
+use crate::service::health::HealthService;
+use axum::Router;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<HealthService>) -> Router {
+    Router::new()
+        .route("/instruments/{id}/health", get(handler::report))
+        .route("/instruments/{id}/health:refresh", post(handler::refresh))
+        .with_state(service)
+}
@@ -1,76 +1,278 @@
-use crate::dto::response::APIResponse;
+use crate::api::etag::{IfMatch, IfNoneMatch, etag};
+use crate::api::extract::{ImportPayload, ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::import::{DataFormat, ExportQuery, ImportQuery, ImportReport};
+use crate::dto::response::{APIResponse, ErrorResponse};
 use crate::dto::instrument::{
-    CreateInstrumentRequest, InstrumentResponse, UpdateInstrumentRequest,
+    CreateInstrumentRequest, InstrumentFilter, InstrumentHistoryFilter, InstrumentHistoryResponse,
+    InstrumentResponse, UpdateInstrumentRequest,
 };
+use crate::dto::pagination::{PageParams, PageResponse};
 use crate::error::code::AppError;
-use crate::service::instrument::{InstrumentService};
+use crate::service::instrument::InstrumentService;
 use axum::{
     Json,
-    extract::{Path, State},
-    response::IntoResponse,
+    body::Body,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
 };
 use std::sync::Arc;
+use utoipa::OpenApi;
 
-pub struct InstrumentHandler;
+#[derive(OpenApi)]
+#[openapi(
+    paths(create, get_all, get_by_id, replace, patch, delete, history, import, export),
+    tags((name = "instrument", description = "交易标的"))
+)]
+pub struct InstrumentApi;
 
-impl InstrumentHandler {
-    pub async fn create(
-        State(service): State<Arc<InstrumentService>>,
-        Json(req): Json<CreateInstrumentRequest>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let cmd = CreateInstrumentRequest {
-            exchange: req.exchange,
-            symbol: req.symbol,
-            asset_type: req.asset_type,
-            name: req.name,
-        };
+/// 创建交易标的
+#[utoipa::path(
+    post,
+    path = "/instruments",
+    tag = "instrument",
+    request_body = CreateInstrumentRequest,
+    responses(
+        (status = 201, description = "创建成功", body = APIResponse<InstrumentResponse>,
+            headers(("ETag" = String, description = "初始版本"))),
+        (status = 404, description = "交易所不存在", body = ErrorResponse),
+        (status = 409, description = "同一交易所下 symbol 已存在", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn create(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedJson(req): ValidatedJson<CreateInstrumentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.create(req).await?;
 
-        let model = service.create(cmd).await?;
-        let response: InstrumentResponse = model.into();
+    Ok((
+        StatusCode::CREATED,
+        [(header::ETAG, etag(response.version))],
+        Json(APIResponse::success(response)),
+    ))
+}
 
-        Ok((axum::http::StatusCode::CREATED, Json(APIResponse::success(response))))
+/// 根据 ID 获取交易标的
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}",
+    tag = "instrument",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("If-None-Match" = Option<String>, Header, description = "客户端缓存的 ETag，版本未变化时返回 304"),
+    ),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<InstrumentResponse>,
+            headers(("ETag" = String, description = "当前版本"))),
+        (status = 304, description = "版本未变化"),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+    )
+)]
+pub async fn get_by_id(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    if_none_match: IfNoneMatch,
+) -> Result<Response, AppError> {
+    let response = service.get_by_id(id).await?;
+    let response = response.ok_or(AppError::NotFound {
+        resource: "Instrument".to_string(),
+        identifier: Some(id.to_string()),
+    })?;
+    let headers = [(header::ETAG, etag(response.version))];
+    if if_none_match.is_fresh(response.version) {
+        return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
     }
+    Ok((headers, Json(APIResponse::success(response))).into_response())
+}
 
-    pub async fn get_by_id(
-        State(service): State<Arc<InstrumentService>>,
-        Path(id): Path<i32>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let model = service.get_by_id(id).await?;
-        let model = model.ok_or(AppError::NotFound {
-            resource: "Instrument".to_string(),
-            identifier: Some(id.to_string()),
-        })?;
-        let response: InstrumentResponse = model.into();
-        Ok(Json(APIResponse::success(response)))
-    }
+/// 分页查询交易标的
+#[utoipa::path(
+    get,
+    path = "/instruments",
+    tag = "instrument",
+    params(InstrumentFilter, PageParams),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<PageResponse<InstrumentResponse>>),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn get_all(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedQuery(filter): ValidatedQuery<InstrumentFilter>,
+    ValidatedQuery(params): ValidatedQuery<PageParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let responses = service.list(filter, params).await?;
+    Ok(Json(APIResponse::success(responses)))
+}
 
-    pub async fn get_all(
-        State(service): State<Arc<InstrumentService>>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let models = service.get_all().await?;
-        let responses: Vec<InstrumentResponse> = models.into_iter().map(Into::into).collect();
-        Ok(Json(APIResponse::success(responses)))
-    }
+/// 部分更新交易标的，只修改请求中提供的字段，可空字段显式传 `null` 时清空
+#[utoipa::path(
+    patch,
+    path = "/instruments/{id}",
+    tag = "instrument",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("If-Match" = String, Header, description = "读取时得到的 ETag，如 `\"3\"`，弱标签 `W/\"3\"` 不匹配；`*` 表示不校验版本"),
+    ),
+    request_body = UpdateInstrumentRequest,
+    responses(
+        (status = 200, description = "更新成功", body = APIResponse<InstrumentResponse>, headers(("ETag" = String, description = "更新后的版本"))),
+        (status = 400, description = "ID 与路径不一致", body = ErrorResponse),
+        (status = 404, description = "标的或交易所不存在", body = ErrorResponse),
+        (status = 409, description = "同一交易所下 symbol 已存在", body = ErrorResponse),
+        (status = 412, description = "版本已被他人修改", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+        (status = 428, description = "缺少 If-Match 请求头", body = ErrorResponse),
+    )
+)]
+pub async fn patch(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    IfMatch(if_match): IfMatch,
+    ValidatedJson(req): ValidatedJson<UpdateInstrumentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.patch(id, req, &if_match).await?;
+    Ok(([(header::ETAG, etag(response.version))], Json(APIResponse::success(response))))
+}
 
-    pub async fn update(
-        State(service): State<Arc<InstrumentService>>,
-        Json(req): Json<UpdateInstrumentRequest>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let model = service.update(req).await?;
-        let response: InstrumentResponse = model.into();
-        Ok(Json(APIResponse::success(response)))
-    }
-    pub async fn delete(
-        State(service): State<Arc<InstrumentService>>,
-        Path(id): Path<i32>,
-    ) -> Result<impl IntoResponse, AppError> {
-        let deleted = service.delete(id).await?;
-        if !deleted {
-            return Err(AppError::NotFound {
-                resource: "Instrument".to_string(),
-                identifier: Some(id.to_string()),
-            });
-        }
-        Ok(axum::http::StatusCode::NO_CONTENT)
-    }
-}
\ No newline at end of file
+/// 整体替换交易标的，未提供的可选字段会被清空
+#[utoipa::path(
+    put,
+    path = "/instruments/{id}",
+    tag = "instrument",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("If-Match" = String, Header, description = "读取时得到的 ETag，如 `\"3\"`，弱标签 `W/\"3\"` 不匹配；`*` 表示不校验版本"),
+    ),
+    request_body = CreateInstrumentRequest,
+    responses(
+        (status = 200, description = "替换成功", body = APIResponse<InstrumentResponse>, headers(("ETag" = String, description = "更新后的版本"))),
+        (status = 404, description = "标的或交易所不存在", body = ErrorResponse),
+        (status = 409, description = "同一交易所下 symbol 已存在", body = ErrorResponse),
+        (status = 412, description = "版本已被他人修改", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+        (status = 428, description = "缺少 If-Match 请求头", body = ErrorResponse),
+    )
+)]
+pub async fn replace(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    IfMatch(if_match): IfMatch,
+    ValidatedJson(req): ValidatedJson<CreateInstrumentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.replace(id, req, &if_match).await?;
+    Ok(([(header::ETAG, etag(response.version))], Json(APIResponse::success(response))))
+}
+
+/// 删除交易标的（软删除，变更历史保留）
+#[utoipa::path(
+    delete,
+    path = "/instruments/{id}",
+    tag = "instrument",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("If-Match" = String, Header, description = "读取时得到的 ETag，如 `\"3\"`，弱标签 `W/\"3\"` 不匹配；`*` 表示不校验版本"),
+    ),
+    responses(
+        (status = 204, description = "删除成功"),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 412, description = "版本已被他人修改", body = ErrorResponse),
+        (status = 428, description = "缺少 If-Match 请求头", body = ErrorResponse),
+    )
+)]
+pub async fn delete(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    IfMatch(if_match): IfMatch,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete(id, &if_match).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 批量导入交易标的（CSV / NDJSON），按 (交易所, symbol) upsert
+///
+/// 数据可通过 `multipart/form-data` 的 `file` 字段上传，也可直接作为请求体发送。
+/// CSV 首行为表头，列名与创建接口的字段一致，`metadata` 列为 JSON 文本。
+#[utoipa::path(
+    post,
+    path = "/instruments:import",
+    tag = "instrument",
+    params(ImportQuery),
+    request_body(
+        content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+            (String = "multipart/form-data"),
+        ),
+        description = "待导入的数据",
+    ),
+    responses(
+        (status = 200, description = "导入完成（含失败行明细）", body = APIResponse<ImportReport>),
+        (status = 400, description = "表头错误或请求体读取失败", body = ErrorResponse),
+        (status = 413, description = "请求体过大"),
+        (status = 422, description = "无法确定数据格式、缺少文件或行数超限", body = ErrorResponse),
+    )
+)]
+pub async fn import(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedQuery(query): ValidatedQuery<ImportQuery>,
+    payload: ImportPayload,
+) -> Result<impl IntoResponse, AppError> {
+    let format = payload.resolve_format(query.format)?;
+    let report = service.import(format, payload.into_reader(), query.dry_run).await?;
+    Ok(Json(APIResponse::success(report)))
+}
+
+/// 流式导出交易标的，导出结果可直接再次导入
+#[utoipa::path(
+    get,
+    path = "/instruments:export",
+    tag = "instrument",
+    params(InstrumentFilter, ExportQuery),
+    responses(
+        (status = 200, description = "导出数据", content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+        )),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn export(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedQuery(filter): ValidatedQuery<InstrumentFilter>,
+    ValidatedQuery(query): ValidatedQuery<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let format = query.format.unwrap_or(DataFormat::Csv);
+    let stream = service.export(filter, format).await?;
+    let disposition = format!("attachment; filename=\"instruments.{}\"", format.extension());
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(stream),
+    ))
+}
+
+/// 查询交易标的字段变更历史
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/history",
+    tag = "instrument",
+    params(("id" = i32, Path, description = "标的 ID"), InstrumentHistoryFilter, PageParams),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<PageResponse<InstrumentHistoryResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn history(
+    State(service): State<Arc<InstrumentService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(filter): ValidatedQuery<InstrumentHistoryFilter>,
+    ValidatedQuery(params): ValidatedQuery<PageParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.history(id, filter, params).await?;
+    Ok(Json(APIResponse::success(response)))
+}
@@ -1,15 +1,24 @@
-use crate::api::instrument::handler::InstrumentHandler;
+use crate::api::extract::MAX_IMPORT_BYTES;
+use crate::api::instrument::handler;
 use crate::service::instrument::InstrumentService;
 use axum::Router;
-use axum::routing::{delete, get, post, put};
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get, patch, post, put};
 use std::sync::Arc;
 
 pub fn routes(service: Arc<InstrumentService>) -> Router {
     Router::new()
-        .route("/instruments", post(InstrumentHandler::create))
-        .route("/instruments", get(InstrumentHandler::get_all))
-        .route("/instruments/{id}", get(InstrumentHandler::get_by_id))
-        .route("/instruments/{id}", put(InstrumentHandler::update))
-        .route("/instruments/{id}", delete(InstrumentHandler::delete))
+        .route("/instruments", post(handler::create))
+        .route("/instruments", get(handler::get_all))
+        .route(
+            "/instruments:import",
+            post(handler::import).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+        )
+        .route("/instruments:export", get(handler::export))
+        .route("/instruments/{id}", get(handler::get_by_id))
+        .route("/instruments/{id}", put(handler::replace))
+        .route("/instruments/{id}", patch(handler::patch))
+        .route("/instruments/{id}", delete(handler::delete))
+        .route("/instruments/{id}/history", get(handler::history))
         .with_state(service)
 }
@@ -0,0 +1,51 @@
+use crate::api::extract::{ValidatedPath, ValidatedQuery};
+use crate::dto::kline::{KlineAdjust, KlineBar, KlineColumns, KlineFormat, KlineQuery};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::kline::KlineService;
+use axum::{
+    Json,
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(query), components(schemas(KlineFormat, KlineAdjust, KlineColumns)), tags((name = "kline", description = "K 线行情")))]
+pub struct KlineApi;
+
+/// 查询标的的 K 线
+///
+/// 库中没有所请求周期的数据时由 1m 重采样：日内周期按交易所交易时段对齐（如 SSE 的 1h 从 09:30、13:00 起算），
+/// 日线及以上按交易所时区的本地日期归并。`format=columnar` 时按列返回数组，`ts` 为 Unix 毫秒时间戳。
+/// `adjust=forward|backward` 时按公司行动计算的复权因子调整价格（成交量不变），因子见 `/instruments/{id}/adjustment-factors`。
+/// 查询参数中的 `+` 需编码为 `%2B`。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/klines",
+    tag = "kline",
+    params(("id" = i32, Path, description = "标的 ID"), KlineQuery),
+    responses(
+        (status = 200, description = "按时间升序的 K 线；`format=columnar` 时为 `KlineColumns`", body = APIResponse<Vec<KlineBar>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败或重采样范围过大", body = ErrorResponse),
+    )
+)]
+pub async fn query(
+    State(service): State<Arc<KlineService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<KlineQuery>,
+) -> Result<Response, AppError> {
+    let klines = service.query(id, &query).await?;
+    let response = match query.format {
+        KlineFormat::Json => {
+            let bars: Vec<KlineBar> = klines.into_iter().map(KlineBar::from).collect();
+            Json(APIResponse::success(bars)).into_response()
+        }
+        KlineFormat::Columnar => {
+            Json(APIResponse::success(KlineColumns::new(query.interval(), klines))).into_response()
+        }
+    };
+    Ok(response)
+}
@@ -0,0 +1,11 @@
+use crate::api::kline::handler;
+use crate::service::kline::KlineService;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn routes(service: Arc<KlineService>) -> Router {
+    Router::new()
+        .route("/instruments/{id}/klines", get(handler::query))
+        .with_state(service)
+}
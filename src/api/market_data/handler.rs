@@ -0,0 +1,79 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath};
+use crate::dto::market_data::{CreateImportJobRequest, ImportJobResponse};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::market_data_import::MarketDataImportService;
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(create_import, list_imports, get_import),
+    tags((name = "market-data", description = "历史行情导入"))
+)]
+pub struct MarketDataApi;
+
+/// 创建 K 线文件导入任务
+///
+/// 从服务端 `market_data.import_dir` 目录读取 CSV 或 Parquet 文件，按列映射解析后在后台流式写入，
+/// 重复的 (标的, 时间) 以文件中靠后的行为准。任务状态只保存在内存中，服务重启后丢失；
+/// 命令行 `uniquant import-klines` 提供相同的导入能力。
+#[utoipa::path(
+    post,
+    path = "/market-data/imports",
+    tag = "market-data",
+    request_body = CreateImportJobRequest,
+    responses(
+        (status = 202, description = "任务已创建", body = APIResponse<ImportJobResponse>),
+        (status = 404, description = "文件、标的或交易所不存在", body = ErrorResponse),
+        (status = 422, description = "路径、格式、时区或列映射校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn create_import(
+    State(service): State<Arc<MarketDataImportService>>,
+    ValidatedJson(req): ValidatedJson<CreateImportJobRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.submit(req).await?;
+    Ok((StatusCode::ACCEPTED, Json(APIResponse::success(response))))
+}
+
+/// 查询导入任务列表，最新创建的在前
+#[utoipa::path(
+    get,
+    path = "/market-data/imports",
+    tag = "market-data",
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<ImportJobResponse>>),
+    )
+)]
+pub async fn list_imports(
+    State(service): State<Arc<MarketDataImportService>>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(APIResponse::success(service.jobs().await)))
+}
+
+/// 查询导入任务，运行中的任务返回实时进度
+#[utoipa::path(
+    get,
+    path = "/market-data/imports/{id}",
+    tag = "market-data",
+    params(("id" = uuid::Uuid, Path, description = "任务 ID")),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<ImportJobResponse>),
+        (status = 404, description = "任务不存在", body = ErrorResponse),
+    )
+)]
+pub async fn get_import(
+    State(service): State<Arc<MarketDataImportService>>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.job(id).await?;
+    Ok(Json(APIResponse::success(response)))
+}
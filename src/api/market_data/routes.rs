@@ -0,0 +1,12 @@
+use crate::api::market_data::handler;
+use crate::service::market_data_import::MarketDataImportService;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn routes(service: Arc<MarketDataImportService>) -> Router {
+    Router::new()
+        .route("/market-data/imports", get(handler::list_imports).post(handler::create_import))
+        .route("/market-data/imports/{id}", get(handler::get_import))
+        .with_state(service)
+}
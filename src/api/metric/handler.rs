@@ -0,0 +1,133 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::metric::{
+    MetricBatchRequest, MetricCrossSectionItem, MetricCrossSectionQuery, MetricDefinitionRequest,
+    MetricDefinitionResponse, MetricSeriesQuery, MetricValue, MetricWriteResponse,
+};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::error::code::AppError;
+use crate::service::metric::MetricService;
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(definitions, put_definition, series, write, cross_section),
+    tags((name = "metric", description = "基本面与特征指标"))
+)]
+pub struct MetricApi;
+
+/// 指标目录
+#[utoipa::path(
+    get,
+    path = "/metric-definitions",
+    tag = "metric",
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<MetricDefinitionResponse>>),
+    )
+)]
+pub async fn definitions(State(service): State<Arc<MetricService>>) -> Result<impl IntoResponse, AppError> {
+    let response = service.definitions().await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 登记或更新指标
+///
+/// 指标名须以小写字母开头，只含小写字母、数字和下划线。
+#[utoipa::path(
+    put,
+    path = "/metric-definitions/{name}",
+    tag = "metric",
+    params(("name" = String, Path, description = "指标名")),
+    request_body = MetricDefinitionRequest,
+    responses(
+        (status = 200, description = "更新成功", body = APIResponse<MetricDefinitionResponse>),
+        (status = 201, description = "登记成功", body = APIResponse<MetricDefinitionResponse>),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn put_definition(
+    State(service): State<Arc<MetricService>>,
+    ValidatedPath(name): ValidatedPath<String>,
+    ValidatedJson(req): ValidatedJson<MetricDefinitionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let (created, response) = service.put_definition(name, req).await?;
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+    Ok((status, Json(APIResponse::success(response))))
+}
+
+/// 查询标的的指标时间序列
+///
+/// 按 (name, ts) 升序。同一 ts 的重述保留为多个版本：指定 `as_of` 时只取该日结束前已公布的最新一版，
+/// 回测时用它避免读到之后才公布的数据；不指定时取最新一版。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/metrics",
+    tag = "metric",
+    params(("id" = i32, Path, description = "标的 ID"), MetricSeriesQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<MetricValue>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn series(
+    State(service): State<Arc<MetricService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<MetricSeriesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.series(id, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 批量写入标的的指标值
+///
+/// 指标须已登记；(name, ts, known_at) 相同的记录覆盖为新值，重述请使用新的 `known_at`。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/metrics",
+    tag = "metric",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = MetricBatchRequest,
+    responses(
+        (status = 200, description = "写入成功", body = APIResponse<MetricWriteResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "字段校验失败或指标未登记", body = ErrorResponse),
+    )
+)]
+pub async fn write(
+    State(service): State<Arc<MetricService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<MetricBatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.write(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 查询指标在某日的截面
+///
+/// 每个标的一条：所属时间与公布时间都在该日（所属交易所的时区）结束前的最新值，按标的 ID 升序。
+#[utoipa::path(
+    get,
+    path = "/metrics/{name}",
+    tag = "metric",
+    params(("name" = String, Path, description = "指标名"), MetricCrossSectionQuery),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<MetricCrossSectionItem>>),
+        (status = 422, description = "查询参数格式错误", body = ErrorResponse),
+        (status = 404, description = "指标未登记", body = ErrorResponse),
+    )
+)]
+pub async fn cross_section(
+    State(service): State<Arc<MetricService>>,
+    ValidatedPath(name): ValidatedPath<String>,
+    ValidatedQuery(query): ValidatedQuery<MetricCrossSectionQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.cross_section(name, query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
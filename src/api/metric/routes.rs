@@ -0,0 +1,14 @@
+use crate::api::metric::handler;
+use crate::service::metric::MetricService;
+use axum::Router;
+use axum::routing::{get, put};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<MetricService>) -> Router {
+    Router::new()
+        .route("/metric-definitions", get(handler::definitions))
+        .route("/metric-definitions/{name}", put(handler::put_definition))
+        .route("/instruments/{id}/metrics", get(handler::series).post(handler::write))
+        .route("/metrics/{name}", get(handler::cross_section))
+        .with_state(service)
+}
@@ -6,30 +6,7 @@ use axum::{
 };
 use uuid::Uuid;
 use std::sync::Arc;
-use tokio::task_local;
-
-#[derive(Clone, Debug)]
-pub struct RequestContext {
-    pub request_id: String,
-    pub lang: String,
-}
-
-impl RequestContext {
-    pub fn new(request_id: String, lang: String) -> Self {
-        Self {
-            request_id,
-            lang,
-        }
-    }
-}
-
-task_local! {
-    pub static CONTEXT: Arc<RequestContext>;
-}
-
-pub fn get_request_context() -> Arc<RequestContext> {
-    CONTEXT.with(|ctx| ctx.clone())
-}
+use crate::core::context::{RequestContext, CONTEXT};
 
 pub async fn request_context_middleware(
     req: Request<Body>,
@@ -45,9 +22,17 @@ pub async fn request_context_middleware(
         .trim()
         .to_string();
 
+    let actor = req
+        .headers()
+        .get("X-User-Id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.chars().take(64).collect());
+
     let request_id = Uuid::new_v4().to_string();
 
-    let ctx = Arc::new(RequestContext::new(request_id, lang));
+    let ctx = Arc::new(RequestContext::new(request_id, lang, actor));
 
     // 使用 task_local 运行异步任务，将上下文绑定到当前请求
     CONTEXT.scope(ctx, async {
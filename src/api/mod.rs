@@ -1,5 +1,20 @@
+pub mod calendar;
+pub mod corporate_action;
+pub mod data_quality;
+pub mod etag;
+pub mod exchange;
+pub mod financial_statement;
+pub mod extract;
+pub mod health;
 pub mod instrument;
+pub mod kline;
+pub mod market_data;
+pub mod metric;
 pub mod middleware;
+pub mod openapi;
+pub mod search;
+pub mod symbology;
+pub mod tick;
 pub mod error;
 
 use std::sync::Arc;
@@ -11,6 +26,7 @@ use tower_http::{
 
 use axum::{
     Router,
+    http::header,
     routing::get,
 };
 use tokio::net::TcpListener;
@@ -34,6 +50,18 @@ impl WebServer {
     pub fn new(config: Arc<AppConfig>, service_factory: Arc<ServiceFactory>) -> Result<Self> {
         // 1. 从工厂获取所有服务
         let instrument_service = service_factory.instrument_service();
+        let exchange_service = service_factory.exchange_service();
+        let symbology_service = service_factory.symbology_service();
+        let search_service = service_factory.search_service();
+        let calendar_service = service_factory.calendar_service();
+        let kline_service = service_factory.kline_service();
+        let market_data_import_service = service_factory.market_data_import_service();
+        let data_quality_service = service_factory.data_quality_service();
+        let tick_service = service_factory.tick_service();
+        let corporate_action_service = service_factory.corporate_action_service();
+        let metric_service = service_factory.metric_service();
+        let financial_statement_service = service_factory.financial_statement_service();
+        let health_service = service_factory.health_service();
         // let strategy_service = service_factory.strategy_service();
         // ... 其他服务
 
@@ -43,9 +71,29 @@ impl WebServer {
             .route("/health", get(health_check))
             // 合并所有业务模块的路由
             .merge(instrument::routes::routes(instrument_service))
+            .merge(exchange::routes::routes(exchange_service))
+            .merge(symbology::routes::routes(symbology_service))
+            .merge(search::routes::routes(search_service))
+            .merge(calendar::routes::routes(calendar_service))
+            .merge(kline::routes::routes(kline_service))
+            .merge(market_data::routes::routes(market_data_import_service))
+            .merge(data_quality::routes::routes(data_quality_service))
+            .merge(tick::routes::routes(tick_service))
+            .merge(corporate_action::routes::routes(corporate_action_service))
+            .merge(metric::routes::routes(metric_service))
+            .merge(financial_statement::routes::routes(financial_statement_service))
+            .merge(health::routes::routes(health_service))
+            // OpenAPI 文档与 Swagger UI
+            .merge(openapi::routes()?)
             .layer(axum::middleware::from_fn(middleware::context::request_context_middleware))
             .layer(TraceLayer::new_for_http())
-            .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any));
+            .layer(
+                CorsLayer::new()
+                    .allow_origin(Any)
+                    .allow_methods(Any)
+                    .allow_headers(Any)
+                    .expose_headers([header::ETAG]),
+            );
 
         // 3. 解析服务器地址
         let addr: SocketAddr = config.server.addr.parse()?;
@@ -70,6 +118,12 @@ impl WebServer {
 }
 
 /// 健康检查处理器
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "system",
+    responses((status = 200, description = "服务可用", body = String, content_type = "text/plain"))
+)]
 async fn health_check() -> &'static str {
     "OK"
 }
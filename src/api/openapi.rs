@@ -0,0 +1,108 @@
+//! OpenAPI 文档与 Swagger UI
+//!
+//! - `/api-docs/openapi.json`、`/api-docs/openapi.yaml`：完整的 OpenAPI 3.1 文档
+//! - `/swagger-ui`：在线调试页面
+
+use axum::{Router, http::header, routing::get};
+use utoipa::openapi::extensions::ExtensionsBuilder;
+use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::api::calendar::handler::CalendarApi;
+use crate::api::corporate_action::handler::CorporateActionApi;
+use crate::api::data_quality::handler::DataQualityApi;
+use crate::api::exchange::handler::ExchangeApi;
+use crate::api::financial_statement::handler::FinancialStatementApi;
+use crate::api::health::handler::HealthApi;
+use crate::api::instrument::handler::InstrumentApi;
+use crate::api::kline::handler::KlineApi;
+use crate::api::market_data::handler::MarketDataApi;
+use crate::api::metric::handler::MetricApi;
+use crate::api::search::handler::SearchApi;
+use crate::api::symbology::handler::SymbologyApi;
+use crate::api::tick::handler::TickApi;
+use crate::error::code::ALL_CODES;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "UniQuant API",
+        description = "所有接口均返回统一的 `APIResponse` 信封，`code` 的取值见 `AppErrorCode`。"
+    ),
+    paths(super::health_check),
+    tags((name = "system", description = "系统")),
+    modifiers(&ErrorCodes)
+)]
+pub struct ApiDoc;
+
+impl ApiDoc {
+    /// 合并各业务模块的接口文档
+    pub fn build() -> utoipa::openapi::OpenApi {
+        let mut doc = Self::openapi();
+        doc.merge(InstrumentApi::openapi());
+        doc.merge(ExchangeApi::openapi());
+        doc.merge(SymbologyApi::openapi());
+        doc.merge(SearchApi::openapi());
+        doc.merge(CalendarApi::openapi());
+        doc.merge(KlineApi::openapi());
+        doc.merge(MarketDataApi::openapi());
+        doc.merge(DataQualityApi::openapi());
+        doc.merge(TickApi::openapi());
+        doc.merge(CorporateActionApi::openapi());
+        doc.merge(MetricApi::openapi());
+        doc.merge(FinancialStatementApi::openapi());
+        doc.merge(HealthApi::openapi());
+        doc
+    }
+}
+
+/// 将 codes.yaml 中的全部返回码注册为 `AppErrorCode` 枚举 schema
+struct ErrorCodes;
+
+impl Modify for ErrorCodes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let mut description = String::from("| code | name | HTTP | description |\n|---|---|---|---|\n");
+        for info in ALL_CODES {
+            description.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                info.code, info.name, info.http_status, info.description
+            ));
+        }
+
+        let extensions = ExtensionsBuilder::new()
+            .add("x-enum-varnames", ALL_CODES.iter().map(|c| c.name).collect::<Vec<_>>())
+            .add("x-enum-descriptions", ALL_CODES.iter().map(|c| c.description).collect::<Vec<_>>())
+            .build();
+
+        let schema = ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int32)))
+            .enum_values(Some(ALL_CODES.iter().map(|c| c.code)))
+            .description(Some(description))
+            .extensions(Some(extensions))
+            .build();
+
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .schemas
+            .insert("AppErrorCode".to_string(), schema.into());
+    }
+}
+
+/// 文档相关路由
+pub fn routes() -> anyhow::Result<Router> {
+    let doc = ApiDoc::build();
+    let yaml = doc.to_yaml()?;
+
+    Ok(Router::new()
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", doc))
+        .route(
+            "/api-docs/openapi.yaml",
+            get(move || {
+                let yaml = yaml.clone();
+                async move { ([(header::CONTENT_TYPE, "application/yaml")], yaml) }
+            }),
+        ))
+}
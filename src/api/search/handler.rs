@@ -0,0 +1,33 @@
+use crate::api::extract::ValidatedQuery;
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::dto::search::{SearchHit, SearchQuery};
+use crate::error::code::AppError;
+use crate::service::search::SearchService;
+use axum::{Json, extract::State, response::IntoResponse};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(search), tags((name = "search", description = "标的搜索")))]
+pub struct SearchApi;
+
+/// 按代码、名称、别名或拼音搜索标的
+///
+/// 依次进行完全匹配、前缀、子串和编辑距离模糊匹配，如 `apple`、`茅台`、`gzmt`、`btc`。
+#[utoipa::path(
+    get,
+    path = "/instruments/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "搜索结果，按匹配度排序", body = APIResponse<Vec<SearchHit>>),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn search(
+    State(service): State<Arc<SearchService>>,
+    ValidatedQuery(query): ValidatedQuery<SearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.search(query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
@@ -0,0 +1,11 @@
+use crate::api::search::handler;
+use crate::service::search::SearchService;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn routes(service: Arc<SearchService>) -> Router {
+    Router::new()
+        .route("/instruments/search", get(handler::search))
+        .with_state(service)
+}
@@ -0,0 +1,108 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::dto::symbology::{AliasResponse, CreateAliasRequest, ResolveQuery, ResolvedInstrument};
+use crate::error::code::AppError;
+use crate::service::symbology::SymbologyService;
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(resolve, list_aliases, add_alias, delete_alias),
+    tags((name = "symbology", description = "代码规范化与跨来源映射"))
+)]
+pub struct SymbologyApi;
+
+/// 将任意来源的代码解析为标的
+///
+/// 例如 `source=binance&symbol=BTC/USDT`、`source=isin&symbol=US0378331005`、
+/// `source=canonical&symbol=BINANCE:BTC-USDT`。
+#[utoipa::path(
+    get,
+    path = "/instruments/resolve",
+    tag = "symbology",
+    params(ResolveQuery),
+    responses(
+        (status = 200, description = "解析成功", body = APIResponse<ResolvedInstrument>),
+        (status = 400, description = "规范 ID 格式错误", body = ErrorResponse),
+        (status = 404, description = "无法解析到标的", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn resolve(
+    State(service): State<Arc<SymbologyService>>,
+    ValidatedQuery(query): ValidatedQuery<ResolveQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.resolve(query).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 查询标的的全部别名
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/aliases",
+    tag = "symbology",
+    params(("id" = i32, Path, description = "标的 ID")),
+    responses(
+        (status = 200, description = "查询成功", body = APIResponse<Vec<AliasResponse>>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+    )
+)]
+pub async fn list_aliases(
+    State(service): State<Arc<SymbologyService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.list_aliases(id).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 为标的添加别名
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/aliases",
+    tag = "symbology",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = CreateAliasRequest,
+    responses(
+        (status = 201, description = "添加成功", body = APIResponse<AliasResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 409, description = "该来源下的代码已映射到标的", body = ErrorResponse),
+        (status = 422, description = "字段校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn add_alias(
+    State(service): State<Arc<SymbologyService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<CreateAliasRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.add_alias(id, req).await?;
+    Ok((StatusCode::CREATED, Json(APIResponse::success(response))))
+}
+
+/// 删除标的的别名
+#[utoipa::path(
+    delete,
+    path = "/instruments/{id}/aliases/{alias_id}",
+    tag = "symbology",
+    params(
+        ("id" = i32, Path, description = "标的 ID"),
+        ("alias_id" = i32, Path, description = "别名 ID"),
+    ),
+    responses(
+        (status = 204, description = "删除成功"),
+        (status = 404, description = "别名不存在", body = ErrorResponse),
+    )
+)]
+pub async fn delete_alias(
+    State(service): State<Arc<SymbologyService>>,
+    ValidatedPath((id, alias_id)): ValidatedPath<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    service.delete_alias(id, alias_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,14 @@
+use crate::api::symbology::handler;
+use crate::service::symbology::SymbologyService;
+use axum::Router;
+use axum::routing::{delete, get, post};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<SymbologyService>) -> Router {
+    Router::new()
+        .route("/instruments/resolve", get(handler::resolve))
+        .route("/instruments/{id}/aliases", get(handler::list_aliases))
+        .route("/instruments/{id}/aliases", post(handler::add_alias))
+        .route("/instruments/{id}/aliases/{alias_id}", delete(handler::delete_alias))
+        .with_state(service)
+}
@@ -0,0 +1,145 @@
+use crate::api::extract::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::dto::import::DataFormat;
+use crate::dto::response::{APIResponse, ErrorResponse};
+use crate::dto::tick::{
+    KlineRebuildReport, OrderBookBatchRequest, OrderBookRecord, TickRangeQuery, TickWriteResponse,
+    TradeBatchRequest, TradeRecord,
+};
+use crate::error::code::AppError;
+use crate::service::tick::TickService;
+use axum::{
+    Json,
+    body::Body,
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(write_trades, export_trades, write_order_book, export_order_book, rebuild_klines),
+    components(schemas(TradeRecord, OrderBookRecord)),
+    tags((name = "tick", description = "逐笔成交与盘口"))
+)]
+pub struct TickApi;
+
+/// 批量写入逐笔成交
+///
+/// 以 (ts, trade_id) 去重，已存在的成交被跳过，重复提交同一批数据是安全的。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/trades",
+    tag = "tick",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = TradeBatchRequest,
+    responses(
+        (status = 200, description = "写入完成", body = APIResponse<TickWriteResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "请求体校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn write_trades(
+    State(service): State<Arc<TickService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<TradeBatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.write_trades(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 流式导出区间内的逐笔成交
+///
+/// 按 (ts, trade_id) 升序，每行一个 `TradeRecord`。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/trades",
+    tag = "tick",
+    params(("id" = i32, Path, description = "标的 ID"), TickRangeQuery),
+    responses(
+        (status = 200, description = "NDJSON 格式的成交", content((String = "application/x-ndjson"))),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn export_trades(
+    State(service): State<Arc<TickService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<TickRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let stream = service.export_trades(id, query).await?;
+    Ok(([(header::CONTENT_TYPE, DataFormat::Ndjson.content_type())], Body::from_stream(stream)))
+}
+
+/// 批量写入 L2 盘口快照和增量
+///
+/// 档位为 `[price, qty]` 数组；增量中数量为 0 表示删除该档。以 (ts, sequence) 去重。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/order-book",
+    tag = "tick",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = OrderBookBatchRequest,
+    responses(
+        (status = 200, description = "写入完成", body = APIResponse<TickWriteResponse>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "请求体校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn write_order_book(
+    State(service): State<Arc<TickService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<OrderBookBatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.write_order_book(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
+
+/// 流式导出区间内的盘口记录
+///
+/// 按 (ts, sequence) 升序，每行一个 `OrderBookRecord`。
+#[utoipa::path(
+    get,
+    path = "/instruments/{id}/order-book",
+    tag = "tick",
+    params(("id" = i32, Path, description = "标的 ID"), TickRangeQuery),
+    responses(
+        (status = 200, description = "NDJSON 格式的盘口记录", content((String = "application/x-ndjson"))),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "查询参数校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn export_order_book(
+    State(service): State<Arc<TickService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedQuery(query): ValidatedQuery<TickRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let stream = service.export_order_book(id, query).await?;
+    Ok(([(header::CONTENT_TYPE, DataFormat::Ndjson.content_type())], Body::from_stream(stream)))
+}
+
+/// 由逐笔成交重建 1m K 线
+///
+/// 区间向外对齐到整分钟，替换区间内原有的 1m K 线；同样的成交总是得到同样的 K 线，没有成交的分钟不生成 K 线。
+/// 单次最多 31 天。
+#[utoipa::path(
+    post,
+    path = "/instruments/{id}/klines/rebuild",
+    tag = "tick",
+    params(("id" = i32, Path, description = "标的 ID")),
+    request_body = TickRangeQuery,
+    responses(
+        (status = 200, description = "重建完成", body = APIResponse<KlineRebuildReport>),
+        (status = 404, description = "标的不存在", body = ErrorResponse),
+        (status = 422, description = "区间校验失败", body = ErrorResponse),
+    )
+)]
+pub async fn rebuild_klines(
+    State(service): State<Arc<TickService>>,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(req): ValidatedJson<TickRangeQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let response = service.rebuild_klines(id, req).await?;
+    Ok(Json(APIResponse::success(response)))
+}
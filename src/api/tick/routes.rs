@@ -0,0 +1,13 @@
+use crate::api::tick::handler;
+use crate::service::tick::TickService;
+use axum::Router;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn routes(service: Arc<TickService>) -> Router {
+    Router::new()
+        .route("/instruments/{id}/trades", get(handler::export_trades).post(handler::write_trades))
+        .route("/instruments/{id}/order-book", get(handler::export_order_book).post(handler::write_order_book))
+        .route("/instruments/{id}/klines/rebuild", post(handler::rebuild_klines))
+        .with_state(service)
+}
@@ -0,0 +1,135 @@
+//! 命令行：不带子命令时启动 Web 服务，子命令用于一次性的运维任务
+
+use std::path::PathBuf;
+use anyhow::{anyhow, bail, Context};
+use clap::{Args, Parser, Subcommand};
+use serde_json::{json, Map, Value};
+use validator::Validate;
+use crate::dto::market_data::{KlineImportSpec, MarketDataFormat};
+use crate::error::code::AppError;
+use crate::service::factory::ServiceFactory;
+
+/// 可通过 `--map` 配置的列映射字段
+const MAPPABLE_FIELDS: &[&str] =
+    &["ts", "open", "high", "low", "close", "volume", "quote_volume", "trade_count", "symbol"];
+
+#[derive(Debug, Parser)]
+#[command(name = "uniquant", version, about = "UniQuant 行情与标的数据服务")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 导入 CSV / Parquet 格式的历史 K 线文件，完成后输出 JSON 格式的导入报告
+    ImportKlines(ImportKlinesArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ImportKlinesArgs {
+    /// 行情文件路径
+    pub file: PathBuf,
+    /// 导入规则文件（TOML / YAML / JSON），字段同导入接口的请求体，命令行参数优先
+    #[arg(long)]
+    pub spec: Option<PathBuf>,
+    /// 文件格式，默认按扩展名推断
+    #[arg(long, value_enum)]
+    pub format: Option<MarketDataFormat>,
+    /// K 线周期，如 1m、1h、1d
+    #[arg(long)]
+    pub interval: Option<String>,
+    /// 文件只包含一个标的时指定其 ID
+    #[arg(long)]
+    pub instrument_id: Option<i32>,
+    /// 按代码列解析标的时所属的交易所代码
+    #[arg(long)]
+    pub exchange: Option<String>,
+    /// 列映射，可重复，如 `--map ts=open_time --map symbol=code`
+    #[arg(long = "map", value_name = "FIELD=COLUMN")]
+    pub columns: Vec<String>,
+    /// 时间列格式：auto、rfc3339、unix_s、unix_ms、unix_us、unix_ns 或 strftime 格式
+    #[arg(long)]
+    pub ts_format: Option<String>,
+    /// 不带时区的时间按该 IANA 时区解析，默认 UTC
+    #[arg(long)]
+    pub timezone: Option<String>,
+    /// CSV 分隔符
+    #[arg(long)]
+    pub delimiter: Option<char>,
+    /// 只解析并统计，不写入数据库
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl ImportKlinesArgs {
+    /// 合并规则文件与命令行参数
+    fn spec(&self) -> anyhow::Result<KlineImportSpec> {
+        let mut spec = match &self.spec {
+            Some(path) => config::Config::builder()
+                .add_source(config::File::from(path.as_path()))
+                .build()
+                .and_then(|config| config.try_deserialize::<Map<String, Value>>())
+                .with_context(|| format!("Cannot read import spec {}", path.display()))?,
+            None => Map::new(),
+        };
+        let mut set = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                spec.insert(key.to_string(), value);
+            }
+        };
+        set("format", self.format.map(|format| json!(format)));
+        set("interval", self.interval.as_ref().map(|interval| json!(interval)));
+        set("instrument_id", self.instrument_id.map(|id| json!(id)));
+        set("exchange", self.exchange.as_ref().map(|exchange| json!(exchange)));
+        set("ts_format", self.ts_format.as_ref().map(|format| json!(format)));
+        set("timezone", self.timezone.as_ref().map(|tz| json!(tz)));
+        set("delimiter", self.delimiter.map(|delimiter| json!(delimiter)));
+        if self.dry_run {
+            set("dry_run", Some(Value::Bool(true)));
+        }
+
+        if !self.columns.is_empty() {
+            let columns = spec.entry("columns").or_insert_with(|| json!({}));
+            let columns = columns.as_object_mut().ok_or_else(|| anyhow!("columns must be a table"))?;
+            for mapping in &self.columns {
+                let Some((field, column)) = mapping.split_once('=') else {
+                    bail!("Invalid --map \"{mapping}\", expected FIELD=COLUMN");
+                };
+                if !MAPPABLE_FIELDS.contains(&field.trim()) {
+                    bail!("Unknown field \"{field}\" in --map, expected one of: {}", MAPPABLE_FIELDS.join(", "));
+                }
+                columns.insert(field.trim().to_string(), json!(column.trim()));
+            }
+        }
+
+        let spec: KlineImportSpec = serde_json::from_value(Value::Object(spec)).context("Invalid import spec")?;
+        spec.validate().map_err(|errors| describe(errors.into()))?;
+        Ok(spec)
+    }
+}
+
+/// 执行 `import-klines`，报告输出到 stdout
+pub async fn import_klines(factory: &ServiceFactory, args: ImportKlinesArgs) -> anyhow::Result<()> {
+    let spec = args.spec()?;
+    let report = factory
+        .market_data_import_service()
+        .import_file(&args.file, &spec)
+        .await
+        .map_err(describe)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+/// 命令行下展开字段级错误和原始消息
+fn describe(err: AppError) -> anyhow::Error {
+    match err {
+        AppError::Validation { errors: Some(errors) } => {
+            let mut fields: Vec<String> = errors.into_iter().map(|(field, message)| format!("{field}: {message}")).collect();
+            fields.sort();
+            anyhow!("Invalid import spec:\n  {}", fields.join("\n  "))
+        }
+        AppError::BadRequest { message } => anyhow!(message),
+        err => anyhow!(err.localized_message()),
+    }
+}
@@ -35,6 +35,17 @@ pub struct LoggingConfig {
     pub otel_exporter_otlp_endpoint: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct MarketDataConfig {
+    /// 通过 API 导入行情文件时允许读取的目录，请求中的路径相对于该目录
+    #[validate(length(min = 1, message = "行情导入目录不能为空"))]
+    pub import_dir: String,
+
+    /// 同时运行的导入任务数
+    #[validate(range(min = 1, message = "并发导入任务数至少为 1"))]
+    pub max_concurrent_imports: usize,
+}
+
 /// -------------------- 应用配置 --------------------
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct AppConfig {
@@ -50,6 +61,9 @@ pub struct AppConfig {
 
     #[validate(nested)]
     pub logging: LoggingConfig,
+
+    #[validate(nested)]
+    pub market_data: MarketDataConfig,
 }
 
 /// 全局配置实例
@@ -67,6 +81,8 @@ impl AppConfig {
             .set_default("logging.max_size", 10 * 1024 * 1024).unwrap()
             .set_default("logging.rotation", 3).unwrap()
             .set_default("logging.enable_otel_logging", false).unwrap()
+            .set_default("market_data.import_dir", "./data/import").unwrap()
+            .set_default("market_data.max_concurrent_imports", 2).unwrap()
     }
 
     /// 从文件 + 环境变量加载
@@ -0,0 +1,72 @@
+//! 请求上下文：由 API 中间件在每个请求的任务内绑定，服务层据此取得语言和操作人
+
+use std::sync::Arc;
+use accept_language::parse_with_quality;
+use tokio::task_local;
+use unic_langid::{langid, LanguageIdentifier};
+use crate::i18n::supported_locales;
+
+#[derive(Clone, Debug)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub lang: String,
+    /// 操作人，取自 `X-User-Id` 请求头
+    pub actor: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(request_id: String, lang: String, actor: Option<String>) -> Self {
+        Self {
+            request_id,
+            lang,
+            actor,
+        }
+    }
+
+    /// 从用户语言以及支持的语言中协商得到最佳语言
+    pub fn locale(&self) -> LanguageIdentifier {
+        let user_langs = parse_accept_language(self.lang.as_str());
+        let supported = supported_locales().unwrap_or_default();
+        user_langs
+            .iter()
+            .find(|l| supported.contains(l)) // 完整匹配
+            .or_else(|| {
+                // 主区域匹配（zh-CN → zh）
+                user_langs
+                    .iter()
+                    .find_map(|l| supported.iter().find(|s| s.language == l.language))
+            })
+            .cloned()
+            .unwrap_or_else(|| langid!("zh-CN"))
+    }
+}
+
+pub fn parse_accept_language(header: &str) -> Vec<LanguageIdentifier> {
+    // 1. 带权重解析 → Vec<(lang, q)>
+    let ranked = parse_with_quality(header); // e.g. "en-US,en;q=0.9,zh-CN;q=0.8"
+    // 2. 转 LanguageIdentifier （失败就跳过）
+    ranked
+        .into_iter()
+        .filter_map(|(lang_str, _q)| lang_str.parse().ok())
+        .collect()
+}
+
+task_local! {
+    pub static CONTEXT: Arc<RequestContext>;
+}
+
+pub fn get_request_context() -> Arc<RequestContext> {
+    CONTEXT.with(|ctx| ctx.clone())
+}
+
+/// 当前请求协商得到的语言；不在请求上下文中（如后台任务）时返回默认语言
+pub fn current_locale() -> LanguageIdentifier {
+    CONTEXT
+        .try_with(|ctx| ctx.locale())
+        .unwrap_or_else(|_| langid!("zh-CN"))
+}
+
+/// 当前请求的操作人；未携带 `X-User-Id` 或不在请求上下文中时返回 `None`
+pub fn current_actor() -> Option<String> {
+    CONTEXT.try_with(|ctx| ctx.actor.clone()).ok().flatten()
+}
@@ -0,0 +1,87 @@
+//! 条件请求头中的实体标签
+//!
+//! ETag 取资源的 `version`，形如 `"3"`。按 RFC 7232，`If-Match` 使用强比较（弱标签 `W/"3"` 不匹配），
+//! `If-None-Match` 使用弱比较（忽略 `W/` 前缀）。
+
+/// 条件请求头中的实体标签列表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ETags {
+    /// `*`，匹配任意版本
+    Any,
+    Tags(Vec<EntityTag>),
+}
+
+/// 单个实体标签
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityTag {
+    pub version: i32,
+    /// 带 `W/` 前缀的弱标签
+    pub weak: bool,
+}
+
+impl ETags {
+    /// 解析 `"1", W/"2"` 或 `*`；包含无法识别的标签时返回 `None`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value == "*" {
+            return Some(Self::Any);
+        }
+        value
+            .split(',')
+            .map(|tag| {
+                let tag = tag.trim();
+                let (weak, tag) = match tag.strip_prefix("W/") {
+                    Some(tag) => (true, tag),
+                    None => (false, tag),
+                };
+                let version = tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()?;
+                Some(EntityTag { version, weak })
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|tags| !tags.is_empty())
+            .map(Self::Tags)
+    }
+
+    /// 强比较（`If-Match`）：弱标签不匹配任何版本
+    pub fn matches(&self, version: i32) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags.iter().any(|tag| !tag.weak && tag.version == version),
+        }
+    }
+
+    /// 弱比较（`If-None-Match`）：忽略 `W/` 前缀
+    pub fn matches_weak(&self, version: i32) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags.iter().any(|tag| tag.version == version),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_etags() {
+        let strong = |version| EntityTag { version, weak: false };
+        assert_eq!(ETags::parse("*"), Some(ETags::Any));
+        assert_eq!(ETags::parse("\"3\""), Some(ETags::Tags(vec![strong(3)])));
+        assert_eq!(
+            ETags::parse("W/\"1\", \"2\""),
+            Some(ETags::Tags(vec![EntityTag { version: 1, weak: true }, strong(2)]))
+        );
+        assert_eq!(ETags::parse("3"), None);
+        assert_eq!(ETags::parse(""), None);
+        assert!(!ETags::Tags(vec![strong(1)]).matches(2));
+    }
+
+    #[test]
+    fn test_weak_tags_only_match_weakly() {
+        let tags = ETags::parse("W/\"1\", \"2\"").unwrap();
+        assert!(!tags.matches(1));
+        assert!(tags.matches(2));
+        assert!(tags.matches_weak(1));
+    }
+}
@@ -1,2 +1,4 @@
 pub mod config;
+pub mod context;
+pub mod etag;
 pub mod logging;
\ No newline at end of file
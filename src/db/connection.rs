@@ -86,6 +86,20 @@ impl DbPool {
     }
 }
 
+#[cfg(test)]
+impl DbPool {
+    /// 测试用的内存 SQLite，已执行全部迁移
+    ///
+    /// 内存库按连接隔离，因此连接池固定为单个连接。
+    pub async fn in_memory() -> Self {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.max_connections(1).min_connections(1).sqlx_logging(false);
+        let connection = Database::connect(opt).await.expect("connect to in-memory SQLite");
+        Migrator::up(&connection, None).await.expect("apply migrations");
+        Self { connection }
+    }
+}
+
 impl Deref for DbPool {
     type Target = DatabaseConnection;
     fn deref(&self) -> &Self::Target {
@@ -1,39 +1,164 @@
+use sea_orm::sqlx::error::{DatabaseError, ErrorKind};
+use sea_orm::{DbErr, RuntimeErr, SqlErr, SqlxError};
 use thiserror::Error;
 
+/// 数据库错误分类
+///
+/// 由 `DbErr` 按约束类型归类，再由 `AppError` 映射为对应的业务错误码。
 #[derive(Error, Debug)]
 pub enum DbError {
     #[error("Database operation failed: {0}")]
-    SeaOrm(#[from] sea_orm::DbErr),
+    SeaOrm(sea_orm::DbErr),
 
-    #[error("Record not found: {entity} with identifier '{id}'")]
-    NotFound { entity: &'static str, id: String },
+    #[error("A unique constraint was violated on {table:?}: {columns:?}")]
+    UniqueViolation { table: Option<String>, columns: Vec<String> },
 
-    #[error("Authentication failed: {reason}")]
-    AuthenticationFailed { reason: String },
+    /// `still_referenced` 为真表示删除或修改的记录仍被引用，否则为引用的记录不存在
+    #[error("A foreign key constraint was violated: {message}")]
+    ForeignKeyViolation { message: String, still_referenced: bool },
 
-    #[error("Invalid data provided: {field} - {message}")]
-    InvalidData { field: String, message: String },
+    #[error("A not-null constraint was violated on column {column:?}")]
+    NotNullViolation { column: Option<String> },
 
-    #[error("A unique constraint was violated: {constraint}")]
-    UniqueViolation { constraint: &'static str },
+    #[error("A check constraint was violated: {constraint:?}")]
+    CheckViolation { constraint: Option<String> },
+
+    #[error("Database unavailable: {reason}")]
+    Unavailable { reason: String },
 
     #[error("MigrationError failed: {reason}")]
     MigrationError { reason: String },
 }
 
-impl DbError {
-    // 便利构造函数，使调用更简洁
-    pub fn user_not_found(id: &str) -> Self {
-        Self::NotFound {
-            entity: "User",
-            id: id.to_string(),
+impl From<DbErr> for DbError {
+    fn from(err: DbErr) -> Self {
+        // 连接池耗尽、连接断开等可重试的错误
+        if let DbErr::ConnectionAcquire(reason) = &err {
+            return Self::Unavailable { reason: reason.to_string() };
+        }
+        if let Some(sqlx_err) = sqlx_error(&err)
+            && matches!(sqlx_err, SqlxError::PoolTimedOut | SqlxError::PoolClosed | SqlxError::Io(_))
+        {
+            return Self::Unavailable { reason: sqlx_err.to_string() };
+        }
+
+        let Some(db_err) = sqlx_error(&err).and_then(|e| e.as_database_error()) else {
+            return Self::SeaOrm(err);
+        };
+
+        match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                let (table, columns) = constraint_target(db_err);
+                return Self::UniqueViolation { table, columns };
+            }
+            Some(SqlErr::ForeignKeyConstraintViolation(message)) => {
+                // Postgres 区分 `insert or update on table ...` 与 `update or delete on table ...`；
+                // SQLite 只报告 `FOREIGN KEY constraint failed`，按引用的记录不存在处理
+                let still_referenced = message.starts_with("update or delete on table");
+                return Self::ForeignKeyViolation { message, still_referenced };
+            }
+            _ => {}
         }
+
+        match db_err.kind() {
+            ErrorKind::NotNullViolation => {
+                let column = pg_column(db_err)
+                    .or_else(|| constraint_target(db_err).1.into_iter().next());
+                Self::NotNullViolation { column }
+            }
+            ErrorKind::CheckViolation => Self::CheckViolation {
+                constraint: db_err.constraint().map(str::to_string),
+            },
+            _ => Self::SeaOrm(err),
+        }
+    }
+}
+
+/// 取出 sqlx 驱动层错误
+fn sqlx_error(err: &DbErr) -> Option<&SqlxError> {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(e))
+        | DbErr::Query(RuntimeErr::SqlxError(e))
+        | DbErr::Conn(RuntimeErr::SqlxError(e)) => Some(e),
+        _ => None,
+    }
+}
+
+/// 解析约束涉及的表和列
+///
+/// - SQLite：`UNIQUE constraint failed: instrument.exchange_id, instrument.symbol`
+/// - Postgres：表名取自错误字段，列名取自 detail `Key (exchange_id, symbol)=(1, BTC) already exists.`
+fn constraint_target(db_err: &dyn DatabaseError) -> (Option<String>, Vec<String>) {
+    if let Some(pg) = db_err.try_downcast_ref::<sea_orm::sqlx::postgres::PgDatabaseError>() {
+        let columns = pg
+            .detail()
+            .and_then(|detail| detail.strip_prefix("Key ("))
+            .and_then(|rest| rest.split_once(")="))
+            .map(|(cols, _)| cols.split(',').map(|c| c.trim().to_string()).collect())
+            .unwrap_or_default();
+        return (pg.table().map(str::to_string), columns);
     }
 
-    pub fn crate_not_found(id: &str) -> Self {
-        Self::NotFound {
-            entity: "Crate",
-            id: id.to_string(),
+    let message = db_err.message();
+    let Some((_, targets)) = message.split_once(": ") else {
+        return (None, Vec::new());
+    };
+    let mut table = None;
+    let columns = targets
+        .split(',')
+        .map(|target| match target.trim().split_once('.') {
+            Some((t, column)) => {
+                table.get_or_insert_with(|| t.to_string());
+                column.to_string()
+            }
+            None => target.trim().to_string(),
+        })
+        .collect();
+    (table, columns)
+}
+
+fn pg_column(db_err: &dyn DatabaseError) -> Option<String> {
+    db_err
+        .try_downcast_ref::<sea_orm::sqlx::postgres::PgDatabaseError>()
+        .and_then(|pg| pg.column())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectionTrait, Database};
+
+    #[tokio::test]
+    async fn test_classify_sqlite_constraint_errors() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE t (id INTEGER PRIMARY KEY, a TEXT NOT NULL, b TEXT NOT NULL, UNIQUE (a, b))",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared("INSERT INTO t (a, b) VALUES ('x', 'y')").await.unwrap();
+
+        let err = db.execute_unprepared("INSERT INTO t (a, b) VALUES ('x', 'y')").await.unwrap_err();
+        match DbError::from(err) {
+            DbError::UniqueViolation { table, columns } => {
+                assert_eq!(table.as_deref(), Some("t"));
+                assert_eq!(columns, vec!["a", "b"]);
+            }
+            other => panic!("expected unique violation, got {other:?}"),
         }
+
+        let err = db.execute_unprepared("INSERT INTO t (a) VALUES ('z')").await.unwrap_err();
+        assert!(matches!(
+            DbError::from(err),
+            DbError::NotNullViolation { column: Some(column) } if column == "b"
+        ));
+
+        db.execute_unprepared("PRAGMA foreign_keys = ON").await.unwrap();
+        db.execute_unprepared("CREATE TABLE c (id INTEGER PRIMARY KEY, t_id INTEGER NOT NULL REFERENCES t (id))")
+            .await
+            .unwrap();
+        let err = db.execute_unprepared("INSERT INTO c (t_id) VALUES (42)").await.unwrap_err();
+        assert!(matches!(DbError::from(err), DbError::ForeignKeyViolation { still_referenced: false, .. }));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::corporate_action;
+
+pub struct CorporateActionRepository {
+    db: Arc<DbPool>,
+}
+
+impl CorporateActionRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 某个标的满足条件的公司行动，按除权日、ID 升序
+    pub async fn find_timeline(
+        &self,
+        instrument_id: i32,
+        condition: Condition,
+    ) -> Result<Vec<corporate_action::Model>, DbErr> {
+        corporate_action::Entity::find()
+            .filter(corporate_action::Column::InstrumentId.eq(instrument_id))
+            .filter(condition)
+            .order_by_asc(corporate_action::Column::ExDate)
+            .order_by_asc(corporate_action::Column::Id)
+            .all(self.conn())
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<corporate_action::Entity> for CorporateActionRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use crate::db::connection::DbPool;
+use crate::db::repositories::{exec_batched, Repository};
+use crate::db::repositories::kline::to_utc;
+use entities::data_quality_issue;
+use entities::sea_orm_active_enums::KlineInterval;
+
+pub struct DataQualityIssueRepository {
+    db: Arc<DbPool>,
+}
+
+impl DataQualityIssueRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 用新的检查结果替换某个标的、某个周期在 `[from, to)` 内开始的全部问题，在同一事务内执行
+    pub async fn replace_range(
+        &self,
+        instrument_id: i32,
+        interval: KlineInterval,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+        issues: Vec<data_quality_issue::ActiveModel>,
+    ) -> Result<u64, DbErr> {
+        let txn = self.conn().begin().await?;
+        data_quality_issue::Entity::delete_many()
+            .filter(data_quality_issue::Column::InstrumentId.eq(instrument_id))
+            .filter(data_quality_issue::Column::Interval.eq(interval))
+            .filter(data_quality_issue::Column::StartTs.gte(to_utc(from)))
+            .filter(data_quality_issue::Column::StartTs.lt(to_utc(to)))
+            .exec(&txn)
+            .await?;
+        let inserted = exec_batched::<data_quality_issue::Entity, _>(&txn, issues, None, self.max_bind_params()).await?;
+        txn.commit().await?;
+        Ok(inserted)
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<data_quality_issue::Entity> for DataQualityIssueRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
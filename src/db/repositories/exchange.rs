@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::*;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::{exchange, instrument};
+
+pub struct ExchangeRepository {
+    db: Arc<DbPool>,
+}
+
+impl ExchangeRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 根据交易所代码查找（代码需事先规范化）
+    pub async fn find_by_code(&self, code: &str) -> Result<Option<exchange::Model>, DbErr> {
+        self.find_one_by_condition(Condition::all().add(exchange::Column::Code.eq(code)))
+            .await
+    }
+
+    /// 全部交易所的 ID -> 代码映射，用于批量组装响应
+    pub async fn codes_by_id(&self) -> Result<HashMap<i32, String>, DbErr> {
+        let rows: Vec<(i32, String)> = exchange::Entity::find()
+            .select_only()
+            .column(exchange::Column::Id)
+            .column(exchange::Column::Code)
+            .into_tuple()
+            .all(self.conn())
+            .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// 统计挂在该交易所下的标的数量
+    pub async fn count_instruments(&self, exchange_id: i32) -> Result<u64, DbErr> {
+        instrument::Entity::find()
+            .filter(instrument::Column::ExchangeId.eq(exchange_id))
+            .count(self.conn())
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<exchange::Entity> for ExchangeRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::{Alias, Expr, OverStatement, Query, SelectStatement, WindowStatement};
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use crate::db::repositories::kline::to_utc;
+use entities::{feature_metric, instrument};
+
+pub struct FeatureMetricRepository {
+    db: Arc<DbPool>,
+}
+
+impl FeatureMetricRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 某个标的若干指标在 `[from, to)` 内的时间序列，按 (metric_name, ts) 升序
+    ///
+    /// 同一 ts 有多个版本时只取 `known_before` 之前公布的最新一版；`known_before` 为空时取最新一版。
+    pub async fn find_series(
+        &self,
+        instrument_id: i32,
+        names: &[String],
+        from: Option<DateTimeWithTimeZone>,
+        to: Option<DateTimeWithTimeZone>,
+        known_before: Option<DateTimeWithTimeZone>,
+    ) -> Result<Vec<feature_metric::Model>, DbErr> {
+        let mut condition = Condition::all()
+            .add(feature_metric::Column::InstrumentId.eq(instrument_id))
+            .add(feature_metric::Column::MetricName.is_in(names.iter().cloned()));
+        if let Some(from) = from {
+            condition = condition.add(feature_metric::Column::Ts.gte(to_utc(from)));
+        }
+        if let Some(to) = to {
+            condition = condition.add(feature_metric::Column::Ts.lt(to_utc(to)));
+        }
+        if let Some(known_before) = known_before {
+            condition = condition.add(feature_metric::Column::KnownAt.lt(to_utc(known_before)));
+        }
+        let window = WindowStatement::partition_by(feature_metric::Column::MetricName)
+            .add_partition_by(Expr::col(feature_metric::Column::Ts).into())
+            .order_by(feature_metric::Column::KnownAt, Order::Desc)
+            .to_owned();
+        let mut query = latest_versions(condition, window);
+        query
+            .order_by(feature_metric::Column::MetricName, Order::Asc)
+            .order_by(feature_metric::Column::Ts, Order::Asc);
+        self.find_by_query(&query).await
+    }
+
+    /// 指标 `name` 在交易所 `exchange_ids` 下各标的上截至 `before` 的最新值：`ts` 与 `known_at` 都早于 `before`，
+    /// 按标的升序
+    pub async fn find_cross_section(
+        &self,
+        name: &str,
+        exchange_ids: &[i32],
+        before: DateTimeWithTimeZone,
+    ) -> Result<Vec<feature_metric::Model>, DbErr> {
+        let before = to_utc(before);
+        let condition = Condition::all()
+            .add(feature_metric::Column::MetricName.eq(name))
+            .add(
+                feature_metric::Column::InstrumentId.in_subquery(
+                    Query::select()
+                        .column(instrument::Column::Id)
+                        .from(instrument::Entity)
+                        .and_where(instrument::Column::ExchangeId.is_in(exchange_ids.iter().copied()))
+                        .to_owned(),
+                ),
+            )
+            .add(feature_metric::Column::Ts.lt(before))
+            .add(feature_metric::Column::KnownAt.lt(before));
+        let window = WindowStatement::partition_by(feature_metric::Column::InstrumentId)
+            .order_by(feature_metric::Column::Ts, Order::Desc)
+            .order_by(feature_metric::Column::KnownAt, Order::Desc)
+            .to_owned();
+        let mut query = latest_versions(condition, window);
+        query.order_by(feature_metric::Column::InstrumentId, Order::Asc);
+        self.find_by_query(&query).await
+    }
+
+    /// 批量写入指标，已存在的 (instrument_id, metric_name, ts, known_at) 覆盖为新值，返回受影响行数
+    ///
+    /// 时间统一转换为 UTC，同一批内的重复键只保留最后一条。
+    pub async fn upsert_batch(&self, metrics: Vec<feature_metric::Model>) -> Result<u64, DbErr> {
+        let mut positions = HashMap::new();
+        let mut unique: Vec<feature_metric::Model> = Vec::with_capacity(metrics.len());
+        for mut model in metrics {
+            model.ts = to_utc(model.ts);
+            model.known_at = to_utc(model.known_at);
+            let key = (model.instrument_id, model.metric_name.clone(), model.ts, model.known_at);
+            match positions.get(&key) {
+                Some(&index) => unique[index] = model,
+                None => {
+                    positions.insert(key, unique.len());
+                    unique.push(model);
+                }
+            }
+        }
+
+        self.upsert_many(
+            unique.into_iter().map(feature_metric::ActiveModel::from).collect(),
+            vec![
+                feature_metric::Column::InstrumentId,
+                feature_metric::Column::MetricName,
+                feature_metric::Column::Ts,
+                feature_metric::Column::KnownAt,
+            ],
+            vec![feature_metric::Column::Value, feature_metric::Column::Detail],
+        )
+        .await
+    }
+
+    async fn find_by_query(&self, query: &SelectStatement) -> Result<Vec<feature_metric::Model>, DbErr> {
+        let statement = self.conn().get_database_backend().build(query);
+        feature_metric::Model::find_by_statement(statement).all(self.conn()).await
+    }
+}
+
+/// 满足 `condition` 的记录按 `window` 分组排序后每组的第一条
+///
+/// 用 `ROW_NUMBER()` 窗口函数在数据库内取最新版本，Postgres 与 SQLite（3.25+）都支持。
+fn latest_versions(condition: Condition, window: WindowStatement) -> SelectStatement {
+    let row_number = Alias::new("row_number");
+    let inner = Query::select()
+        .columns(feature_metric::Column::iter())
+        .expr_window_as(Expr::cust("ROW_NUMBER()"), window, row_number.clone())
+        .from(feature_metric::Entity)
+        .cond_where(condition)
+        .to_owned();
+    Query::select()
+        .columns(feature_metric::Column::iter())
+        .from_subquery(inner, Alias::new("versions"))
+        .and_where(Expr::col(row_number).eq(1))
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl Repository<feature_metric::Entity> for FeatureMetricRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, FixedOffset};
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus, MetricFrequency};
+    use entities::{exchange, metric_definition};
+
+    async fn repository() -> FeatureMetricRepository {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        for (id, symbol) in [(1, "600000"), (2, "600519")] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(1),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(AssetType::Stock),
+                name: Set(format!("Stock {symbol}")),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(serde_json::json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        metric_definition::ActiveModel {
+            name: Set("revenue".to_string()),
+            frequency: Set(MetricFrequency::Quarterly),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        FeatureMetricRepository::new(db)
+    }
+
+    fn ts(value: &str) -> DateTimeWithTimeZone {
+        DateTime::<FixedOffset>::parse_from_rfc3339(value).unwrap()
+    }
+
+    fn metric(instrument_id: i32, at: &str, known_at: &str, value: f64) -> feature_metric::Model {
+        feature_metric::Model {
+            instrument_id,
+            metric_name: "revenue".to_string(),
+            ts: ts(at),
+            known_at: ts(known_at),
+            value: Some(value),
+            detail: None,
+        }
+    }
+
+    fn values(rows: &[feature_metric::Model]) -> Vec<(i32, Option<f64>)> {
+        rows.iter().map(|row| (row.instrument_id, row.value)).collect()
+    }
+
+    #[tokio::test]
+    async fn series_returns_the_version_known_before_the_cutoff() {
+        let repository = repository().await;
+        repository
+            .upsert_batch(vec![
+                metric(1, "2024-03-31T00:00:00Z", "2024-04-20T00:00:00Z", 100.0),
+                metric(1, "2024-03-31T00:00:00Z", "2024-08-15T00:00:00Z", 90.0),
+                metric(1, "2024-06-30T00:00:00Z", "2024-08-15T00:00:00Z", 120.0),
+            ])
+            .await
+            .unwrap();
+        let names = vec!["revenue".to_string()];
+
+        let latest = repository.find_series(1, &names, None, None, None).await.unwrap();
+        assert_eq!(values(&latest), vec![(1, Some(90.0)), (1, Some(120.0))]);
+
+        let before_restatement = repository
+            .find_series(1, &names, None, None, Some(ts("2024-05-01T00:00:00Z")))
+            .await
+            .unwrap();
+        assert_eq!(values(&before_restatement), vec![(1, Some(100.0))]);
+
+        let after_restatement = repository
+            .find_series(1, &names, None, None, Some(ts("2024-09-01T00:00:00Z")))
+            .await
+            .unwrap();
+        assert_eq!(values(&after_restatement), vec![(1, Some(90.0)), (1, Some(120.0))]);
+    }
+
+    #[tokio::test]
+    async fn cross_section_takes_the_latest_period_known_as_of_the_date() {
+        let repository = repository().await;
+        repository
+            .upsert_batch(vec![
+                metric(1, "2024-03-31T00:00:00Z", "2024-04-20T00:00:00Z", 100.0),
+                metric(1, "2024-06-30T00:00:00Z", "2024-08-15T00:00:00Z", 120.0),
+                metric(2, "2024-03-31T00:00:00Z", "2024-04-25T00:00:00Z", 50.0),
+                metric(2, "2024-03-31T00:00:00Z", "2024-07-01T00:00:00Z", 55.0),
+            ])
+            .await
+            .unwrap();
+
+        let in_july = repository.find_cross_section("revenue", &[1], ts("2024-07-15T00:00:00Z")).await.unwrap();
+        assert_eq!(values(&in_july), vec![(1, Some(100.0)), (2, Some(55.0))]);
+
+        let in_may = repository.find_cross_section("revenue", &[1], ts("2024-05-01T00:00:00Z")).await.unwrap();
+        assert_eq!(values(&in_may), vec![(1, Some(100.0)), (2, Some(50.0))]);
+
+        let in_september = repository.find_cross_section("revenue", &[1], ts("2024-09-01T00:00:00Z")).await.unwrap();
+        assert_eq!(values(&in_september), vec![(1, Some(120.0)), (2, Some(55.0))]);
+        assert!(repository.find_cross_section("revenue", &[2], ts("2024-09-01T00:00:00Z")).await.unwrap().is_empty());
+    }
+}
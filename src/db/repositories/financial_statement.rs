@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use sea_orm::sea_query::Expr;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::{financial_statement, financial_statement_item};
+
+/// 按 ID 查询科目时每批的 ID 数，避免超出绑定参数上限
+const ITEM_QUERY_BATCH: usize = 5_000;
+
+pub struct FinancialStatementRepository {
+    db: Arc<DbPool>,
+}
+
+impl FinancialStatementRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 某个标的满足条件的报表（全部版本），按报告期末、报表类型、版本升序
+    pub async fn find_statements(
+        &self,
+        instrument_id: i32,
+        condition: Condition,
+    ) -> Result<Vec<financial_statement::Model>, DbErr> {
+        financial_statement::Entity::find()
+            .filter(financial_statement::Column::InstrumentId.eq(instrument_id))
+            .filter(condition)
+            .order_by_asc(financial_statement::Column::PeriodEnd)
+            .order_by_asc(financial_statement::Column::StatementType)
+            .order_by_asc(financial_statement::Column::Version)
+            .all(self.conn())
+            .await
+    }
+
+    /// 若干报表的全部科目
+    pub async fn find_items(&self, statement_ids: &[i32]) -> Result<Vec<financial_statement_item::Model>, DbErr> {
+        let mut items = Vec::new();
+        for ids in statement_ids.chunks(ITEM_QUERY_BATCH) {
+            items.extend(
+                financial_statement_item::Entity::find()
+                    .filter(financial_statement_item::Column::StatementId.is_in(ids.iter().copied()))
+                    .all(self.conn())
+                    .await?,
+            );
+        }
+        Ok(items)
+    }
+
+    /// 在一个事务内把已有报表 `renumber` 改为新的版本号 `(id, version)`，并写入若干报表版本及其科目
+    ///
+    /// 版本号由调用方分配，与已有版本冲突时整个事务回滚。改号的报表先移到临时的负版本号，
+    /// 新版本写入后再改为最终版本号，因此版本号可以互换。
+    pub async fn save_versions(
+        &self,
+        renumber: Vec<(i32, i32)>,
+        versions: Vec<(financial_statement::ActiveModel, Vec<(String, f64)>)>,
+    ) -> Result<(), DbErr> {
+        let txn = self.conn().begin().await?;
+        let set_version = |id: i32, version: i32| {
+            financial_statement::Entity::update_many()
+                .col_expr(financial_statement::Column::Version, Expr::value(version))
+                .filter(financial_statement::Column::Id.eq(id))
+        };
+        for &(id, version) in &renumber {
+            set_version(id, -version).exec(&txn).await?;
+        }
+        for (statement, items) in versions {
+            let statement = statement.insert(&txn).await?;
+            let items: Vec<_> = items
+                .into_iter()
+                .map(|(item_code, value)| financial_statement_item::ActiveModel {
+                    statement_id: Set(statement.id),
+                    item_code: Set(item_code),
+                    value: Set(value),
+                })
+                .collect();
+            if !items.is_empty() {
+                financial_statement_item::Entity::insert_many(items).exec(&txn).await?;
+            }
+        }
+        for (id, version) in renumber {
+            set_version(id, version).exec(&txn).await?;
+        }
+        txn.commit().await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<financial_statement::Entity> for FinancialStatementRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
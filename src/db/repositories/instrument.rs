@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
 use sea_orm::*;
+use serde_json::Value as Json;
 use crate::db::connection::DbPool;
-use crate::db::repositories::Repository;
-use entities::instrument;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use crate::db::repositories::{apply_changes, escape_like, Repository};
+use entities::{instrument, instrument_history};
+
+/// 不写入变更历史的列：主键、审计列本身及派生列
+const UNTRACKED_FIELDS: &[&str] = &["id", "created_at", "updated_at", "created_by", "version", "name_pinyin"];
 
 pub struct InstrumentRepository {
     db: Arc<DbPool>,
@@ -17,14 +23,342 @@ impl InstrumentRepository {
     /// 检查交易所和标的代码是否已存在
     pub async fn find_by_exchange_and_symbol(
         &self,
-        exchange: &str,
+        exchange_id: i32,
         symbol: &str,
     ) -> Result<Option<instrument::Model>, DbErr> {
         let condition = Condition::all()
-            .add(instrument::Column::Exchange.eq(exchange))
+            .add(instrument::Column::ExchangeId.eq(exchange_id))
             .add(instrument::Column::Symbol.eq(symbol));
         self.find_one_by_condition(condition).await
     }
+
+    /// 在某个交易所下按多个候选 symbol 查找，返回第一个命中的候选对应的标的
+    pub async fn find_by_exchange_and_symbols(
+        &self,
+        exchange_id: i32,
+        symbols: &[String],
+    ) -> Result<Option<instrument::Model>, DbErr> {
+        let condition = Condition::all()
+            .add(instrument::Column::ExchangeId.eq(exchange_id))
+            .add(instrument::Column::Symbol.is_in(symbols.iter().cloned()));
+        let mut found = self.find_by_condition(condition).await?;
+        found.sort_by_key(|model| symbols.iter().position(|s| *s == model.symbol));
+        Ok(found.into_iter().next())
+    }
+
+    /// 数据库是否可用 `pg_trgm` 三元组检索（仅 Postgres 且已启用扩展）
+    pub async fn trigram_available(&self) -> Result<bool, DbErr> {
+        if self.conn().get_database_backend() != DbBackend::Postgres {
+            return Ok(false);
+        }
+        let row = self.conn()
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_trgm') AS available",
+            ))
+            .await?;
+        row.map_or(Ok(false), |row| row.try_get("", "available"))
+    }
+
+    /// 用 `pg_trgm` 粗筛搜索候选：symbol / 名称 / 拼音 / 别名的子串、相似度（`%`）或词相似度（`<%`）命中，
+    /// 按相似度取前 `limit` 条
+    ///
+    /// 精确排序由调用方完成，这里只负责利用三元组索引缩小范围。
+    pub async fn trigram_candidates(
+        &self,
+        query: &str,
+        condition: Condition,
+        limit: u64,
+    ) -> Result<Vec<instrument::Model>, DbErr> {
+        let query = query.trim().to_lowercase();
+        let like = format!("%{}%", escape_like(&query));
+        let matches = Condition::any()
+            .add(Expr::cust_with_values("lower(symbol) LIKE $1 ESCAPE '\\'", [like.clone()]))
+            .add(Expr::cust_with_values("lower(name) LIKE $1 ESCAPE '\\'", [like.clone()]))
+            .add(Expr::cust_with_values("name_pinyin LIKE $1 ESCAPE '\\'", [like.clone()]))
+            .add(Expr::cust_with_values("($1 <% lower(symbol) OR $1 % lower(symbol))", [query.clone()]))
+            .add(Expr::cust_with_values("($1 <% lower(name) OR $1 % lower(name))", [query.clone()]))
+            .add(Expr::cust_with_values("($1 <% name_pinyin OR $1 % name_pinyin)", [query.clone()]))
+            .add(Expr::cust_with_values(
+                "EXISTS (SELECT 1 FROM instrument_alias a WHERE a.instrument_id = instrument.id \
+                 AND (lower(a.value) LIKE $1 ESCAPE '\\' OR $2 <% lower(a.value) OR $2 % lower(a.value)))",
+                [like, query.clone()],
+            ));
+        let similarity = Expr::cust_with_values(
+            "GREATEST(word_similarity($1, lower(symbol)), word_similarity($1, lower(name)), \
+             word_similarity($1, coalesce(name_pinyin, '')))",
+            [query],
+        );
+        instrument::Entity::find()
+            .filter(self.scope())
+            .filter(condition)
+            .filter(matches)
+            .order_by(similarity, Order::Desc)
+            .order_by_asc(instrument::Column::Id)
+            .limit(limit)
+            .all(self.conn())
+            .await
+    }
+
+    /// 为尚未生成拼音的历史记录补齐 `name_pinyin`，不影响版本号与变更历史；返回补齐的条数
+    pub async fn backfill_name_pinyin(&self) -> Result<u64, DbErr> {
+        const BATCH: u64 = 1000;
+        let mut after = 0;
+        let mut filled = 0;
+        loop {
+            let rows: Vec<(i32, String)> = instrument::Entity::find()
+                .select_only()
+                .column(instrument::Column::Id)
+                .column(instrument::Column::Name)
+                .filter(instrument::Column::NamePinyin.is_null())
+                .filter(instrument::Column::Id.gt(after))
+                .order_by_asc(instrument::Column::Id)
+                .limit(BATCH)
+                .into_tuple()
+                .all(self.conn())
+                .await?;
+            let Some((last, _)) = rows.last() else {
+                return Ok(filled);
+            };
+            after = *last;
+            for (id, name) in rows {
+                if let Some(pinyin) = instrument::name_pinyin(&name) {
+                    instrument::Entity::update_many()
+                        .col_expr(instrument::Column::NamePinyin, Expr::value(pinyin))
+                        .filter(instrument::Column::Id.eq(id))
+                        .exec(self.conn())
+                        .await?;
+                    filled += 1;
+                }
+            }
+        }
+    }
+
+    /// 搜索索引的数据指纹：(记录数, 最近更新时间, 最近删除时间)，任何写入都会改变指纹
+    ///
+    /// 通用的 `delete` / `delete_many` 软删除只写入 `deleted_at`，因此单独取删除时间。
+    pub async fn fingerprint(
+        &self,
+    ) -> Result<(i64, Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>), DbErr> {
+        instrument::Entity::find()
+            .select_only()
+            .column_as(instrument::Column::Id.count(), "count")
+            .column_as(instrument::Column::UpdatedAt.max(), "updated_at")
+            .column_as(instrument::Column::DeletedAt.max(), "deleted_at")
+            .into_tuple()
+            .one(self.conn())
+            .await
+            .map(|row| row.unwrap_or_default())
+    }
+
+    /// 创建标的，并在同一事务内记录各字段的初始值
+    pub async fn create_with_history(
+        &self,
+        active: instrument::ActiveModel,
+        actor: Option<String>,
+    ) -> Result<instrument::Model, DbErr> {
+        let txn = self.conn().begin().await?;
+        let model = insert_tracked(&txn, active, actor).await?;
+        txn.commit().await?;
+        Ok(model)
+    }
+
+    /// 更新标的，并在同一事务内记录发生变化的字段
+    ///
+    /// 以 `before.version` 作为乐观锁条件并将版本号加 1；记录已被他人修改时返回 `DbErr::RecordNotUpdated`。
+    pub async fn update_with_history(
+        &self,
+        before: &instrument::Model,
+        active: instrument::ActiveModel,
+        actor: Option<String>,
+    ) -> Result<instrument::Model, DbErr> {
+        let txn = self.conn().begin().await?;
+        let model = update_tracked(&txn, before, active, actor).await?;
+        txn.commit().await?;
+        Ok(model)
+    }
+
+    /// 开启一次批量导入：所有批次在同一事务中写入，`finish` 时统一提交或回滚
+    pub async fn begin_import(&self, actor: Option<String>) -> Result<ImportSession, DbErr> {
+        let txn = self.conn().begin().await?;
+        Ok(ImportSession { txn, actor })
+    }
+
+    /// 按主键顺序读取 `after_id` 之后的一批记录，用于导出等全量遍历场景
+    pub async fn find_batch_after(
+        &self,
+        cond: Condition,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<instrument::Model>, DbErr> {
+        instrument::Entity::find()
+            .filter(self.scope())
+            .filter(cond)
+            .filter(instrument::Column::Id.gt(after_id))
+            .order_by_asc(instrument::Column::Id)
+            .limit(limit)
+            .all(self.conn())
+            .await
+    }
+
+    /// 软删除标的（写入 `deleted_at`），删除动作同样记入变更历史
+    pub async fn soft_delete_with_history(
+        &self,
+        before: &instrument::Model,
+        actor: Option<String>,
+    ) -> Result<instrument::Model, DbErr> {
+        let mut active = before.clone().into_active_model();
+        active.deleted_at = Set(Some(chrono::Utc::now().fixed_offset()));
+        self.update_with_history(before, active, actor).await
+    }
+}
+
+/// 进行中的批量导入，持有导入事务
+pub struct ImportSession {
+    txn: DatabaseTransaction,
+    actor: Option<String>,
+}
+
+impl ImportSession {
+    /// 按 (交易所, symbol) 逐行 upsert 一批记录；每行使用独立的保存点，
+    /// 单行失败只回滚该行，返回与输入一一对应的结果
+    ///
+    /// 已存在的记录按整行替换合并，内容未变化的行不写入；新建的行以导入者为 `created_by`。
+    /// 每行须已设置 `exchange_id` 与 `symbol`。
+    pub async fn apply(&self, rows: Vec<instrument::ActiveModel>) -> Result<Vec<Result<ImportOutcome, DbErr>>, DbErr> {
+        let mut outcomes = Vec::with_capacity(rows.len());
+        for active in rows {
+            let savepoint = self.txn.begin().await?;
+            let outcome = import_row(&savepoint, active, self.actor.clone()).await;
+            if outcome.is_ok() {
+                savepoint.commit().await?;
+            } else {
+                savepoint.rollback().await?;
+            }
+            outcomes.push(outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// 结束导入：`dry_run` 时回滚全部写入，否则提交
+    pub async fn finish(self, dry_run: bool) -> Result<(), DbErr> {
+        if dry_run {
+            self.txn.rollback().await
+        } else {
+            self.txn.commit().await
+        }
+    }
+}
+
+/// 单行导入的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+async fn import_row<C: ConnectionTrait>(
+    conn: &C,
+    active: instrument::ActiveModel,
+    actor: Option<String>,
+) -> Result<ImportOutcome, DbErr> {
+    let condition = Condition::all()
+        .add(instrument::Column::DeletedAt.is_null())
+        .add(instrument::Column::ExchangeId.eq(active.exchange_id.clone().unwrap()))
+        .add(instrument::Column::Symbol.eq(active.symbol.clone().unwrap()));
+    let Some(before) = instrument::Entity::find().filter(condition).one(conn).await? else {
+        let mut active = active;
+        active.created_by = Set(actor.clone());
+        insert_tracked(conn, active, actor).await?;
+        return Ok(ImportOutcome::Created);
+    };
+
+    let mut merged = before.clone().into_active_model();
+    apply_changes(&mut merged, active);
+    let changed = instrument::Column::iter()
+        .any(|column| matches!(merged.get(column), ActiveValue::Set(value) if value != before.get(column)));
+    if !changed {
+        return Ok(ImportOutcome::Unchanged);
+    }
+    update_tracked(conn, &before, merged, actor).await?;
+    Ok(ImportOutcome::Updated)
+}
+
+async fn insert_tracked<C: ConnectionTrait>(
+    conn: &C,
+    active: instrument::ActiveModel,
+    actor: Option<String>,
+) -> Result<instrument::Model, DbErr> {
+    let model = active.insert(conn).await?;
+    record_changes(conn, None, &model, actor).await?;
+    Ok(model)
+}
+
+async fn update_tracked<C: ConnectionTrait>(
+    conn: &C,
+    before: &instrument::Model,
+    mut active: instrument::ActiveModel,
+    actor: Option<String>,
+) -> Result<instrument::Model, DbErr> {
+    active.version = Set(before.version + 1);
+    let active = active.before_save(conn, false).await?;
+    let model = instrument::Entity::update(active)
+        .filter(instrument::Column::Version.eq(before.version))
+        .exec(conn)
+        .await?;
+    record_changes(conn, Some(before), &model, actor).await?;
+    Ok(model)
+}
+
+/// 对比前后两个版本，为每个变化的字段写入一条历史记录
+async fn record_changes<C: ConnectionTrait>(
+    conn: &C,
+    before: Option<&instrument::Model>,
+    after: &instrument::Model,
+    actor: Option<String>,
+) -> Result<(), DbErr> {
+    let entries = diff_fields(before, after);
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let changed_at = after.updated_at;
+    let rows = entries.into_iter().map(|(field, old_value, new_value)| instrument_history::ActiveModel {
+        instrument_id: Set(after.id),
+        field: Set(field),
+        old_value: Set(old_value),
+        new_value: Set(new_value),
+        changed_at: Set(changed_at),
+        changed_by: Set(actor.clone()),
+        ..Default::default()
+    });
+    instrument_history::Entity::insert_many(rows)
+        .exec_without_returning(conn)
+        .await?;
+    Ok(())
+}
+
+/// 逐字段比较序列化后的值，返回 `(字段, 旧值, 新值)`；`before` 为空时视为新建，只记录非空字段
+fn diff_fields(
+    before: Option<&instrument::Model>,
+    after: &instrument::Model,
+) -> Vec<(String, Option<Json>, Option<Json>)> {
+    let to_map = |model: &instrument::Model| match serde_json::to_value(model) {
+        Ok(Json::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let old = before.map(to_map).unwrap_or_default();
+    let new = to_map(after);
+
+    new.into_iter()
+        .filter(|(field, _)| !UNTRACKED_FIELDS.contains(&field.as_str()))
+        .filter_map(|(field, new_value)| {
+            let old_value = old.get(&field).cloned().filter(|v| !v.is_null());
+            let new_value = Some(new_value).filter(|v| !v.is_null());
+            (old_value != new_value).then_some((field, old_value, new_value))
+        })
+        .collect()
 }
 
 // 关键：实现通用 Repository trait
@@ -33,4 +367,106 @@ impl Repository<instrument::Entity> for InstrumentRepository {
     fn conn(&self) -> &DatabaseConnection {
         &self.db
     }
-}
\ No newline at end of file
+
+    fn soft_delete_column(&self) -> Option<instrument::Column> {
+        Some(instrument::Column::DeletedAt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::exchange;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+
+    async fn repository() -> InstrumentRepository {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        InstrumentRepository::new(db)
+    }
+
+    fn instrument(symbol: &str) -> instrument::ActiveModel {
+        instrument::ActiveModel {
+            exchange_id: Set(1),
+            symbol: Set(symbol.to_string()),
+            asset_type: Set(AssetType::Stock),
+            name: Set(format!("Stock {symbol}")),
+            status: Set(InstrumentStatus::Active),
+            metadata: Set(serde_json::json!({})),
+            ..Default::default()
+        }
+    }
+
+    async fn history(repo: &InstrumentRepository, id: i32) -> Vec<(String, Option<Json>, Option<Json>)> {
+        instrument_history::Entity::find()
+            .filter(instrument_history::Column::InstrumentId.eq(id))
+            .order_by_asc(instrument_history::Column::Id)
+            .all(repo.conn())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.field, row.old_value, row.new_value))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_soft_deleted_rows_are_out_of_scope() {
+        let repo = repository().await;
+        let kept = repo.create(instrument("600000")).await.unwrap();
+        let deleted = repo.create(instrument("600519")).await.unwrap();
+        let purged = repo.create(instrument("601318")).await.unwrap();
+
+        assert!(repo.delete(deleted.id).await.unwrap());
+        assert!(!repo.delete(deleted.id).await.unwrap(), "already deleted");
+        assert!(repo.find_by_id(deleted.id).await.unwrap().is_none());
+        assert!(repo.find_by_id_with_deleted(deleted.id).await.unwrap().unwrap().deleted_at.is_some());
+        assert!(repo.find_by_exchange_and_symbol(1, "600519").await.unwrap().is_none());
+
+        // 批量删除同样只标记删除时间，且不重复计入已删除的记录
+        let symbols = Condition::all().add(instrument::Column::Symbol.is_in(["600519", "601318"]));
+        assert_eq!(repo.delete_many(symbols).await.unwrap(), 1);
+        assert!(repo.find_by_id_with_deleted(purged.id).await.unwrap().is_some());
+
+        assert_eq!(repo.count().await.unwrap(), 1);
+        let page = repo.paginate(Condition::all(), vec![], 1, 10).await.unwrap();
+        assert_eq!((page.total, page.items[0].id), (1, kept.id));
+    }
+
+    #[tokio::test]
+    async fn test_history_records_changed_fields_only() {
+        let repo = repository().await;
+        let created = repo.create_with_history(instrument("600000"), Some("alice".to_string())).await.unwrap();
+        let fields: Vec<_> = history(&repo, created.id).await.into_iter().map(|(field, ..)| field).collect();
+        // 新建只记录非空字段，审计列与派生列不记录
+        assert!(fields.contains(&"symbol".to_string()) && fields.contains(&"name".to_string()));
+        assert!(!fields.iter().any(|f| UNTRACKED_FIELDS.contains(&f.as_str()) || f == "tick_size"));
+
+        let mut active = created.clone().into_active_model();
+        active.name = Set("浦发银行".to_string());
+        active.tick_size = Set(Some(0.01));
+        let updated = repo.update_with_history(&created, active, None).await.unwrap();
+        assert_eq!(updated.version, created.version + 1);
+
+        let changes = history(&repo, created.id).await.split_off(fields.len());
+        assert_eq!(changes, vec![
+            ("name".to_string(), Some(Json::from("Stock 600000")), Some(Json::from("浦发银行"))),
+            ("tick_size".to_string(), None, Some(Json::from(0.01))),
+        ]);
+
+        // 基于过期版本的更新不生效，也不写历史
+        let stale = created.clone().into_active_model();
+        assert!(matches!(repo.update_with_history(&created, stale, None).await, Err(DbErr::RecordNotUpdated)));
+        assert_eq!(history(&repo, created.id).await.len(), fields.len() + 2);
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::instrument_alias;
+
+pub struct InstrumentAliasRepository {
+    db: Arc<DbPool>,
+}
+
+impl InstrumentAliasRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 按 (来源, 取值) 精确查找别名
+    pub async fn find_by_source_value(
+        &self,
+        source: &str,
+        value: &str,
+    ) -> Result<Option<instrument_alias::Model>, DbErr> {
+        let condition = Condition::all()
+            .add(instrument_alias::Column::Source.eq(source))
+            .add(instrument_alias::Column::Value.eq(value));
+        self.find_one_by_condition(condition).await
+    }
+
+    /// 某个标的的全部别名，按来源、取值排序
+    pub async fn find_by_instrument(&self, instrument_id: i32) -> Result<Vec<instrument_alias::Model>, DbErr> {
+        instrument_alias::Entity::find()
+            .filter(instrument_alias::Column::InstrumentId.eq(instrument_id))
+            .order_by_asc(instrument_alias::Column::Source)
+            .order_by_asc(instrument_alias::Column::Value)
+            .all(self.conn())
+            .await
+    }
+
+    pub async fn find_by_instruments(&self, instrument_ids: &[i32]) -> Result<Vec<instrument_alias::Model>, DbErr> {
+        if instrument_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.find_by_condition(
+            Condition::all().add(instrument_alias::Column::InstrumentId.is_in(instrument_ids.iter().copied())),
+        )
+        .await
+    }
+
+    /// 搜索索引的数据指纹：(记录数, 最大 ID)，增删别名都会改变指纹
+    pub async fn fingerprint(&self) -> Result<(i64, Option<i32>), DbErr> {
+        instrument_alias::Entity::find()
+            .select_only()
+            .column_as(instrument_alias::Column::Id.count(), "count")
+            .column_as(instrument_alias::Column::Id.max(), "max_id")
+            .into_tuple()
+            .one(self.conn())
+            .await
+            .map(|row| row.unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<instrument_alias::Entity> for InstrumentAliasRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
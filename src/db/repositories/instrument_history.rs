@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::instrument_history;
+
+pub struct InstrumentHistoryRepository {
+    db: Arc<DbPool>,
+}
+
+impl InstrumentHistoryRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<instrument_history::Entity> for InstrumentHistoryRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
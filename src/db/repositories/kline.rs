@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sea_orm::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use crate::db::connection::DbPool;
+use crate::db::repositories::{exec_batched, Repository};
+use entities::kline;
+use entities::sea_orm_active_enums::KlineInterval;
+
+pub struct KlineRepository {
+    db: Arc<DbPool>,
+}
+
+impl KlineRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 查询某个标的、某个周期在 `[from, to)` 内的 K 线，按时间 `order` 排序，最多 `limit` 条
+    ///
+    /// `from` / `to` 为空时不限制对应一侧。
+    pub async fn find_range(
+        &self,
+        instrument_id: i32,
+        interval: KlineInterval,
+        from: Option<DateTimeWithTimeZone>,
+        to: Option<DateTimeWithTimeZone>,
+        order: Order,
+        limit: u64,
+    ) -> Result<Vec<kline::Model>, DbErr> {
+        let mut condition = Condition::all()
+            .add(kline::Column::InstrumentId.eq(instrument_id))
+            .add(kline::Column::Interval.eq(interval));
+        if let Some(from) = from {
+            condition = condition.add(kline::Column::Ts.gte(to_utc(from)));
+        }
+        if let Some(to) = to {
+            condition = condition.add(kline::Column::Ts.lt(to_utc(to)));
+        }
+        kline::Entity::find()
+            .filter(condition)
+            .order_by(kline::Column::Ts, order)
+            .limit(limit)
+            .all(self.conn())
+            .await
+    }
+
+    /// 某个标的、某个周期最早的一根 K 线
+    pub async fn earliest(&self, instrument_id: i32, interval: KlineInterval) -> Result<Option<kline::Model>, DbErr> {
+        kline::Entity::find()
+            .filter(kline::Column::InstrumentId.eq(instrument_id))
+            .filter(kline::Column::Interval.eq(interval))
+            .order_by_asc(kline::Column::Ts)
+            .one(self.conn())
+            .await
+    }
+
+    /// 某个标的、某个周期最新的一根 K 线
+    pub async fn latest(&self, instrument_id: i32, interval: KlineInterval) -> Result<Option<kline::Model>, DbErr> {
+        kline::Entity::find()
+            .filter(kline::Column::InstrumentId.eq(instrument_id))
+            .filter(kline::Column::Interval.eq(interval))
+            .order_by_desc(kline::Column::Ts)
+            .one(self.conn())
+            .await
+    }
+
+    /// 是否启用了 TimescaleDB（仅 Postgres）
+    pub async fn timescale_available(&self) -> Result<bool, DbErr> {
+        if self.conn().get_database_backend() != DbBackend::Postgres {
+            return Ok(false);
+        }
+        let row = self.conn()
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') AS available",
+            ))
+            .await?;
+        row.map_or(Ok(false), |row| row.try_get("", "available"))
+    }
+
+    /// 用 TimescaleDB 的 `time_bucket` 将 `source` 周期的 K 线聚合为 `interval`，桶按交易所时区 `timezone` 对齐
+    ///
+    /// `bucket_width` 为 Postgres interval 写法（如 `1 hour`、`1 month`）。按 `order` 取前 `limit` 个桶，
+    /// 结果总是按时间升序返回。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn time_bucket(
+        &self,
+        instrument_id: i32,
+        source: KlineInterval,
+        interval: KlineInterval,
+        bucket_width: &str,
+        timezone: &str,
+        from: Option<DateTimeWithTimeZone>,
+        to: Option<DateTimeWithTimeZone>,
+        order: Order,
+        limit: u64,
+    ) -> Result<Vec<kline::Model>, DbErr> {
+        let direction = match order {
+            Order::Desc => "DESC",
+            _ => "ASC",
+        };
+        let sql = format!(
+            r#"SELECT instrument_id, $2 AS "interval", bucket AS ts, open, high, low, close, volume, quote_volume, trade_count
+               FROM (
+                   SELECT instrument_id, time_bucket($3::interval, ts, $4::text) AS bucket,
+                          first(open, ts) AS open, max(high) AS high, min(low) AS low, last(close, ts) AS close,
+                          sum(volume) AS volume, sum(quote_volume) AS quote_volume, sum(trade_count)::bigint AS trade_count
+                   FROM kline
+                   WHERE instrument_id = $1 AND "interval" = $5
+                     AND ($6::timestamptz IS NULL OR ts >= $6) AND ($7::timestamptz IS NULL OR ts < $7)
+                   GROUP BY instrument_id, bucket
+                   ORDER BY bucket {direction}
+                   LIMIT $8
+               ) buckets
+               ORDER BY ts"#
+        );
+        let values: [Value; 8] = [
+            instrument_id.into(),
+            interval.to_value().into(),
+            bucket_width.into(),
+            timezone.into(),
+            source.to_value().into(),
+            from.map(to_utc).into(),
+            to.map(to_utc).into(),
+            (limit as i64).into(),
+        ];
+        kline::Model::find_by_statement(Statement::from_sql_and_values(DbBackend::Postgres, sql, values))
+            .all(self.conn())
+            .await
+    }
+
+    /// 批量写入 K 线，已存在的 (instrument_id, interval, ts) 覆盖为新值，返回受影响行数
+    ///
+    /// `ts` 统一转换为 UTC，同一批内的重复键只保留最后一条。
+    pub async fn upsert_batch(&self, klines: Vec<kline::Model>) -> Result<u64, DbErr> {
+        let mut positions = HashMap::new();
+        let mut unique: Vec<kline::Model> = Vec::with_capacity(klines.len());
+        for mut model in klines {
+            model.ts = to_utc(model.ts);
+            let key = (model.instrument_id, model.interval, model.ts);
+            match positions.get(&key) {
+                Some(&index) => unique[index] = model,
+                None => {
+                    positions.insert(key, unique.len());
+                    unique.push(model);
+                }
+            }
+        }
+
+        self.upsert_many(
+            unique.into_iter().map(kline::ActiveModel::from).collect(),
+            vec![kline::Column::InstrumentId, kline::Column::Interval, kline::Column::Ts],
+            vec![
+                kline::Column::Open,
+                kline::Column::High,
+                kline::Column::Low,
+                kline::Column::Close,
+                kline::Column::Volume,
+                kline::Column::QuoteVolume,
+                kline::Column::TradeCount,
+            ],
+        )
+        .await
+    }
+
+    /// 用 `klines` 替换某个标的、某个周期在 `[from, to)` 内的全部 K 线，删除与写入在同一事务内执行
+    ///
+    /// 返回 (删除的行数, 写入的行数)；`klines` 须落在区间内且 `ts` 互不重复。
+    pub async fn replace_range(
+        &self,
+        instrument_id: i32,
+        interval: KlineInterval,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+        klines: Vec<kline::Model>,
+    ) -> Result<(u64, u64), DbErr> {
+        let txn = self.conn().begin().await?;
+        let deleted = kline::Entity::delete_many()
+            .filter(kline::Column::InstrumentId.eq(instrument_id))
+            .filter(kline::Column::Interval.eq(interval))
+            .filter(kline::Column::Ts.gte(to_utc(from)))
+            .filter(kline::Column::Ts.lt(to_utc(to)))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let actives = klines
+            .into_iter()
+            .map(|mut model| {
+                model.ts = to_utc(model.ts);
+                kline::ActiveModel::from(model)
+            })
+            .collect();
+        let inserted = exec_batched::<kline::Entity, _>(&txn, actives, None, self.max_bind_params()).await?;
+        txn.commit().await?;
+        Ok((deleted, inserted))
+    }
+}
+
+/// 时间统一以 UTC 存储和比较：SQLite 以文本保存时间，偏移不同的同一时刻会被视为不同的值
+pub fn to_utc(ts: DateTimeWithTimeZone) -> DateTimeWithTimeZone {
+    ts.to_utc().fixed_offset()
+}
+
+#[async_trait::async_trait]
+impl Repository<kline::Entity> for KlineRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, FixedOffset};
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+    use entities::{exchange, instrument};
+
+    async fn repository() -> KlineRepository {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        for (id, symbol) in [(1, "600000"), (2, "600519")] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(1),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(AssetType::Stock),
+                name: Set(format!("Stock {symbol}")),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(serde_json::json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        KlineRepository::new(db)
+    }
+
+    fn ts(value: &str) -> DateTimeWithTimeZone {
+        DateTime::<FixedOffset>::parse_from_rfc3339(value).unwrap()
+    }
+
+    fn bar(instrument_id: i32, interval: KlineInterval, ts: DateTimeWithTimeZone, close: f64) -> kline::Model {
+        kline::Model {
+            instrument_id,
+            interval,
+            ts,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+            quote_volume: None,
+            trade_count: None,
+        }
+    }
+
+    /// 2026-10-19 01:30 UTC 起每分钟一根
+    fn minutes(instrument_id: i32, count: i64) -> Vec<kline::Model> {
+        let start = ts("2026-10-19T01:30:00Z");
+        (0..count)
+            .map(|i| bar(instrument_id, KlineInterval::Minute1, start + Duration::minutes(i), 10.0 + i as f64))
+            .collect()
+    }
+
+    fn closes(bars: &[kline::Model]) -> Vec<f64> {
+        bars.iter().map(|bar| bar.close).collect()
+    }
+
+    #[tokio::test]
+    async fn test_find_range_bounds_order_and_limit() {
+        let repo = repository().await;
+        let mut bars = minutes(1, 5);
+        bars.extend(minutes(2, 5));
+        bars.push(bar(1, KlineInterval::Minute5, ts("2026-10-19T01:30:00Z"), 99.0));
+        repo.upsert_batch(bars).await.unwrap();
+
+        // 左闭右开，只返回指定标的与周期；带偏移的边界与 UTC 边界等价
+        let from = Some(ts("2026-10-19T09:31:00+08:00"));
+        let to = Some(ts("2026-10-19T01:34:00Z"));
+        let found = repo.find_range(1, KlineInterval::Minute1, from, to, Order::Asc, 10).await.unwrap();
+        assert_eq!(closes(&found), vec![11.0, 12.0, 13.0]);
+
+        let found = repo.find_range(1, KlineInterval::Minute1, None, None, Order::Desc, 2).await.unwrap();
+        assert_eq!(closes(&found), vec![14.0, 13.0]);
+        let found = repo.find_range(1, KlineInterval::Minute1, None, to, Order::Asc, 10).await.unwrap();
+        assert_eq!(found.len(), 4);
+        assert!(repo.find_range(1, KlineInterval::Minute1, to, from, Order::Asc, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_batch_overwrites_and_dedups() {
+        let repo = repository().await;
+        repo.upsert_batch(minutes(1, 3)).await.unwrap();
+
+        // 同一时刻以不同偏移写入视为同一根 K 线；批内重复键保留最后一条
+        let batch = vec![
+            bar(1, KlineInterval::Minute1, ts("2026-10-19T09:31:00+08:00"), 50.0),
+            bar(1, KlineInterval::Minute1, ts("2026-10-19T01:31:00Z"), 51.0),
+            bar(1, KlineInterval::Minute1, ts("2026-10-19T01:33:00Z"), 13.0),
+        ];
+        repo.upsert_batch(batch).await.unwrap();
+
+        let found = repo.find_range(1, KlineInterval::Minute1, None, None, Order::Asc, 10).await.unwrap();
+        assert_eq!(closes(&found), vec![10.0, 51.0, 12.0, 13.0]);
+        assert!(found.iter().all(|bar| bar.ts.offset().local_minus_utc() == 0));
+        assert_eq!(repo.upsert_batch(Vec::new()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_earliest_and_latest() {
+        let repo = repository().await;
+        assert!(repo.earliest(1, KlineInterval::Minute1).await.unwrap().is_none());
+
+        let mut bars = minutes(1, 3);
+        bars.reverse();
+        bars.extend(minutes(2, 5));
+        repo.upsert_batch(bars).await.unwrap();
+
+        let earliest = repo.earliest(1, KlineInterval::Minute1).await.unwrap().unwrap();
+        let latest = repo.latest(1, KlineInterval::Minute1).await.unwrap().unwrap();
+        assert_eq!((earliest.ts, latest.ts), (ts("2026-10-19T01:30:00Z"), ts("2026-10-19T01:32:00Z")));
+        assert!(repo.latest(1, KlineInterval::Minute5).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replace_range_swaps_bars_inside_range_only() {
+        let repo = repository().await;
+        repo.upsert_batch(minutes(1, 5)).await.unwrap();
+
+        let rebuilt = vec![bar(1, KlineInterval::Minute1, ts("2026-10-19T09:32:00+08:00"), 42.0)];
+        let replaced = repo
+            .replace_range(1, KlineInterval::Minute1, ts("2026-10-19T01:31:00Z"), ts("2026-10-19T01:34:00Z"), rebuilt)
+            .await
+            .unwrap();
+        assert_eq!(replaced, (3, 1));
+        let found = repo.find_range(1, KlineInterval::Minute1, None, None, Order::Asc, 10).await.unwrap();
+        assert_eq!(closes(&found), vec![10.0, 42.0, 14.0]);
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use sea_orm::*;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use entities::metric_definition;
+
+pub struct MetricDefinitionRepository {
+    db: Arc<DbPool>,
+}
+
+impl MetricDefinitionRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 按名称升序列出全部指标
+    pub async fn list(&self) -> Result<Vec<metric_definition::Model>, DbErr> {
+        metric_definition::Entity::find()
+            .order_by_asc(metric_definition::Column::Name)
+            .all(self.conn())
+            .await
+    }
+
+    /// `names` 中已登记的指标名
+    pub async fn existing_names(&self, names: &[String]) -> Result<Vec<String>, DbErr> {
+        metric_definition::Entity::find()
+            .select_only()
+            .column(metric_definition::Column::Name)
+            .filter(metric_definition::Column::Name.is_in(names.iter().cloned()))
+            .into_tuple()
+            .all(self.conn())
+            .await
+    }
+
+    /// 登记尚不存在的指标，已登记的保持不变，返回新登记的条数
+    pub async fn insert_missing(&self, definitions: Vec<metric_definition::ActiveModel>) -> Result<u64, DbErr> {
+        self.upsert_many(definitions, vec![metric_definition::Column::Name], vec![]).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<metric_definition::Entity> for MetricDefinitionRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
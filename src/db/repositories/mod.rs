@@ -1,10 +1,62 @@
+pub mod corporate_action;
+pub mod data_quality_issue;
+pub mod exchange;
+pub mod feature_metric;
+pub mod financial_statement;
 pub mod instrument;
+pub mod instrument_alias;
+pub mod instrument_history;
+pub mod kline;
+pub mod metric_definition;
+pub mod order_book;
+pub mod trade;
 
 use sea_orm::{
-    ActiveModelTrait, Condition, DatabaseConnection, DbErr, DeleteResult, EntityTrait, IntoActiveModel, PaginatorTrait, PrimaryKeyTrait, QueryFilter
+    ActiveModelBehavior, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, IntoActiveModel, Iterable, Order, PaginatorTrait, PrimaryKeyToColumn, PrimaryKeyTrait, QueryFilter, QueryOrder, TransactionTrait
 };
+use sea_orm::sea_query::{Expr, IntoValueTuple, OnConflict};
 use async_trait::async_trait;
 
+/// 单条 SQL 允许的最大绑定参数个数
+fn max_bind_params(backend: DbBackend) -> usize {
+    match backend {
+        // SQLITE_MAX_VARIABLE_NUMBER（3.32 起默认 32766）
+        DbBackend::Sqlite => 32766,
+        // Postgres / MySQL 协议限制为 u16
+        DbBackend::Postgres | DbBackend::MySql => 65535,
+    }
+}
+
+/// 将 `changes` 中已赋值（Set）的字段合并到 `target`，其余字段保持不变
+///
+/// 用于部分更新：`target` 通常由数据库中的记录转换而来（Unchanged），合并后只有被修改的列会写回。
+pub fn apply_changes<A: ActiveModelTrait>(target: &mut A, changes: A) {
+    for column in <A::Entity as EntityTrait>::Column::iter() {
+        if let ActiveValue::Set(value) = changes.get(column) {
+            target.set(column, value);
+        }
+    }
+}
+
+/// 转义 LIKE 通配符（转义符为 `\`），使用户输入按字面匹配
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 分页查询结果
+#[derive(Debug)]
+pub struct Page<M> {
+    pub items: Vec<M>,
+    /// 满足条件的记录总数
+    pub total: u64,
+    /// 当前页码，从 1 开始
+    pub page: u64,
+    pub per_page: u64,
+}
+
 /// 通用 CRUD Trait
 #[async_trait]
 pub trait Repository<E>
@@ -16,6 +68,35 @@ where
     /// 获取数据库连接
     fn conn(&self) -> &DatabaseConnection;
 
+    // --- 软删除 ---
+    /// 软删除标记列（删除时间，未删除为 NULL）
+    ///
+    /// 返回 `Some` 时，默认查询（`find_*`、`paginate`、`exists`、`count`）会跳过已删除的记录，
+    /// `delete` 改为写入删除时间；默认 `None` 即物理删除。
+    fn soft_delete_column(&self) -> Option<E::Column> {
+        None
+    }
+
+    /// 批量写入时单条 SQL 的绑定参数上限，决定每批的行数；默认取数据库的限制
+    fn max_bind_params(&self) -> usize {
+        max_bind_params(self.conn().get_database_backend())
+    }
+
+    /// 默认查询的作用域：启用软删除时只保留未删除的记录
+    fn scope(&self) -> Condition {
+        match self.soft_delete_column() {
+            Some(column) => Condition::all().add(column.is_null()),
+            None => Condition::all(),
+        }
+    }
+
+    /// 根据主键查找，包含已软删除的记录
+    async fn find_by_id_with_deleted(&self, id: <E::PrimaryKey as PrimaryKeyTrait>::ValueType)
+        -> Result<Option<E::Model>, DbErr>
+    {
+        E::find_by_id(id).one(self.conn()).await
+    }
+
     /// 创建
     async fn create(&self, active: E::ActiveModel) -> Result<E::Model, DbErr> {
         active.insert(self.conn()).await
@@ -25,12 +106,12 @@ where
     async fn find_by_id(&self, id: <E::PrimaryKey as PrimaryKeyTrait>::ValueType) 
         -> Result<Option<E::Model>, DbErr> 
     {
-        E::find_by_id(id).one(self.conn()).await
+        E::find_by_id(id).filter(self.scope()).one(self.conn()).await
     }
 
     /// 查找所有
     async fn find_all(&self) -> Result<Vec<E::Model>, DbErr> {
-        E::find().all(self.conn()).await
+        E::find().filter(self.scope()).all(self.conn()).await
     }
 
     /// 更新
@@ -38,54 +119,243 @@ where
         active.update(self.conn()).await
     }
 
-    /// 删除
+    /// 删除；启用软删除时只标记删除时间，已删除的记录视为不存在
     async fn delete(&self, id: <E::PrimaryKey as PrimaryKeyTrait>::ValueType) -> Result<bool, DbErr> {
-        let res = E::delete_by_id(id).exec(self.conn()).await?;
+        let Some(column) = self.soft_delete_column() else {
+            let res = E::delete_by_id(id).exec(self.conn()).await?;
+            return Ok(res.rows_affected > 0);
+        };
+
+        let mut cond = self.scope();
+        for (key, value) in E::PrimaryKey::iter().zip(id.into_value_tuple()) {
+            cond = cond.add(key.into_column().eq(value));
+        }
+        let res = E::update_many()
+            .col_expr(column, Expr::value(chrono::Utc::now().fixed_offset()))
+            .filter(cond)
+            .exec(self.conn())
+            .await?;
         Ok(res.rows_affected > 0)
     }
 
     // --- 常见扩展方法 ---
     /// 条件查询
     async fn find_by_condition(&self, cond: Condition) -> Result<Vec<E::Model>, DbErr> {
-        E::find().filter(cond).all(self.conn()).await
+        E::find().filter(self.scope()).filter(cond).all(self.conn()).await
     }
 
     /// 根据条件查找第一个匹配的记录
     async fn find_one_by_condition(&self, condition: Condition) -> Result<Option<E::Model>, DbErr> {
-        E::find().filter(condition).one(self.conn()).await
+        E::find().filter(self.scope()).filter(condition).one(self.conn()).await
     }
 
-    /// 分页查询
+    /// 按条件、排序分页查询（页码从 1 开始），同时返回总数
+    ///
+    /// 排序字段之后总会追加主键升序，保证翻页结果稳定。
     async fn paginate(
         &self,
+        cond: Condition,
+        order_by: Vec<(E::Column, Order)>,
         page: u64,
         per_page: u64,
-    ) -> Result<Vec<E::Model>, DbErr> {
-        let paginator = E::find().paginate(self.conn(), per_page);
-        let models = paginator.fetch_page(page).await?;
-        Ok(models)
+    ) -> Result<Page<E::Model>, DbErr> {
+        let mut query = E::find().filter(self.scope()).filter(cond);
+        for (column, order) in order_by {
+            query = query.order_by(column, order);
+        }
+        for key in E::PrimaryKey::iter() {
+            query = query.order_by(key.into_column(), Order::Asc);
+        }
+
+        let paginator = query.paginate(self.conn(), per_page);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page.saturating_sub(1)).await?;
+        Ok(Page { items, total, page, per_page })
     }
 
     /// 检查某条记录是否存在
     async fn exists(&self, cond: Condition) -> Result<bool, DbErr> {
-        Ok(E::find().filter(cond).one(self.conn()).await?.is_some())
+        Ok(E::find().filter(self.scope()).filter(cond).one(self.conn()).await?.is_some())
+    }
+
+    /// 基于唯一约束的 upsert（`INSERT ... ON CONFLICT (...) DO UPDATE`），返回受影响行数
+    ///
+    /// `update_columns` 为空时冲突行保持不变（`DO NOTHING`）。
+    async fn upsert_on(
+        &self,
+        active: E::ActiveModel,
+        conflict_columns: Vec<E::Column>,
+        update_columns: Vec<E::Column>,
+    ) -> Result<u64, DbErr> {
+        self.upsert_many(vec![active], conflict_columns, update_columns).await
+    }
+
+    /// 批量插入，按数据库绑定参数上限分批并在同一事务内执行，返回插入行数
+    ///
+    /// 每条记录先经过 `ActiveModelBehavior::before_save(insert = true)`，与单条 `insert` 行为一致。
+    async fn insert_many(&self, models: Vec<E::ActiveModel>) -> Result<u64, DbErr> {
+        exec_batched::<E, _>(self.conn(), models, None, self.max_bind_params()).await
     }
 
-    /// Upsert（如果存在则更新，否则插入）
-    async fn upsert(&self, active: E::ActiveModel) -> Result<E::Model, DbErr> {
-        match active.clone().update(self.conn()).await {
-            Ok(model) => Ok(model),
-            Err(_) => active.insert(self.conn()).await,
+    /// 批量 upsert，分批规则同 `insert_many`，返回受影响行数
+    ///
+    /// 同一批内不能出现重复的冲突键（Postgres 不允许一条语句多次更新同一行）。
+    /// `before_save` 同样按插入调用，冲突时只有 `update_columns` 中的列会被覆盖。
+    async fn upsert_many(
+        &self,
+        models: Vec<E::ActiveModel>,
+        conflict_columns: Vec<E::Column>,
+        update_columns: Vec<E::Column>,
+    ) -> Result<u64, DbErr> {
+        let mut on_conflict = OnConflict::columns(conflict_columns);
+        if update_columns.is_empty() {
+            on_conflict.do_nothing();
+        } else {
+            on_conflict.update_columns(update_columns);
         }
+        exec_batched::<E, _>(self.conn(), models, Some(on_conflict), self.max_bind_params()).await
     }
 
     // --- 计数功能 ---
     /// 统计所有记录总数
     async fn count(&self) -> Result<u64, DbErr> {
-        E::find().count(self.conn()).await
+        E::find().filter(self.scope()).count(self.conn()).await
+    }
+    /// 根据条件批量删除，返回删除的行数；启用软删除时同 `delete` 只标记删除时间
+    async fn delete_many(&self, condition: Condition) -> Result<u64, DbErr> {
+        let Some(column) = self.soft_delete_column() else {
+            let res = E::delete_many().filter(condition).exec(self.conn()).await?;
+            return Ok(res.rows_affected);
+        };
+        let res = E::update_many()
+            .col_expr(column, Expr::value(chrono::Utc::now().fixed_offset()))
+            .filter(self.scope())
+            .filter(condition)
+            .exec(self.conn())
+            .await?;
+        Ok(res.rows_affected)
+    }
+}
+/// 分批执行 INSERT（可附带 ON CONFLICT 子句），所有批次在同一事务内提交；`conn` 为事务时使用保存点
+///
+/// 每批的行数为 `max_params` 除以列数。
+async fn exec_batched<E, C>(
+    conn: &C,
+    models: Vec<E::ActiveModel>,
+    on_conflict: Option<OnConflict>,
+    max_params: usize,
+) -> Result<u64, DbErr>
+where
+    C: ConnectionTrait + TransactionTrait,
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + ActiveModelBehavior + Send,
+{
+    if models.is_empty() {
+        return Ok(0);
     }
-    /// 根据条件批量删除
-    async fn delete_many(&self, condition: Condition) -> Result<DeleteResult, DbErr> {
-        E::delete_many().filter(condition).exec(self.conn()).await
+
+    let columns = E::Column::iter().count().max(1);
+    let chunk_size = (max_params / columns).max(1);
+
+    let txn = conn.begin().await?;
+    let mut affected = 0;
+    let mut models = models.into_iter().peekable();
+    while models.peek().is_some() {
+        let mut chunk = Vec::with_capacity(chunk_size);
+        for model in models.by_ref().take(chunk_size) {
+            chunk.push(model.before_save(&txn, true).await?);
+        }
+        let mut insert = E::insert_many(chunk);
+        if let Some(on_conflict) = &on_conflict {
+            insert = insert.on_conflict(on_conflict.clone());
+        }
+        affected += insert.exec_without_returning(&txn).await?;
+    }
+    txn.commit().await?;
+    Ok(affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::exchange;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveValue::Set, ColumnTrait, Database};
+
+    struct TestRepository(DatabaseConnection);
+
+    impl Repository<exchange::Entity> for TestRepository {
+        fn conn(&self) -> &DatabaseConnection {
+            &self.0
+        }
+
+        // 缩小上限，以少量数据覆盖分批逻辑
+        fn max_bind_params(&self) -> usize {
+            64
+        }
+    }
+
+    fn exchange(code: &str, name: &str) -> exchange::ActiveModel {
+        exchange::ActiveModel {
+            code: Set(code.to_string()),
+            name: Set(name.to_string()),
+            timezone: Set("UTC".to_string()),
+            country: Set(None),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batched_insert_and_upsert() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        let repo = TestRepository(db);
+
+        // 超过单条语句的参数上限，需要分多批执行
+        let models: Vec<_> = (0..30).map(|i| exchange(&format!("EX{i}"), "old")).collect();
+        assert_eq!(repo.insert_many(models).await.unwrap(), 30);
+
+        let affected = repo
+            .upsert_many(
+                vec![exchange("EX0", "new"), exchange("NEW", "new")],
+                vec![exchange::Column::Code],
+                vec![exchange::Column::Name],
+            )
+            .await
+            .unwrap();
+        assert_eq!(affected, 2);
+        assert_eq!(repo.count().await.unwrap(), 31);
+
+        let updated = repo.find_one_by_condition(Condition::all().add(exchange::Column::Code.eq("EX0"))).await.unwrap();
+        assert_eq!(updated.unwrap().name, "new");
+
+        let ignored = repo
+            .upsert_on(exchange("EX1", "new"), vec![exchange::Column::Code], vec![])
+            .await
+            .unwrap();
+        assert_eq!(ignored, 0);
+    }
+
+    #[tokio::test]
+    async fn test_batched_insert_applies_before_save() {
+        use crate::db::connection::DbPool;
+        use crate::db::repositories::metric_definition::MetricDefinitionRepository;
+        use entities::{metric_definition, sea_orm_active_enums::MetricFrequency};
+        use std::sync::Arc;
+
+        let repo = MetricDefinitionRepository::new(Arc::new(DbPool::in_memory().await));
+        // 未设置 created_at / updated_at，由 before_save 填充
+        let definition = |name: &str| metric_definition::ActiveModel {
+            name: Set(name.to_string()),
+            frequency: Set(MetricFrequency::Daily),
+            ..Default::default()
+        };
+        assert_eq!(repo.insert_missing(vec![definition("pe_ttm"), definition("roe")]).await.unwrap(), 2);
+        let stored = repo.list().await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(stored.iter().all(|m| m.updated_at >= m.created_at));
     }
-}
\ No newline at end of file
+}
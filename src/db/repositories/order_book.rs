@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use futures::Stream;
+use sea_orm::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use crate::db::repositories::kline::to_utc;
+use entities::order_book;
+use entities::sea_orm_active_enums::OrderBookKind;
+
+pub struct OrderBookRepository {
+    db: Arc<DbPool>,
+}
+
+impl OrderBookRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 按 (ts, sequence) 升序流式读取某个标的在 `[from, to)` 内的快照和增量，每批最多 `batch` 条
+    ///
+    /// 分批方式同 `TradeRepository::stream_range`。
+    pub fn stream_range(
+        &self,
+        instrument_id: i32,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+        batch: u64,
+    ) -> impl Stream<Item = Result<Vec<order_book::Model>, DbErr>> + Send + 'static {
+        let db = self.db.clone();
+        let (from, to) = (to_utc(from), to_utc(to));
+        futures::stream::unfold(Some(None), move |state| {
+            let db = db.clone();
+            async move {
+                let cursor: Option<(DateTimeWithTimeZone, i64)> = state?;
+                let mut condition = Condition::all()
+                    .add(order_book::Column::InstrumentId.eq(instrument_id))
+                    .add(order_book::Column::Ts.lt(to));
+                condition = match cursor {
+                    None => condition.add(order_book::Column::Ts.gte(from)),
+                    Some((ts, sequence)) => condition.add(
+                        Condition::any()
+                            .add(order_book::Column::Ts.gt(ts))
+                            .add(order_book::Column::Ts.eq(ts).and(order_book::Column::Sequence.gt(sequence))),
+                    ),
+                };
+                let result = order_book::Entity::find()
+                    .filter(condition)
+                    .order_by_asc(order_book::Column::Ts)
+                    .order_by_asc(order_book::Column::Sequence)
+                    .limit(batch)
+                    .all(&**db)
+                    .await;
+                match result {
+                    Ok(updates) if updates.is_empty() => None,
+                    Ok(updates) => {
+                        let next = updates.last()
+                            .filter(|_| updates.len() as u64 == batch)
+                            .map(|last| Some((last.ts, last.sequence)));
+                        Some((Ok(updates), next))
+                    }
+                    Err(err) => Some((Err(err), None)),
+                }
+            }
+        })
+    }
+
+    /// `at`（含）之前最近的一条快照，重建盘口时从它开始叠加之后的增量
+    pub async fn latest_snapshot(
+        &self,
+        instrument_id: i32,
+        at: DateTimeWithTimeZone,
+    ) -> Result<Option<order_book::Model>, DbErr> {
+        order_book::Entity::find()
+            .filter(order_book::Column::InstrumentId.eq(instrument_id))
+            .filter(order_book::Column::Kind.eq(OrderBookKind::Snapshot))
+            .filter(order_book::Column::Ts.lte(to_utc(at)))
+            .order_by_desc(order_book::Column::Ts)
+            .order_by_desc(order_book::Column::Sequence)
+            .one(self.conn())
+            .await
+    }
+
+    /// 批量写入快照和增量，已存在的 (instrument_id, ts, sequence) 保持不变，返回新写入的行数
+    pub async fn insert_batch(&self, updates: Vec<order_book::Model>) -> Result<u64, DbErr> {
+        let actives = updates
+            .into_iter()
+            .map(|mut model| {
+                model.ts = to_utc(model.ts);
+                order_book::ActiveModel::from(model)
+            })
+            .collect();
+        self.upsert_many(
+            actives,
+            vec![order_book::Column::InstrumentId, order_book::Column::Ts, order_book::Column::Sequence],
+            vec![],
+        )
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<order_book::Entity> for OrderBookRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
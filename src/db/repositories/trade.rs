@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use futures::Stream;
+use sea_orm::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use crate::db::connection::DbPool;
+use crate::db::repositories::Repository;
+use crate::db::repositories::kline::to_utc;
+use entities::trade;
+
+pub struct TradeRepository {
+    db: Arc<DbPool>,
+}
+
+impl TradeRepository {
+    pub fn new(db: Arc<DbPool>) -> Self {
+        Self { db }
+    }
+
+    /// 按 (ts, trade_id) 升序流式读取某个标的在 `[from, to)` 内的成交，每批最多 `batch` 条
+    ///
+    /// 以上一批最后一条的 (ts, trade_id) 为游标分批查询，不会长时间占用连接，也不会一次性加载全部数据。
+    pub fn stream_range(
+        &self,
+        instrument_id: i32,
+        from: DateTimeWithTimeZone,
+        to: DateTimeWithTimeZone,
+        batch: u64,
+    ) -> impl Stream<Item = Result<Vec<trade::Model>, DbErr>> + Send + 'static {
+        let db = self.db.clone();
+        let (from, to) = (to_utc(from), to_utc(to));
+        // 状态：上一批最后一条的 (ts, trade_id)，首批为 None；读完后为外层 None
+        futures::stream::unfold(Some(None), move |state| {
+            let db = db.clone();
+            async move {
+                let cursor: Option<(DateTimeWithTimeZone, i64)> = state?;
+                let mut condition = Condition::all()
+                    .add(trade::Column::InstrumentId.eq(instrument_id))
+                    .add(trade::Column::Ts.lt(to));
+                condition = match cursor {
+                    None => condition.add(trade::Column::Ts.gte(from)),
+                    Some((ts, trade_id)) => condition.add(
+                        Condition::any()
+                            .add(trade::Column::Ts.gt(ts))
+                            .add(trade::Column::Ts.eq(ts).and(trade::Column::TradeId.gt(trade_id))),
+                    ),
+                };
+                let result = trade::Entity::find()
+                    .filter(condition)
+                    .order_by_asc(trade::Column::Ts)
+                    .order_by_asc(trade::Column::TradeId)
+                    .limit(batch)
+                    .all(&**db)
+                    .await;
+                match result {
+                    Ok(trades) if trades.is_empty() => None,
+                    Ok(trades) => {
+                        let next = trades.last()
+                            .filter(|_| trades.len() as u64 == batch)
+                            .map(|last| Some((last.ts, last.trade_id)));
+                        Some((Ok(trades), next))
+                    }
+                    Err(err) => Some((Err(err), None)),
+                }
+            }
+        })
+    }
+
+    /// 批量写入成交，已存在的 (instrument_id, ts, trade_id) 保持不变，返回新写入的行数
+    pub async fn insert_batch(&self, trades: Vec<trade::Model>) -> Result<u64, DbErr> {
+        let actives = trades
+            .into_iter()
+            .map(|mut model| {
+                model.ts = to_utc(model.ts);
+                trade::ActiveModel::from(model)
+            })
+            .collect();
+        self.upsert_many(
+            actives,
+            vec![trade::Column::InstrumentId, trade::Column::Ts, trade::Column::TradeId],
+            vec![],
+        )
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository<trade::Entity> for TradeRepository {
+    fn conn(&self) -> &DatabaseConnection {
+        &self.db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, FixedOffset};
+    use futures::TryStreamExt;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+    use entities::{exchange, instrument};
+
+    async fn repository() -> TradeRepository {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("BINANCE".to_string()),
+            name: Set("Binance".to_string()),
+            timezone: Set("UTC".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        instrument::ActiveModel {
+            id: Set(1),
+            exchange_id: Set(1),
+            symbol: Set("BTCUSDT".to_string()),
+            asset_type: Set(AssetType::CryptoSpot),
+            name: Set("BTC/USDT".to_string()),
+            status: Set(InstrumentStatus::Active),
+            metadata: Set(serde_json::json!({})),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        TradeRepository::new(db)
+    }
+
+    fn ts(value: &str) -> DateTimeWithTimeZone {
+        DateTime::<FixedOffset>::parse_from_rfc3339(value).unwrap()
+    }
+
+    fn trade(ts_value: &str, trade_id: i64) -> trade::Model {
+        trade::Model { instrument_id: 1, ts: ts(ts_value), trade_id, price: 1.0, qty: 1.0, side: None }
+    }
+
+    #[tokio::test]
+    async fn test_stream_range_cursor_at_batch_boundary() {
+        let repo = repository().await;
+        // 同一时刻的成交跨越批次边界：游标须按 (ts, trade_id) 继续，不能按 ts 跳过或重复
+        repo.insert_batch(vec![
+            trade("2026-10-19T00:00:00Z", 9),
+            trade("2026-10-19T00:00:01Z", 3),
+            trade("2026-10-19T00:00:01Z", 1),
+            trade("2026-10-19T00:00:01Z", 2),
+            trade("2026-10-19T00:00:02Z", 1),
+            trade("2026-10-19T00:00:03Z", 1),
+        ])
+        .await
+        .unwrap();
+
+        let stream = repo.stream_range(1, ts("2026-10-19T08:00:01+08:00"), ts("2026-10-19T00:00:03Z"), 2);
+        let batches: Vec<Vec<(i64, i64)>> = stream
+            .map_ok(|batch| batch.iter().map(|t| (t.ts.timestamp() % 60, t.trade_id)).collect())
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(batches, vec![vec![(1, 1), (1, 2)], vec![(1, 3), (2, 1)]]);
+
+        // 最后一批恰好凑满时多查一次空批后结束
+        let stream = repo.stream_range(1, ts("2026-10-19T00:00:00Z"), ts("2026-10-19T00:00:02Z"), 2);
+        let batches: Vec<Vec<trade::Model>> = stream.try_collect().await.unwrap();
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 2]);
+    }
+}
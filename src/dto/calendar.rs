@@ -0,0 +1,54 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 开市状态查询条件
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CalendarStatusQuery {
+    /// 查询时刻（RFC 3339，如 `2026-10-19T10:00:00+08:00`），默认为当前时间
+    pub at: Option<DateTime<FixedOffset>>,
+}
+
+/// 交易时段查询条件，返回与 `[from, to)` 有交集的时段，跨度不超过 366 天
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SessionRangeQuery {
+    pub from: DateTime<FixedOffset>,
+    pub to: DateTime<FixedOffset>,
+}
+
+/// 交易日查询条件，日期为交易所本地日期（含首尾），跨度不超过 366 天
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TradingDaysQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+/// 一个交易时段，时间带交易所本地时区偏移
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SessionResponse {
+    /// 所属交易日（交易所本地日期）
+    pub date: NaiveDate,
+    pub open: DateTime<FixedOffset>,
+    pub close: DateTime<FixedOffset>,
+}
+
+/// 交易所在某一时刻的开市状态
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CalendarStatus {
+    pub exchange: String,
+    pub timezone: String,
+    /// 查询时刻（交易所本地时间）
+    pub at: DateTime<FixedOffset>,
+    pub is_open: bool,
+    /// 当前所在的时段，休市时为空
+    pub session: Option<SessionResponse>,
+    /// 下一次开盘，不含当前所在时段；两年内无交易或持续交易（如 7x24）时为空
+    pub next_open: Option<DateTime<FixedOffset>>,
+    /// 下一次收盘，开市中为当前时段的收盘；首尾相接的时段视为连续交易，持续交易时为空
+    pub next_close: Option<DateTime<FixedOffset>>,
+    /// 已收录休市安排的年份，范围外的交易日与时段查询会被拒绝；不限年份（如 7x24 交易）时为空
+    pub covered_years: Option<Vec<i32>>,
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use entities::corporate_action;
+use entities::sea_orm_active_enums::CorporateActionKind;
+
+/// 新增或替换公司行动，各类型需要的字段：
+///
+/// - `split` / `reverse_split`：`ratio` 为每股变为多少股，拆股大于 1、合股小于 1
+/// - `cash_dividend`：`cash` 为每股派现
+/// - `stock_dividend`：`ratio` 为每股送转股数（10 送 3 为 0.3）
+/// - `rights_issue`：`ratio` 为每股配股数，`subscription_price` 为配股价
+/// - `symbol_change`：`new_symbol`
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CorporateActionRequest {
+    pub kind: CorporateActionKind,
+    /// 除权除息日（交易所本地日期）
+    pub ex_date: NaiveDate,
+    pub record_date: Option<NaiveDate>,
+    pub pay_date: Option<NaiveDate>,
+    pub ratio: Option<f64>,
+    pub cash: Option<f64>,
+    pub subscription_price: Option<f64>,
+    #[validate(length(min = 1, max = 50))]
+    pub old_symbol: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub new_symbol: Option<String>,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// 公司行动时间线过滤条件
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CorporateActionFilter {
+    pub kind: Option<CorporateActionKind>,
+    /// 除权日不早于该日期
+    pub start: Option<NaiveDate>,
+    /// 除权日不晚于该日期
+    pub end: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CorporateActionResponse {
+    pub id: i32,
+    pub instrument_id: i32,
+    pub kind: CorporateActionKind,
+    pub ex_date: NaiveDate,
+    pub record_date: Option<NaiveDate>,
+    pub pay_date: Option<NaiveDate>,
+    pub ratio: Option<f64>,
+    pub cash: Option<f64>,
+    pub subscription_price: Option<f64>,
+    pub old_symbol: Option<String>,
+    pub new_symbol: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<corporate_action::Model> for CorporateActionResponse {
+    fn from(model: corporate_action::Model) -> Self {
+        Self {
+            id: model.id,
+            instrument_id: model.instrument_id,
+            kind: model.kind,
+            ex_date: model.ex_date,
+            record_date: model.record_date,
+            pay_date: model.pay_date,
+            ratio: model.ratio,
+            cash: model.cash,
+            subscription_price: model.subscription_price,
+            old_symbol: model.old_symbol,
+            new_symbol: model.new_symbol,
+            note: model.note,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// 一个除权日的复权因子，同一天的多项行动合并计算
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AdjustmentFactorResponse {
+    pub ex_date: NaiveDate,
+    /// 除权日前最后一根 K 线的收盘价；现金分红和配股需要它，缺失时该日不复权
+    pub prev_close: Option<f64>,
+    /// 除权参考价 / 前收盘价，无法计算时为空
+    pub ratio: Option<f64>,
+    /// 后复权因子：该日及之后（至下一除权日前）的价格乘以该值
+    pub backward_factor: f64,
+    /// 前复权因子：该日及之后（至下一除权日前）的价格乘以该值，最新价格不变
+    pub forward_factor: f64,
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use entities::data_quality_issue;
+use entities::sea_orm_active_enums::{DataQualityIssueKind, KlineInterval};
+
+/// 数据质量检查参数
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema)]
+pub struct DataQualityCheckRequest {
+    /// 检查的周期，默认 1m
+    pub interval: Option<KlineInterval>,
+    /// 开始时间（含），默认为库中最早一根 K 线
+    pub start: Option<DateTime<FixedOffset>>,
+    /// 结束时间（不含），默认到库中最新一根 K 线为止
+    pub end: Option<DateTime<FixedOffset>>,
+    /// 连续零成交量达到该根数时记为问题，默认 5
+    #[validate(range(min = 1, max = 100000))]
+    pub zero_volume_streak: Option<u32>,
+    /// 价格跳变阈值（标准差倍数），默认 8
+    #[validate(range(min = 2.0, max = 100.0))]
+    pub spike_sigma: Option<f64>,
+    /// 计算跳变阈值所用的最近收益率个数，默认 100
+    #[validate(range(min = 20, max = 10000))]
+    pub spike_window: Option<u32>,
+}
+
+impl DataQualityCheckRequest {
+    pub fn interval(&self) -> KlineInterval {
+        self.interval.unwrap_or(KlineInterval::Minute1)
+    }
+}
+
+/// 各类问题的条数
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct DataQualitySummary {
+    pub missing_bars: u64,
+    pub duplicate_timestamp: u64,
+    pub ohlc_violation: u64,
+    pub zero_volume_streak: u64,
+    pub price_spike: u64,
+}
+
+impl DataQualitySummary {
+    pub fn add(&mut self, kind: DataQualityIssueKind) {
+        let count = match kind {
+            DataQualityIssueKind::MissingBars => &mut self.missing_bars,
+            DataQualityIssueKind::DuplicateTimestamp => &mut self.duplicate_timestamp,
+            DataQualityIssueKind::OhlcViolation => &mut self.ohlc_violation,
+            DataQualityIssueKind::ZeroVolumeStreak => &mut self.zero_volume_streak,
+            DataQualityIssueKind::PriceSpike => &mut self.price_spike,
+        };
+        *count += 1;
+    }
+}
+
+/// 数据质量检查结果，问题明细通过列表接口查询
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DataQualityReport {
+    pub instrument_id: i32,
+    pub interval: KlineInterval,
+    /// 实际检查的区间 `[start, end)`，库中没有 K 线且未指定区间时为空
+    pub start: Option<DateTime<FixedOffset>>,
+    pub end: Option<DateTime<FixedOffset>>,
+    /// 已检查的 K 线数
+    pub bars_checked: u64,
+    /// 按交易日历应有的 K 线数
+    pub expected_bars: u64,
+    /// 缺失的 K 线数
+    pub missing_bars: u64,
+    /// 各类问题的条数（连续缺失计为一条）
+    pub issues: DataQualitySummary,
+    /// 问题过多，只保存了前一部分
+    pub truncated: bool,
+}
+
+/// 数据质量问题过滤条件
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DataQualityFilter {
+    pub interval: Option<KlineInterval>,
+    pub kind: Option<DataQualityIssueKind>,
+    /// 问题开始时间不早于该时间
+    pub start: Option<DateTime<FixedOffset>>,
+    /// 问题开始时间早于该时间
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+/// 数据质量问题，`start_ts` / `end_ts` 为首根和末根受影响 K 线（或缺失周期）的开始时间
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DataQualityIssueResponse {
+    pub id: i32,
+    pub instrument_id: i32,
+    pub interval: KlineInterval,
+    pub kind: DataQualityIssueKind,
+    pub start_ts: DateTime<FixedOffset>,
+    pub end_ts: DateTime<FixedOffset>,
+    /// 涉及的 K 线数，缺失时为缺失的根数
+    pub bar_count: i32,
+    /// 明细：OHLC 问题为各价格，跳变为收益率与阈值，重复为同一周期内的各时间
+    pub detail: Option<serde_json::Value>,
+    pub detected_at: DateTime<FixedOffset>,
+}
+
+impl From<data_quality_issue::Model> for DataQualityIssueResponse {
+    fn from(model: data_quality_issue::Model) -> Self {
+        Self {
+            id: model.id,
+            instrument_id: model.instrument_id,
+            interval: model.interval,
+            kind: model.kind,
+            start_ts: model.start_ts,
+            end_ts: model.end_ts,
+            bar_count: model.bar_count,
+            detail: model.detail,
+            detected_at: model.detected_at,
+        }
+    }
+}
+
+/// 补数区间查询
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BackfillQuery {
+    /// 周期，默认 1m
+    pub interval: Option<KlineInterval>,
+}
+
+/// 需要补数的区间 `[start, end)`，由最近一次检查发现的缺失合并而来
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BackfillRange {
+    pub interval: KlineInterval,
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    /// 区间内缺失的 K 线数
+    pub missing_bars: i32,
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::dto::nullable::{nullable, nullable_value};
+use entities::exchange;
+
+/// 单个交易时段（交易所本地时间，格式 HH:MM）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct TradingSession {
+    #[validate(length(equal = 5))]
+    pub open: String,
+    #[validate(length(equal = 5))]
+    pub close: String,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateExchangeRequest {
+    #[validate(length(min = 1, max = 32))]
+    pub code: String,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: String,
+    #[validate(length(min = 1, max = 64))]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub asset_classes: Vec<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub trading_hours: Vec<TradingSession>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateExchangeRequest {
+    #[validate(length(min = 1, max = 32))]
+    pub code: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: Option<String>,
+    /// 显式传 `null` 时清空
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(min = 1, max = 64))]
+    pub country: Option<Option<String>>,
+    pub asset_classes: Option<Vec<String>>,
+    #[validate(nested)]
+    pub trading_hours: Option<Vec<TradingSession>>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ExchangeResponse {
+    pub id: i32,
+    pub code: String,
+    pub name: String,
+    pub timezone: String,
+    pub country: Option<String>,
+    pub asset_classes: Vec<String>,
+    pub trading_hours: Vec<TradingSession>,
+}
+
+// CreateExchangeRequest -> ActiveModel
+impl From<CreateExchangeRequest> for exchange::ActiveModel {
+    fn from(req: CreateExchangeRequest) -> Self {
+        Self {
+            id: sea_orm::ActiveValue::NotSet,
+            code: sea_orm::ActiveValue::Set(req.code),
+            name: sea_orm::ActiveValue::Set(req.name),
+            timezone: sea_orm::ActiveValue::Set(req.timezone),
+            country: sea_orm::ActiveValue::Set(req.country),
+            asset_classes: sea_orm::ActiveValue::Set(serde_json::json!(req.asset_classes)),
+            trading_hours: sea_orm::ActiveValue::Set(serde_json::json!(req.trading_hours)),
+        }
+    }
+}
+
+// UpdateExchangeRequest -> ActiveModel（仅设置提供了的字段，id 由调用方填写）
+impl From<UpdateExchangeRequest> for exchange::ActiveModel {
+    fn from(req: UpdateExchangeRequest) -> Self {
+        Self {
+            id: Default::default(),
+            code: match req.code {
+                Some(v) => sea_orm::ActiveValue::Set(v),
+                None => Default::default(),
+            },
+            name: match req.name {
+                Some(v) => sea_orm::ActiveValue::Set(v),
+                None => Default::default(),
+            },
+            timezone: match req.timezone {
+                Some(v) => sea_orm::ActiveValue::Set(v),
+                None => Default::default(),
+            },
+            country: nullable_value(req.country),
+            asset_classes: match req.asset_classes {
+                Some(v) => sea_orm::ActiveValue::Set(serde_json::json!(v)),
+                None => Default::default(),
+            },
+            trading_hours: match req.trading_hours {
+                Some(v) => sea_orm::ActiveValue::Set(serde_json::json!(v)),
+                None => Default::default(),
+            },
+        }
+    }
+}
+
+// Model -> ExchangeResponse
+impl From<exchange::Model> for ExchangeResponse {
+    fn from(model: exchange::Model) -> Self {
+        Self {
+            id: model.id,
+            code: model.code,
+            name: model.name,
+            timezone: model.timezone,
+            country: model.country,
+            asset_classes: serde_json::from_value(model.asset_classes).unwrap_or_default(),
+            trading_hours: serde_json::from_value(model.trading_hours).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use entities::financial_statement;
+use entities::sea_orm_active_enums::{FiscalPeriod, StatementType};
+
+/// 交换格式（NDJSON）中的一份报表，格式说明见 `docs/financial_statements.md`
+#[derive(Debug, Deserialize)]
+pub struct StatementRecord {
+    pub statement_type: StatementType,
+    pub fiscal_year: i32,
+    pub fiscal_period: FiscalPeriod,
+    pub period_end: NaiveDate,
+    pub announce_date: NaiveDate,
+    pub currency: String,
+    pub source: Option<String>,
+    /// 标准科目代码 -> 数值
+    pub items: BTreeMap<String, f64>,
+}
+
+/// 交换格式（CSV）中的一行：一份报表的一个科目，报表字段相同的行组成一份报表
+#[derive(Debug, Deserialize)]
+pub struct StatementItemRow {
+    pub statement_type: StatementType,
+    pub fiscal_year: i32,
+    pub fiscal_period: FiscalPeriod,
+    pub period_end: NaiveDate,
+    pub announce_date: NaiveDate,
+    pub currency: String,
+    pub source: Option<String>,
+    pub item: String,
+    pub value: f64,
+}
+
+/// 报表查询
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FinancialStatementQuery {
+    pub statement_type: Option<StatementType>,
+    pub fiscal_period: Option<FiscalPeriod>,
+    /// 财年不早于
+    pub start_year: Option<i32>,
+    /// 财年不晚于
+    pub end_year: Option<i32>,
+    /// 知识日期：只使用该日及之前公告的版本，默认为全部已入库的版本
+    pub as_of: Option<NaiveDate>,
+    /// 返回每个报告期的全部版本，默认只返回最新（截至 `as_of`）的一版
+    #[serde(default)]
+    pub all_versions: bool,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct FinancialStatementResponse {
+    pub id: i32,
+    pub statement_type: StatementType,
+    pub fiscal_year: i32,
+    pub fiscal_period: FiscalPeriod,
+    pub period_end: NaiveDate,
+    pub currency: String,
+    pub announce_date: NaiveDate,
+    /// 同一报告期内从 1 开始，重述后递增
+    pub version: i32,
+    pub source: Option<String>,
+    /// 标准科目代码 -> 数值
+    pub items: BTreeMap<String, f64>,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl FinancialStatementResponse {
+    pub fn new(model: financial_statement::Model, items: BTreeMap<String, f64>) -> Self {
+        Self {
+            id: model.id,
+            statement_type: model.statement_type,
+            fiscal_year: model.fiscal_year,
+            fiscal_period: model.fiscal_period,
+            period_end: model.period_end,
+            currency: model.currency,
+            announce_date: model.announce_date,
+            version: model.version,
+            source: model.source,
+            items,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// 科目表查询
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LineItemQuery {
+    pub statement_type: Option<StatementType>,
+}
+
+/// 标准科目
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LineItemResponse {
+    pub code: &'static str,
+    pub statement_type: StatementType,
+    pub name: &'static str,
+    pub name_zh: &'static str,
+}
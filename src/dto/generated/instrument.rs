@@ -2,11 +2,21 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Model {
     pub id: i32,
-    pub exchange: String,
+    pub exchange_id: i32,
     pub symbol: String,
     pub asset_type: String,
     pub name: String,
+    pub base_currency: Option<String>,
+    pub quote_currency: Option<String>,
+    pub tick_size: Option<f64>,
+    pub lot_size: Option<f64>,
+    pub min_notional: Option<f64>,
+    pub contract_multiplier: Option<f64>,
+    pub listing_date: Option<chrono::NaiveDate>,
+    pub delisting_date: Option<chrono::NaiveDate>,
+    pub status: String,
+    pub metadata: serde_json::Value,
 }
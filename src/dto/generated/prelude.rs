@@ -1,3 +1,4 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
 
+pub use super::exchange::Model as Exchange;
 pub use super::instrument::Model as Instrument;
@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 评分结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthRating {
+    Healthy,
+    Caution,
+    Risk,
+}
+
+impl HealthRating {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HealthRating::Healthy => "healthy",
+            HealthRating::Caution => "caution",
+            HealthRating::Risk => "risk",
+        }
+    }
+}
+
+/// 财务健康报告查询
+#[derive(Debug, Default, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HealthQuery {
+    /// 财年，默认为截至 `as_of` 已有年报的最近一个财年
+    #[validate(range(min = 1900, max = 2100))]
+    pub fiscal_year: Option<i32>,
+    /// 知识日期：只使用该日及之前公告的报表版本，默认为全部已入库的版本
+    pub as_of: Option<NaiveDate>,
+}
+
+/// 财务健康报告，基于年报计算
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HealthReport {
+    pub instrument_id: i32,
+    pub fiscal_year: i32,
+    pub period_end: NaiveDate,
+    /// 所用报表中最晚的公告日
+    pub known_at: NaiveDate,
+    /// 上一财年有年报时为其财年，评分中的同比项依赖上一财年
+    pub prior_fiscal_year: Option<i32>,
+    /// 可计算的财务比率
+    pub ratios: Vec<HealthRatio>,
+    pub scores: Vec<HealthScore>,
+}
+
+/// 一个财务比率
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HealthRatio {
+    /// 指标名，同 `feature_metric` 中的名称
+    pub name: &'static str,
+    /// 按请求语言翻译的名称
+    pub label: String,
+    pub value: f64,
+}
+
+/// 一个综合评分
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HealthScore {
+    /// `piotroski_f` / `altman_z` / `beneish_m`
+    pub name: &'static str,
+    /// 数据不足时为空
+    pub value: Option<f64>,
+    pub rating: Option<HealthRating>,
+    /// 所用模型，如 Altman Z 的 `public`（市值）/ `private`（账面权益）
+    pub model: Option<&'static str>,
+    /// 按请求语言翻译的结论
+    pub summary: String,
+    pub components: Vec<HealthComponent>,
+    /// 缺失的输入；可选输入缺失时按中性值计算，必需输入缺失时不给出评分
+    pub missing: Vec<&'static str>,
+}
+
+/// 评分的组成项
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HealthComponent {
+    pub name: &'static str,
+    pub value: Option<f64>,
+    /// Piotroski F 各信号是否得分，其余评分为空
+    pub passed: Option<bool>,
+    /// 按请求语言翻译的解释
+    pub explanation: String,
+}
+
+/// 重新计算并写入 `feature_metric` 的结果
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct HealthRefreshResponse {
+    /// 计算的 (财年, 知识日期) 快照数
+    pub snapshots: usize,
+    /// 写入或覆盖的指标值条数
+    pub written: u64,
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde::de::Error as _;
+use validator::Validate;
+use crate::error::code::AppError;
+
+/// 批量导入 / 导出的数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    /// 首行为表头的 CSV
+    Csv,
+    /// 每行一个 JSON 对象（JSON Lines）
+    Ndjson,
+}
+
+impl DataFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    /// 根据 Content-Type 推断格式（忽略参数部分）
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let essence = mime.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "text/csv" | "application/csv" => Some(Self::Csv),
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(Self::Ndjson)
+            }
+            _ => None,
+        }
+    }
+
+    /// 根据文件扩展名推断格式
+    pub fn from_filename(name: &str) -> Option<Self> {
+        let (_, ext) = name.rsplit_once('.')?;
+        match ext.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+/// 导入参数
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// 数据格式；省略时根据 Content-Type 或上传文件名推断
+    pub format: Option<DataFormat>,
+    /// 只校验并统计结果，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 导出参数
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// 数据格式，默认 csv
+    pub format: Option<DataFormat>,
+}
+
+/// 导入结果汇总
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// 数据行总数
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    /// 与现有记录完全一致而跳过的行数
+    pub unchanged: usize,
+    pub failed: usize,
+    /// 失败行明细，按行号排序
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    pub fn new(dry_run: bool, total: usize) -> Self {
+        Self { dry_run, total, ..Default::default() }
+    }
+
+    /// 记录一行失败
+    pub fn fail(&mut self, row: u64, key: Option<String>, err: AppError) {
+        self.failed += 1;
+        self.errors.push(ImportRowError::new(row, key, err));
+    }
+}
+
+/// 单行导入错误
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ImportRowError {
+    /// 所在行号（CSV 含表头，从 1 开始）
+    pub row: u64,
+    /// 行的业务主键，能解析时返回，如 `SSE:600000`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// 应用级别的错误码，取值见 `AppErrorCode`
+    pub code: i32,
+    pub message: String,
+    /// 字段级错误（字段名 -> 本地化消息）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<HashMap<String, String>>,
+}
+
+impl ImportRowError {
+    pub fn new(row: u64, key: Option<String>, err: AppError) -> Self {
+        let message = err.localized_message();
+        let code = err.code();
+        let errors = match err {
+            AppError::Validation { errors } => errors,
+            _ => None,
+        };
+        Self { row, key, code, message, errors }
+    }
+}
+
+/// 反序列化 JSON 对象字段：CSV 中以 JSON 文本出现（如 `{"lot": 100}`），JSON 中为原生对象
+pub fn json_or_text<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::String(text)) if text.trim().is_empty() => Ok(None),
+        Some(serde_json::Value::String(text)) => serde_json::from_str(&text).map(Some).map_err(D::Error::custom),
+        other => Ok(other),
+    }
+}
@@ -1,59 +1,246 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
-use entities::instrument;
+use crate::dto::import::json_or_text;
+use crate::dto::nullable::{nullable, nullable_value};
+use entities::{instrument, instrument_history};
+use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
 
 #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
 pub struct CreateInstrumentRequest {
+    /// 交易所代码，大小写及首尾空白不敏感
     #[validate(length(min = 1, max = 50))]
     pub exchange: String,
     #[validate(length(min = 1, max = 20))]
     pub symbol: String,
-    #[validate(length(min = 1, max = 20))]
+    /// 资产类型，未知取值会返回校验错误
+    #[schema(value_type = AssetType)]
     pub asset_type: String,
     #[validate(length(min = 1, max = 100))]
     pub name: String,
+    /// 基础货币，如 BTC
+    #[validate(length(min = 1, max = 10))]
+    pub base_currency: Option<String>,
+    /// 计价货币，如 USDT
+    #[validate(length(min = 1, max = 10))]
+    pub quote_currency: Option<String>,
+    /// 最小价格变动单位
+    pub tick_size: Option<f64>,
+    /// 最小数量变动单位
+    pub lot_size: Option<f64>,
+    /// 最小名义价值
+    pub min_notional: Option<f64>,
+    /// 合约乘数
+    pub contract_multiplier: Option<f64>,
+    pub listing_date: Option<NaiveDate>,
+    pub delisting_date: Option<NaiveDate>,
+    /// 生命周期状态，默认 active
+    pub status: Option<InstrumentStatus>,
+    /// 扩展元数据，必须为 JSON 对象（CSV 导入时为 JSON 文本）
+    #[serde(default, deserialize_with = "json_or_text")]
+    pub metadata: Option<serde_json::Value>,
 }
 
+/// 部分更新请求：缺省的字段保持不变，可空字段显式传 `null` 时清空
 #[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
 pub struct UpdateInstrumentRequest {
-    pub id: i32,
+    /// 可选；提供时必须与路径中的 ID 一致
+    pub id: Option<i32>,
     #[validate(length(min = 1, max = 50))]
     pub exchange: Option<String>,
     #[validate(length(min = 1, max = 20))]
     pub symbol: Option<String>,
-    #[validate(length(min = 1, max = 20))]
+    #[schema(value_type = Option<AssetType>)]
     pub asset_type: Option<String>,
     #[validate(length(min = 1, max = 100))]
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(min = 1, max = 10))]
+    pub base_currency: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(min = 1, max = 10))]
+    pub quote_currency: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<f64>)]
+    pub tick_size: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<f64>)]
+    pub lot_size: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<f64>)]
+    pub min_notional: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<f64>)]
+    pub contract_multiplier: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub listing_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub delisting_date: Option<Option<NaiveDate>>,
+    pub status: Option<InstrumentStatus>,
+    pub metadata: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct InstrumentResponse {
     pub id: i32,
+    /// 交易所代码
     pub exchange: String,
+    pub exchange_id: i32,
     pub symbol: String,
-    pub asset_type: String,
+    pub asset_type: AssetType,
     pub name: String,
+    pub base_currency: Option<String>,
+    pub quote_currency: Option<String>,
+    pub tick_size: Option<f64>,
+    pub lot_size: Option<f64>,
+    pub min_notional: Option<f64>,
+    pub contract_multiplier: Option<f64>,
+    pub listing_date: Option<NaiveDate>,
+    pub delisting_date: Option<NaiveDate>,
+    pub status: InstrumentStatus,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// 创建人，取自请求头 `X-User-Id`
+    pub created_by: Option<String>,
+    /// 版本号，与响应头 `ETag` 一致
+    pub version: i32,
 }
 
 // 用于分页查询的过滤器
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct InstrumentFilter {
+    /// 交易所代码，大小写不敏感
+    #[validate(length(min = 1, max = 50))]
     pub exchange: Option<String>,
+    /// 标的代码，精确匹配
+    #[validate(length(min = 1, max = 20))]
     pub symbol: Option<String>,
-    pub asset_type: Option<String>,
+    /// 标的代码前缀，大小写不敏感
+    #[validate(length(min = 1, max = 20))]
+    pub symbol_prefix: Option<String>,
+    /// 名称关键字，大小写不敏感的包含匹配
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    pub asset_type: Option<AssetType>,
+    pub status: Option<InstrumentStatus>,
+}
+
+/// 导出的一行，字段与 `CreateInstrumentRequest` 一致，可直接用于再次导入
+#[derive(Debug, Serialize)]
+pub struct InstrumentRecord {
+    pub exchange: String,
+    pub symbol: String,
+    pub asset_type: AssetType,
+    pub name: String,
+    pub base_currency: Option<String>,
+    pub quote_currency: Option<String>,
+    pub tick_size: Option<f64>,
+    pub lot_size: Option<f64>,
+    pub min_notional: Option<f64>,
+    pub contract_multiplier: Option<f64>,
+    pub listing_date: Option<NaiveDate>,
+    pub delisting_date: Option<NaiveDate>,
+    pub status: InstrumentStatus,
+    pub metadata: serde_json::Value,
+}
+
+impl InstrumentRecord {
+    /// CSV 表头，顺序与字段声明一致
+    pub const COLUMNS: &[&str] = &[
+        "exchange", "symbol", "asset_type", "name", "base_currency", "quote_currency", "tick_size",
+        "lot_size", "min_notional", "contract_multiplier", "listing_date", "delisting_date", "status",
+        "metadata",
+    ];
+
+    pub fn new(model: instrument::Model, exchange: String) -> Self {
+        Self {
+            exchange,
+            symbol: model.symbol,
+            asset_type: model.asset_type,
+            name: model.name,
+            base_currency: model.base_currency,
+            quote_currency: model.quote_currency,
+            tick_size: model.tick_size,
+            lot_size: model.lot_size,
+            min_notional: model.min_notional,
+            contract_multiplier: model.contract_multiplier,
+            listing_date: model.listing_date,
+            delisting_date: model.delisting_date,
+            status: model.status,
+            metadata: model.metadata,
+        }
+    }
+
+    /// CSV 单元格不能嵌套对象，元数据以 JSON 文本写出
+    pub fn flatten_metadata(mut self) -> Self {
+        if !self.metadata.is_string() {
+            self.metadata = serde_json::Value::String(self.metadata.to_string());
+        }
+        self
+    }
+}
+
+/// 变更历史查询条件
+///
+/// 查询某字段在时刻 X 的取值：`field=name&to=X&sort=changed_at:desc&per_page=1`，取第一条的 `new_value`。
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct InstrumentHistoryFilter {
+    /// 字段名，如 name、symbol、deleted_at
+    #[validate(length(min = 1, max = 64))]
+    pub field: Option<String>,
+    /// 变更时间下限（含）
+    pub from: Option<DateTime<Utc>>,
+    /// 变更时间上限（含）
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// 单个字段的一次变更
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct InstrumentHistoryResponse {
+    pub id: i32,
+    pub instrument_id: i32,
+    pub field: String,
+    /// 变更前的值，新建时为空
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+    pub changed_at: DateTime<FixedOffset>,
+    pub changed_by: Option<String>,
 }
 
 
-// CreateInstrumentRequest -> ActiveModel
+// CreateInstrumentRequest -> ActiveModel（exchange_id、asset_type 需由调用方解析后填写）
 impl From<CreateInstrumentRequest> for instrument::ActiveModel {
     fn from(req: CreateInstrumentRequest) -> Self {
         Self {
             id: sea_orm::ActiveValue::NotSet,
-            exchange: sea_orm::ActiveValue::Set(req.exchange),
+            exchange_id: sea_orm::ActiveValue::NotSet,
             symbol: sea_orm::ActiveValue::Set(req.symbol),
-            asset_type: sea_orm::ActiveValue::Set(req.asset_type),
+            asset_type: sea_orm::ActiveValue::NotSet,
             name: sea_orm::ActiveValue::Set(req.name),
+            base_currency: sea_orm::ActiveValue::Set(req.base_currency),
+            quote_currency: sea_orm::ActiveValue::Set(req.quote_currency),
+            tick_size: sea_orm::ActiveValue::Set(req.tick_size),
+            lot_size: sea_orm::ActiveValue::Set(req.lot_size),
+            min_notional: sea_orm::ActiveValue::Set(req.min_notional),
+            contract_multiplier: sea_orm::ActiveValue::Set(req.contract_multiplier),
+            listing_date: sea_orm::ActiveValue::Set(req.listing_date),
+            delisting_date: sea_orm::ActiveValue::Set(req.delisting_date),
+            status: sea_orm::ActiveValue::Set(req.status.unwrap_or(InstrumentStatus::Active)),
+            metadata: sea_orm::ActiveValue::Set(req.metadata.unwrap_or_else(|| serde_json::json!({}))),
+            // 审计列由实体的 ActiveModelBehavior 及服务层维护
+            created_at: sea_orm::ActiveValue::NotSet,
+            updated_at: sea_orm::ActiveValue::NotSet,
+            deleted_at: sea_orm::ActiveValue::NotSet,
+            created_by: sea_orm::ActiveValue::NotSet,
+            version: sea_orm::ActiveValue::NotSet,
+            name_pinyin: sea_orm::ActiveValue::NotSet,
         }
     }
 }
@@ -62,37 +249,70 @@ impl From<CreateInstrumentRequest> for instrument::ActiveModel {
 impl From<UpdateInstrumentRequest> for instrument::ActiveModel {
     fn from(req: UpdateInstrumentRequest) -> Self {
         Self {
-            // ⚠️ 注意：这里 id 通常需要单独传入，而不是从 DTO 填
+            // id 取自请求路径，由调用方设置
             id: Default::default(),
-            exchange: match req.exchange {
+            // exchange 为交易所代码，需由调用方解析为 exchange_id
+            exchange_id: Default::default(),
+            symbol: match req.symbol {
                 Some(v) => sea_orm::ActiveValue::Set(v),
                 None => Default::default(),
             },
-            symbol: match req.symbol {
+            // asset_type 为原始字符串，需由调用方解析为 AssetType
+            asset_type: Default::default(),
+            name: match req.name {
                 Some(v) => sea_orm::ActiveValue::Set(v),
                 None => Default::default(),
             },
-            asset_type: match req.asset_type {
+            base_currency: nullable_value(req.base_currency),
+            quote_currency: nullable_value(req.quote_currency),
+            tick_size: nullable_value(req.tick_size),
+            lot_size: nullable_value(req.lot_size),
+            min_notional: nullable_value(req.min_notional),
+            contract_multiplier: nullable_value(req.contract_multiplier),
+            listing_date: nullable_value(req.listing_date),
+            delisting_date: nullable_value(req.delisting_date),
+            status: match req.status {
                 Some(v) => sea_orm::ActiveValue::Set(v),
                 None => Default::default(),
             },
-            name: match req.name {
+            metadata: match req.metadata {
                 Some(v) => sea_orm::ActiveValue::Set(v),
                 None => Default::default(),
             },
+            created_at: Default::default(),
+            updated_at: Default::default(),
+            deleted_at: Default::default(),
+            created_by: Default::default(),
+            version: Default::default(),
+            name_pinyin: Default::default(),
         }
     }
 }
 
-// Model -> InstrumentResponse
-impl From<instrument::Model> for InstrumentResponse {
-    fn from(model: instrument::Model) -> Self {
+// Model -> InstrumentResponse（交易所代码由调用方查询后传入）
+impl InstrumentResponse {
+    pub fn new(model: instrument::Model, exchange: String) -> Self {
         Self {
             id: model.id,
-            exchange: model.exchange,
+            exchange,
+            exchange_id: model.exchange_id,
             symbol: model.symbol,
             asset_type: model.asset_type,
             name: model.name,
+            base_currency: model.base_currency,
+            quote_currency: model.quote_currency,
+            tick_size: model.tick_size,
+            lot_size: model.lot_size,
+            min_notional: model.min_notional,
+            contract_multiplier: model.contract_multiplier,
+            listing_date: model.listing_date,
+            delisting_date: model.delisting_date,
+            status: model.status,
+            metadata: model.metadata,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            created_by: model.created_by,
+            version: model.version,
         }
     }
 }
@@ -102,10 +322,41 @@ impl From<InstrumentResponse> for instrument::Model {
     fn from(resp: InstrumentResponse) -> Self {
         Self {
             id: resp.id,
-            exchange: resp.exchange,
+            exchange_id: resp.exchange_id,
             symbol: resp.symbol,
             asset_type: resp.asset_type,
             name: resp.name,
+            base_currency: resp.base_currency,
+            quote_currency: resp.quote_currency,
+            tick_size: resp.tick_size,
+            lot_size: resp.lot_size,
+            min_notional: resp.min_notional,
+            contract_multiplier: resp.contract_multiplier,
+            listing_date: resp.listing_date,
+            delisting_date: resp.delisting_date,
+            status: resp.status,
+            metadata: resp.metadata,
+            created_at: resp.created_at,
+            updated_at: resp.updated_at,
+            deleted_at: None,
+            created_by: resp.created_by,
+            version: resp.version,
+            name_pinyin: None,
         }
     }
-}
\ No newline at end of file
+}
+
+// Model -> InstrumentHistoryResponse
+impl From<instrument_history::Model> for InstrumentHistoryResponse {
+    fn from(model: instrument_history::Model) -> Self {
+        Self {
+            id: model.id,
+            instrument_id: model.instrument_id,
+            field: model.field,
+            old_value: model.old_value,
+            new_value: model.new_value,
+            changed_at: model.changed_at,
+            changed_by: model.changed_by,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use entities::kline;
+use entities::sea_orm_active_enums::KlineInterval;
+
+/// 响应格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KlineFormat {
+    /// 每根 K 线一个对象
+    #[default]
+    Json,
+    /// 按列返回数组，适合大范围查询
+    Columnar,
+}
+
+/// 复权方式，依据标的的公司行动计算，只调整价格
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum KlineAdjust {
+    /// 不复权
+    #[default]
+    None,
+    /// 前复权：最新价格不变，历史价格按之后的除权比例调整
+    Forward,
+    /// 后复权：最早价格不变，之后的价格按累计除权比例调整
+    Backward,
+}
+
+/// K 线查询条件
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct KlineQuery {
+    /// 周期，默认 1m；库中没有该周期的数据或只覆盖部分区间时由 1m 实时重采样补齐
+    pub interval: Option<KlineInterval>,
+    /// 开始时间（含，RFC 3339）；为空时返回 `end` 之前最近的 `limit` 根
+    pub start: Option<DateTime<FixedOffset>>,
+    /// 结束时间（不含，RFC 3339），默认为当前时间
+    pub end: Option<DateTime<FixedOffset>>,
+    /// 返回条数，默认 500，最大 5000
+    #[validate(range(min = 1, max = 5000))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    pub format: KlineFormat,
+    #[serde(default)]
+    pub adjust: KlineAdjust,
+}
+
+impl KlineQuery {
+    pub fn interval(&self) -> KlineInterval {
+        self.interval.unwrap_or(KlineInterval::Minute1)
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(500)
+    }
+}
+
+/// 一根 K 线，`ts` 为周期开始时间
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct KlineBar {
+    pub ts: DateTime<FixedOffset>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub quote_volume: Option<f64>,
+    pub trade_count: Option<i64>,
+}
+
+impl From<kline::Model> for KlineBar {
+    fn from(model: kline::Model) -> Self {
+        Self {
+            ts: model.ts,
+            open: model.open,
+            high: model.high,
+            low: model.low,
+            close: model.close,
+            volume: model.volume,
+            quote_volume: model.quote_volume,
+            trade_count: model.trade_count,
+        }
+    }
+}
+
+/// 按列返回的 K 线，各数组等长且按时间升序对应
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct KlineColumns {
+    pub interval: KlineInterval,
+    /// 周期开始时间，Unix 毫秒时间戳
+    pub ts: Vec<i64>,
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+    pub quote_volume: Vec<Option<f64>>,
+    pub trade_count: Vec<Option<i64>>,
+}
+
+impl KlineColumns {
+    pub fn new(interval: KlineInterval, klines: Vec<kline::Model>) -> Self {
+        let len = klines.len();
+        let mut columns = Self {
+            interval,
+            ts: Vec::with_capacity(len),
+            open: Vec::with_capacity(len),
+            high: Vec::with_capacity(len),
+            low: Vec::with_capacity(len),
+            close: Vec::with_capacity(len),
+            volume: Vec::with_capacity(len),
+            quote_volume: Vec::with_capacity(len),
+            trade_count: Vec::with_capacity(len),
+        };
+        for model in klines {
+            columns.ts.push(model.ts.timestamp_millis());
+            columns.open.push(model.open);
+            columns.high.push(model.high);
+            columns.low.push(model.low);
+            columns.close.push(model.close);
+            columns.volume.push(model.volume);
+            columns.quote_volume.push(model.quote_volume);
+            columns.trade_count.push(model.trade_count);
+        }
+        columns
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+use entities::sea_orm_active_enums::KlineInterval;
+use crate::dto::import::ImportRowError;
+use crate::error::code::AppError;
+
+/// 报告中最多保留的失败行明细数
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// 行情文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketDataFormat {
+    /// 首行为表头的 CSV
+    Csv,
+    Parquet,
+}
+
+impl MarketDataFormat {
+    /// 根据文件扩展名推断格式
+    pub fn from_filename(name: &str) -> Option<Self> {
+        let (_, ext) = name.rsplit_once('.')?;
+        match ext.to_ascii_lowercase().as_str() {
+            "csv" | "txt" => Some(Self::Csv),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// 源文件列名到 K 线字段的映射，未配置时列名与字段名相同
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, utoipa::ToSchema)]
+#[serde(default)]
+pub struct ColumnMapping {
+    #[validate(length(min = 1, max = 128))]
+    pub ts: String,
+    #[validate(length(min = 1, max = 128))]
+    pub open: String,
+    #[validate(length(min = 1, max = 128))]
+    pub high: String,
+    #[validate(length(min = 1, max = 128))]
+    pub low: String,
+    #[validate(length(min = 1, max = 128))]
+    pub close: String,
+    #[validate(length(min = 1, max = 128))]
+    pub volume: String,
+    /// 成交额列，可选
+    #[validate(length(min = 1, max = 128))]
+    pub quote_volume: Option<String>,
+    /// 成交笔数列，可选
+    #[validate(length(min = 1, max = 128))]
+    pub trade_count: Option<String>,
+    /// 代码列：一个文件包含多个标的时使用，与 `exchange` 一起解析标的
+    #[validate(length(min = 1, max = 128))]
+    pub symbol: Option<String>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            ts: "ts".to_string(),
+            open: "open".to_string(),
+            high: "high".to_string(),
+            low: "low".to_string(),
+            close: "close".to_string(),
+            volume: "volume".to_string(),
+            quote_volume: None,
+            trade_count: None,
+            symbol: None,
+        }
+    }
+}
+
+/// K 线文件的导入规则
+#[derive(Debug, Clone, Deserialize, Serialize, Validate, utoipa::ToSchema)]
+pub struct KlineImportSpec {
+    /// 文件格式；省略时根据扩展名推断
+    pub format: Option<MarketDataFormat>,
+    pub interval: KlineInterval,
+    /// 文件只包含一个标的时指定其 ID
+    pub instrument_id: Option<i32>,
+    /// 按 `columns.symbol` 解析标的时所属的交易所代码，代码按该交易所的规范化规则匹配
+    #[validate(length(min = 1, max = 32))]
+    pub exchange: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub columns: ColumnMapping,
+    /// 时间列格式：`auto`（默认）、`rfc3339`、`unix_s`、`unix_ms`、`unix_us`、`unix_ns`，
+    /// 或 strftime 格式如 `%Y-%m-%d %H:%M:%S`；`auto` 下数字按量级判断秒 / 毫秒 / 微秒 / 纳秒
+    #[validate(length(min = 1, max = 64))]
+    pub ts_format: Option<String>,
+    /// 不带时区的时间按该 IANA 时区解析，默认 UTC
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: Option<String>,
+    /// CSV 分隔符，默认 `,`
+    pub delimiter: Option<char>,
+    /// 只解析并统计，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 行情导入结果汇总
+#[derive(Debug, Clone, Default, Serialize, utoipa::ToSchema)]
+pub struct MarketDataImportReport {
+    pub dry_run: bool,
+    /// 已读取的数据行数
+    pub total: u64,
+    /// 通过校验的行数（不含重复行）
+    pub accepted: u64,
+    /// 解析或校验失败的行数
+    pub rejected: u64,
+    /// 与文件中前面的行 (标的, 时间) 重复的行数，按后出现的行覆盖
+    pub duplicated: u64,
+    /// 写入（插入或覆盖）数据库的行数，`dry_run` 时为 0
+    pub written: u64,
+    /// 失败行明细，只保留前若干条
+    pub errors: Vec<ImportRowError>,
+}
+
+impl MarketDataImportReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run, ..Default::default() }
+    }
+
+    /// 记录一行失败，明细超过上限后只计数
+    pub fn reject(&mut self, row: u64, key: Option<String>, err: AppError) {
+        self.rejected += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(ImportRowError::new(row, key, err));
+        }
+    }
+}
+
+/// 导入任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// 创建导入任务
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateImportJobRequest {
+    /// 文件路径，相对于服务端配置的 `market_data.import_dir`
+    #[validate(length(min = 1, max = 1024))]
+    pub path: String,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub spec: KlineImportSpec,
+}
+
+/// 导入任务，运行中 `report` 为实时进度
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ImportJobResponse {
+    pub id: Uuid,
+    pub path: String,
+    pub interval: KlineInterval,
+    pub status: ImportJobStatus,
+    pub created_at: DateTime<FixedOffset>,
+    pub started_at: Option<DateTime<FixedOffset>>,
+    pub finished_at: Option<DateTime<FixedOffset>>,
+    pub report: MarketDataImportReport,
+    /// 任务失败原因（如文件无法读取、缺少列）
+    pub error: Option<String>,
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use entities::{feature_metric, metric_definition};
+use entities::sea_orm_active_enums::MetricFrequency;
+
+/// 登记或更新指标目录项，指标名取自路径
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct MetricDefinitionRequest {
+    #[validate(length(min = 1, max = 30))]
+    pub unit: Option<String>,
+    pub frequency: MetricFrequency,
+    #[validate(length(min = 1, max = 100))]
+    pub source: Option<String>,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MetricDefinitionResponse {
+    pub name: String,
+    pub unit: Option<String>,
+    pub frequency: MetricFrequency,
+    pub source: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+}
+
+impl From<metric_definition::Model> for MetricDefinitionResponse {
+    fn from(model: metric_definition::Model) -> Self {
+        Self {
+            name: model.name,
+            unit: model.unit,
+            frequency: model.frequency,
+            source: model.source,
+            description: model.description,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// 一个指标值
+#[derive(Debug, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct MetricInput {
+    /// 指标名，须已在指标目录中登记
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// 数值所属的时间，如报告期末
+    pub ts: DateTime<FixedOffset>,
+    /// 数值公布的时间，按时点读取（`as_of`）时据此判断当时是否已知，因此必填；
+    /// 财报类指标通常晚于 `ts`，误填为 `ts` 会引入未来数据。重述时填写更正公布的时间
+    pub known_at: DateTime<FixedOffset>,
+    pub value: Option<f64>,
+    /// 附加信息（JSON）
+    pub detail: Option<serde_json::Value>,
+}
+
+/// 批量写入指标，已存在的 (name, ts, known_at) 覆盖为新值
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct MetricBatchRequest {
+    #[validate(length(min = 1, max = 10000), nested)]
+    pub metrics: Vec<MetricInput>,
+}
+
+/// 批量写入结果
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MetricWriteResponse {
+    /// 请求中的条数
+    pub received: usize,
+    /// 写入或覆盖的条数
+    pub written: u64,
+}
+
+/// 标的指标时间序列查询
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MetricSeriesQuery {
+    /// 指标名，多个以逗号分隔，最多 50 个
+    #[validate(length(min = 1, max = 2000))]
+    pub names: String,
+    /// 开始时间（含，RFC 3339）
+    pub start: Option<DateTime<FixedOffset>>,
+    /// 结束时间（不含，RFC 3339）
+    pub end: Option<DateTime<FixedOffset>>,
+    /// 时点：只返回该日（标的所属交易所的时区）结束前已公布的值，同一时间有多版时取当时最新的一版；默认返回最新版本
+    pub as_of: Option<NaiveDate>,
+}
+
+/// 截面查询
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MetricCrossSectionQuery {
+    /// 时点：各标的取该日（所属交易所的时区）结束前所属且已公布的最新值
+    pub date: NaiveDate,
+}
+
+/// 时间序列中的一个值
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MetricValue {
+    pub name: String,
+    pub ts: DateTime<FixedOffset>,
+    pub known_at: DateTime<FixedOffset>,
+    pub value: Option<f64>,
+    pub detail: Option<serde_json::Value>,
+}
+
+impl From<feature_metric::Model> for MetricValue {
+    fn from(model: feature_metric::Model) -> Self {
+        Self {
+            name: model.metric_name,
+            ts: model.ts,
+            known_at: model.known_at,
+            value: model.value,
+            detail: model.detail,
+        }
+    }
+}
+
+/// 截面中一个标的的值
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct MetricCrossSectionItem {
+    pub instrument_id: i32,
+    pub symbol: String,
+    pub ts: DateTime<FixedOffset>,
+    pub known_at: DateTime<FixedOffset>,
+    pub value: Option<f64>,
+    pub detail: Option<serde_json::Value>,
+}
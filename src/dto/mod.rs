@@ -1,2 +1,17 @@
+pub mod calendar;
+pub mod corporate_action;
+pub mod data_quality;
+pub mod exchange;
+pub mod financial_statement;
+pub mod health;
+pub mod import;
 pub mod instrument;
-pub mod response;
\ No newline at end of file
+pub mod kline;
+pub mod market_data;
+pub mod metric;
+pub mod nullable;
+pub mod pagination;
+pub mod response;
+pub mod search;
+pub mod symbology;
+pub mod tick;
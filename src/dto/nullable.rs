@@ -0,0 +1,27 @@
+//! 部分更新（PATCH）中可清空的字段
+//!
+//! 字段类型为 `Option<Option<T>>` 并标注 `#[serde(default, deserialize_with = "nullable")]`：
+//! 缺省为 `None`（不修改），显式 `null` 为 `Some(None)`（清空），有值为 `Some(Some(v))`。
+
+use sea_orm::ActiveValue;
+use serde::{Deserialize, Deserializer};
+
+/// 反序列化可清空字段，出现即为 `Some`，`null` 解析为 `Some(None)`
+pub fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 可清空字段转换为 ActiveValue：未提供时保持不变
+pub fn nullable_value<T>(value: Option<Option<T>>) -> ActiveValue<Option<T>>
+where
+    Option<T>: Into<sea_orm::Value>,
+{
+    match value {
+        Some(v) => ActiveValue::Set(v),
+        None => ActiveValue::NotSet,
+    }
+}
@@ -0,0 +1,107 @@
+use sea_orm::{ColumnTrait, Order};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::db::repositories::Page;
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+
+/// 默认每页条数
+pub const DEFAULT_PER_PAGE: u64 = 20;
+/// 每页条数上限
+pub const MAX_PER_PAGE: u64 = 100;
+
+/// 通用分页 / 排序查询参数
+///
+/// `sort` 形如 `symbol:asc,id:desc`，方向省略时默认升序。
+#[derive(Debug, Default, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// 页码，从 1 开始，默认 1
+    #[validate(range(min = 1))]
+    pub page: Option<u64>,
+    /// 每页条数，默认 20，最大 100
+    #[validate(range(min = 1, max = 100))]
+    pub per_page: Option<u64>,
+    /// 排序，`field:asc|desc`，多个字段以逗号分隔
+    #[validate(length(max = 200))]
+    pub sort: Option<String>,
+}
+
+impl PageParams {
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE)
+    }
+
+    /// 将 `sort` 解析为实体列；列名取实体字段名（snake_case），只允许 `sortable` 中的列
+    pub fn order_by<C: ColumnTrait>(&self, sortable: &[C]) -> Result<Vec<(C, Order)>, AppError> {
+        let mut order_by = Vec::new();
+        let mut errors = FieldErrors::new();
+
+        let items = self.sort.as_deref().unwrap_or_default().split(',');
+        for item in items.map(str::trim).filter(|s| !s.is_empty()) {
+            let (field, direction) = item.split_once(':').unwrap_or((item, "asc"));
+            let order = match direction.trim().to_lowercase().as_str() {
+                "asc" => Order::Asc,
+                "desc" => Order::Desc,
+                other => {
+                    errors.add("sort", "validation-sort_order", TranslateArgs::new().add("value", other));
+                    continue;
+                }
+            };
+            match sortable.iter().find(|c| c.as_str() == field.trim()) {
+                Some(column) => order_by.push((*column, order)),
+                None => {
+                    let expected = sortable.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(", ");
+                    errors.add(
+                        "sort",
+                        "validation-sort_field",
+                        TranslateArgs::new().add("value", field.trim()).add("expected", expected),
+                    );
+                }
+            }
+        }
+
+        errors.into_result().map(|_| order_by)
+    }
+}
+
+/// 分页响应体
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// 满足条件的记录总数
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
+
+impl<T> PageResponse<T> {
+    /// 逐项转换，分页信息保持不变
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PageResponse<U> {
+        PageResponse {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            per_page: self.per_page,
+            total_pages: self.total_pages,
+        }
+    }
+}
+
+impl<M, T: From<M>> From<Page<M>> for PageResponse<T> {
+    fn from(page: Page<M>) -> Self {
+        Self {
+            items: page.items.into_iter().map(T::from).collect(),
+            total: page.total,
+            page: page.page,
+            per_page: page.per_page,
+            total_pages: page.total.div_ceil(page.per_page),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use serde::{Serialize};
 use crate::error::code::AppError;
 
@@ -6,13 +7,30 @@ use crate::error::code::AppError;
 pub struct APIResponse<T> {
     /// 请求是否成功
     pub success: bool,
-    /// 应用级别的返回码
+    /// 应用级别的返回码，取值见 `AppErrorCode`
     pub code: i32,
     /// 返回消息
     pub message: String,
     /// 返回的数据，错误时为 null
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
+    /// 字段级错误（字段名 -> 本地化消息），仅校验失败时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<HashMap<String, String>>,
+}
+
+/// 错误响应的文档结构，与 `APIResponse::error` 的序列化结果一致
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+pub struct ErrorResponse {
+    /// 恒为 false
+    pub success: bool,
+    /// 应用级别的返回码，取值见 `AppErrorCode`
+    pub code: i32,
+    /// 本地化后的错误消息
+    pub message: String,
+    /// 字段级错误（字段名 -> 本地化消息），仅校验失败时返回
+    pub errors: Option<HashMap<String, String>>,
 }
 
 impl<T> APIResponse<T> {
@@ -23,6 +41,7 @@ impl<T> APIResponse<T> {
             code: AppError::Success.code(),
             message: AppError::Success.description().to_string(),
             data: Some(data),
+            errors: None,
         }
     }
 
@@ -33,16 +52,23 @@ impl<T> APIResponse<T> {
             code: AppError::Success.code(),
             message: message.into(),
             data: Some(data),
+            errors: None,
         }
     }
 
     /// 创建一个失败的响应
     pub fn error(code: AppError, message: impl Into<String>) -> APIResponse<()> {
+        let app_code = code.code();
+        let errors = match code {
+            AppError::Validation { errors } => errors,
+            _ => None,
+        };
         APIResponse {
             success: false,
-            code: code.code(),
+            code: app_code,
             message: message.into(),
             data: None,
+            errors,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::dto::instrument::InstrumentResponse;
+use entities::sea_orm_active_enums::AssetType;
+
+/// 搜索条件
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// 关键字：代码、名称、别名或中文名称的拼音（首字母 / 全拼），大小写不敏感
+    #[validate(length(min = 1, max = 64))]
+    pub q: String,
+    /// 返回条数，默认 20，最大 50
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<u64>,
+    /// 只搜索该交易所（代码，大小写不敏感）
+    #[validate(length(min = 1, max = 50))]
+    pub exchange: Option<String>,
+    pub asset_type: Option<AssetType>,
+}
+
+impl SearchQuery {
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(20) as usize
+    }
+}
+
+/// 命中的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    Symbol,
+    Name,
+    /// 中文名称的拼音首字母或全拼
+    Pinyin,
+    Alias,
+}
+
+/// 一条搜索结果，按 `score` 从高到低排列
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SearchHit {
+    /// 匹配度，0 到 1 之间
+    pub score: f64,
+    pub matched_field: MatchField,
+    /// 命中的原始文本，如名称或别名
+    pub matched_text: String,
+    pub instrument: InstrumentResponse,
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::dto::instrument::InstrumentResponse;
+use entities::instrument_alias;
+
+/// 标的解析条件
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResolveQuery {
+    /// 代码来源：交易所代码（如 binance）、别名来源（如 isin、figi、bloomberg），
+    /// 或 `canonical`（此时 symbol 为 `交易所:规范写法`，如 `BINANCE:BTC-USDT`）
+    #[validate(length(min = 1, max = 50))]
+    pub source: String,
+    #[validate(length(min = 1, max = 64))]
+    pub symbol: String,
+}
+
+/// 解析命中的方式
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// 命中别名表
+    Alias,
+    /// 按交易所规则规范化后命中标的代码
+    Exchange,
+}
+
+/// 解析结果
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ResolvedInstrument {
+    /// 规范 ID：`交易所代码:规范写法`
+    pub canonical_id: String,
+    /// 交易所原生写法
+    pub native_symbol: String,
+    pub matched_by: MatchKind,
+    pub instrument: InstrumentResponse,
+    pub aliases: Vec<AliasResponse>,
+}
+
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateAliasRequest {
+    /// 来源，如 isin、cusip、figi 或供应商名称，大小写不敏感
+    #[validate(length(min = 1, max = 32))]
+    pub source: String,
+    /// 该来源下的代码；ISIN / CUSIP / FIGI 会校验格式与校验位
+    #[validate(length(min = 1, max = 64))]
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct AliasResponse {
+    pub id: i32,
+    pub instrument_id: i32,
+    pub source: String,
+    pub value: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub created_by: Option<String>,
+}
+
+// Model -> AliasResponse
+impl From<instrument_alias::Model> for AliasResponse {
+    fn from(model: instrument_alias::Model) -> Self {
+        Self {
+            id: model.id,
+            instrument_id: model.instrument_id,
+            source: model.source,
+            value: model.value,
+            created_at: model.created_at,
+            created_by: model.created_by,
+        }
+    }
+}
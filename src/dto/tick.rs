@@ -0,0 +1,141 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+use entities::{order_book, trade};
+use entities::sea_orm_active_enums::{OrderBookKind, TradeSide};
+
+/// 一档盘口 `[price, qty]`
+pub type BookLevel = [f64; 2];
+
+/// 一笔成交
+#[derive(Debug, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct TradeInput {
+    pub ts: DateTime<FixedOffset>,
+    /// 交易所成交 ID，同一时间内决定成交顺序
+    pub trade_id: i64,
+    #[validate(range(exclusive_min = 0.0, code = "positive"))]
+    pub price: f64,
+    #[validate(range(exclusive_min = 0.0, code = "positive"))]
+    pub qty: f64,
+    /// 主动方，未知时省略
+    pub side: Option<TradeSide>,
+}
+
+/// 批量写入成交，已存在的 (ts, trade_id) 会被跳过
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct TradeBatchRequest {
+    #[validate(length(min = 1, max = 10000), nested)]
+    pub trades: Vec<TradeInput>,
+}
+
+/// 一条盘口快照或增量
+#[derive(Debug, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct OrderBookInput {
+    pub ts: DateTime<FixedOffset>,
+    /// 交易所的更新序号
+    pub sequence: i64,
+    pub kind: OrderBookKind,
+    /// 买盘 `[[price, qty], ...]`，增量中数量为 0 表示删除该档
+    #[validate(custom(function = "validate_levels"))]
+    #[schema(value_type = Vec<Vec<f64>>)]
+    pub bids: Vec<BookLevel>,
+    /// 卖盘，格式同 `bids`
+    #[validate(custom(function = "validate_levels"))]
+    #[schema(value_type = Vec<Vec<f64>>)]
+    pub asks: Vec<BookLevel>,
+}
+
+/// 批量写入盘口，已存在的 (ts, sequence) 会被跳过
+#[derive(Debug, Deserialize, Validate, utoipa::ToSchema)]
+pub struct OrderBookBatchRequest {
+    #[validate(length(min = 1, max = 1000), nested)]
+    pub updates: Vec<OrderBookInput>,
+}
+
+fn validate_levels(levels: &[BookLevel]) -> Result<(), ValidationError> {
+    if levels.iter().all(|&[price, qty]| price > 0.0 && qty >= 0.0) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("book_levels"))
+    }
+}
+
+/// 批量写入结果
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TickWriteResponse {
+    /// 请求中的条数
+    pub received: usize,
+    /// 新写入的条数，其余为已存在
+    pub inserted: u64,
+}
+
+/// 逐笔数据的时间范围 `[start, end)`
+#[derive(Debug, Deserialize, Validate, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct TickRangeQuery {
+    /// 开始时间（含，RFC 3339）
+    pub start: DateTime<FixedOffset>,
+    /// 结束时间（不含，RFC 3339）
+    pub end: DateTime<FixedOffset>,
+}
+
+/// 一笔成交，导出为 NDJSON 的一行
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TradeRecord {
+    pub ts: DateTime<FixedOffset>,
+    pub trade_id: i64,
+    pub price: f64,
+    pub qty: f64,
+    pub side: Option<TradeSide>,
+}
+
+impl From<trade::Model> for TradeRecord {
+    fn from(model: trade::Model) -> Self {
+        Self {
+            ts: model.ts,
+            trade_id: model.trade_id,
+            price: model.price,
+            qty: model.qty,
+            side: model.side,
+        }
+    }
+}
+
+/// 一条盘口记录，导出为 NDJSON 的一行
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct OrderBookRecord {
+    pub ts: DateTime<FixedOffset>,
+    pub sequence: i64,
+    pub kind: OrderBookKind,
+    #[schema(value_type = Vec<Vec<f64>>)]
+    pub bids: serde_json::Value,
+    #[schema(value_type = Vec<Vec<f64>>)]
+    pub asks: serde_json::Value,
+}
+
+impl From<order_book::Model> for OrderBookRecord {
+    fn from(model: order_book::Model) -> Self {
+        Self {
+            ts: model.ts,
+            sequence: model.sequence,
+            kind: model.kind,
+            bids: model.bids,
+            asks: model.asks,
+        }
+    }
+}
+
+/// 由逐笔成交重建 1m K 线的结果
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct KlineRebuildReport {
+    pub instrument_id: i32,
+    /// 实际重建的区间 `[start, end)`，已对齐到整分钟
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    /// 读取的成交笔数
+    pub trades: u64,
+    /// 删除的原有 1m K 线数
+    pub replaced: u64,
+    /// 写入的 1m K 线数，没有成交的分钟不生成 K 线
+    pub bars: u64,
+}
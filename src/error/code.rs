@@ -50,6 +50,20 @@ pub enum AppError {
         message: String,
     }
     ,
+    #[error("Service unavailable")]
+    ServiceUnavailable,
+    #[error("Precondition failed")]
+    PreconditionFailed {
+        resource: String,
+        version: i64,
+    }
+    ,
+    #[error("Precondition required")]
+    PreconditionRequired,
+    #[error("Reference conflict")]
+    ReferenceConflict,
+    #[error("Record not updated")]
+    RecordNotUpdated,
 }
 
 impl AppError {
@@ -64,6 +78,11 @@ impl AppError {
             Self::Validation { .. } => 4010,
             Self::Internal { .. } => 5000,
             Self::Database { .. } => 5001,
+            Self::ServiceUnavailable { .. } => 5003,
+            Self::PreconditionFailed { .. } => 4012,
+            Self::PreconditionRequired { .. } => 4028,
+            Self::ReferenceConflict { .. } => 4109,
+            Self::RecordNotUpdated { .. } => 4209,
         }
     }
 
@@ -78,6 +97,11 @@ impl AppError {
             Self::Validation { .. } => Category::Error,
             Self::Internal { .. } => Category::Error,
             Self::Database { .. } => Category::Error,
+            Self::ServiceUnavailable { .. } => Category::Error,
+            Self::PreconditionFailed { .. } => Category::Error,
+            Self::PreconditionRequired { .. } => Category::Error,
+            Self::ReferenceConflict { .. } => Category::Error,
+            Self::RecordNotUpdated { .. } => Category::Error,
         }
     }
 
@@ -92,6 +116,11 @@ impl AppError {
             Self::Validation { .. } => "error-validation",
             Self::Internal { .. } => "error-internal",
             Self::Database { .. } => "error-database",
+            Self::ServiceUnavailable { .. } => "error-service_unavailable",
+            Self::PreconditionFailed { .. } => "error-precondition_failed",
+            Self::PreconditionRequired { .. } => "error-precondition_required",
+            Self::ReferenceConflict { .. } => "error-reference_conflict",
+            Self::RecordNotUpdated { .. } => "error-record_not_updated",
         }
     }
 
@@ -106,6 +135,11 @@ impl AppError {
             Self::Validation { .. } => "Validation Error",
             Self::Internal { .. } => "Internal server error",
             Self::Database { .. } => "Database error",
+            Self::ServiceUnavailable { .. } => "Service unavailable",
+            Self::PreconditionFailed { .. } => "Precondition failed",
+            Self::PreconditionRequired { .. } => "Precondition required",
+            Self::ReferenceConflict { .. } => "Reference conflict",
+            Self::RecordNotUpdated { .. } => "Record not updated",
         }
     }
 
@@ -120,6 +154,11 @@ impl AppError {
             Self::Validation { .. } => 422,
             Self::Internal { .. } => 500,
             Self::Database { .. } => 500,
+            Self::ServiceUnavailable { .. } => 503,
+            Self::PreconditionFailed { .. } => 412,
+            Self::PreconditionRequired { .. } => 428,
+            Self::ReferenceConflict { .. } => 409,
+            Self::RecordNotUpdated { .. } => 409,
         }
     }
 
@@ -147,7 +186,43 @@ impl AppError {
             Self::Database { message } => {
                 map.insert("message".to_string(), message.to_string());
             },
+            Self::ServiceUnavailable { .. } => {},
+            Self::PreconditionFailed { resource, version } => {
+                map.insert("resource".to_string(), resource.to_string());
+                map.insert("version".to_string(), version.to_string());
+            },
+            Self::PreconditionRequired { .. } => {},
+            Self::ReferenceConflict { .. } => {},
+            Self::RecordNotUpdated { .. } => {},
         }
         map
     }
-}
\ No newline at end of file
+}
+
+/// 返回码元信息
+#[derive(Debug, Clone, Copy)]
+pub struct CodeInfo {
+    pub code: i32,
+    pub name: &'static str,
+    pub key: &'static str,
+    pub description: &'static str,
+    pub http_status: u16,
+}
+
+/// codes.yaml 中定义的全部返回码
+pub const ALL_CODES: &[CodeInfo] = &[
+    CodeInfo { code: 0, name: "Success", key: "success-success", description: "Success", http_status: 200 },
+    CodeInfo { code: 4000, name: "BadRequest", key: "error-bad_request", description: "Bad request", http_status: 400 },
+    CodeInfo { code: 4001, name: "Unauthorized", key: "error-unauthorized", description: "Unauthorized", http_status: 401 },
+    CodeInfo { code: 4003, name: "Forbidden", key: "error-forbidden", description: "Forbidden", http_status: 403 },
+    CodeInfo { code: 4004, name: "NotFound", key: "error-not_found", description: "Not found", http_status: 404 },
+    CodeInfo { code: 4009, name: "Conflict", key: "error-conflict", description: "Conflict", http_status: 409 },
+    CodeInfo { code: 4010, name: "Validation", key: "error-validation", description: "Validation Error", http_status: 422 },
+    CodeInfo { code: 5000, name: "Internal", key: "error-internal", description: "Internal server error", http_status: 500 },
+    CodeInfo { code: 5001, name: "Database", key: "error-database", description: "Database error", http_status: 500 },
+    CodeInfo { code: 5003, name: "ServiceUnavailable", key: "error-service_unavailable", description: "Service unavailable", http_status: 503 },
+    CodeInfo { code: 4012, name: "PreconditionFailed", key: "error-precondition_failed", description: "Precondition failed", http_status: 412 },
+    CodeInfo { code: 4028, name: "PreconditionRequired", key: "error-precondition_required", description: "Precondition required", http_status: 428 },
+    CodeInfo { code: 4109, name: "ReferenceConflict", key: "error-reference_conflict", description: "Reference conflict", http_status: 409 },
+    CodeInfo { code: 4209, name: "RecordNotUpdated", key: "error-record_not_updated", description: "Record not updated", http_status: 409 },
+];
\ No newline at end of file
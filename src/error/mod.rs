@@ -1,17 +1,112 @@
 pub mod code;
+pub mod validation;
+
+use crate::core::context::current_locale;
+use crate::db::error::DbError;
+use crate::i18n::{t_locale, TranslateArgs};
+use validation::FieldErrors;
+
+impl code::AppError {
+    /// 按当前请求协商的语言翻译错误消息，缺少翻译时退回错误描述
+    pub fn localized_message(&self) -> String {
+        t_locale(self.as_key(), &current_locale(), self.to_args().into()).unwrap_or(self.to_string())
+    }
+
+    /// 请求整体无效时的 `BadRequest`，消息为按当前请求语言翻译的 `key`
+    pub fn bad_request(key: &str) -> Self {
+        Self::bad_request_with(key, TranslateArgs::new())
+    }
+
+    /// 同 `bad_request`，消息带参数
+    pub fn bad_request_with(key: &str, args: TranslateArgs) -> Self {
+        let message = t_locale(key, &current_locale(), args).unwrap_or_else(|_| key.to_string());
+        code::AppError::BadRequest { message }
+    }
+}
 
 impl From<sea_orm::DbErr> for code::AppError {
     fn from(err: sea_orm::DbErr) -> Self {
+        DbError::from(err).into()
+    }
+}
+
+impl From<DbError> for code::AppError {
+    fn from(err: DbError) -> Self {
         match err {
-            sea_orm::DbErr::RecordNotFound(_) => code::AppError::Database {
-                message: "Record not found".to_string()
-            },
-            sea_orm::DbErr::Exec(_) | sea_orm::DbErr::Query(_) => code::AppError::Database {
-                message: "SQL execution error".to_string()
-            },
-            _ => code::AppError::Database {
-                message: "Unknown database error".to_string()
+            DbError::UniqueViolation { table, columns } => code::AppError::Conflict {
+                resource: table.unwrap_or_else(|| "Record".to_string()),
+                identifier: columns.join(", "),
             },
+            DbError::ForeignKeyViolation { message, still_referenced: true } => {
+                tracing::debug!(%message, "Record is still referenced");
+                code::AppError::ReferenceConflict
+            }
+            DbError::ForeignKeyViolation { message, still_referenced: false } => {
+                tracing::debug!(%message, "Referenced record does not exist");
+                code::AppError::bad_request("validation-reference_missing")
+            }
+            DbError::NotNullViolation { column } => {
+                let mut errors = FieldErrors::new();
+                errors.add(column.unwrap_or_else(|| "record".to_string()), "validation-required", TranslateArgs::new());
+                errors.into()
+            }
+            DbError::CheckViolation { constraint } => {
+                let mut errors = FieldErrors::new();
+                errors.add(constraint.unwrap_or_else(|| "record".to_string()), "validation-invalid", TranslateArgs::new());
+                errors.into()
+            }
+            DbError::Unavailable { reason } => {
+                tracing::warn!(%reason, "Database unavailable");
+                code::AppError::ServiceUnavailable
+            }
+            DbError::MigrationError { reason } => code::AppError::Database { message: reason },
+            // 记录不存在或乐观更新未命中属于请求层面的结果，不按服务端错误记录
+            DbError::SeaOrm(sea_orm::DbErr::RecordNotFound(message)) => {
+                tracing::debug!(%message, "Record not found");
+                code::AppError::NotFound { resource: "Record".to_string(), identifier: None }
+            }
+            DbError::SeaOrm(sea_orm::DbErr::RecordNotUpdated) => code::AppError::RecordNotUpdated,
+            DbError::SeaOrm(err) => {
+                tracing::error!(error = %err, "Database error");
+                match err {
+                    sea_orm::DbErr::Exec(_) | sea_orm::DbErr::Query(_) => code::AppError::Database {
+                        message: "SQL execution error".to_string()
+                    },
+                    _ => code::AppError::Database {
+                        message: "Unknown database error".to_string()
+                    },
+                }
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code::AppError;
+
+    #[test]
+    fn test_db_errors_map_to_client_statuses() {
+        let missing_parent = AppError::from(DbError::ForeignKeyViolation {
+            message: "FOREIGN KEY constraint failed".into(),
+            still_referenced: false,
+        });
+        assert!(matches!(missing_parent, AppError::BadRequest { .. }));
+        assert_eq!(missing_parent.http_status(), 400);
+
+        let still_referenced = AppError::from(DbError::ForeignKeyViolation {
+            message: r#"update or delete on table "exchange" violates foreign key constraint"#.into(),
+            still_referenced: true,
+        });
+        assert!(matches!(still_referenced, AppError::ReferenceConflict));
+        assert_eq!(still_referenced.http_status(), 409);
+
+        let not_found = AppError::from(sea_orm::DbErr::RecordNotFound("instrument".into()));
+        assert!(matches!(not_found, AppError::NotFound { identifier: None, .. }));
+        assert_eq!(not_found.http_status(), 404);
+
+        let not_updated = AppError::from(sea_orm::DbErr::RecordNotUpdated);
+        assert_eq!(not_updated.http_status(), 409);
+    }
+}
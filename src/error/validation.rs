@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::core::context::current_locale;
+use crate::error::code::AppError;
+use crate::i18n::{t_locale, TranslateArgs};
+
+/// 字段级校验错误收集器
+///
+/// 消息 key 定义在 `configs/locales/validation/<lang>.ftl`，按当前请求协商的语言翻译；
+/// 缺少翻译时退回通用的 `validation-invalid`。
+#[derive(Debug, Default)]
+pub struct FieldErrors {
+    errors: HashMap<String, String>,
+}
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一个字段错误；同一字段只保留第一条
+    pub fn add(&mut self, field: impl Into<String>, key: &str, args: TranslateArgs) {
+        self.errors.entry(field.into()).or_insert_with(|| {
+            let locale = current_locale();
+            t_locale(key, &locale, args)
+                .or_else(|_| t_locale("validation-invalid", &locale, TranslateArgs::new()))
+                .unwrap_or_else(|_| key.to_string())
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 没有错误时返回 Ok，否则转换为 `AppError::Validation`
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.into())
+        }
+    }
+}
+
+impl From<FieldErrors> for AppError {
+    fn from(errors: FieldErrors) -> Self {
+        AppError::Validation { errors: Some(errors.errors) }
+    }
+}
+
+/// `validator` 的校验结果转换为字段错误，嵌套字段以 `a.b` / `a[0].b` 表示
+impl From<ValidationErrors> for FieldErrors {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut field_errors = FieldErrors::new();
+        collect_validation_errors(&errors, "", &mut field_errors);
+        field_errors
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        FieldErrors::from(errors).into()
+    }
+}
+
+fn collect_validation_errors(errors: &ValidationErrors, prefix: &str, out: &mut FieldErrors) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(errs) => {
+                for err in errs {
+                    let (key, args) = message_key(err);
+                    out.add(path.clone(), &key, args);
+                }
+            }
+            ValidationErrorsKind::Struct(inner) => collect_validation_errors(inner, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, inner) in items {
+                    collect_validation_errors(inner, &format!("{path}[{index}]"), out);
+                }
+            }
+        }
+    }
+}
+
+/// 由 validator 的错误码与参数得到消息 key，如 `length` + min/max -> `validation-length`
+fn message_key(err: &ValidationError) -> (String, TranslateArgs) {
+    let param = |name: &str| err.params.get(name).and_then(|v| v.as_f64());
+    let code = err.code.as_ref();
+
+    match code {
+        "length" | "range" => {
+            if let Some(equal) = param("equal") {
+                return (format!("validation-{code}_equal"), TranslateArgs::new().add("equal", equal));
+            }
+            let (min, max) = (param("min"), param("max"));
+            let mut args = TranslateArgs::new();
+            if let Some(min) = min {
+                args = args.add("min", min);
+            }
+            if let Some(max) = max {
+                args = args.add("max", max);
+            }
+            let suffix = match (min, max) {
+                (Some(_), None) => "_min",
+                (None, Some(_)) => "_max",
+                _ => "",
+            };
+            (format!("validation-{code}{suffix}"), args)
+        }
+        _ => (format!("validation-{code}"), TranslateArgs::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Item {
+        #[validate(length(min = 1, max = 3))]
+        name: String,
+    }
+
+    #[derive(Validate)]
+    struct Request {
+        #[validate(range(min = 1))]
+        page: u64,
+        #[validate(nested)]
+        items: Vec<Item>,
+    }
+
+    #[test]
+    fn test_nested_field_paths() {
+        let req = Request {
+            page: 0,
+            items: vec![Item { name: "ok".into() }, Item { name: "toolong".into() }],
+        };
+        let errors = FieldErrors::from(req.validate().unwrap_err());
+        let mut fields: Vec<_> = errors.errors.into_keys().collect();
+        fields.sort();
+        assert_eq!(fields, vec!["items[1].name", "page"]);
+    }
+}
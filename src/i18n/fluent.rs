@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, fs, sync::{Arc, RwLock}};
+use std::{borrow::Cow, collections::HashMap, fs, path::Path, sync::{Arc, RwLock}};
 use fluent_bundle::{concurrent::FluentBundle, FluentResource, FluentArgs, FluentValue};
 use unic_langid::LanguageIdentifier;
 
@@ -14,31 +14,31 @@ pub struct FluentBackend {
 
 impl FluentBackend {
     /// 初始化：从 `locales/` 目录加载所有 `.ftl` 文件
+    ///
+    /// - `locales/<lang>.ftl`：由 ftl-codegen 根据 codes.yaml 生成的错误码翻译
+    /// - `locales/<domain>/<lang>.ftl`：按领域手写的翻译（如字段校验消息），合并进同一语言的 bundle
     pub fn new(locales_dir: &str, default_locale: &str) -> I18nResult<Self> {
-        let mut bundles = HashMap::new();
-        let mut supported = vec![];
+        let mut resources: HashMap<LanguageIdentifier, Vec<FluentResource>> = HashMap::new();
 
         for entry in fs::read_dir(locales_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) != Some("ftl") {
-                continue;
+            let path = entry?.path();
+            if path.is_dir() {
+                for sub_entry in fs::read_dir(&path)? {
+                    Self::load_resource(&sub_entry?.path(), &mut resources)?;
+                }
+            } else {
+                Self::load_resource(&path, &mut resources)?;
             }
+        }
 
-            let stem = path.file_stem()
-                .and_then(|s| s.to_str())
-                .ok_or_else(|| anyhow::anyhow!("Invalid file name"))?;
-
-            let lang: LanguageIdentifier = stem.parse()
-                .map_err(|_| anyhow::anyhow!("Invalid language identifier: {}", stem))?;
-
-            let ftl_str = fs::read_to_string(&path)?;
-            let resource = FluentResource::try_new(ftl_str)
-                .map_err(|(_, errs)| anyhow::anyhow!("FTL parse errors: {:?}", errs))?;
-
+        let mut bundles = HashMap::new();
+        let mut supported = vec![];
+        for (lang, lang_resources) in resources {
             let mut bundle = FluentBundle::new_concurrent(vec![lang.clone()]);
-            bundle.add_resource(resource)
-                .map_err(|errs| anyhow::anyhow!("Fluent resource errors: {:?}", errs))?;
+            for resource in lang_resources {
+                bundle.add_resource(resource)
+                    .map_err(|errs| anyhow::anyhow!("Fluent resource errors: {:?}", errs))?;
+            }
 
             bundles.insert(lang.clone(), Arc::new(bundle));
             supported.push(lang);
@@ -55,6 +55,30 @@ impl FluentBackend {
         })
     }
 
+    /// 内部方法：解析单个 `<lang>.ftl` 文件，非 ftl 文件直接跳过
+    fn load_resource(
+        path: &Path,
+        resources: &mut HashMap<LanguageIdentifier, Vec<FluentResource>>,
+    ) -> I18nResult<()> {
+        if path.extension().and_then(|s| s.to_str()) != Some("ftl") {
+            return Ok(());
+        }
+
+        let stem = path.file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid file name"))?;
+
+        let lang: LanguageIdentifier = stem.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid language identifier: {}", stem))?;
+
+        let ftl_str = fs::read_to_string(path)?;
+        let resource = FluentResource::try_new(ftl_str)
+            .map_err(|(_, errs)| anyhow::anyhow!("FTL parse errors: {:?}", errs))?;
+
+        resources.entry(lang).or_default().push(resource);
+        Ok(())
+    }
+
     /// 内部方法：获取 FluentBundle
     fn get_bundle(&self, locale: &LanguageIdentifier) -> Option<Arc<FluentBundle<FluentResource>>> {
         self.bundles.get(locale).cloned()
@@ -142,7 +166,7 @@ mod tests {
     use std::path::Path;
 
     fn setup_backend() -> FluentBackend {
-        let locales_dir = Path::new("/root/opensources/uniquant/configs/locales");
+        let locales_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("configs/locales");
         FluentBackend::new(locales_dir.to_str().unwrap(), "en").unwrap()
     }
 
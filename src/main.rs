@@ -1,8 +1,11 @@
 use std::{path::Path, sync::Arc};
 
+use clap::Parser;
+
 use crate::i18n::{fluent::FluentBackend, GlobalI18n};
 
 pub mod api;
+pub mod cli;
 pub mod core;
 pub mod db;
 pub mod dto;
@@ -12,6 +15,7 @@ pub mod i18n;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let cli = cli::Cli::parse();
     core::logging::logger::init();
     // 初始化配置 & 日志
     let config = core::config::AppConfig::bootstrap()?;
@@ -28,10 +32,21 @@ async fn main() -> anyhow::Result<()> {
     
     let repo = Arc::new(db);
 
+    // 加载交易日历文件
+    let calendars_path = Path::new(config.configs_dir.as_str()).join("calendars");
+    let calendars = service::calendar::CalendarFiles::load(&calendars_path)?;
+
     // 初始化服务工厂
-    let service_factory = service::factory::ServiceFactory::new(repo.clone());
+    let service_factory = service::factory::ServiceFactory::new(repo.clone(), calendars, &config.market_data);
     let service_factory = Arc::new(service_factory);
 
+    // 命令行子命令执行完即退出
+    if let Some(command) = cli.command {
+        return match command {
+            cli::Command::ImportKlines(args) => cli::import_klines(&service_factory, args).await,
+        };
+    }
+
     // 构建 app
     let app = api::WebServer::new(config, service_factory)?;
 
@@ -0,0 +1,317 @@
+//! 复权因子
+//!
+//! 每个除权日的价格比例 `r = 除权参考价 / 前收盘价`，同一天的多项行动合并计算：
+//! `除权参考价 = (前收盘 - 每股派现 + 配股价 × 配股比例) / ((1 + 送转比例 + 配股比例) × 拆合股比例)`。
+//! 后复权因子为截至该时刻各 `1 / r` 的乘积（最早的价格不变），前复权因子为该时刻之后各 `r` 的乘积（最新的价格不变）。
+//! 只调整价格，成交量与成交额保持原值。
+//!
+//! 因子按 K 线的开始时刻取值：日线及日内周期不会跨越除权日（除权日从本地 0 点开始），
+//! 跨越除权日的周线、月线须由复权后的日线重新聚合（见 `KlineService`）。
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::{DbErr, Order};
+use tokio::sync::RwLock;
+use crate::db::repositories::kline::KlineRepository;
+use crate::service::trading_calendar::TradingCalendar;
+use entities::{corporate_action, kline};
+use entities::sea_orm_active_enums::{CorporateActionKind, KlineInterval};
+
+/// 缓存的复权因子的有效期，已有前收盘价的更正在过期后生效
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// 复权方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdjustMode {
+    /// 前复权
+    Forward,
+    /// 后复权
+    Backward,
+}
+
+/// 同一除权日的行动合计
+#[derive(Debug, Clone, Copy)]
+struct ExDateEvents {
+    cash: f64,
+    bonus: f64,
+    rights: f64,
+    /// 配股价 × 配股比例之和
+    rights_cost: f64,
+    split: f64,
+}
+
+impl Default for ExDateEvents {
+    fn default() -> Self {
+        Self { cash: 0.0, bonus: 0.0, rights: 0.0, rights_cost: 0.0, split: 1.0 }
+    }
+}
+
+impl ExDateEvents {
+    fn add(&mut self, action: &corporate_action::Model) {
+        let ratio = action.ratio.unwrap_or(0.0);
+        match action.kind {
+            CorporateActionKind::Split | CorporateActionKind::ReverseSplit if ratio > 0.0 => self.split *= ratio,
+            CorporateActionKind::CashDividend => self.cash += action.cash.unwrap_or(0.0),
+            CorporateActionKind::StockDividend => self.bonus += ratio,
+            CorporateActionKind::RightsIssue => {
+                self.rights += ratio;
+                self.rights_cost += ratio * action.subscription_price.unwrap_or(0.0);
+            }
+            _ => {}
+        }
+    }
+
+    /// 是否需要前收盘价才能计算比例
+    fn needs_prev_close(&self) -> bool {
+        self.cash != 0.0 || self.rights != 0.0
+    }
+
+    /// 价格比例；需要前收盘价而缺失，或除权参考价不为正时返回 `None`
+    fn ratio(&self, prev_close: Option<f64>) -> Option<f64> {
+        let shares = (1.0 + self.bonus + self.rights) * self.split;
+        if !self.needs_prev_close() {
+            return Some(1.0 / shares);
+        }
+        let prev_close = prev_close.filter(|&price| price > 0.0)?;
+        let ex_price = (prev_close - self.cash + self.rights_cost) / shares;
+        (ex_price > 0.0).then_some(ex_price / prev_close)
+    }
+}
+
+/// 一个除权日的复权信息
+#[derive(Debug, Clone)]
+pub struct AdjustmentPoint {
+    pub ex_date: NaiveDate,
+    /// 除权日本地 0 点，此后的价格属于除权后
+    pub start: DateTime<Utc>,
+    pub prev_close: Option<f64>,
+    pub ratio: Option<f64>,
+    /// 截至该除权日（含）的后复权因子
+    pub backward: f64,
+}
+
+/// 某个标的按除权日排列的复权因子
+#[derive(Debug, Clone, Default)]
+pub struct AdjustmentFactors {
+    points: Vec<AdjustmentPoint>,
+}
+
+impl AdjustmentFactors {
+    /// 由公司行动计算复权因子，前收盘价取除权日前最后一根日线（没有时取 1m）的收盘价
+    pub async fn load(
+        actions: &[corporate_action::Model],
+        kline_repo: &KlineRepository,
+        calendar: &TradingCalendar,
+        instrument_id: i32,
+    ) -> Result<Self, DbErr> {
+        let mut days: BTreeMap<NaiveDate, ExDateEvents> = BTreeMap::new();
+        for action in actions.iter().filter(|action| action.kind != CorporateActionKind::SymbolChange) {
+            days.entry(action.ex_date).or_default().add(action);
+        }
+        let mut inputs = Vec::with_capacity(days.len());
+        for (ex_date, events) in days {
+            let start = calendar.start_of_day(ex_date);
+            let prev_close = if events.needs_prev_close() {
+                prev_close(kline_repo, instrument_id, start).await?
+            } else {
+                None
+            };
+            inputs.push((ex_date, start, events, prev_close));
+        }
+        Ok(Self::from_events(inputs))
+    }
+
+    fn from_events(inputs: Vec<(NaiveDate, DateTime<Utc>, ExDateEvents, Option<f64>)>) -> Self {
+        let mut backward = 1.0;
+        let points = inputs
+            .into_iter()
+            .map(|(ex_date, start, events, prev_close)| {
+                let ratio = events.ratio(prev_close);
+                backward /= ratio.unwrap_or(1.0);
+                AdjustmentPoint { ex_date, start, prev_close, ratio, backward }
+            })
+            .collect();
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[AdjustmentPoint] {
+        &self.points
+    }
+
+    /// 全部除权日之后的后复权因子，即前复权因子的换算系数
+    fn latest_backward(&self) -> f64 {
+        self.points.last().map_or(1.0, |point| point.backward)
+    }
+
+    /// 时刻 `ts` 的价格应乘的因子
+    pub fn factor(&self, ts: DateTime<Utc>, mode: AdjustMode) -> f64 {
+        let index = self.points.partition_point(|point| point.start <= ts);
+        let backward = index.checked_sub(1).map_or(1.0, |index| self.points[index].backward);
+        match mode {
+            AdjustMode::Backward => backward,
+            AdjustMode::Forward => backward / self.latest_backward(),
+        }
+    }
+
+    /// 除权日 `point` 起（至下一除权日前）的前复权因子
+    pub fn forward_at(&self, point: &AdjustmentPoint) -> f64 {
+        point.backward / self.latest_backward()
+    }
+
+    /// `(from, to)` 内（不含 `from`）是否有除权日，即从 `from` 开始、到 `to` 结束的 K 线是否跨越除权日
+    pub fn has_ex_date_within(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        let index = self.points.partition_point(|point| point.start <= from);
+        self.points.get(index).is_some_and(|point| point.start < to)
+    }
+
+    /// 按复权方式调整 K 线价格
+    pub fn apply(&self, bars: &mut [kline::Model], mode: AdjustMode) {
+        if self.points.is_empty() {
+            return;
+        }
+        for bar in bars {
+            let factor = self.factor(bar.ts.to_utc(), mode);
+            bar.open *= factor;
+            bar.high *= factor;
+            bar.low *= factor;
+            bar.close *= factor;
+        }
+    }
+}
+
+/// 按标的缓存的复权因子，全局共享一份
+///
+/// 每次取用时比对公司行动的指纹，行动有增删改时重新计算；缺少前收盘价的结果不缓存，补录 K 线后立即生效。
+#[derive(Default)]
+pub struct AdjustmentCache {
+    entries: RwLock<HashMap<i32, CachedFactors>>,
+}
+
+struct CachedFactors {
+    fingerprint: u64,
+    loaded_at: Instant,
+    factors: Arc<AdjustmentFactors>,
+}
+
+impl AdjustmentCache {
+    /// 取标的的复权因子，缓存失效时按 [`AdjustmentFactors::load`] 重新计算
+    pub async fn get(
+        &self,
+        actions: &[corporate_action::Model],
+        kline_repo: &KlineRepository,
+        calendar: &TradingCalendar,
+        instrument_id: i32,
+    ) -> Result<Arc<AdjustmentFactors>, DbErr> {
+        let fingerprint = fingerprint(actions);
+        if let Some(entry) = self.entries.read().await.get(&instrument_id)
+            && entry.fingerprint == fingerprint
+            && entry.loaded_at.elapsed() < CACHE_TTL
+        {
+            return Ok(entry.factors.clone());
+        }
+
+        let factors = Arc::new(AdjustmentFactors::load(actions, kline_repo, calendar, instrument_id).await?);
+        let mut entries = self.entries.write().await;
+        if factors.points.iter().all(|point| point.ratio.is_some()) {
+            let entry = CachedFactors { fingerprint, loaded_at: Instant::now(), factors: factors.clone() };
+            entries.insert(instrument_id, entry);
+        } else {
+            entries.remove(&instrument_id);
+        }
+        Ok(factors)
+    }
+}
+
+/// 公司行动的指纹：任一行动新增、删除或修改后都会变化
+fn fingerprint(actions: &[corporate_action::Model]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for action in actions {
+        (action.id, action.ex_date, action.updated_at).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// 除权日前最后一根 K 线的收盘价
+async fn prev_close(kline_repo: &KlineRepository, instrument_id: i32, before: DateTime<Utc>) -> Result<Option<f64>, DbErr> {
+    for interval in [KlineInterval::Day1, KlineInterval::Minute1] {
+        let bars = kline_repo
+            .find_range(instrument_id, interval, None, Some(before.fixed_offset()), Order::Desc, 1)
+            .await?;
+        if let Some(bar) = bars.first() {
+            return Ok(Some(bar.close));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(kind: CorporateActionKind, ratio: Option<f64>, cash: Option<f64>, price: Option<f64>) -> corporate_action::Model {
+        let now = Utc::now().fixed_offset();
+        corporate_action::Model {
+            id: 1,
+            instrument_id: 1,
+            kind,
+            ex_date: NaiveDate::default(),
+            record_date: None,
+            pay_date: None,
+            ratio,
+            cash,
+            subscription_price: price,
+            old_symbol: None,
+            new_symbol: None,
+            note: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn events(actions: &[corporate_action::Model]) -> ExDateEvents {
+        let mut events = ExDateEvents::default();
+        actions.iter().for_each(|action| events.add(action));
+        events
+    }
+
+    #[test]
+    fn test_ex_date_ratio_and_factors() {
+        // 10 送 3 派 2 元，前收 13.2：除权参考价 (13.2 - 0.2) / 1.3 = 10
+        let day1 = events(&[
+            action(CorporateActionKind::StockDividend, Some(0.3), None, None),
+            action(CorporateActionKind::CashDividend, None, Some(0.2), None),
+        ]);
+        assert!((day1.ratio(Some(13.2)).unwrap() - 10.0 / 13.2).abs() < 1e-12);
+        assert_eq!(day1.ratio(None), None);
+        // 1 拆 2 不需要前收盘价；10 配 2、配股价 5，前收 11：(11 + 1) / 1.2 = 10
+        let day2 = events(&[action(CorporateActionKind::Split, Some(2.0), None, None)]);
+        assert_eq!(day2.ratio(None), Some(0.5));
+        let rights = events(&[action(CorporateActionKind::RightsIssue, Some(0.2), None, Some(5.0))]);
+        assert!((rights.ratio(Some(11.0)).unwrap() - 10.0 / 11.0).abs() < 1e-12);
+
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        let start = |d: u32| date(d).and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let factors = AdjustmentFactors::from_events(vec![
+            (date(10), start(10), day1, Some(13.2)),
+            (date(20), start(20), day2, None),
+        ]);
+        let r1 = 10.0 / 13.2;
+        let at = |d: u32, mode| factors.factor(start(d) + chrono::Duration::hours(1), mode);
+        assert_eq!(at(9, AdjustMode::Backward), 1.0);
+        assert!((at(15, AdjustMode::Backward) - 1.0 / r1).abs() < 1e-12);
+        assert!((at(25, AdjustMode::Backward) - 2.0 / r1).abs() < 1e-12);
+        assert!((at(9, AdjustMode::Forward) - r1 * 0.5).abs() < 1e-12);
+        assert!((at(15, AdjustMode::Forward) - 0.5).abs() < 1e-12);
+        assert_eq!(at(25, AdjustMode::Forward), 1.0);
+        // 除权日 0 点即属于除权后
+        assert_eq!(factors.factor(start(20), AdjustMode::Forward), 1.0);
+        // 从除权日开始的 K 线不算跨越，覆盖除权日 0 点的才算
+        assert!(!factors.has_ex_date_within(start(20), start(27)));
+        assert!(factors.has_ex_date_within(start(19), start(26)));
+        assert!(!factors.has_ex_date_within(start(11), start(18)));
+    }
+}
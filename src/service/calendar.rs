@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use crate::db::repositories::Repository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::dto::calendar::{CalendarStatus, SessionRangeQuery, SessionResponse, TradingDaysQuery};
+use crate::dto::exchange::TradingSession;
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::exchange::normalize_code;
+use crate::service::trading_calendar::{CalendarFile, Session, TradingCalendar};
+use entities::exchange;
+use super::APPResult;
+
+/// 单次查询允许的最大天数
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// 启动时从 `configs/calendars/<交易所代码>.toml` 加载的日历文件，按交易所代码索引
+#[derive(Debug, Default)]
+pub struct CalendarFiles {
+    files: HashMap<String, CalendarFile>,
+}
+
+impl CalendarFiles {
+    /// 加载目录下的全部日历文件；目录不存在时为空，文件格式错误时启动失败
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let mut files = HashMap::new();
+        if !dir.is_dir() {
+            tracing::warn!(dir = %dir.display(), "Calendar directory not found, holidays are not applied");
+            return Ok(Self { files });
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+            let Some(code) = path.file_stem().and_then(|stem| stem.to_str()).map(normalize_code) else {
+                continue;
+            };
+            let file: CalendarFile = config::Config::builder()
+                .add_source(config::File::from(path.as_path()))
+                .build()
+                .and_then(|cfg| cfg.try_deserialize())
+                .map_err(|err| anyhow::anyhow!("invalid calendar file {}: {err}", path.display()))?;
+            // 提前校验时段与星期，避免请求时才发现配置错误
+            TradingCalendar::new("UTC", &[], Some(&file))
+                .map_err(|err| anyhow::anyhow!("invalid calendar file {}: {err}", path.display()))?;
+            files.insert(code, file);
+        }
+        tracing::info!(count = files.len(), "Loaded trading calendars");
+        Ok(Self { files })
+    }
+
+    pub fn get(&self, code: &str) -> Option<&CalendarFile> {
+        self.files.get(code)
+    }
+
+    /// 构建交易所的交易日历：时区、常规时段取自交易所记录，休市日与特殊交易日取自日历文件
+    pub fn calendar_for(&self, model: &exchange::Model) -> APPResult<TradingCalendar> {
+        let trading_hours: Vec<TradingSession> = serde_json::from_value(model.trading_hours.clone()).unwrap_or_default();
+        TradingCalendar::new(&model.timezone, &trading_hours, self.get(&model.code)).map_err(|err| {
+            tracing::error!(exchange = %model.code, error = %err, "Invalid exchange schedule");
+            AppError::Internal
+        })
+    }
+}
+
+pub struct CalendarService {
+    exchange_repo: Arc<ExchangeRepository>,
+    files: Arc<CalendarFiles>,
+}
+
+impl CalendarService {
+    pub fn new(exchange_repo: Arc<ExchangeRepository>, files: Arc<CalendarFiles>) -> Self {
+        Self { exchange_repo, files }
+    }
+
+    /// 按 ID 或代码查找交易所
+    async fn exchange(&self, id_or_code: &str) -> APPResult<exchange::Model> {
+        let model = match id_or_code.parse::<i32>() {
+            Ok(id) => self.exchange_repo.find_by_id(id).await,
+            Err(_) => self.exchange_repo.find_by_code(&normalize_code(id_or_code)).await,
+        }
+        .map_err(AppError::from)?;
+        model.ok_or_else(|| AppError::NotFound {
+            resource: "Exchange".to_string(),
+            identifier: Some(id_or_code.to_string()),
+        })
+    }
+
+    /// 按 ID 或代码构建交易所的交易日历
+    pub async fn calendar(&self, id_or_code: &str) -> APPResult<(exchange::Model, TradingCalendar)> {
+        let model = self.exchange(id_or_code).await?;
+        let calendar = self.files.calendar_for(&model)?;
+        Ok((model, calendar))
+    }
+
+    /// 某一时刻的开市状态，未指定时刻时取当前时间
+    pub async fn status(&self, id_or_code: &str, at: Option<DateTime<FixedOffset>>) -> APPResult<CalendarStatus> {
+        let (model, calendar) = self.calendar(id_or_code).await?;
+        let at = at.map_or_else(Utc::now, |at| at.with_timezone(&Utc));
+        let session = calendar.session_at(at);
+        let local = |ts: DateTime<Utc>| ts.with_timezone(&calendar.timezone()).fixed_offset();
+
+        Ok(CalendarStatus {
+            exchange: model.code,
+            timezone: model.timezone,
+            at: local(at),
+            is_open: session.is_some(),
+            session: session.map(|session| to_response(&calendar, session)),
+            next_open: calendar.next_open(at).map(local),
+            next_close: calendar.next_close(at).map(local),
+            covered_years: calendar.covered_years(),
+        })
+    }
+
+    /// 时间范围内的交易时段
+    pub async fn sessions(&self, id_or_code: &str, query: SessionRangeQuery) -> APPResult<Vec<SessionResponse>> {
+        let (from, to) = (query.from.with_timezone(&Utc), query.to.with_timezone(&Utc));
+        check_range("to", (to - from).num_days(), to < from)?;
+        let (_, calendar) = self.calendar(id_or_code).await?;
+        let last = calendar.local_date(from.max(to - chrono::Duration::seconds(1)));
+        check_coverage(&calendar, ("from", calendar.local_date(from)), ("to", last))?;
+        Ok(calendar
+            .sessions_between(from, to)
+            .into_iter()
+            .map(|session| to_response(&calendar, session))
+            .collect())
+    }
+
+    /// 日期范围内（含首尾）的交易日
+    pub async fn trading_days(&self, id_or_code: &str, query: TradingDaysQuery) -> APPResult<Vec<chrono::NaiveDate>> {
+        check_range("to", (query.to - query.from).num_days(), query.to < query.from)?;
+        let (_, calendar) = self.calendar(id_or_code).await?;
+        check_coverage(&calendar, ("from", query.from), ("to", query.to))?;
+        Ok(calendar.trading_days(query.from, query.to))
+    }
+}
+
+/// 校验查询范围：结束不早于开始，跨度不超过 `MAX_RANGE_DAYS`
+fn check_range(field: &str, days: i64, reversed: bool) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    if reversed {
+        errors.add(field, "validation-date_order", TranslateArgs::new().add("other", "from"));
+    } else if days > MAX_RANGE_DAYS {
+        errors.add(field, "validation-date_span", TranslateArgs::new().add("max", MAX_RANGE_DAYS));
+    }
+    errors.into_result()
+}
+
+/// 校验 `[from, to]` 都在日历已收录休市安排的年份内，否则休市日会被当作交易日；错误记在超出范围的一端
+pub fn check_coverage(
+    calendar: &TradingCalendar,
+    (from_field, from): (&str, NaiveDate),
+    (to_field, to): (&str, NaiveDate),
+) -> APPResult<()> {
+    if calendar.covers(from, to) {
+        return Ok(());
+    }
+    let field = if calendar.covers(from, from) { to_field } else { from_field };
+    let years = calendar.covered_years().unwrap_or_default();
+    let mut errors = FieldErrors::new();
+    errors.add(field, "validation-calendar_coverage", TranslateArgs::new().add("years", format_years(&years)));
+    Err(errors.into())
+}
+
+/// 年份列表格式化为连续区间，如 `2022-2024, 2026`
+fn format_years(years: &[i32]) -> String {
+    let mut ranges: Vec<(i32, i32)> = Vec::new();
+    for &year in years {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == year => *end = year,
+            _ => ranges.push((year, year)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| if start == end { start.to_string() } else { format!("{start}-{end}") })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 时段转换为响应，时间使用交易所本地时区偏移
+fn to_response(calendar: &TradingCalendar, session: Session) -> SessionResponse {
+    let tz = calendar.timezone();
+    SessionResponse {
+        date: session.date,
+        open: session.open.with_timezone(&tz).fixed_offset(),
+        close: session.close.with_timezone(&tz).fixed_offset(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_calendars_declare_coverage() {
+        let files = CalendarFiles::load(Path::new("configs/calendars")).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        for code in ["SSE", "SZSE", "NYSE"] {
+            let calendar = TradingCalendar::new("UTC", &[], files.get(code)).unwrap();
+            assert!(calendar.covers(date(2022, 1, 1), date(2026, 12, 31)), "{code}");
+            assert!(check_coverage(&calendar, ("from", date(2021, 12, 1)), ("to", date(2022, 1, 31))).is_err(), "{code}");
+        }
+        assert_eq!(format_years(&[2022, 2023, 2024, 2026]), "2022-2024, 2026");
+
+        // 2024 年春节：02-09 起休市，02-19 恢复交易
+        let sse = TradingCalendar::new("Asia/Shanghai", &[], files.get("SSE")).unwrap();
+        assert_eq!(sse.trading_days(date(2024, 2, 8), date(2024, 2, 19)), vec![date(2024, 2, 8), date(2024, 2, 19)]);
+    }
+}
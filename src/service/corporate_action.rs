@@ -0,0 +1,211 @@
+use std::sync::Arc;
+use sea_orm::{ActiveEnum, ColumnTrait, Condition, DbErr};
+use sea_orm::ActiveValue::Set;
+use crate::db::error::DbError;
+use crate::db::repositories::Repository;
+use crate::db::repositories::corporate_action::CorporateActionRepository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::dto::corporate_action::{
+    AdjustmentFactorResponse, CorporateActionFilter, CorporateActionRequest, CorporateActionResponse,
+};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::adjustment::AdjustmentCache;
+use crate::service::calendar::CalendarFiles;
+use crate::service::instrument::find_instrument;
+use entities::corporate_action;
+use entities::sea_orm_active_enums::CorporateActionKind;
+use super::APPResult;
+
+pub struct CorporateActionService {
+    repo: Arc<CorporateActionRepository>,
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    calendars: Arc<CalendarFiles>,
+    adjustments: Arc<AdjustmentCache>,
+}
+
+impl CorporateActionService {
+    pub fn new(
+        repo: Arc<CorporateActionRepository>,
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        calendars: Arc<CalendarFiles>,
+        adjustments: Arc<AdjustmentCache>,
+    ) -> Self {
+        Self { repo, kline_repo, instrument_repo, exchange_repo, calendars, adjustments }
+    }
+
+    /// 标的的公司行动时间线，按除权日升序
+    pub async fn timeline(
+        &self,
+        instrument_id: i32,
+        filter: CorporateActionFilter,
+    ) -> APPResult<Vec<CorporateActionResponse>> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let mut cond = Condition::all();
+        if let Some(kind) = filter.kind {
+            cond = cond.add(corporate_action::Column::Kind.eq(kind));
+        }
+        if let Some(start) = filter.start {
+            cond = cond.add(corporate_action::Column::ExDate.gte(start));
+        }
+        if let Some(end) = filter.end {
+            cond = cond.add(corporate_action::Column::ExDate.lte(end));
+        }
+        let actions = self.repo.find_timeline(instrument_id, cond).await.map_err(AppError::from)?;
+        Ok(actions.into_iter().map(CorporateActionResponse::from).collect())
+    }
+
+    /// 新增公司行动
+    /// 业务规则：同一标的同一除权日同一类型只能有一条，冲突时返回 `Conflict`
+    pub async fn create(&self, instrument_id: i32, req: CorporateActionRequest) -> APPResult<CorporateActionResponse> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        validate_action(&req)?;
+        let identifier = action_identifier(instrument_id, &req);
+        let mut active = corporate_action::ActiveModel { instrument_id: Set(instrument_id), ..Default::default() };
+        assign(&mut active, req);
+        let model = self.repo.create(active).await.map_err(|err| conflict_error(err, identifier))?;
+
+        tracing::info!(instrument_id, action_id = model.id, kind = ?model.kind, "Added corporate action");
+        Ok(model.into())
+    }
+
+    /// 整体替换公司行动
+    pub async fn replace(
+        &self,
+        instrument_id: i32,
+        action_id: i32,
+        req: CorporateActionRequest,
+    ) -> APPResult<CorporateActionResponse> {
+        let existing = self.find_action(instrument_id, action_id).await?;
+        validate_action(&req)?;
+        let identifier = action_identifier(instrument_id, &req);
+        let mut active: corporate_action::ActiveModel = existing.into();
+        assign(&mut active, req);
+        let model = self.repo.update(active).await.map_err(|err| conflict_error(err, identifier))?;
+
+        tracing::info!(instrument_id, action_id, "Replaced corporate action");
+        Ok(model.into())
+    }
+
+    pub async fn delete(&self, instrument_id: i32, action_id: i32) -> APPResult<()> {
+        self.find_action(instrument_id, action_id).await?;
+        self.repo.delete(action_id).await.map_err(AppError::from)?;
+        tracing::info!(instrument_id, action_id, "Deleted corporate action");
+        Ok(())
+    }
+
+    /// 按除权日列出复权因子；前收盘价取自库中的 K 线，补录缺失的 K 线后结果随之更新，
+    /// 已有前收盘价的更正在缓存过期后生效
+    pub async fn factors(&self, instrument_id: i32) -> APPResult<Vec<AdjustmentFactorResponse>> {
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+        let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::Internal)?;
+        let calendar = self.calendars.calendar_for(&exchange)?;
+        let actions = self.repo.find_timeline(instrument_id, Condition::all()).await.map_err(AppError::from)?;
+        let factors = self.adjustments
+            .get(&actions, &self.kline_repo, &calendar, instrument_id)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(factors
+            .points()
+            .iter()
+            .map(|point| AdjustmentFactorResponse {
+                ex_date: point.ex_date,
+                prev_close: point.prev_close,
+                ratio: point.ratio,
+                backward_factor: point.backward,
+                forward_factor: factors.forward_at(point),
+            })
+            .collect())
+    }
+
+
+    async fn find_action(&self, instrument_id: i32, action_id: i32) -> APPResult<corporate_action::Model> {
+        self.repo.find_by_id(action_id).await
+            .map_err(AppError::from)?
+            .filter(|action| action.instrument_id == instrument_id)
+            .ok_or(AppError::NotFound {
+                resource: "CorporateAction".to_string(),
+                identifier: Some(action_id.to_string()),
+            })
+    }
+}
+
+fn assign(active: &mut corporate_action::ActiveModel, req: CorporateActionRequest) {
+    active.kind = Set(req.kind);
+    active.ex_date = Set(req.ex_date);
+    active.record_date = Set(req.record_date);
+    active.pay_date = Set(req.pay_date);
+    active.ratio = Set(req.ratio);
+    active.cash = Set(req.cash);
+    active.subscription_price = Set(req.subscription_price);
+    active.old_symbol = Set(req.old_symbol.map(|symbol| symbol.trim().to_string()));
+    active.new_symbol = Set(req.new_symbol.map(|symbol| symbol.trim().to_string()));
+    active.note = Set(req.note);
+}
+
+/// 按类型校验必填字段与取值范围
+fn validate_action(req: &CorporateActionRequest) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    let required = |errors: &mut FieldErrors, field: &str| {
+        errors.add(field, "validation-required_for_action", TranslateArgs::new().add("kind", req.kind.to_value()));
+    };
+    let positive = |errors: &mut FieldErrors, field: &str, value: Option<f64>| match value {
+        None => required(errors, field),
+        Some(value) if !value.is_finite() || value <= 0.0 => {
+            errors.add(field, "validation-positive", TranslateArgs::new());
+        }
+        Some(_) => {}
+    };
+
+    match req.kind {
+        CorporateActionKind::Split | CorporateActionKind::ReverseSplit => {
+            positive(&mut errors, "ratio", req.ratio);
+            let split = req.kind == CorporateActionKind::Split;
+            if req.ratio.is_some_and(|ratio| ratio > 0.0 && if split { ratio <= 1.0 } else { ratio >= 1.0 }) {
+                errors.add("ratio", "validation-split_ratio", TranslateArgs::new());
+            }
+        }
+        CorporateActionKind::CashDividend => positive(&mut errors, "cash", req.cash),
+        CorporateActionKind::StockDividend => positive(&mut errors, "ratio", req.ratio),
+        CorporateActionKind::RightsIssue => {
+            positive(&mut errors, "ratio", req.ratio);
+            positive(&mut errors, "subscription_price", req.subscription_price);
+        }
+        CorporateActionKind::SymbolChange => {
+            if req.new_symbol.as_deref().is_none_or(|symbol| symbol.trim().is_empty()) {
+                required(&mut errors, "new_symbol");
+            }
+        }
+    }
+    if let Some(pay_date) = req.pay_date
+        && pay_date < req.ex_date
+    {
+        errors.add("pay_date", "validation-date_order", TranslateArgs::new().add("other", "ex_date"));
+    }
+    errors.into_result()
+}
+
+fn action_identifier(instrument_id: i32, req: &CorporateActionRequest) -> String {
+    format!("{instrument_id}:{}:{}", req.ex_date, req.kind.to_value())
+}
+
+/// 唯一索引冲突转换为 `Conflict`，其余错误按数据库错误处理
+fn conflict_error(err: DbErr, identifier: String) -> AppError {
+    match DbError::from(err) {
+        DbError::UniqueViolation { .. } => AppError::Conflict {
+            resource: "CorporateAction".to_string(),
+            identifier,
+        },
+        other => other.into(),
+    }
+}
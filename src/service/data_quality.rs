@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use sea_orm::{ColumnTrait, Condition, Order};
+use sea_orm::ActiveValue::{NotSet, Set};
+use crate::db::repositories::Repository;
+use crate::db::repositories::data_quality_issue::DataQualityIssueRepository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::dto::data_quality::{
+    BackfillQuery, BackfillRange, DataQualityCheckRequest, DataQualityFilter, DataQualityIssueResponse,
+    DataQualityReport, DataQualitySummary,
+};
+use crate::dto::pagination::{PageParams, PageResponse};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::calendar::{check_coverage, CalendarFiles};
+use crate::service::data_quality_checks::{bucket_end, CheckOptions, QualityChecker};
+use crate::service::instrument::find_instrument;
+use crate::service::kline_resample::interval_minutes;
+use entities::data_quality_issue;
+use entities::sea_orm_active_enums::{DataQualityIssueKind, KlineInterval};
+use super::APPResult;
+
+/// 每批读取的 K 线数
+const SCAN_BATCH: u64 = 10_000;
+/// 日内周期单次检查的最大跨度（天）
+const MAX_INTRADAY_DAYS: i64 = 366;
+/// 日线及以上单次检查的最大跨度（天）
+const MAX_DAILY_DAYS: i64 = 366 * 30;
+
+const SORTABLE_COLUMNS: &[data_quality_issue::Column] = &[
+    data_quality_issue::Column::StartTs,
+    data_quality_issue::Column::Kind,
+    data_quality_issue::Column::BarCount,
+    data_quality_issue::Column::DetectedAt,
+];
+
+pub struct DataQualityService {
+    issue_repo: Arc<DataQualityIssueRepository>,
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    calendars: Arc<CalendarFiles>,
+}
+
+impl DataQualityService {
+    pub fn new(
+        issue_repo: Arc<DataQualityIssueRepository>,
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        calendars: Arc<CalendarFiles>,
+    ) -> Self {
+        Self { issue_repo, kline_repo, instrument_repo, exchange_repo, calendars }
+    }
+
+    /// 检查标的某个周期的 K 线，并用结果替换区间内已保存的问题
+    ///
+    /// 未指定区间时检查库中最早到最新的 K 线；只指定 `start` 时检查到当前时间。
+    /// 区间须在交易所日历已收录休市安排的年份内。
+    pub async fn check(&self, instrument_id: i32, request: DataQualityCheckRequest) -> APPResult<DataQualityReport> {
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+        let interval = request.interval();
+        let mut report = DataQualityReport {
+            instrument_id,
+            interval,
+            start: None,
+            end: None,
+            bars_checked: 0,
+            expected_bars: 0,
+            missing_bars: 0,
+            issues: DataQualitySummary::default(),
+            truncated: false,
+        };
+
+        let start = match request.start {
+            Some(start) => Some(start),
+            None => self.kline_repo.earliest(instrument_id, interval).await.map_err(AppError::from)?.map(|bar| bar.ts),
+        };
+        let end = match (request.end, request.start) {
+            (Some(end), _) => Some(end),
+            (None, Some(_)) => Some(Utc::now().fixed_offset()),
+            // 最新一根 K 线之后 1 秒，使其包含在区间内
+            (None, None) => self.kline_repo.latest(instrument_id, interval).await
+                .map_err(AppError::from)?
+                .map(|bar| bar.ts + Duration::seconds(1)),
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            return Ok(report);
+        };
+        let max_days = if interval_minutes(interval).is_some() { MAX_INTRADAY_DAYS } else { MAX_DAILY_DAYS };
+        let mut errors = FieldErrors::new();
+        if end <= start {
+            errors.add("end", "validation-date_order", TranslateArgs::new().add("other", "start"));
+        } else if end - start > Duration::days(max_days) {
+            errors.add("end", "validation-date_span", TranslateArgs::new().add("max", max_days));
+        }
+        errors.into_result()?;
+
+        let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::Internal)?;
+        let calendar = self.calendars.calendar_for(&exchange)?;
+        // 日历未收录的年份缺少休市日，休市日会被误报为缺失 K 线并进入补数区间，因此直接拒绝
+        let last = calendar.local_date((end - Duration::seconds(1)).to_utc());
+        check_coverage(&calendar, ("start", calendar.local_date(start.to_utc())), ("end", last))?;
+        let options = CheckOptions {
+            zero_volume_streak: request.zero_volume_streak.unwrap_or(5),
+            spike_sigma: request.spike_sigma.unwrap_or(8.0),
+            spike_window: request.spike_window.unwrap_or(100) as usize,
+        };
+        let mut checker = QualityChecker::new(interval, &calendar, (start.to_utc(), end.to_utc()), options);
+
+        // 按时间升序分批读取，下一批从上一批最后一根（含）开始，跳过已检查的那一根
+        let mut from = start;
+        let mut cursor = None;
+        loop {
+            let batch = self.kline_repo
+                .find_range(instrument_id, interval, Some(from), Some(end), Order::Asc, SCAN_BATCH)
+                .await
+                .map_err(AppError::from)?;
+            for bar in batch.iter().filter(|bar| cursor.is_none_or(|cursor| bar.ts > cursor)) {
+                checker.push(bar);
+            }
+            let Some(last) = batch.last().filter(|_| batch.len() as u64 == SCAN_BATCH) else { break };
+            from = last.ts;
+            cursor = Some(last.ts);
+        }
+        let outcome = checker.finish();
+
+        let detected_at = Utc::now().fixed_offset();
+        let issues = outcome.findings.iter().map(|finding| {
+            report.issues.add(finding.kind);
+            data_quality_issue::ActiveModel {
+                id: NotSet,
+                instrument_id: Set(instrument_id),
+                interval: Set(interval),
+                kind: Set(finding.kind),
+                start_ts: Set(finding.start.fixed_offset()),
+                end_ts: Set(finding.end.fixed_offset()),
+                bar_count: Set(finding.bar_count as i32),
+                detail: Set(finding.detail.clone()),
+                detected_at: Set(detected_at),
+            }
+        }).collect();
+        self.issue_repo
+            .replace_range(instrument_id, interval, start, end, issues)
+            .await
+            .map_err(AppError::from)?;
+        tracing::info!(
+            instrument_id,
+            interval = ?interval,
+            bars = outcome.bars,
+            missing = outcome.missing_bars,
+            issues = outcome.findings.len(),
+            "Data quality check finished"
+        );
+
+        Ok(DataQualityReport {
+            start: Some(start),
+            end: Some(end),
+            bars_checked: outcome.bars,
+            expected_bars: outcome.expected_bars,
+            missing_bars: outcome.missing_bars,
+            truncated: outcome.truncated,
+            ..report
+        })
+    }
+
+    /// 分页查询已保存的问题，默认按开始时间升序
+    pub async fn list(
+        &self,
+        instrument_id: i32,
+        filter: DataQualityFilter,
+        params: PageParams,
+    ) -> APPResult<PageResponse<DataQualityIssueResponse>> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let mut order_by = params.order_by(SORTABLE_COLUMNS)?;
+        if order_by.is_empty() {
+            order_by.push((data_quality_issue::Column::StartTs, Order::Asc));
+        }
+
+        let mut cond = Condition::all().add(data_quality_issue::Column::InstrumentId.eq(instrument_id));
+        if let Some(interval) = filter.interval {
+            cond = cond.add(data_quality_issue::Column::Interval.eq(interval));
+        }
+        if let Some(kind) = filter.kind {
+            cond = cond.add(data_quality_issue::Column::Kind.eq(kind));
+        }
+        if let Some(start) = filter.start {
+            cond = cond.add(data_quality_issue::Column::StartTs.gte(start.to_utc().fixed_offset()));
+        }
+        if let Some(end) = filter.end {
+            cond = cond.add(data_quality_issue::Column::StartTs.lt(end.to_utc().fixed_offset()));
+        }
+        let page = self.issue_repo
+            .paginate(cond, order_by, params.page(), params.per_page())
+            .await
+            .map_err(AppError::from)?;
+        Ok(page.into())
+    }
+
+    /// 最近一次检查发现的缺失区间，供补数任务按区间拉取数据
+    pub async fn backfill_ranges(&self, instrument_id: i32, query: BackfillQuery) -> APPResult<Vec<BackfillRange>> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let interval = query.interval.unwrap_or(KlineInterval::Minute1);
+        let cond = Condition::all()
+            .add(data_quality_issue::Column::InstrumentId.eq(instrument_id))
+            .add(data_quality_issue::Column::Interval.eq(interval))
+            .add(data_quality_issue::Column::Kind.eq(DataQualityIssueKind::MissingBars));
+        let mut issues = self.issue_repo.find_by_condition(cond).await.map_err(AppError::from)?;
+        issues.sort_by_key(|issue| issue.start_ts);
+
+        Ok(issues
+            .into_iter()
+            .map(|issue| BackfillRange {
+                interval,
+                start: issue.start_ts,
+                end: bucket_end(interval, issue.end_ts.to_utc()).fixed_offset(),
+                missing_bars: issue.bar_count,
+            })
+            .collect())
+    }
+
+}
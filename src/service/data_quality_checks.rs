@@ -0,0 +1,376 @@
+//! 行情数据质量检测：按时间升序逐根输入 K 线，流式发现问题
+//!
+//! - 缺失：与交易日历推算出的应有周期（同 `kline_resample` 的对齐方式）逐一比对，连续缺失合并为一条
+//! - 重复：多根 K 线落在同一周期内（时间未对齐到周期起点）
+//! - OHLC 不自洽：最高价低于最低价、开收盘价超出高低价区间、成交量为负或非有限数
+//! - 连续零成交量：达到指定根数的零成交量区间
+//! - 价格跳变：收盘价对数收益率偏离最近若干根均值超过 N 倍标准差
+
+use std::collections::VecDeque;
+use std::iter::Peekable;
+use chrono::{DateTime, Datelike, Duration, Months, Utc};
+use serde_json::json;
+use entities::kline;
+use entities::sea_orm_active_enums::{DataQualityIssueKind, KlineInterval};
+use crate::service::kline_resample::{interval_minutes, Bucketer};
+use crate::service::trading_calendar::TradingCalendar;
+
+/// 单次检查最多记录的问题数，超出后只继续统计缺失根数
+const MAX_FINDINGS: usize = 10_000;
+/// 价格跳变检测至少需要的历史收益率个数
+const MIN_SPIKE_SAMPLES: usize = 20;
+/// 偏离均值不足该对数收益率（约 0.1%）时不视为跳变，避免波动极小时的误报
+const MIN_SPIKE_RETURN: f64 = 0.001;
+
+/// 检测阈值
+#[derive(Debug, Clone, Copy)]
+pub struct CheckOptions {
+    /// 连续零成交量达到该根数时记为问题
+    pub zero_volume_streak: u32,
+    /// 价格跳变阈值（标准差倍数）
+    pub spike_sigma: f64,
+    /// 计算均值、标准差所用的最近收益率个数
+    pub spike_window: usize,
+}
+
+/// 检出的问题，`start` / `end` 为首根和末根受影响 K 线（或缺失周期）的开始时间
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub kind: DataQualityIssueKind,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub bar_count: u32,
+    pub detail: Option<serde_json::Value>,
+}
+
+/// 一次检查的结果
+#[derive(Debug, Clone, Default)]
+pub struct CheckOutcome {
+    /// 按开始时间排序的问题
+    pub findings: Vec<Finding>,
+    /// 已检查的 K 线数
+    pub bars: u64,
+    /// 应有的周期数
+    pub expected_bars: u64,
+    /// 缺失的周期数
+    pub missing_bars: u64,
+    /// 问题数超过上限，之后的问题未记录
+    pub truncated: bool,
+}
+
+/// `[from, to)` 内交易日历要求存在的周期开始时间，升序
+///
+/// 日内周期在每个交易时段内从开盘起每 N 分钟一个；日线为每个交易日的本地 0 点，
+/// 周线 / 月线为包含交易日的周一 / 1 日本地 0 点。
+pub fn expected_buckets<'a>(
+    interval: KlineInterval,
+    calendar: &'a TradingCalendar,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Box<dyn Iterator<Item = DateTime<Utc>> + Send + 'a> {
+    let in_range = move |ts: &DateTime<Utc>| *ts >= from && *ts < to;
+    if let Some(minutes) = interval_minutes(interval) {
+        let step = Duration::minutes(minutes.into());
+        let buckets = calendar.sessions_between(from, to).into_iter().flat_map(move |session| {
+            std::iter::successors(Some(session.open), move |ts| Some(*ts + step)).take_while(move |ts| *ts < session.close)
+        });
+        return Box::new(buckets.filter(in_range));
+    }
+
+    let mut starts: Vec<DateTime<Utc>> = calendar
+        .trading_days(calendar.local_date(from), calendar.local_date(to))
+        .into_iter()
+        .map(|date| match interval {
+            KlineInterval::Week1 => date - Duration::days(date.weekday().num_days_from_monday().into()),
+            KlineInterval::Month1 => date.with_day(1).unwrap_or(date),
+            _ => date,
+        })
+        .map(|date| calendar.start_of_day(date))
+        .collect();
+    starts.dedup();
+    Box::new(starts.into_iter().filter(in_range))
+}
+
+/// 周期开始时间为 `start` 的 K 线的结束时间（不含）
+pub fn bucket_end(interval: KlineInterval, start: DateTime<Utc>) -> DateTime<Utc> {
+    match (interval, interval_minutes(interval)) {
+        (_, Some(minutes)) => start + Duration::minutes(minutes.into()),
+        (KlineInterval::Week1, _) => start + Duration::weeks(1),
+        (KlineInterval::Month1, _) => start.checked_add_months(Months::new(1)).unwrap_or(start),
+        _ => start + Duration::days(1),
+    }
+}
+
+/// 连续区间：首、末时间及根数
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    count: u32,
+}
+
+impl Run {
+    fn extend(run: &mut Option<Run>, ts: DateTime<Utc>) {
+        match run {
+            Some(run) => {
+                run.end = ts;
+                run.count += 1;
+            }
+            None => *run = Some(Run { start: ts, end: ts, count: 1 }),
+        }
+    }
+}
+
+/// 记录一个缺失的周期，并入当前的缺失区间
+fn miss(outcome: &mut CheckOutcome, gap: &mut Option<Run>, expected: DateTime<Utc>) {
+    outcome.expected_bars += 1;
+    outcome.missing_bars += 1;
+    Run::extend(gap, expected);
+}
+
+/// 流式检测器
+pub struct QualityChecker<'a> {
+    options: CheckOptions,
+    bucketer: Bucketer<'a>,
+    expected: Peekable<Box<dyn Iterator<Item = DateTime<Utc>> + Send + 'a>>,
+    outcome: CheckOutcome,
+    gap: Option<Run>,
+    /// 当前周期及落在其中的 K 线时间
+    bucket: Option<(DateTime<Utc>, Vec<DateTime<Utc>>)>,
+    zero_volume: Option<Run>,
+    prev_close: Option<f64>,
+    returns: VecDeque<f64>,
+}
+
+impl<'a> QualityChecker<'a> {
+    pub fn new(
+        interval: KlineInterval,
+        calendar: &'a TradingCalendar,
+        (from, to): (DateTime<Utc>, DateTime<Utc>),
+        options: CheckOptions,
+    ) -> Self {
+        Self {
+            options,
+            bucketer: Bucketer::new(interval, calendar),
+            expected: expected_buckets(interval, calendar, from, to).peekable(),
+            outcome: CheckOutcome::default(),
+            gap: None,
+            bucket: None,
+            zero_volume: None,
+            prev_close: None,
+            returns: VecDeque::new(),
+        }
+    }
+
+    /// 输入下一根 K 线，须按时间升序
+    pub fn push(&mut self, bar: &kline::Model) {
+        let ts = bar.ts.to_utc();
+        let bucket = self.bucketer.bucket(ts);
+        self.outcome.bars += 1;
+
+        while let Some(expected) = self.expected.next_if(|expected| *expected < bucket) {
+            miss(&mut self.outcome, &mut self.gap, expected);
+        }
+        if self.expected.next_if_eq(&bucket).is_some() {
+            self.outcome.expected_bars += 1;
+            self.close_gap();
+        }
+
+        match &mut self.bucket {
+            Some((current, timestamps)) if *current == bucket => timestamps.push(ts),
+            _ => {
+                self.close_bucket();
+                self.bucket = Some((bucket, vec![ts]));
+            }
+        }
+
+        self.check_ohlc(bar, ts);
+        if bar.volume == 0.0 {
+            Run::extend(&mut self.zero_volume, ts);
+        } else {
+            self.close_zero_volume();
+        }
+        self.check_spike(bar, ts);
+    }
+
+    /// 结束检查：区间末尾仍未出现的周期计为缺失
+    pub fn finish(mut self) -> CheckOutcome {
+        for expected in self.expected.by_ref() {
+            miss(&mut self.outcome, &mut self.gap, expected);
+        }
+        self.close_gap();
+        self.close_bucket();
+        self.close_zero_volume();
+        self.outcome.findings.sort_by_key(|finding| finding.start);
+        self.outcome
+    }
+
+    fn record(&mut self, finding: Finding) {
+        if self.outcome.findings.len() < MAX_FINDINGS {
+            self.outcome.findings.push(finding);
+        } else {
+            self.outcome.truncated = true;
+        }
+    }
+
+    fn close_gap(&mut self) {
+        if let Some(run) = self.gap.take() {
+            self.record(Finding {
+                kind: DataQualityIssueKind::MissingBars,
+                start: run.start,
+                end: run.end,
+                bar_count: run.count,
+                detail: None,
+            });
+        }
+    }
+
+    fn close_bucket(&mut self) {
+        let Some((bucket, timestamps)) = self.bucket.take().filter(|(_, timestamps)| timestamps.len() > 1) else {
+            return;
+        };
+        self.record(Finding {
+            kind: DataQualityIssueKind::DuplicateTimestamp,
+            start: timestamps[0],
+            end: timestamps[timestamps.len() - 1],
+            bar_count: timestamps.len() as u32,
+            detail: Some(json!({ "bucket": bucket, "timestamps": timestamps })),
+        });
+    }
+
+    fn close_zero_volume(&mut self) {
+        if let Some(run) = self.zero_volume.take().filter(|run| run.count >= self.options.zero_volume_streak) {
+            self.record(Finding {
+                kind: DataQualityIssueKind::ZeroVolumeStreak,
+                start: run.start,
+                end: run.end,
+                bar_count: run.count,
+                detail: None,
+            });
+        }
+    }
+
+    fn check_ohlc(&mut self, bar: &kline::Model, ts: DateTime<Utc>) {
+        let prices = [bar.open, bar.high, bar.low, bar.close, bar.volume];
+        let violated = prices.iter().any(|value| !value.is_finite())
+            || bar.high < bar.low
+            || !(bar.low..=bar.high).contains(&bar.open)
+            || !(bar.low..=bar.high).contains(&bar.close)
+            || bar.volume < 0.0;
+        if violated {
+            self.record(Finding {
+                kind: DataQualityIssueKind::OhlcViolation,
+                start: ts,
+                end: ts,
+                bar_count: 1,
+                detail: Some(json!({
+                    "open": bar.open,
+                    "high": bar.high,
+                    "low": bar.low,
+                    "close": bar.close,
+                    "volume": bar.volume,
+                })),
+            });
+        }
+    }
+
+    /// 跳变的收益率同样计入统计窗口，行情换挡后阈值随之调整
+    fn check_spike(&mut self, bar: &kline::Model, ts: DateTime<Utc>) {
+        let prev_close = self.prev_close.replace(bar.close);
+        let Some(prev_close) = prev_close.filter(|prev| *prev > 0.0 && bar.close > 0.0) else {
+            return;
+        };
+        let value = (bar.close / prev_close).ln();
+        if !value.is_finite() {
+            return;
+        }
+
+        if self.returns.len() >= MIN_SPIKE_SAMPLES {
+            let n = self.returns.len() as f64;
+            let mean = self.returns.iter().sum::<f64>() / n;
+            let std = (self.returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+            let deviation = (value - mean).abs();
+            if deviation > MIN_SPIKE_RETURN && deviation > self.options.spike_sigma * std {
+                self.record(Finding {
+                    kind: DataQualityIssueKind::PriceSpike,
+                    start: ts,
+                    end: ts,
+                    bar_count: 1,
+                    detail: Some(json!({
+                        "prev_close": prev_close,
+                        "close": bar.close,
+                        "log_return": value,
+                        "mean": mean,
+                        "std": std,
+                        "sigma": deviation / std,
+                    })),
+                });
+            }
+        }
+        self.returns.push_back(value);
+        if self.returns.len() > self.options.spike_window {
+            self.returns.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::exchange::TradingSession;
+
+    fn bar(ts: DateTime<Utc>, close: f64, volume: f64) -> kline::Model {
+        kline::Model {
+            instrument_id: 1,
+            interval: KlineInterval::Minute1,
+            ts: ts.fixed_offset(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+            quote_volume: None,
+            trade_count: None,
+        }
+    }
+
+    #[test]
+    fn test_detects_each_issue_kind() {
+        let session = TradingSession { open: "09:30".into(), close: "11:30".into() };
+        let calendar = TradingCalendar::new("Asia/Shanghai", &[session], None).unwrap();
+        // 2026-10-19（周一）09:30–11:30 北京时间，共 120 根 1m
+        let open: DateTime<Utc> = "2026-10-19T01:30:00Z".parse().unwrap();
+        let range = (open - Duration::hours(1), open + Duration::hours(3));
+        let options = CheckOptions { zero_volume_streak: 3, spike_sigma: 6.0, spike_window: 50 };
+        let mut checker = QualityChecker::new(KlineInterval::Minute1, &calendar, range, options);
+
+        let minute = |i: i64| open + Duration::minutes(i);
+        for i in 0..120 {
+            match i {
+                // 缺 10–14 共 5 根
+                10..15 => continue,
+                // 20–23 零成交量
+                20..24 => checker.push(&bar(minute(i), 100.0 + (i % 2) as f64 * 0.1, 0.0)),
+                // 60 跳变
+                60 => checker.push(&bar(minute(i), 150.0, 1.0)),
+                _ => checker.push(&bar(minute(i), 100.0 + (i % 2) as f64 * 0.1, 1.0)),
+            }
+            if i == 30 {
+                // 同一分钟内的第二根，且最高价低于最低价
+                checker.push(&kline::Model { high: 90.0, ..bar(minute(30) + Duration::seconds(30), 100.0, 1.0) });
+            }
+        }
+        let outcome = checker.finish();
+        assert_eq!((outcome.bars, outcome.expected_bars, outcome.missing_bars), (116, 120, 5));
+        let findings = outcome.findings;
+
+        let kinds: Vec<_> = findings.iter().map(|f| (f.kind, f.start, f.bar_count)).collect();
+        assert_eq!(kinds[0], (DataQualityIssueKind::MissingBars, minute(10), 5));
+        assert_eq!(kinds[1], (DataQualityIssueKind::ZeroVolumeStreak, minute(20), 4));
+        assert_eq!(kinds[2], (DataQualityIssueKind::DuplicateTimestamp, minute(30), 2));
+        assert_eq!(kinds[3], (DataQualityIssueKind::OhlcViolation, minute(30) + Duration::seconds(30), 1));
+        // 跳升与回落各一次
+        assert_eq!(kinds[4..], [(DataQualityIssueKind::PriceSpike, minute(60), 1), (DataQualityIssueKind::PriceSpike, minute(61), 1)]);
+        assert_eq!(findings[0].end, minute(14));
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+use sea_orm::ActiveValue::Set;
+use crate::db::repositories::Repository;
+use crate::error::code::AppError;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::dto::exchange::{CreateExchangeRequest, UpdateExchangeRequest, ExchangeResponse};
+use crate::service::trading_calendar::validate_exchange_schedule;
+use entities::exchange;
+use super::APPResult;
+
+/// 规范化交易所代码：去除首尾空白并转为大写，保证 "binance" / "Binance " 指向同一交易所
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+pub struct ExchangeService {
+    repo: Arc<ExchangeRepository>,
+}
+
+impl ExchangeService {
+    pub fn new(repo: Arc<ExchangeRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// 创建交易所
+    /// 业务规则：交易所代码规范化后不能重复；时区与交易时段须能用于构建交易日历
+    pub async fn create(&self, mut new_exchange: CreateExchangeRequest) -> APPResult<ExchangeResponse> {
+        new_exchange.code = normalize_code(&new_exchange.code);
+        validate_exchange_schedule(Some(&new_exchange.timezone), Some(&new_exchange.trading_hours))?;
+
+        if self.repo.find_by_code(&new_exchange.code).await
+            .map_err(AppError::from)?.is_some() {
+            return Err(AppError::Conflict {
+                resource: "Exchange".to_string(),
+                identifier: new_exchange.code,
+            });
+        }
+
+        let model = self.repo.create(new_exchange.into()).await
+            .map_err(AppError::from)?;
+
+        tracing::info!(exchange_id = model.id, code = %model.code, "Created new exchange");
+        Ok(model.into())
+    }
+
+    pub async fn get_by_id(&self, id: i32) -> APPResult<Option<ExchangeResponse>> {
+        self.repo.find_by_id(id).await
+            .map_err(AppError::from)
+            .map(|model| model.map(ExchangeResponse::from))
+    }
+
+    pub async fn get_all(&self) -> APPResult<Vec<ExchangeResponse>> {
+        self.repo.find_all().await
+            .map_err(AppError::from)
+            .map(|models| models.into_iter().map(ExchangeResponse::from).collect())
+    }
+
+    pub async fn update(&self, id: i32, mut req: UpdateExchangeRequest) -> APPResult<ExchangeResponse> {
+        validate_exchange_schedule(req.timezone.as_deref(), req.trading_hours.as_deref())?;
+        let existing = self.repo.find_by_id(id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::NotFound {
+                resource: "Exchange".to_string(),
+                identifier: Some(id.to_string()),
+            })?;
+
+        if let Some(code) = req.code.as_deref() {
+            let code = normalize_code(code);
+            if code != existing.code && self.repo.find_by_code(&code).await
+                .map_err(AppError::from)?.is_some() {
+                return Err(AppError::Conflict {
+                    resource: "Exchange".to_string(),
+                    identifier: code,
+                });
+            }
+            req.code = Some(code);
+        }
+
+        let mut active: exchange::ActiveModel = req.into();
+        active.id = Set(existing.id);
+        let updated_model = self.repo.update(active).await
+            .map_err(AppError::from)?;
+
+        tracing::info!(exchange_id = updated_model.id, "Updated exchange");
+        Ok(updated_model.into())
+    }
+
+    /// 删除交易所
+    /// 业务规则：仍有标的挂在该交易所下时不允许删除
+    pub async fn delete(&self, id: i32) -> APPResult<bool> {
+        let instruments = self.repo.count_instruments(id).await
+            .map_err(AppError::from)?;
+        if instruments > 0 {
+            tracing::info!(exchange_id = id, instruments, "Exchange still referenced by instruments");
+            return Err(AppError::ReferenceConflict);
+        }
+
+        let deleted = self.repo.delete(id).await
+            .map_err(AppError::from)?;
+
+        if deleted {
+            tracing::info!(exchange_id = id, "Deleted exchange");
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::DbPool;
+
+    async fn service() -> ExchangeService {
+        let db = Arc::new(DbPool::in_memory().await);
+        ExchangeService::new(Arc::new(ExchangeRepository::new(db)))
+    }
+
+    fn create_request(code: &str) -> CreateExchangeRequest {
+        serde_json::from_value(serde_json::json!({
+            "code": code,
+            "name": "Shanghai Stock Exchange",
+            "timezone": "Asia/Shanghai",
+            "country": "CN",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_exchange_crud() {
+        let service = service().await;
+
+        let created = service.create(create_request(" sse ")).await.unwrap();
+        assert_eq!(created.code, "SSE");
+        assert_eq!(created.country.as_deref(), Some("CN"));
+        assert!(matches!(
+            service.create(create_request("SSE")).await,
+            Err(AppError::Conflict { .. })
+        ));
+
+        // 未提供的字段保持不变，显式 null 清空
+        let req = serde_json::from_value(serde_json::json!({ "name": "SSE" })).unwrap();
+        let updated = service.update(created.id, req).await.unwrap();
+        assert_eq!((updated.name.as_str(), updated.country.as_deref()), ("SSE", Some("CN")));
+        let req = serde_json::from_value(serde_json::json!({ "country": null })).unwrap();
+        let updated = service.update(created.id, req).await.unwrap();
+        assert_eq!(updated.country, None);
+
+        assert_eq!(service.get_all().await.unwrap().len(), 1);
+        assert!(service.delete(created.id).await.unwrap());
+        assert!(service.get_by_id(created.id).await.unwrap().is_none());
+        assert!(matches!(
+            service.update(created.id, serde_json::from_value(serde_json::json!({})).unwrap()).await,
+            Err(AppError::NotFound { .. })
+        ));
+    }
+}
@@ -1,22 +1,181 @@
 // src/service/factory.rs
+use std::path::PathBuf;
 use std::sync::Arc;
+use crate::core::config::MarketDataConfig;
 use crate::db::connection::DbPool;
+use crate::db::repositories::corporate_action::CorporateActionRepository;
+use crate::db::repositories::data_quality_issue::DataQualityIssueRepository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::feature_metric::FeatureMetricRepository;
+use crate::db::repositories::financial_statement::FinancialStatementRepository;
 use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::instrument_alias::InstrumentAliasRepository;
+use crate::db::repositories::instrument_history::InstrumentHistoryRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::db::repositories::metric_definition::MetricDefinitionRepository;
+use crate::db::repositories::order_book::OrderBookRepository;
+use crate::db::repositories::trade::TradeRepository;
 use crate::service::{
-    instrument::InstrumentService
+    adjustment::AdjustmentCache,
+    calendar::{CalendarFiles, CalendarService},
+    corporate_action::CorporateActionService,
+    data_quality::DataQualityService,
+    exchange::ExchangeService,
+    financial_statement::FinancialStatementService,
+    health::HealthService,
+    instrument::InstrumentService,
+    kline::KlineService,
+    market_data_import::MarketDataImportService,
+    metric::MetricService,
+    search::SearchService,
+    symbology::SymbologyService,
+    symbology_rules::SymbologyRegistry,
+    tick::TickService,
 };
 
 pub struct ServiceFactory {
     db: Arc<DbPool>,
+    /// 各交易所的代码规范化规则，新增交易所规则时在此注册
+    symbology: Arc<SymbologyRegistry>,
+    /// 搜索服务持有应用内索引，全局共享一份
+    search: Arc<SearchService>,
+    /// 启动时加载的交易日历文件
+    calendars: Arc<CalendarFiles>,
+    /// 行情导入服务持有任务列表，全局共享一份
+    market_data_import: Arc<MarketDataImportService>,
+    /// 按标的缓存的复权因子，K 线与公司行动服务共用
+    adjustments: Arc<AdjustmentCache>,
 }
 
 impl ServiceFactory {
-    pub fn new(db: Arc<DbPool>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<DbPool>, calendars: CalendarFiles, market_data: &MarketDataConfig) -> Self {
+        let symbology = Arc::new(SymbologyRegistry::default());
+        let search = Arc::new(SearchService::new(
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db.clone())),
+            Arc::new(InstrumentAliasRepository::new(db.clone())),
+        ));
+        let market_data_import = Arc::new(MarketDataImportService::new(
+            Arc::new(KlineRepository::new(db.clone())),
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db.clone())),
+            symbology.clone(),
+            PathBuf::from(&market_data.import_dir),
+            market_data.max_concurrent_imports,
+        ));
+        Self {
+            db,
+            symbology,
+            search,
+            calendars: Arc::new(calendars),
+            market_data_import,
+            adjustments: Arc::new(AdjustmentCache::default()),
+        }
     }
 
     pub fn instrument_service(&self) -> Arc<InstrumentService> {
         let repo = Arc::new(InstrumentRepository::new(self.db.clone()));
-        Arc::new(InstrumentService::new(repo))
+        let exchange_repo = Arc::new(ExchangeRepository::new(self.db.clone()));
+        let history_repo = Arc::new(InstrumentHistoryRepository::new(self.db.clone()));
+        Arc::new(InstrumentService::new(repo, exchange_repo, history_repo))
     }
-}
\ No newline at end of file
+
+    pub fn exchange_service(&self) -> Arc<ExchangeService> {
+        let repo = Arc::new(ExchangeRepository::new(self.db.clone()));
+        Arc::new(ExchangeService::new(repo))
+    }
+
+    pub fn symbology_service(&self) -> Arc<SymbologyService> {
+        let instrument_repo = Arc::new(InstrumentRepository::new(self.db.clone()));
+        let exchange_repo = Arc::new(ExchangeRepository::new(self.db.clone()));
+        let alias_repo = Arc::new(InstrumentAliasRepository::new(self.db.clone()));
+        Arc::new(SymbologyService::new(instrument_repo, exchange_repo, alias_repo, self.symbology.clone()))
+    }
+
+    pub fn search_service(&self) -> Arc<SearchService> {
+        self.search.clone()
+    }
+
+    pub fn calendar_service(&self) -> Arc<CalendarService> {
+        let exchange_repo = Arc::new(ExchangeRepository::new(self.db.clone()));
+        Arc::new(CalendarService::new(exchange_repo, self.calendars.clone()))
+    }
+
+    pub fn kline_service(&self) -> Arc<KlineService> {
+        let kline_repo = Arc::new(KlineRepository::new(self.db.clone()));
+        let instrument_repo = Arc::new(InstrumentRepository::new(self.db.clone()));
+        let exchange_repo = Arc::new(ExchangeRepository::new(self.db.clone()));
+        let corporate_action_repo = Arc::new(CorporateActionRepository::new(self.db.clone()));
+        Arc::new(KlineService::new(
+            kline_repo,
+            instrument_repo,
+            exchange_repo,
+            corporate_action_repo,
+            self.calendars.clone(),
+            self.adjustments.clone(),
+        ))
+    }
+
+    pub fn corporate_action_service(&self) -> Arc<CorporateActionService> {
+        Arc::new(CorporateActionService::new(
+            Arc::new(CorporateActionRepository::new(self.db.clone())),
+            Arc::new(KlineRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+            Arc::new(ExchangeRepository::new(self.db.clone())),
+            self.calendars.clone(),
+            self.adjustments.clone(),
+        ))
+    }
+
+    pub fn data_quality_service(&self) -> Arc<DataQualityService> {
+        Arc::new(DataQualityService::new(
+            Arc::new(DataQualityIssueRepository::new(self.db.clone())),
+            Arc::new(KlineRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+            Arc::new(ExchangeRepository::new(self.db.clone())),
+            self.calendars.clone(),
+        ))
+    }
+
+    pub fn tick_service(&self) -> Arc<TickService> {
+        Arc::new(TickService::new(
+            Arc::new(TradeRepository::new(self.db.clone())),
+            Arc::new(OrderBookRepository::new(self.db.clone())),
+            Arc::new(KlineRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+        ))
+    }
+
+    pub fn metric_service(&self) -> Arc<MetricService> {
+        Arc::new(MetricService::new(
+            Arc::new(FeatureMetricRepository::new(self.db.clone())),
+            Arc::new(MetricDefinitionRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+            Arc::new(ExchangeRepository::new(self.db.clone())),
+            self.calendars.clone(),
+        ))
+    }
+
+    pub fn financial_statement_service(&self) -> Arc<FinancialStatementService> {
+        Arc::new(FinancialStatementService::new(
+            Arc::new(FinancialStatementRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+        ))
+    }
+
+    pub fn health_service(&self) -> Arc<HealthService> {
+        Arc::new(HealthService::new(
+            Arc::new(FinancialStatementRepository::new(self.db.clone())),
+            Arc::new(FeatureMetricRepository::new(self.db.clone())),
+            Arc::new(MetricDefinitionRepository::new(self.db.clone())),
+            Arc::new(KlineRepository::new(self.db.clone())),
+            Arc::new(InstrumentRepository::new(self.db.clone())),
+            Arc::new(ExchangeRepository::new(self.db.clone())),
+            self.calendars.clone(),
+        ))
+    }
+
+    pub fn market_data_import_service(&self) -> Arc<MarketDataImportService> {
+        self.market_data_import.clone()
+    }
+}
@@ -0,0 +1,624 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use chrono::NaiveDate;
+use sea_orm::{ActiveEnum, ColumnTrait, Condition, DbErr};
+use sea_orm::ActiveValue::Set;
+use crate::db::error::DbError;
+use crate::db::repositories::financial_statement::FinancialStatementRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::dto::financial_statement::{
+    FinancialStatementQuery, FinancialStatementResponse, LineItemQuery, LineItemResponse, StatementItemRow,
+    StatementRecord,
+};
+use crate::dto::import::{DataFormat, ImportReport};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::financial_taxonomy::{is_line_item, LINE_ITEMS};
+use crate::service::instrument::find_instrument;
+use crate::service::tabular::{check_row_count, decode_records};
+use entities::financial_statement;
+use entities::sea_orm_active_enums::{FiscalPeriod, StatementType};
+use super::APPResult;
+
+/// 报告期：同一标的下由 (报表类型, 财年, 报告期) 确定，各版本共享
+type PeriodKey = (StatementType, i32, FiscalPeriod);
+
+/// 报告期下的一个版本：已入库的带 (ID, 版本号)，待写入的为空
+type PeriodVersion = (Option<(i32, i32)>, Filing);
+
+pub struct FinancialStatementService {
+    repo: Arc<FinancialStatementRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+}
+
+impl FinancialStatementService {
+    pub fn new(repo: Arc<FinancialStatementRepository>, instrument_repo: Arc<InstrumentRepository>) -> Self {
+        Self { repo, instrument_repo }
+    }
+
+    /// 标准科目表
+    pub fn line_items(&self, query: LineItemQuery) -> Vec<LineItemResponse> {
+        LINE_ITEMS
+            .iter()
+            .filter(|item| query.statement_type.is_none_or(|statement_type| item.statement_type == statement_type))
+            .map(|item| LineItemResponse {
+                code: item.code,
+                statement_type: item.statement_type,
+                name: item.name,
+                name_zh: item.name_zh,
+            })
+            .collect()
+    }
+
+    /// 查询报表，默认每个报告期只返回截至 `as_of` 已公告的最新版本
+    pub async fn statements(
+        &self,
+        instrument_id: i32,
+        query: FinancialStatementQuery,
+    ) -> APPResult<Vec<FinancialStatementResponse>> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        if let (Some(start), Some(end)) = (query.start_year, query.end_year)
+            && end < start
+        {
+            let mut errors = FieldErrors::new();
+            errors.add("end_year", "validation-date_order", TranslateArgs::new().add("other", "start_year"));
+            errors.into_result()?;
+        }
+
+        let mut cond = Condition::all();
+        if let Some(statement_type) = query.statement_type {
+            cond = cond.add(financial_statement::Column::StatementType.eq(statement_type));
+        }
+        if let Some(fiscal_period) = query.fiscal_period {
+            cond = cond.add(financial_statement::Column::FiscalPeriod.eq(fiscal_period));
+        }
+        if let Some(start) = query.start_year {
+            cond = cond.add(financial_statement::Column::FiscalYear.gte(start));
+        }
+        if let Some(end) = query.end_year {
+            cond = cond.add(financial_statement::Column::FiscalYear.lte(end));
+        }
+        if let Some(as_of) = query.as_of {
+            cond = cond.add(financial_statement::Column::AnnounceDate.lte(as_of));
+        }
+        let statements = self.repo.find_statements(instrument_id, cond).await.map_err(AppError::from)?;
+        let statements = if query.all_versions { statements } else { latest_versions(statements) };
+
+        let ids: Vec<i32> = statements.iter().map(|statement| statement.id).collect();
+        let mut items: HashMap<i32, BTreeMap<String, f64>> = HashMap::new();
+        for item in self.repo.find_items(&ids).await.map_err(AppError::from)? {
+            items.entry(item.statement_id).or_default().insert(item.item_code, item.value);
+        }
+        Ok(statements
+            .into_iter()
+            .map(|statement| {
+                let items = items.remove(&statement.id).unwrap_or_default();
+                FinancialStatementResponse::new(statement, items)
+            })
+            .collect())
+    }
+
+    /// 导入交换格式的报表（CSV / NDJSON），格式见 `docs/financial_statements.md`
+    ///
+    /// 与已有某个版本公告日和内容都相同的报表跳过，其余写入为该报告期的新版本，原有版本保留。
+    /// 版本号按 (公告日, 入库或数据行先后) 编排，补录早于已有版本的公告时该报告期重新编号。
+    /// 报告中的 `total` / `failed` 按数据行计，`created`（报告期的首个版本）/ `updated`（重述）/ `unchanged` 按报表计。
+    pub async fn import(
+        &self,
+        instrument_id: i32,
+        format: DataFormat,
+        data: &[u8],
+        dry_run: bool,
+    ) -> APPResult<ImportReport> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let (mut filings, mut report) = parse_filings(format, data, dry_run)?;
+        filings.sort_by_key(|filing| (filing.announce_date, filing.row));
+
+        let existing = self.repo.find_statements(instrument_id, Condition::all()).await.map_err(AppError::from)?;
+        let ids: Vec<i32> = existing.iter().map(|statement| statement.id).collect();
+        let mut existing_items: HashMap<i32, BTreeMap<String, f64>> = HashMap::new();
+        for item in self.repo.find_items(&ids).await.map_err(AppError::from)? {
+            existing_items.entry(item.statement_id).or_default().insert(item.item_code, item.value);
+        }
+        // 已入库的版本按版本号升序
+        let mut periods: HashMap<PeriodKey, Vec<PeriodVersion>> = HashMap::new();
+        for statement in existing {
+            let items = existing_items.remove(&statement.id).unwrap_or_default();
+            let filing = Filing::from_model(&statement, items);
+            periods.entry(filing.period_key()).or_default().push((Some((statement.id, statement.version)), filing));
+        }
+
+        let mut changed = Vec::new();
+        for filing in filings {
+            let key = filing.period_key();
+            let known = periods.entry(key).or_default();
+            if known.iter().any(|(_, other)| other.same_content(&filing)) {
+                report.unchanged += 1;
+                continue;
+            }
+            if known.is_empty() {
+                report.created += 1;
+            } else {
+                report.updated += 1;
+            }
+            known.push((None, filing));
+            if !changed.contains(&key) {
+                changed.push(key);
+            }
+        }
+
+        // 有新版本的报告期按公告日重新编号，同一天的已有版本在前，新版本按数据行先后
+        let mut renumber = Vec::new();
+        let mut versions = Vec::new();
+        for key in changed {
+            let mut known = periods.remove(&key).unwrap_or_default();
+            known.sort_by_key(|(stored, filing)| (filing.announce_date, stored.is_none()));
+            for (index, (stored, filing)) in known.into_iter().enumerate() {
+                let version = index as i32 + 1;
+                match stored {
+                    Some((id, current)) if current != version => renumber.push((id, version)),
+                    Some(_) => {}
+                    None => versions.push((filing.active_model(instrument_id, version), filing.items.into_iter().collect())),
+                }
+            }
+        }
+
+        if !dry_run && !versions.is_empty() {
+            self.repo.save_versions(renumber, versions).await.map_err(conflict_error)?;
+        }
+        report.errors.sort_by_key(|e| e.row);
+
+        tracing::info!(
+            instrument_id,
+            dry_run,
+            created = report.created,
+            updated = report.updated,
+            failed = report.failed,
+            "Imported financial statements"
+        );
+        Ok(report)
+    }
+
+}
+
+/// 一份待导入（或已入库）的报表
+#[derive(Debug, Clone)]
+struct Filing {
+    /// 所在的首个数据行
+    row: u64,
+    statement_type: StatementType,
+    fiscal_year: i32,
+    fiscal_period: FiscalPeriod,
+    period_end: NaiveDate,
+    announce_date: NaiveDate,
+    currency: String,
+    source: Option<String>,
+    items: BTreeMap<String, f64>,
+}
+
+impl Filing {
+    fn from_model(model: &financial_statement::Model, items: BTreeMap<String, f64>) -> Self {
+        Self {
+            row: 0,
+            statement_type: model.statement_type,
+            fiscal_year: model.fiscal_year,
+            fiscal_period: model.fiscal_period,
+            period_end: model.period_end,
+            announce_date: model.announce_date,
+            currency: model.currency.clone(),
+            source: model.source.clone(),
+            items,
+        }
+    }
+
+    fn period_key(&self) -> PeriodKey {
+        (self.statement_type, self.fiscal_year, self.fiscal_period)
+    }
+
+    /// 导入报告中的业务主键，如 `income:2025:fy:2026-03-28`
+    fn key(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.statement_type.to_value(),
+            self.fiscal_year,
+            self.fiscal_period.to_value(),
+            self.announce_date
+        )
+    }
+
+    /// 公告日与报表内容相同；来源只是说明，不参与比较
+    fn same_content(&self, other: &Filing) -> bool {
+        self.announce_date == other.announce_date
+            && self.period_end == other.period_end
+            && self.currency == other.currency
+            && self.items == other.items
+    }
+
+    fn active_model(&self, instrument_id: i32, version: i32) -> financial_statement::ActiveModel {
+        financial_statement::ActiveModel {
+            instrument_id: Set(instrument_id),
+            statement_type: Set(self.statement_type),
+            fiscal_year: Set(self.fiscal_year),
+            fiscal_period: Set(self.fiscal_period),
+            period_end: Set(self.period_end),
+            currency: Set(self.currency.clone()),
+            announce_date: Set(self.announce_date),
+            version: Set(version),
+            source: Set(self.source.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// 校验报表字段，科目由调用方逐项校验
+    fn validate(&self, errors: &mut FieldErrors) {
+        if !(1900..=2100).contains(&self.fiscal_year) {
+            errors.add("fiscal_year", "validation-range", TranslateArgs::new().add("min", 1900).add("max", 2100));
+        }
+        if !(3..=10).contains(&self.currency.len()) || !self.currency.chars().all(|c| c.is_ascii_alphanumeric()) {
+            errors.add("currency", "validation-currency_code", TranslateArgs::new());
+        }
+        if self.source.as_ref().is_some_and(|source| source.chars().count() > 100) {
+            errors.add("source", "validation-length_max", TranslateArgs::new().add("max", 100));
+        }
+        if self.announce_date < self.period_end {
+            errors.add("announce_date", "validation-date_order", TranslateArgs::new().add("other", "period_end"));
+        }
+    }
+}
+
+/// 校验一个科目：代码属于该类报表，数值有限
+fn validate_item(statement_type: StatementType, code: &str, value: f64, field: &str, errors: &mut FieldErrors) {
+    if !is_line_item(statement_type, code) {
+        errors.add(
+            field,
+            "validation-line_item",
+            TranslateArgs::new().add("value", code).add("statement_type", statement_type.to_value()),
+        );
+    } else if !value.is_finite() {
+        errors.add(field, "validation-invalid", TranslateArgs::new());
+    }
+}
+
+/// 解析交换格式，返回通过校验的报表和已记录失败行的报告
+///
+/// NDJSON 每行一份报表；CSV 每行一个科目，报表类型、财年、报告期和公告日相同的行组成一份报表，
+/// 其中任一行无效时整份报表不导入。
+fn parse_filings(format: DataFormat, data: &[u8], dry_run: bool) -> APPResult<(Vec<Filing>, ImportReport)> {
+    let bad_request = |message: String| AppError::BadRequest { message };
+    let (filings, mut report) = match format {
+        DataFormat::Ndjson => {
+            let records = decode_records::<StatementRecord>(format, data).map_err(bad_request)?;
+            check_row_count(records.len())?;
+            let mut report = ImportReport::new(dry_run, records.len());
+            let mut filings = Vec::new();
+            for (row, record) in records {
+                let record = match record {
+                    Ok(record) => record,
+                    Err(message) => {
+                        report.fail(row, None, bad_request(message));
+                        continue;
+                    }
+                };
+                let filing = Filing {
+                    row,
+                    statement_type: record.statement_type,
+                    fiscal_year: record.fiscal_year,
+                    fiscal_period: record.fiscal_period,
+                    period_end: record.period_end,
+                    announce_date: record.announce_date,
+                    currency: record.currency.trim().to_uppercase(),
+                    source: record.source,
+                    items: record.items,
+                };
+                let mut errors = FieldErrors::new();
+                filing.validate(&mut errors);
+                if filing.items.is_empty() {
+                    errors.add("items", "validation-required", TranslateArgs::new());
+                }
+                for (code, value) in &filing.items {
+                    validate_item(filing.statement_type, code, *value, &format!("items.{code}"), &mut errors);
+                }
+                match errors.into_result() {
+                    Ok(()) => filings.push(filing),
+                    Err(err) => report.fail(row, Some(filing.key()), err),
+                }
+            }
+            (filings, report)
+        }
+        DataFormat::Csv => {
+            let records = decode_records::<StatementItemRow>(format, data).map_err(bad_request)?;
+            check_row_count(records.len())?;
+            let mut report = ImportReport::new(dry_run, records.len());
+            // 报表 -> 各行 (行号, 科目行)
+            let mut groups: Vec<Vec<(u64, StatementItemRow)>> = Vec::new();
+            let mut positions: HashMap<(PeriodKey, NaiveDate), usize> = HashMap::new();
+            for (row, record) in records {
+                match record {
+                    Ok(record) => {
+                        let key = ((record.statement_type, record.fiscal_year, record.fiscal_period), record.announce_date);
+                        let index = *positions.entry(key).or_insert_with(|| {
+                            groups.push(Vec::new());
+                            groups.len() - 1
+                        });
+                        groups[index].push((row, record));
+                    }
+                    Err(message) => report.fail(row, None, bad_request(message)),
+                }
+            }
+
+            let mut filings = Vec::new();
+            for rows in groups {
+                let (first_row, first) = &rows[0];
+                let mut filing = Filing {
+                    row: *first_row,
+                    statement_type: first.statement_type,
+                    fiscal_year: first.fiscal_year,
+                    fiscal_period: first.fiscal_period,
+                    period_end: first.period_end,
+                    announce_date: first.announce_date,
+                    currency: first.currency.trim().to_uppercase(),
+                    source: first.source.clone().filter(|source| !source.is_empty()),
+                    items: BTreeMap::new(),
+                };
+                let key = filing.key();
+                let mut header_errors = FieldErrors::new();
+                filing.validate(&mut header_errors);
+                let mut failures = Vec::new();
+                if let Err(err) = header_errors.into_result() {
+                    failures.push((*first_row, err));
+                }
+                let mut item_rows: HashMap<String, u64> = HashMap::new();
+                for (row, record) in &rows {
+                    if record.period_end != filing.period_end || record.currency.trim().to_uppercase() != filing.currency {
+                        failures.push((*row, field_error("period_end", "validation-statement_mismatch", *first_row)));
+                        continue;
+                    }
+                    if let Some(previous) = item_rows.get(&record.item) {
+                        failures.push((*row, field_error("item", "validation-import_duplicate", *previous)));
+                        continue;
+                    }
+                    let mut errors = FieldErrors::new();
+                    validate_item(filing.statement_type, &record.item, record.value, "item", &mut errors);
+                    match errors.into_result() {
+                        Ok(()) => {
+                            item_rows.insert(record.item.clone(), *row);
+                            filing.items.insert(record.item.clone(), record.value);
+                        }
+                        Err(err) => failures.push((*row, err)),
+                    }
+                }
+                if failures.is_empty() {
+                    filings.push(filing);
+                    continue;
+                }
+                // 报表不完整时不导入，其余行一并记为失败
+                let failed_rows: Vec<u64> = failures.iter().map(|(row, _)| *row).collect();
+                let listed = failed_rows.iter().map(u64::to_string).collect::<Vec<_>>().join(", ");
+                for (row, _) in rows.iter().filter(|(row, _)| !failed_rows.contains(row)) {
+                    let mut errors = FieldErrors::new();
+                    errors.add("body", "validation-statement_skipped", TranslateArgs::new().add("rows", listed.clone()));
+                    report.fail(*row, Some(key.clone()), errors.into());
+                }
+                for (row, err) in failures {
+                    report.fail(row, Some(key.clone()), err);
+                }
+            }
+            (filings, report)
+        }
+    };
+
+    // NDJSON 中同一报表出现多次时以首次出现的行为准
+    let mut seen: HashMap<String, u64> = HashMap::new();
+    let mut unique = Vec::with_capacity(filings.len());
+    for filing in filings {
+        let key = filing.key();
+        match seen.get(&key) {
+            Some(first) => {
+                report.fail(filing.row, Some(key), field_error("fiscal_period", "validation-import_duplicate", *first))
+            }
+            None => {
+                seen.insert(key, filing.row);
+                unique.push(filing);
+            }
+        }
+    }
+    Ok((unique, report))
+}
+
+/// 指向同一文件中第 `row` 行的字段错误
+fn field_error(field: &str, key: &str, row: u64) -> AppError {
+    let mut errors = FieldErrors::new();
+    errors.add(field, key, TranslateArgs::new().add("row", row as i64));
+    errors.into()
+}
+
+/// 每个报告期只保留公告日最晚（同一天取版本号最大）的版本，保持原有顺序
+fn latest_versions(statements: Vec<financial_statement::Model>) -> Vec<financial_statement::Model> {
+    let mut latest: HashMap<PeriodKey, (NaiveDate, i32)> = HashMap::new();
+    for statement in &statements {
+        let key = (statement.statement_type, statement.fiscal_year, statement.fiscal_period);
+        let candidate = (statement.announce_date, statement.version);
+        latest.entry(key).and_modify(|best| *best = (*best).max(candidate)).or_insert(candidate);
+    }
+    statements
+        .into_iter()
+        .filter(|statement| {
+            let key = (statement.statement_type, statement.fiscal_year, statement.fiscal_period);
+            latest[&key] == (statement.announce_date, statement.version)
+        })
+        .collect()
+}
+
+/// 并发导入分配到相同版本号时返回 `Conflict`，重试即可
+fn conflict_error(err: DbErr) -> AppError {
+    match DbError::from(err) {
+        DbError::UniqueViolation { .. } => AppError::Conflict {
+            resource: "FinancialStatement".to_string(),
+            identifier: "version".to_string(),
+        },
+        other => other.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveModelTrait;
+    use crate::db::connection::DbPool;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+    use entities::{exchange, instrument};
+
+    async fn service() -> FinancialStatementService {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        instrument::ActiveModel {
+            id: Set(1),
+            exchange_id: Set(1),
+            symbol: Set("600000".to_string()),
+            asset_type: Set(AssetType::Stock),
+            name: Set("Stock 600000".to_string()),
+            status: Set(InstrumentStatus::Active),
+            metadata: Set(serde_json::json!({})),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        FinancialStatementService::new(
+            Arc::new(FinancialStatementRepository::new(db.clone())),
+            Arc::new(InstrumentRepository::new(db)),
+        )
+    }
+
+    fn statement(id: i32, fiscal_year: i32, announce_date: &str, version: i32) -> financial_statement::Model {
+        financial_statement::Model {
+            id,
+            instrument_id: 1,
+            statement_type: StatementType::Income,
+            fiscal_year,
+            fiscal_period: FiscalPeriod::Fy,
+            period_end: NaiveDate::from_ymd_opt(fiscal_year, 12, 31).unwrap(),
+            currency: "CNY".to_string(),
+            announce_date: announce_date.parse().unwrap(),
+            version,
+            source: None,
+            created_at: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_latest_versions() {
+        let statements = vec![
+            statement(1, 2024, "2025-03-28", 1),
+            statement(2, 2025, "2026-03-28", 1),
+            statement(3, 2024, "2025-06-30", 2),
+            // 同一天的多个版本取版本号最大的一版
+            statement(4, 2025, "2026-03-28", 2),
+        ];
+        let ids: Vec<i32> = latest_versions(statements).iter().map(|statement| statement.id).collect();
+        assert_eq!(ids, vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_statements_as_of() {
+        let service = service().await;
+        let csv = "\
+statement_type,fiscal_year,fiscal_period,period_end,announce_date,currency,source,item,value
+income,2025,fy,2025-12-31,2026-03-28,CNY,annual report,revenue,100
+income,2025,fy,2025-12-31,2026-05-10,CNY,correction,revenue,98
+";
+        let report = service.import(1, DataFormat::Csv, csv.as_bytes(), false).await.unwrap();
+        assert_eq!(report.failed, 0);
+        let revenue = |query: FinancialStatementQuery| async {
+            let statements = service.statements(1, query).await.unwrap();
+            statements.iter().map(|statement| (statement.version, statement.items["revenue"])).collect::<Vec<_>>()
+        };
+
+        assert_eq!(revenue(FinancialStatementQuery::default()).await, vec![(2, 98.0)]);
+        let before_announcement = FinancialStatementQuery { as_of: "2026-03-27".parse().ok(), ..Default::default() };
+        assert_eq!(revenue(before_announcement).await, vec![]);
+        // 公告日当天即视为已知
+        let before_restatement = FinancialStatementQuery { as_of: "2026-03-28".parse().ok(), ..Default::default() };
+        assert_eq!(revenue(before_restatement).await, vec![(1, 100.0)]);
+        let all_versions = FinancialStatementQuery { all_versions: true, ..Default::default() };
+        assert_eq!(revenue(all_versions).await, vec![(1, 100.0), (2, 98.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_backdated_filings_are_renumbered() {
+        let service = service().await;
+        let filing = |announce_date: &str, revenue: f64| {
+            format!(
+                "statement_type,fiscal_year,fiscal_period,period_end,announce_date,currency,source,item,value\n\
+                 income,2025,fy,2025-12-31,{announce_date},CNY,,revenue,{revenue}\n"
+            )
+        };
+        let versions = || async {
+            let query = FinancialStatementQuery { all_versions: true, ..Default::default() };
+            let statements = service.statements(1, query).await.unwrap();
+            statements
+                .iter()
+                .map(|statement| (statement.version, statement.announce_date.to_string(), statement.items["revenue"]))
+                .collect::<Vec<_>>()
+        };
+
+        // 先导入更正公告，再补录原始公告
+        service.import(1, DataFormat::Csv, filing("2026-05-10", 98.0).as_bytes(), false).await.unwrap();
+        let report = service.import(1, DataFormat::Csv, filing("2026-03-28", 100.0).as_bytes(), true).await.unwrap();
+        assert_eq!((report.created, report.updated), (0, 1));
+        assert_eq!(versions().await, vec![(1, "2026-05-10".to_string(), 98.0)]);
+        service.import(1, DataFormat::Csv, filing("2026-03-28", 100.0).as_bytes(), false).await.unwrap();
+        assert_eq!(versions().await, vec![(1, "2026-03-28".to_string(), 100.0), (2, "2026-05-10".to_string(), 98.0)]);
+
+        // 插在两个已有版本之间
+        service.import(1, DataFormat::Csv, filing("2026-04-15", 99.0).as_bytes(), false).await.unwrap();
+        assert_eq!(
+            versions().await,
+            vec![
+                (1, "2026-03-28".to_string(), 100.0),
+                (2, "2026-04-15".to_string(), 99.0),
+                (3, "2026-05-10".to_string(), 98.0),
+            ]
+        );
+        let latest = service.statements(1, FinancialStatementQuery::default()).await.unwrap();
+        assert_eq!((latest[0].version, latest[0].items["revenue"]), (3, 98.0));
+    }
+
+    #[test]
+    fn test_parse_csv_filings() {
+        let csv = "\
+statement_type,fiscal_year,fiscal_period,period_end,announce_date,currency,source,item,value
+income,2025,fy,2025-12-31,2026-03-28,cny,annual report,revenue,100
+income,2025,fy,2025-12-31,2026-03-28,cny,annual report,net_income,10
+income,2025,fy,2025-12-31,2026-05-10,CNY,correction,revenue,98
+income,2025,fy,2025-12-31,2026-05-10,CNY,correction,net_income,9
+balance_sheet,2025,fy,2025-12-31,2026-03-28,CNY,,revenue,1
+balance_sheet,2025,fy,2025-12-31,2026-03-28,CNY,,total_assets,500
+";
+        let (filings, mut report) = parse_filings(DataFormat::Csv, csv.as_bytes(), true).unwrap();
+        assert_eq!(report.total, 6);
+        assert_eq!(filings.len(), 2);
+        assert_eq!(filings[0].currency, "CNY");
+        assert_eq!(filings[0].items["net_income"], 10.0);
+        assert_eq!(filings[1].items["revenue"], 98.0);
+        assert_eq!(filings[1].row, 4);
+        // 资产负债表中出现利润表科目，整份报表不导入
+        assert_eq!(report.failed, 2);
+        report.errors.sort_by_key(|e| e.row);
+        assert_eq!(report.errors.iter().map(|e| e.row).collect::<Vec<_>>(), vec![6, 7]);
+        assert!(report.errors[0].errors.as_ref().unwrap().contains_key("item"));
+        assert!(report.errors[1].errors.as_ref().unwrap().contains_key("body"));
+    }
+}
@@ -0,0 +1,100 @@
+//! 标准财务科目表
+//!
+//! 各数据源的科目名称不一，导入时统一映射为这里的代码后入库；科目含义按合并报表口径，金额为报表货币的元（非千元 / 万元），
+//! 每股指标为每股金额，股数为股。新增科目只需在对应报表的列表中追加，代码一经发布不再修改。
+
+use entities::sea_orm_active_enums::StatementType;
+use entities::sea_orm_active_enums::StatementType::{BalanceSheet, CashFlow, Income};
+
+/// 一个标准科目
+#[derive(Debug, Clone, Copy)]
+pub struct LineItem {
+    pub code: &'static str,
+    pub statement_type: StatementType,
+    pub name: &'static str,
+    pub name_zh: &'static str,
+}
+
+const fn item(statement_type: StatementType, code: &'static str, name: &'static str, name_zh: &'static str) -> LineItem {
+    LineItem { code, statement_type, name, name_zh }
+}
+
+/// 全部标准科目，按报表类型和报表中的顺序排列
+pub const LINE_ITEMS: &[LineItem] = &[
+    // 利润表
+    item(Income, "revenue", "Total revenue", "营业总收入"),
+    item(Income, "cost_of_revenue", "Cost of revenue", "营业成本"),
+    item(Income, "gross_profit", "Gross profit", "毛利"),
+    item(Income, "selling_expense", "Selling expenses", "销售费用"),
+    item(Income, "admin_expense", "General and administrative expenses", "管理费用"),
+    item(Income, "rd_expense", "Research and development expenses", "研发费用"),
+    item(Income, "finance_expense", "Finance expenses", "财务费用"),
+    item(Income, "operating_profit", "Operating profit", "营业利润"),
+    item(Income, "non_operating_income", "Non-operating income", "营业外收入"),
+    item(Income, "non_operating_expense", "Non-operating expenses", "营业外支出"),
+    item(Income, "profit_before_tax", "Profit before tax", "利润总额"),
+    item(Income, "income_tax", "Income tax expense", "所得税费用"),
+    item(Income, "net_income", "Net income", "净利润"),
+    item(Income, "net_income_parent", "Net income attributable to shareholders of the parent", "归属于母公司股东的净利润"),
+    item(Income, "minority_interest_income", "Net income attributable to minority interests", "少数股东损益"),
+    item(Income, "eps_basic", "Basic earnings per share", "基本每股收益"),
+    item(Income, "eps_diluted", "Diluted earnings per share", "稀释每股收益"),
+    // 资产负债表
+    item(BalanceSheet, "cash", "Cash and cash equivalents", "货币资金"),
+    item(BalanceSheet, "trading_assets", "Trading financial assets", "交易性金融资产"),
+    item(BalanceSheet, "accounts_receivable", "Accounts receivable", "应收账款"),
+    item(BalanceSheet, "inventory", "Inventories", "存货"),
+    item(BalanceSheet, "current_assets", "Total current assets", "流动资产合计"),
+    item(BalanceSheet, "fixed_assets", "Property, plant and equipment", "固定资产"),
+    item(BalanceSheet, "intangible_assets", "Intangible assets", "无形资产"),
+    item(BalanceSheet, "goodwill", "Goodwill", "商誉"),
+    item(BalanceSheet, "total_assets", "Total assets", "资产总计"),
+    item(BalanceSheet, "short_term_debt", "Short-term borrowings", "短期借款"),
+    item(BalanceSheet, "accounts_payable", "Accounts payable", "应付账款"),
+    item(BalanceSheet, "current_liabilities", "Total current liabilities", "流动负债合计"),
+    item(BalanceSheet, "long_term_debt", "Long-term borrowings", "长期借款"),
+    item(BalanceSheet, "total_liabilities", "Total liabilities", "负债合计"),
+    item(BalanceSheet, "share_capital", "Share capital", "股本"),
+    item(BalanceSheet, "shares_outstanding", "Total shares outstanding", "总股本（股）"),
+    item(BalanceSheet, "retained_earnings", "Retained earnings", "未分配利润"),
+    item(BalanceSheet, "equity_parent", "Equity attributable to shareholders of the parent", "归属于母公司股东权益合计"),
+    item(BalanceSheet, "minority_interest", "Minority interests", "少数股东权益"),
+    item(BalanceSheet, "total_equity", "Total equity", "股东权益合计"),
+    // 现金流量表
+    item(CashFlow, "operating_cash_flow", "Net cash from operating activities", "经营活动产生的现金流量净额"),
+    item(CashFlow, "depreciation", "Depreciation and amortization", "固定资产折旧和无形资产摊销"),
+    item(CashFlow, "capex", "Purchase of property, plant, equipment and intangible assets", "购建固定资产、无形资产和其他长期资产支付的现金"),
+    item(CashFlow, "investing_cash_flow", "Net cash from investing activities", "投资活动产生的现金流量净额"),
+    item(CashFlow, "dividends_paid", "Dividends and interest paid", "分配股利、利润或偿付利息支付的现金"),
+    item(CashFlow, "financing_cash_flow", "Net cash from financing activities", "筹资活动产生的现金流量净额"),
+    item(CashFlow, "fx_effect", "Effect of exchange rate changes on cash", "汇率变动对现金的影响"),
+    item(CashFlow, "net_change_in_cash", "Net increase in cash and cash equivalents", "现金及现金等价物净增加额"),
+    item(CashFlow, "cash_end", "Cash and cash equivalents at end of period", "期末现金及现金等价物余额"),
+];
+
+/// 某类报表的科目
+pub fn line_items(statement_type: StatementType) -> impl Iterator<Item = &'static LineItem> {
+    LINE_ITEMS.iter().filter(move |item| item.statement_type == statement_type)
+}
+
+/// 科目代码是否属于该类报表
+pub fn is_line_item(statement_type: StatementType, code: &str) -> bool {
+    line_items(statement_type).any(|item| item.code == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_line_item_codes_are_unique() {
+        let mut codes = HashSet::new();
+        for item in LINE_ITEMS {
+            assert!(codes.insert(item.code), "duplicate line item {}", item.code);
+            assert!(item.code.chars().all(|c| c.is_ascii_lowercase() || c == '_'));
+        }
+        assert!(is_line_item(StatementType::Income, "revenue"));
+        assert!(!is_line_item(StatementType::BalanceSheet, "revenue"));
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use chrono::{Days, NaiveDate};
+use sea_orm::{ColumnTrait, Condition, Order};
+use sea_orm::ActiveValue::Set;
+use serde_json::json;
+use crate::db::repositories::Repository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::feature_metric::FeatureMetricRepository;
+use crate::db::repositories::financial_statement::FinancialStatementRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::db::repositories::metric_definition::MetricDefinitionRepository;
+use crate::dto::health::{HealthComponent, HealthQuery, HealthRatio, HealthRefreshResponse, HealthReport, HealthScore};
+use crate::error::code::AppError;
+use crate::i18n::{t_locale, TranslateArgs};
+use crate::service::calendar::CalendarFiles;
+use crate::service::health_scores::{self, AnnualFigures, Score, METRICS};
+use crate::service::instrument::find_instrument;
+use crate::service::trading_calendar::TradingCalendar;
+use entities::sea_orm_active_enums::{FiscalPeriod, KlineInterval, MetricFrequency, StatementType};
+use entities::{feature_metric, financial_statement, instrument, metric_definition};
+use unic_langid::LanguageIdentifier;
+use super::APPResult;
+
+/// 写入 `feature_metric` 时登记的指标来源
+const METRIC_SOURCE: &str = "uniquant.health";
+
+/// 期末总市值取报告期末及之前这么多天内最后一根日线的收盘价
+const PRICE_LOOKBACK_DAYS: u64 = 10;
+
+/// 年报及其科目
+type AnnualStatement = (financial_statement::Model, BTreeMap<String, f64>);
+
+/// 基于已入库年报的财务健康诊断：财务比率与 Piotroski F、Altman Z、Beneish M 评分
+///
+/// 报告按请求即时计算；`refresh` 把每个知识日期可见的结果写入 `feature_metric`，供时点查询和截面使用。
+pub struct HealthService {
+    statement_repo: Arc<FinancialStatementRepository>,
+    metric_repo: Arc<FeatureMetricRepository>,
+    definition_repo: Arc<MetricDefinitionRepository>,
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    calendars: Arc<CalendarFiles>,
+}
+
+impl HealthService {
+    pub fn new(
+        statement_repo: Arc<FinancialStatementRepository>,
+        metric_repo: Arc<FeatureMetricRepository>,
+        definition_repo: Arc<MetricDefinitionRepository>,
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        calendars: Arc<CalendarFiles>,
+    ) -> Self {
+        Self { statement_repo, metric_repo, definition_repo, kline_repo, instrument_repo, exchange_repo, calendars }
+    }
+
+    /// 某个财年的财务健康报告，说明文字按 `locale` 翻译
+    pub async fn report(
+        &self,
+        instrument_id: i32,
+        query: HealthQuery,
+        locale: &LanguageIdentifier,
+    ) -> APPResult<HealthReport> {
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+        let statements = self.annual_statements(instrument_id).await?;
+        let years = annual_snapshot(&statements, query.as_of);
+        let fiscal_year = query.fiscal_year.or_else(|| years.keys().next_back().copied());
+        let Some(current) = fiscal_year.and_then(|year| years.get(&year)) else {
+            return Err(AppError::NotFound {
+                resource: "FinancialStatement".to_string(),
+                identifier: Some(match fiscal_year {
+                    Some(year) => format!("{instrument_id}:{year}:fy"),
+                    None => format!("{instrument_id}:fy"),
+                }),
+            });
+        };
+        let prior = years.get(&(current.fiscal_year - 1));
+        let calendar = self.calendar(&instrument).await?;
+        let market_cap = self.market_cap(instrument_id, &calendar, current).await?;
+
+        let ratios = health_scores::ratios(current, prior)
+            .into_iter()
+            .map(|(name, value)| HealthRatio {
+                name,
+                label: translate(locale, &format!("health-ratio-{name}"), TranslateArgs::new()),
+                value,
+            })
+            .collect();
+        let scores = health_scores::scores(current, prior, market_cap).into_iter().map(|score| localize(score, locale)).collect();
+        Ok(HealthReport {
+            instrument_id,
+            fiscal_year: current.fiscal_year,
+            period_end: current.period_end,
+            known_at: prior.map_or(current.known_at, |prior| prior.known_at.max(current.known_at)),
+            prior_fiscal_year: prior.map(|prior| prior.fiscal_year),
+            ratios,
+            scores,
+        })
+    }
+
+    /// 重新计算全部财年的比率与评分并写入 `feature_metric`
+    ///
+    /// 按公告日逐个回放：每个公告日只重算本年或上年年报在当天有变化的财年，`ts` 为报告期末，
+    /// `known_at` 为该公告日，因此重述不会覆盖历史上已公布的值。日期均取交易所本地 0 点。
+    /// 指标首次写入时自动登记到指标目录。
+    pub async fn refresh(&self, instrument_id: i32) -> APPResult<HealthRefreshResponse> {
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+        let calendar = self.calendar(&instrument).await?;
+        let statements = self.annual_statements(instrument_id).await?;
+        let dates: BTreeSet<NaiveDate> = statements.iter().map(|(statement, _)| statement.announce_date).collect();
+
+        let mut closes: HashMap<NaiveDate, Option<f64>> = HashMap::new();
+        let mut metrics = Vec::new();
+        let mut snapshots = 0;
+        for date in dates {
+            let years = annual_snapshot(&statements, Some(date));
+            for current in years.values() {
+                let prior = years.get(&(current.fiscal_year - 1));
+                if current.known_at != date && prior.is_none_or(|prior| prior.known_at != date) {
+                    continue;
+                }
+                let close = match closes.get(&current.period_end) {
+                    Some(close) => *close,
+                    None => {
+                        let close = self.period_close(instrument_id, &calendar, current.period_end).await?;
+                        closes.insert(current.period_end, close);
+                        close
+                    }
+                };
+                let market_cap = market_cap(current, close);
+                snapshots += 1;
+                metrics.extend(metric_values(instrument_id, &calendar, current, prior, market_cap, date));
+            }
+        }
+
+        let definitions = METRICS
+            .iter()
+            .map(|(name, description)| metric_definition::ActiveModel {
+                name: Set(name.to_string()),
+                unit: Set(None),
+                frequency: Set(MetricFrequency::Annual),
+                source: Set(Some(METRIC_SOURCE.to_string())),
+                description: Set(Some(description.to_string())),
+                ..Default::default()
+            })
+            .collect();
+        self.definition_repo.insert_missing(definitions).await.map_err(AppError::from)?;
+        let written = self.metric_repo.upsert_batch(metrics).await.map_err(AppError::from)?;
+
+        tracing::info!(instrument_id, snapshots, written, "Refreshed financial health metrics");
+        Ok(HealthRefreshResponse { snapshots, written })
+    }
+
+    /// 标的全部年报（含各版本）及其科目
+    async fn annual_statements(&self, instrument_id: i32) -> APPResult<Vec<AnnualStatement>> {
+        let statements = self.statement_repo
+            .find_statements(
+                instrument_id,
+                Condition::all().add(financial_statement::Column::FiscalPeriod.eq(FiscalPeriod::Fy)),
+            )
+            .await
+            .map_err(AppError::from)?;
+        let ids: Vec<i32> = statements.iter().map(|statement| statement.id).collect();
+        let mut items: HashMap<i32, BTreeMap<String, f64>> = HashMap::new();
+        for item in self.statement_repo.find_items(&ids).await.map_err(AppError::from)? {
+            items.entry(item.statement_id).or_default().insert(item.item_code, item.value);
+        }
+        Ok(statements
+            .into_iter()
+            .map(|statement| {
+                let items = items.remove(&statement.id).unwrap_or_default();
+                (statement, items)
+            })
+            .collect())
+    }
+
+    /// 标的所属交易所的交易日历，用于把日期换算为交易所本地 0 点
+    async fn calendar(&self, instrument: &instrument::Model) -> APPResult<TradingCalendar> {
+        let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::Internal)?;
+        self.calendars.calendar_for(&exchange)
+    }
+
+    /// 报告期末总市值：总股本乘以期末最后一根日线的收盘价，缺少任一项时为空
+    async fn market_cap(
+        &self,
+        instrument_id: i32,
+        calendar: &TradingCalendar,
+        figures: &AnnualFigures,
+    ) -> APPResult<Option<f64>> {
+        if !figures.items.contains_key("shares_outstanding") {
+            return Ok(None);
+        }
+        let close = self.period_close(instrument_id, calendar, figures.period_end).await?;
+        Ok(market_cap(figures, close))
+    }
+
+    /// `date` 及之前 `PRICE_LOOKBACK_DAYS` 天内（交易所本地日期）最后一根日线的收盘价（未复权）
+    async fn period_close(
+        &self,
+        instrument_id: i32,
+        calendar: &TradingCalendar,
+        date: NaiveDate,
+    ) -> APPResult<Option<f64>> {
+        let start_of_day = |date: NaiveDate| calendar.start_of_day(date).fixed_offset();
+        let from = date.checked_sub_days(Days::new(PRICE_LOOKBACK_DAYS)).map(start_of_day);
+        let to = date.checked_add_days(Days::new(1)).map(start_of_day);
+        let klines = self.kline_repo
+            .find_range(instrument_id, KlineInterval::Day1, from, to, Order::Desc, 1)
+            .await
+            .map_err(AppError::from)?;
+        Ok(klines.first().map(|kline| kline.close))
+    }
+
+}
+
+/// 截至 `as_of`（含）各财年的年报数据，三张报表各取当时最新的一版后合并
+fn annual_snapshot(statements: &[AnnualStatement], as_of: Option<NaiveDate>) -> BTreeMap<i32, AnnualFigures> {
+    let mut latest: HashMap<(StatementType, i32), &AnnualStatement> = HashMap::new();
+    for entry in statements {
+        let statement = &entry.0;
+        if as_of.is_some_and(|as_of| statement.announce_date > as_of) {
+            continue;
+        }
+        latest
+            .entry((statement.statement_type, statement.fiscal_year))
+            .and_modify(|best| {
+                if (statement.announce_date, statement.version) > (best.0.announce_date, best.0.version) {
+                    *best = entry;
+                }
+            })
+            .or_insert(entry);
+    }
+
+    let mut years: BTreeMap<i32, AnnualFigures> = BTreeMap::new();
+    for (statement, items) in latest.into_values() {
+        let figures = years.entry(statement.fiscal_year).or_insert_with(|| AnnualFigures {
+            fiscal_year: statement.fiscal_year,
+            period_end: statement.period_end,
+            known_at: statement.announce_date,
+            items: BTreeMap::new(),
+        });
+        figures.period_end = figures.period_end.max(statement.period_end);
+        figures.known_at = figures.known_at.max(statement.announce_date);
+        figures.items.extend(items.iter().map(|(code, value)| (code.clone(), *value)));
+    }
+    years
+}
+
+/// 总股本乘以收盘价
+fn market_cap(figures: &AnnualFigures, close: Option<f64>) -> Option<f64> {
+    Some(figures.items.get("shares_outstanding")? * close?).filter(|market_cap| *market_cap > 0.0)
+}
+
+/// 一个财年在某个知识日期的全部指标值，算不出的比率和评分不写入
+fn metric_values(
+    instrument_id: i32,
+    calendar: &TradingCalendar,
+    current: &AnnualFigures,
+    prior: Option<&AnnualFigures>,
+    market_cap: Option<f64>,
+    known_at: NaiveDate,
+) -> Vec<feature_metric::Model> {
+    let ts = calendar.start_of_day(current.period_end).fixed_offset();
+    let known_at = calendar.start_of_day(known_at).fixed_offset();
+    let metric = |name: &str, value: f64, detail: serde_json::Value| feature_metric::Model {
+        instrument_id,
+        metric_name: name.to_string(),
+        ts,
+        known_at,
+        value: Some(value),
+        detail: Some(detail),
+    };
+
+    let mut metrics: Vec<_> = health_scores::ratios(current, prior)
+        .into_iter()
+        .map(|(name, value)| metric(name, value, json!({ "fiscal_year": current.fiscal_year })))
+        .collect();
+    for score in health_scores::scores(current, prior, market_cap) {
+        let Some(value) = score.value else { continue };
+        let components: serde_json::Map<String, serde_json::Value> = score.components
+            .iter()
+            .map(|component| (component.name.to_string(), json!(component.value)))
+            .collect();
+        let detail = json!({
+            "fiscal_year": current.fiscal_year,
+            "rating": score.rating,
+            "model": score.model,
+            "components": components,
+            "missing": score.missing,
+        });
+        metrics.push(metric(score.name, value, detail));
+    }
+    metrics
+}
+
+/// 翻译评分的结论与各组成项的解释
+fn localize(score: Score, locale: &LanguageIdentifier) -> HealthScore {
+    let model = score.model.unwrap_or_default();
+    let mut summary = match (score.value, score.rating) {
+        (Some(value), Some(rating)) => translate(
+            locale,
+            &format!("health-{}-{}", score.name, rating.as_str()),
+            TranslateArgs::new()
+                .add("score", format_value(value, if score.name == "piotroski_f" { 0 } else { 2 }))
+                .add("model", model),
+        ),
+        _ => translate(locale, "health-unavailable", TranslateArgs::new()),
+    };
+    if !score.missing.is_empty() {
+        summary.push(' ');
+        let args = TranslateArgs::new().add("items", score.missing.join(", "));
+        summary.push_str(&translate(locale, "health-missing", args));
+    }
+
+    let components = score.components
+        .into_iter()
+        .map(|component| {
+            let explanation = match component.value {
+                Some(value) => translate(
+                    locale,
+                    &format!("health-{}-{}", score.name, component.name),
+                    TranslateArgs::new()
+                        .add("value", format_value(value, 4))
+                        .add("passed", component.passed.map_or("false", |passed| if passed { "true" } else { "false" }))
+                        .add("model", model),
+                ),
+                // 评分已算出时，缺少数据的组成项按各模型的规则处理（取中性值）
+                None if score.value.is_some() => {
+                    translate(locale, &format!("health-{}-missing", score.name), TranslateArgs::new())
+                }
+                None => translate(locale, "health-component-missing", TranslateArgs::new()),
+            };
+            HealthComponent { name: component.name, value: component.value, passed: component.passed, explanation }
+        })
+        .collect();
+
+    HealthScore {
+        name: score.name,
+        value: score.value,
+        rating: score.rating,
+        model: score.model,
+        summary,
+        components,
+        missing: score.missing,
+    }
+}
+
+/// 按 `locale` 翻译，缺少翻译时退回 key
+fn translate(locale: &LanguageIdentifier, key: &str, args: TranslateArgs) -> String {
+    t_locale(key, locale, args).unwrap_or_else(|_| key.to_string())
+}
+
+fn format_value(value: f64, decimals: usize) -> String {
+    format!("{value:.decimals$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::prelude::DateTimeWithTimeZone;
+
+    #[test]
+    fn test_metric_times_use_the_exchange_local_midnight() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let calendar = TradingCalendar::new("Asia/Shanghai", &[], None).unwrap();
+        let figures = AnnualFigures {
+            fiscal_year: 2023,
+            period_end: date(2023, 12, 31),
+            known_at: date(2024, 3, 28),
+            items: BTreeMap::from([("total_assets".to_string(), 100.0), ("total_liabilities".to_string(), 40.0)]),
+        };
+        let metrics = metric_values(1, &calendar, &figures, None, None, date(2024, 3, 28));
+        assert!(metrics.iter().any(|metric| metric.metric_name == "debt_to_assets" && metric.value == Some(0.4)));
+        for metric in metrics {
+            assert_eq!(metric.ts, "2023-12-30T16:00:00Z".parse::<DateTimeWithTimeZone>().unwrap());
+            assert_eq!(metric.known_at, "2024-03-27T16:00:00Z".parse::<DateTimeWithTimeZone>().unwrap());
+        }
+    }
+}
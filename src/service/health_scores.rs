@@ -0,0 +1,410 @@
+//! 财务比率与综合评分（Piotroski F、Altman Z、Beneish M）的计算
+//!
+//! 输入为某个财年合并后的年报科目，只做计算，不涉及取数和翻译。比率中的资产、权益等存量项在有上一财年时
+//! 取期初期末平均；各评分按原文口径取期末值。缺少的科目不按 0 处理，算不出的比率直接省略。
+
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use crate::dto::health::HealthRating;
+
+/// 一个财年的年报数据，三张报表的科目合并在一起（科目代码在各报表间不重复）
+#[derive(Debug, Clone)]
+pub struct AnnualFigures {
+    pub fiscal_year: i32,
+    pub period_end: NaiveDate,
+    /// 所用报表中最晚的公告日
+    pub known_at: NaiveDate,
+    pub items: BTreeMap<String, f64>,
+}
+
+impl AnnualFigures {
+    fn get(&self, code: &str) -> Option<f64> {
+        self.items.get(code).copied()
+    }
+
+    fn total_assets(&self) -> Option<f64> {
+        self.get("total_assets")
+    }
+
+    fn revenue(&self) -> Option<f64> {
+        self.get("revenue")
+    }
+
+    fn cost_of_revenue(&self) -> Option<f64> {
+        self.get("cost_of_revenue").or_else(|| Some(self.revenue()? - self.get("gross_profit")?))
+    }
+
+    fn gross_profit(&self) -> Option<f64> {
+        self.get("gross_profit").or_else(|| Some(self.revenue()? - self.get("cost_of_revenue")?))
+    }
+
+    fn net_income(&self) -> Option<f64> {
+        self.get("net_income")
+    }
+
+    /// 归母净利润，未披露时用净利润
+    fn net_income_parent(&self) -> Option<f64> {
+        self.get("net_income_parent").or_else(|| self.net_income())
+    }
+
+    /// 归母权益，未披露时用股东权益合计
+    fn equity_parent(&self) -> Option<f64> {
+        self.get("equity_parent").or_else(|| self.get("total_equity"))
+    }
+
+    /// 有息负债：短期借款与长期借款之和，两者都未披露时为空
+    fn debt(&self) -> Option<f64> {
+        match (self.get("short_term_debt"), self.get("long_term_debt")) {
+            (None, None) => None,
+            (short, long) => Some(short.unwrap_or(0.0) + long.unwrap_or(0.0)),
+        }
+    }
+
+    /// 息税前利润：利润总额加回财务费用，缺少利润总额时用营业利润
+    fn ebit(&self) -> Option<f64> {
+        match self.get("profit_before_tax") {
+            Some(profit) => Some(profit + self.get("finance_expense").unwrap_or(0.0)),
+            None => self.get("operating_profit"),
+        }
+    }
+
+    /// 税后营业利润，税率取实际税率（限定在 0–1），亏损或缺少所得税时按 0
+    fn nopat(&self) -> Option<f64> {
+        let rate = div(self.get("income_tax"), self.get("profit_before_tax"))
+            .filter(|_| self.get("profit_before_tax").is_some_and(|profit| profit > 0.0))
+            .map_or(0.0, |rate| rate.clamp(0.0, 1.0));
+        Some(self.ebit()? * (1.0 - rate))
+    }
+
+    /// 投入资本：股东权益合计加有息负债
+    fn invested_capital(&self) -> Option<f64> {
+        Some(self.get("total_equity")? + self.debt().unwrap_or(0.0))
+    }
+
+    fn current_ratio(&self) -> Option<f64> {
+        div(self.get("current_assets"), self.get("current_liabilities"))
+    }
+
+    fn gross_margin(&self) -> Option<f64> {
+        div(self.gross_profit(), self.revenue())
+    }
+
+    fn roa(&self) -> Option<f64> {
+        div(self.net_income(), self.total_assets())
+    }
+}
+
+/// 写入 `feature_metric` 的指标：(名称, 说明)
+pub const METRICS: &[(&str, &str)] = &[
+    ("roe", "Return on equity: net income attributable to the parent / average parent equity"),
+    ("roa", "Return on assets: net income / average total assets"),
+    ("roic", "Return on invested capital: NOPAT / average (total equity + borrowings)"),
+    ("gross_margin", "Gross profit / revenue"),
+    ("operating_margin", "Operating profit / revenue"),
+    ("net_margin", "Net income / revenue"),
+    ("debt_to_equity", "Short and long-term borrowings / total equity"),
+    ("debt_to_assets", "Total liabilities / total assets"),
+    ("current_ratio", "Current assets / current liabilities"),
+    ("quick_ratio", "(Current assets - inventory) / current liabilities"),
+    ("asset_turnover", "Revenue / average total assets"),
+    ("inventory_turnover", "Cost of revenue / average inventory"),
+    ("receivables_turnover", "Revenue / average accounts receivable"),
+    ("piotroski_f", "Piotroski F-Score (0-9), higher is stronger"),
+    ("altman_z", "Altman Z-Score, lower means higher distress risk"),
+    ("beneish_m", "Beneish M-Score, above -1.78 suggests earnings manipulation"),
+];
+
+/// 可计算的财务比率，按 `METRICS` 中的顺序
+pub fn ratios(current: &AnnualFigures, prior: Option<&AnnualFigures>) -> Vec<(&'static str, f64)> {
+    let average = |value: fn(&AnnualFigures) -> Option<f64>| {
+        let end = value(current)?;
+        Some(prior.and_then(value).map_or(end, |start| (start + end) / 2.0))
+    };
+    let quick_assets = current.get("current_assets").map(|assets| assets - current.get("inventory").unwrap_or(0.0));
+    [
+        ("roe", div(current.net_income_parent(), average(AnnualFigures::equity_parent))),
+        ("roa", div(current.net_income(), average(AnnualFigures::total_assets))),
+        ("roic", div(current.nopat(), average(AnnualFigures::invested_capital))),
+        ("gross_margin", current.gross_margin()),
+        ("operating_margin", div(current.get("operating_profit"), current.revenue())),
+        ("net_margin", div(current.net_income(), current.revenue())),
+        ("debt_to_equity", div(current.debt(), current.get("total_equity"))),
+        ("debt_to_assets", div(current.get("total_liabilities"), current.total_assets())),
+        ("current_ratio", current.current_ratio()),
+        ("quick_ratio", div(quick_assets, current.get("current_liabilities"))),
+        ("asset_turnover", div(current.revenue(), average(AnnualFigures::total_assets))),
+        ("inventory_turnover", div(current.cost_of_revenue(), average(|figures| figures.get("inventory")))),
+        ("receivables_turnover", div(current.revenue(), average(|figures| figures.get("accounts_receivable")))),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value?)))
+    .collect()
+}
+
+/// 一个综合评分
+#[derive(Debug, Clone)]
+pub struct Score {
+    pub name: &'static str,
+    /// 必需输入缺失时为空
+    pub value: Option<f64>,
+    pub rating: Option<HealthRating>,
+    pub model: Option<&'static str>,
+    pub components: Vec<Component>,
+    /// 缺失输入的组成项
+    pub missing: Vec<&'static str>,
+}
+
+/// 评分的组成项
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: &'static str,
+    pub value: Option<f64>,
+    pub passed: Option<bool>,
+}
+
+impl Score {
+    fn new(name: &'static str, model: Option<&'static str>, components: Vec<Component>) -> Self {
+        let missing = components
+            .iter()
+            .filter(|component| component.value.is_none())
+            .map(|component| component.name)
+            .collect();
+        Self { name, value: None, rating: None, model, components, missing }
+    }
+}
+
+/// 全部综合评分；`market_cap` 为期末总市值，缺少时 Altman Z 用账面权益模型
+pub fn scores(current: &AnnualFigures, prior: Option<&AnnualFigures>, market_cap: Option<f64>) -> Vec<Score> {
+    vec![piotroski(current, prior), altman(current, market_cap), beneish(current, prior)]
+}
+
+/// Piotroski F-Score：9 个二元信号（盈利 4 个、杠杆与流动性 3 个、经营效率 2 个）的得分之和
+///
+/// 同比信号需要上一财年；任一信号算不出时不给出分值和评级，只列出 `missing`，
+/// 以免缺项不得分的低分与完整的 0–9 分混在一起比较。
+pub fn piotroski(current: &AnnualFigures, prior: Option<&AnnualFigures>) -> Score {
+    let roa = current.roa();
+    let cfo = current.get("operating_cash_flow");
+    let cfo_to_assets = div(cfo, current.total_assets());
+    let leverage = |figures: &AnnualFigures| div(Some(figures.get("long_term_debt").unwrap_or(0.0)), figures.total_assets());
+    let turnover = |figures: &AnnualFigures| div(figures.revenue(), figures.total_assets());
+    let shares = |figures: &AnnualFigures| figures.get("shares_outstanding").or_else(|| figures.get("share_capital"));
+    let improved = |value: fn(&AnnualFigures) -> Option<f64>| Some(value(current)? > value(prior?)?);
+
+    let signal = |name, value: Option<f64>, passed: Option<bool>| Component {
+        name,
+        value: value.filter(|_| passed.is_some()),
+        passed,
+    };
+    let components = vec![
+        signal("roa_positive", roa, roa.map(|roa| roa > 0.0)),
+        signal("cfo_positive", cfo_to_assets, cfo.map(|cfo| cfo > 0.0)),
+        signal("roa_improved", roa, improved(AnnualFigures::roa)),
+        signal("accruals", cfo_to_assets, (|| Some(cfo? > current.net_income()?))()),
+        signal("leverage_decreased", leverage(current), (|| Some(leverage(current)? <= leverage(prior?)?))()),
+        signal("current_ratio_improved", current.current_ratio(), improved(AnnualFigures::current_ratio)),
+        signal(
+            "no_dilution",
+            div(shares(current), prior.and_then(shares)).map(|ratio| ratio - 1.0),
+            (|| Some(shares(current)? <= shares(prior?)?))(),
+        ),
+        signal("gross_margin_improved", current.gross_margin(), improved(AnnualFigures::gross_margin)),
+        signal("asset_turnover_improved", turnover(current), (|| Some(turnover(current)? > turnover(prior?)?))()),
+    ];
+
+    let mut score = Score::new("piotroski_f", None, components);
+    if score.missing.is_empty() {
+        let passed = score.components.iter().filter(|component| component.passed == Some(true)).count();
+        score.value = Some(passed as f64);
+        score.rating = Some(match passed {
+            8.. => HealthRating::Healthy,
+            3..=7 => HealthRating::Caution,
+            _ => HealthRating::Risk,
+        });
+    }
+    score
+}
+
+/// Altman Z-Score
+///
+/// 有市值时用上市公司原始模型 `public`（安全区 > 2.99，困境区 < 1.81），
+/// 否则用以账面权益代替市值的 Z' 模型 `private`（安全区 > 2.9，困境区 < 1.23）。
+pub fn altman(current: &AnnualFigures, market_cap: Option<f64>) -> Score {
+    let total_assets = current.total_assets();
+    let working_capital = current
+        .get("current_assets")
+        .zip(current.get("current_liabilities"))
+        .map(|(assets, liabilities)| assets - liabilities);
+    let (model, equity) = match market_cap {
+        Some(market_cap) => ("public", Some(market_cap)),
+        None => ("private", current.get("total_equity")),
+    };
+    let component = |name, value| Component { name, value, passed: None };
+    let components = vec![
+        component("working_capital", div(working_capital, total_assets)),
+        component("retained_earnings", div(current.get("retained_earnings"), total_assets)),
+        component("ebit", div(current.ebit(), total_assets)),
+        component("equity_to_liabilities", div(equity, current.get("total_liabilities"))),
+        component("sales", div(current.revenue(), total_assets)),
+    ];
+
+    let mut score = Score::new("altman_z", Some(model), components);
+    if score.missing.is_empty() {
+        let (weights, safe, distress) = match model {
+            "public" => ([1.2, 1.4, 3.3, 0.6, 1.0], 2.99, 1.81),
+            _ => ([0.717, 0.847, 3.107, 0.420, 0.998], 2.9, 1.23),
+        };
+        let z: f64 = score.components
+            .iter()
+            .zip(weights)
+            .map(|(component, weight)| component.value.unwrap_or_default() * weight)
+            .sum();
+        score.value = Some(z);
+        score.rating = Some(if z > safe {
+            HealthRating::Healthy
+        } else if z >= distress {
+            HealthRating::Caution
+        } else {
+            HealthRating::Risk
+        });
+    }
+    score
+}
+
+/// Beneish M-Score（8 变量模型），高于 -1.78 提示可能存在盈余操纵，-2.22 到 -1.78 之间需要关注
+///
+/// 需要上一财年。折旧指数 `depi` 与销售管理费用指数 `sgai` 缺少数据时按中性值 1 计算，其余变量缺失时不给出评分。
+pub fn beneish(current: &AnnualFigures, prior: Option<&AnnualFigures>) -> Score {
+    const OPTIONAL: [&str; 2] = ["depi", "sgai"];
+
+    let index = |value: fn(&AnnualFigures) -> Option<f64>| div(value(current), prior.and_then(value));
+    let receivables_to_sales = |figures: &AnnualFigures| div(figures.get("accounts_receivable"), figures.revenue());
+    let soft_assets = |figures: &AnnualFigures| {
+        let hard = figures.get("current_assets")? + figures.get("fixed_assets")? + figures.get("trading_assets").unwrap_or(0.0);
+        Some(1.0 - div(Some(hard), figures.total_assets())?)
+    };
+    let depreciation_rate = |figures: &AnnualFigures| {
+        let depreciation = figures.get("depreciation")?;
+        div(Some(depreciation), Some(depreciation + figures.get("fixed_assets")?))
+    };
+    let expense_to_sales = |figures: &AnnualFigures| {
+        let expense = match (figures.get("selling_expense"), figures.get("admin_expense")) {
+            (None, None) => None,
+            (selling, admin) => Some(selling.unwrap_or(0.0) + admin.unwrap_or(0.0)),
+        };
+        div(expense, figures.revenue())
+    };
+    let leverage = |figures: &AnnualFigures| {
+        let liabilities = figures.get("current_liabilities")? + figures.get("long_term_debt").unwrap_or(0.0);
+        div(Some(liabilities), figures.total_assets())
+    };
+    let accruals = current.net_income().zip(current.get("operating_cash_flow")).map(|(income, cash)| income - cash);
+
+    let component = |name, value: Option<f64>| Component { name, value: value.filter(|_| prior.is_some()), passed: None };
+    let components = vec![
+        component("dsri", index(receivables_to_sales)),
+        component("gmi", div(prior.and_then(AnnualFigures::gross_margin), current.gross_margin())),
+        component("aqi", index(soft_assets)),
+        component("sgi", index(AnnualFigures::revenue)),
+        component("depi", div(prior.and_then(depreciation_rate), depreciation_rate(current))),
+        component("sgai", index(expense_to_sales)),
+        component("lvgi", index(leverage)),
+        component("tata", div(accruals, current.total_assets())),
+    ];
+
+    let mut score = Score::new("beneish_m", None, components);
+    if score.missing.iter().all(|name| OPTIONAL.contains(name)) {
+        let weights = [0.920, 0.528, 0.404, 0.892, 0.115, -0.172, -0.327, 4.679];
+        let m: f64 = -4.84
+            + score.components
+                .iter()
+                .zip(weights)
+                .map(|(component, weight)| component.value.unwrap_or(1.0) * weight)
+                .sum::<f64>();
+        score.value = Some(m);
+        score.rating = Some(if m > -1.78 {
+            HealthRating::Risk
+        } else if m > -2.22 {
+            HealthRating::Caution
+        } else {
+            HealthRating::Healthy
+        });
+    }
+    score
+}
+
+/// 除法，分子分母缺失、分母为 0 或结果非有限值时为空
+fn div(numerator: Option<f64>, denominator: Option<f64>) -> Option<f64> {
+    let value = numerator? / denominator.filter(|denominator| *denominator != 0.0)?;
+    value.is_finite().then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn figures(fiscal_year: i32, items: &[(&str, f64)]) -> AnnualFigures {
+        AnnualFigures {
+            fiscal_year,
+            period_end: NaiveDate::from_ymd_opt(fiscal_year, 12, 31).unwrap(),
+            known_at: NaiveDate::from_ymd_opt(fiscal_year + 1, 3, 31).unwrap(),
+            items: items.iter().map(|(code, value)| (code.to_string(), *value)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_scores_for_unchanged_company() {
+        let items = [
+            ("revenue", 1000.0),
+            ("cost_of_revenue", 600.0),
+            ("selling_expense", 50.0),
+            ("admin_expense", 50.0),
+            ("profit_before_tax", 130.0),
+            ("income_tax", 30.0),
+            ("net_income", 100.0),
+            ("accounts_receivable", 100.0),
+            ("current_assets", 400.0),
+            ("fixed_assets", 300.0),
+            ("total_assets", 1000.0),
+            ("current_liabilities", 200.0),
+            ("long_term_debt", 100.0),
+            ("total_liabilities", 400.0),
+            ("retained_earnings", 200.0),
+            ("total_equity", 600.0),
+            ("shares_outstanding", 100.0),
+            ("operating_cash_flow", 100.0),
+            ("depreciation", 30.0),
+        ];
+        let prior = figures(2024, &items);
+        let current = figures(2025, &items);
+
+        let ratios: BTreeMap<_, _> = ratios(&current, Some(&prior)).into_iter().collect();
+        assert!((ratios["roe"] - 100.0 / 600.0).abs() < 1e-9);
+        assert!((ratios["gross_margin"] - 0.4).abs() < 1e-9);
+        assert!((ratios["current_ratio"] - 2.0).abs() < 1e-9);
+        assert!(!ratios.contains_key("inventory_turnover"));
+
+        // 各项持平：ROA、CFO 为正，杠杆未升，股本未增
+        let f = piotroski(&current, Some(&prior));
+        assert_eq!(f.value, Some(4.0));
+        assert!(f.missing.is_empty());
+        // 没有上一财年时同比信号缺失，不给出分值
+        let first_year = piotroski(&current, None);
+        assert_eq!(first_year.missing.len(), 6);
+        assert_eq!(first_year.value, None);
+        assert_eq!(first_year.rating, None);
+
+        // 0.717*0.2 + 0.847*0.2 + 3.107*0.13 + 0.420*1.5 + 0.998*1.0
+        let z = altman(&current, None);
+        assert_eq!(z.model, Some("private"));
+        assert!((z.value.unwrap() - 2.3447).abs() < 1e-4);
+        assert_eq!(z.rating, Some(HealthRating::Caution));
+        assert_eq!(altman(&current, Some(1200.0)).model, Some("public"));
+
+        // 各指数为 1、应计为 0 时 M = -2.48
+        let m = beneish(&current, Some(&prior));
+        assert!((m.value.unwrap() + 2.48).abs() < 1e-9);
+        assert_eq!(m.rating, Some(HealthRating::Healthy));
+        assert_eq!(beneish(&current, None).value, None);
+    }
+}
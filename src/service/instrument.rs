@@ -1,66 +1,699 @@
+use std::collections::HashMap;
+use std::io::Read;
 use std::sync::Arc;
-use crate::db::repositories::Repository;
+use futures::Stream;
+use tokio::sync::mpsc;
+use validator::Validate;
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::sea_query::{Expr, Func, LikeExpr, Query};
+use sea_orm::{ColumnTrait, Condition, DbErr, IntoActiveModel, Order};
+use crate::core::context::current_actor;
+use crate::core::etag::ETags;
+use crate::db::error::DbError;
+use crate::db::repositories::{apply_changes, escape_like, Repository};
 use crate::error::code::AppError;
-use crate::db::repositories::instrument::InstrumentRepository;
-use crate::dto::instrument::{CreateInstrumentRequest, UpdateInstrumentRequest, InstrumentResponse};
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::{ImportOutcome, InstrumentRepository};
+use crate::db::repositories::instrument_history::InstrumentHistoryRepository;
+use crate::dto::instrument::{
+    CreateInstrumentRequest, InstrumentFilter, InstrumentHistoryFilter, InstrumentHistoryResponse,
+    InstrumentRecord, InstrumentResponse, UpdateInstrumentRequest,
+};
+use crate::dto::import::{DataFormat, ImportReport};
+use crate::dto::pagination::{PageParams, PageResponse};
+use crate::service::exchange::normalize_code;
+use crate::service::instrument_rules::{normalize_currencies, parse_asset_type, validate_instrument};
+use crate::service::tabular::{check_row_count, decode_reader, encode_records, read_failure, DecodeError};
+use entities::{exchange, instrument, instrument_history};
 use super::APPResult;
 
+/// 列表接口允许排序的字段
+const SORTABLE_COLUMNS: &[instrument::Column] = &[
+    instrument::Column::Id,
+    instrument::Column::ExchangeId,
+    instrument::Column::Symbol,
+    instrument::Column::Name,
+    instrument::Column::AssetType,
+    instrument::Column::Status,
+    instrument::Column::ListingDate,
+    instrument::Column::DelistingDate,
+    instrument::Column::CreatedAt,
+    instrument::Column::UpdatedAt,
+];
+
+/// 变更历史允许排序的字段
+const HISTORY_SORTABLE_COLUMNS: &[instrument_history::Column] = &[
+    instrument_history::Column::Id,
+    instrument_history::Column::Field,
+    instrument_history::Column::ChangedAt,
+];
+
 pub struct InstrumentService {
     repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    history_repo: Arc<InstrumentHistoryRepository>,
 }
 
 impl InstrumentService {
-    pub fn new(repo: Arc<InstrumentRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        history_repo: Arc<InstrumentHistoryRepository>,
+    ) -> Self {
+        Self { repo, exchange_repo, history_repo }
+    }
+
+    /// 将请求中的交易所代码解析为交易所记录
+    async fn resolve_exchange(&self, code: &str) -> APPResult<exchange::Model> {
+        let code = normalize_code(code);
+        self.exchange_repo.find_by_code(&code).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::NotFound {
+                resource: "Exchange".to_string(),
+                identifier: Some(code),
+            })
     }
 
     /// 创建交易标的
-    /// 业务规则：同一个交易所的 symbol 不能重复
+    /// 业务规则：同一个交易所的 symbol 不能重复，冲突时返回 `Conflict`
     pub async fn create(&self, new_instrument: CreateInstrumentRequest) -> APPResult<InstrumentResponse> {
-        let exchange = new_instrument.exchange.as_ref();
-        let symbol = new_instrument.symbol.as_ref();
+        let exchange = self.resolve_exchange(&new_instrument.exchange).await?;
+        let asset_type = parse_asset_type(&new_instrument.asset_type)?;
+        let identifier = format!("{}:{}", exchange.code, new_instrument.symbol);
 
-        // 业务逻辑检查
-        if let Some(_existing) = self.repo.find_by_exchange_and_symbol(exchange, symbol).await
-            .map_err(AppError::from)? {
-            return Err(AppError::Conflict{resource:exchange.to_string(), identifier:symbol.to_string()});
-        }
-
-        // 调用 Repo 创建
-        let model = self.repo.create(new_instrument.into()).await
-            .map_err(AppError::from)?;
+        // 调用 Repo 创建，(exchange_id, symbol) 的唯一性由数据库唯一索引保证
+        let mut active: instrument::ActiveModel = new_instrument.into();
+        active.exchange_id = Set(exchange.id);
+        active.asset_type = Set(asset_type);
+        active.created_by = Set(current_actor());
+        normalize_currencies(&mut active);
+        validate_instrument(&active)?;
+        let model = self.repo.create_with_history(active, current_actor()).await
+            .map_err(|err| conflict_error(err, identifier))?;
 
         tracing::info!(instrument_id = model.id, "Created new instrument");
-        Ok(model.into())
+        Ok(InstrumentResponse::new(model, exchange.code))
     }
 
     pub async fn get_by_id(&self, id: i32) -> APPResult<Option<InstrumentResponse>> {
-        self.repo.find_by_id(id).await
-            .map_err(AppError::from)
-            .map(|model| model.map(InstrumentResponse::from))
+        let Some(model) = self.repo.find_by_id(id).await.map_err(AppError::from)? else {
+            return Ok(None);
+        };
+        let exchange = self.find_exchange(model.exchange_id).await?;
+        Ok(Some(InstrumentResponse::new(model, exchange.code)))
     }
 
-    pub async fn get_all(&self) -> APPResult<Vec<InstrumentResponse>> {
-        self.repo.find_all().await
-            .map_err(AppError::from)
-            .map(|models| models.into_iter().map(InstrumentResponse::from).collect())
+    /// 按 ID 加载标的所属的交易所
+    async fn find_exchange(&self, exchange_id: i32) -> APPResult<exchange::Model> {
+        self.exchange_repo.find_by_id(exchange_id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::NotFound {
+                resource: "Exchange".to_string(),
+                identifier: Some(exchange_id.to_string()),
+            })
     }
 
-    pub async fn update(&self, active: UpdateInstrumentRequest) -> APPResult<InstrumentResponse> {
-        let updated_model = self.repo.update(active.into()).await
+    /// 按过滤条件分页查询
+    pub async fn list(
+        &self,
+        filter: InstrumentFilter,
+        params: PageParams,
+    ) -> APPResult<PageResponse<InstrumentResponse>> {
+        let order_by = params.order_by(SORTABLE_COLUMNS)?;
+        let page = self.repo
+            .paginate(filter_condition(filter), order_by, params.page(), params.per_page())
+            .await
             .map_err(AppError::from)?;
-        
-        tracing::info!(instrument_id = updated_model.id, "Updated instrument");
-        Ok(updated_model.into())
+        let exchanges = self.exchange_repo.codes_by_id().await.map_err(AppError::from)?;
+        let page: PageResponse<instrument::Model> = page.into();
+        Ok(page.map(|model| {
+            let code = exchanges.get(&model.exchange_id).cloned().unwrap_or_default();
+            InstrumentResponse::new(model, code)
+        }))
+    }
+
+
+    /// 按 ID 加载标的并校验 `If-Match` 中的版本号
+    async fn find_matching(&self, id: i32, if_match: &ETags) -> APPResult<instrument::Model> {
+        let existing = find_instrument(&self.repo, id).await?;
+        if !if_match.matches(existing.version) {
+            return Err(precondition_failed(existing.version));
+        }
+        Ok(existing)
+    }
+
+    /// 写入时版本号已被并发修改：重新读取当前版本用于提示，记录已删除时返回 `NotFound`
+    async fn write_error(&self, id: i32, err: DbErr, identifier: String) -> AppError {
+        if !matches!(err, DbErr::RecordNotUpdated) {
+            return conflict_error(err, identifier);
+        }
+        match find_instrument(&self.repo, id).await {
+            Ok(current) => precondition_failed(current.version),
+            Err(err) => err,
+        }
+    }
+
+    /// 部分更新：只修改请求中提供的字段，校验基于合并后的完整记录
+    pub async fn patch(
+        &self,
+        id: i32,
+        req: UpdateInstrumentRequest,
+        if_match: &ETags,
+    ) -> APPResult<InstrumentResponse> {
+        if let Some(body_id) = req.id
+            && body_id != id
+        {
+            return Err(AppError::BadRequest {
+                message: format!("Body id {body_id} does not match path id {id}"),
+            });
+        }
+
+        let existing = self.find_matching(id, if_match).await?;
+        let exchange = match req.exchange.as_deref() {
+            Some(code) => self.resolve_exchange(code).await?,
+            None => self.find_exchange(existing.exchange_id).await?,
+        };
+        let asset_type = req.asset_type.as_deref().map(parse_asset_type).transpose()?;
+
+        let mut changes: instrument::ActiveModel = req.into();
+        changes.exchange_id = Set(exchange.id);
+        if let Some(asset_type) = asset_type {
+            changes.asset_type = Set(asset_type);
+        }
+
+        let mut active = existing.clone().into_active_model();
+        apply_changes(&mut active, changes);
+        normalize_currencies(&mut active);
+        validate_instrument(&active)?;
+
+        let identifier = format!("{}:{}", exchange.code, active.symbol.as_ref());
+        let updated_model = match self.repo.update_with_history(&existing, active, current_actor()).await {
+            Ok(model) => model,
+            Err(err) => return Err(self.write_error(id, err, identifier).await),
+        };
+
+        tracing::info!(instrument_id = updated_model.id, "Patched instrument");
+        Ok(InstrumentResponse::new(updated_model, exchange.code))
+    }
+
+    /// 整体替换：请求体需包含完整字段，未提供的可选字段会被清空 / 重置为默认值
+    pub async fn replace(
+        &self,
+        id: i32,
+        req: CreateInstrumentRequest,
+        if_match: &ETags,
+    ) -> APPResult<InstrumentResponse> {
+        let existing = self.find_matching(id, if_match).await?;
+        let exchange = self.resolve_exchange(&req.exchange).await?;
+        let asset_type = parse_asset_type(&req.asset_type)?;
+        let identifier = format!("{}:{}", exchange.code, req.symbol);
+
+        let mut active: instrument::ActiveModel = req.into();
+        active.id = Unchanged(id);
+        active.exchange_id = Set(exchange.id);
+        active.asset_type = Set(asset_type);
+        normalize_currencies(&mut active);
+        validate_instrument(&active)?;
+        let updated_model = match self.repo.update_with_history(&existing, active, current_actor()).await {
+            Ok(model) => model,
+            Err(err) => return Err(self.write_error(id, err, identifier).await),
+        };
+
+        tracing::info!(instrument_id = updated_model.id, "Replaced instrument");
+        Ok(InstrumentResponse::new(updated_model, exchange.code))
     }
 
-    pub async fn delete(&self, id: i32) -> APPResult<bool> {
-        let deleted = self.repo.delete(id).await
+    /// 软删除：记录保留用于历史追溯，默认查询不再返回
+    pub async fn delete(&self, id: i32, if_match: &ETags) -> APPResult<()> {
+        let existing = self.find_matching(id, if_match).await?;
+        if let Err(err) = self.repo.soft_delete_with_history(&existing, current_actor()).await {
+            return Err(self.write_error(id, err, id.to_string()).await);
+        }
+
+        tracing::info!(instrument_id = id, "Deleted instrument");
+        Ok(())
+    }
+
+    /// 批量导入（CSV / NDJSON），按 (交易所, symbol) upsert
+    ///
+    /// 数据按 [`IMPORT_BATCH`] 行分批解析、写入，不会整体读入内存；所有批次在同一事务中执行，
+    /// 读取中途失败时不会留下部分写入。每行按 `CreateInstrumentRequest` 的规则校验，失败行记入报告，
+    /// 其余行照常写入；文件内重复的 (交易所, symbol) 以首次出现的行为准，后续行报错。
+    pub async fn import(
+        &self,
+        format: DataFormat,
+        body: impl Read + Send + 'static,
+        dry_run: bool,
+    ) -> APPResult<ImportReport> {
+        // 导入事务会占用连接，交易所需在开启事务前读出
+        let exchanges: HashMap<String, exchange::Model> = self.exchange_repo
+            .find_all()
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|exchange| (exchange.code.clone(), exchange))
+            .collect();
+
+        let (tx, mut rx) = mpsc::channel(2);
+        let decoder = tokio::task::spawn_blocking(move || {
+            decode_reader::<CreateInstrumentRequest, _>(format, body, IMPORT_BATCH, |rows| tx.blocking_send(rows).is_ok())
+        });
+
+        let mut report = ImportReport::new(dry_run, 0);
+        let mut seen: HashMap<String, u64> = HashMap::new();
+        let session = self.repo.begin_import(current_actor()).await.map_err(AppError::from)?;
+        while let Some(records) = rx.recv().await {
+            report.total += records.len();
+            check_row_count(report.total)?;
+
+            let mut rows = Vec::with_capacity(records.len());
+            for (row, record) in records {
+                let req = match record {
+                    Ok(req) => req,
+                    Err(message) => {
+                        report.fail(row, None, AppError::BadRequest { message });
+                        continue;
+                    }
+                };
+
+                let code = normalize_code(&req.exchange);
+                let key = format!("{code}:{}", req.symbol);
+                if let Some(&first) = seen.get(&key) {
+                    report.fail(row, Some(key), duplicate_error(first));
+                    continue;
+                }
+                seen.insert(key.clone(), row);
+
+                match build_import_row(req, exchanges.get(&code), &code) {
+                    Ok(active) => rows.push((row, key, active)),
+                    Err(err) => report.fail(row, Some(key), err),
+                }
+            }
+
+            let (meta, actives): (Vec<_>, Vec<_>) = rows
+                .into_iter()
+                .map(|(row, key, active)| ((row, key), active))
+                .unzip();
+            let outcomes = session.apply(actives).await.map_err(AppError::from)?;
+            for ((row, key), outcome) in meta.into_iter().zip(outcomes) {
+                match outcome {
+                    Ok(ImportOutcome::Created) => report.created += 1,
+                    Ok(ImportOutcome::Updated) => report.updated += 1,
+                    Ok(ImportOutcome::Unchanged) => report.unchanged += 1,
+                    Err(err) => report.fail(row, Some(key.clone()), conflict_error(err, key)),
+                }
+            }
+        }
+
+        decoder.await
+            .map_err(|_| AppError::Internal)?
+            .map_err(|err| match err {
+                DecodeError::Header(message) => AppError::BadRequest { message },
+                DecodeError::Read(err) => read_failure(err),
+            })?;
+        session.finish(dry_run).await.map_err(AppError::from)?;
+        report.errors.sort_by_key(|e| e.row);
+
+        tracing::info!(
+            dry_run,
+            created = report.created,
+            updated = report.updated,
+            failed = report.failed,
+            "Imported instruments"
+        );
+        Ok(report)
+    }
+
+    /// 按过滤条件流式导出，按主键分批读取，不会一次性加载全部数据
+    pub async fn export(
+        &self,
+        filter: InstrumentFilter,
+        format: DataFormat,
+    ) -> APPResult<impl Stream<Item = APPResult<Vec<u8>>> + Send + 'static> {
+        let exchanges = self.exchange_repo.codes_by_id().await.map_err(AppError::from)?;
+        let cond = filter_condition(filter);
+        let repo = self.repo.clone();
+
+        // 状态：(下一批的起始主键, 是否为首批)；读完后为 None
+        let stream = futures::stream::unfold(Some((0, true)), move |state| {
+            let repo = repo.clone();
+            let cond = cond.clone();
+            let exchanges = exchanges.clone();
+            async move {
+                let (after_id, first) = state?;
+                let batch = match repo.find_batch_after(cond, after_id, EXPORT_BATCH_SIZE).await {
+                    Ok(batch) => batch,
+                    Err(err) => return Some((Err(AppError::from(err)), None)),
+                };
+                if batch.is_empty() && !first {
+                    return None;
+                }
+
+                let next = match batch.last() {
+                    Some(last) if batch.len() as u64 == EXPORT_BATCH_SIZE => Some((last.id, false)),
+                    _ => None,
+                };
+                let records: Vec<_> = batch
+                    .into_iter()
+                    .map(|model| {
+                        let code = exchanges.get(&model.exchange_id).cloned().unwrap_or_default();
+                        let record = InstrumentRecord::new(model, code);
+                        match format {
+                            DataFormat::Csv => record.flatten_metadata(),
+                            DataFormat::Ndjson => record,
+                        }
+                    })
+                    .collect();
+                let header = first.then_some(InstrumentRecord::COLUMNS);
+                let chunk = encode_records(format, &records, header).map_err(|message| {
+                    tracing::error!(%message, "Failed to encode exported instruments");
+                    AppError::Internal
+                });
+                Some((chunk, next))
+            }
+        });
+        Ok(stream)
+    }
+
+    /// 分页查询字段变更历史，默认按变更时间倒序；已删除的标的同样可查
+    pub async fn history(
+        &self,
+        id: i32,
+        filter: InstrumentHistoryFilter,
+        params: PageParams,
+    ) -> APPResult<PageResponse<InstrumentHistoryResponse>> {
+        self.repo.find_by_id_with_deleted(id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::NotFound {
+                resource: "Instrument".to_string(),
+                identifier: Some(id.to_string()),
+            })?;
+
+        let mut order_by = params.order_by(HISTORY_SORTABLE_COLUMNS)?;
+        if order_by.is_empty() {
+            order_by.push((instrument_history::Column::ChangedAt, Order::Desc));
+        }
+
+        let mut cond = Condition::all().add(instrument_history::Column::InstrumentId.eq(id));
+        if let Some(field) = filter.field {
+            cond = cond.add(instrument_history::Column::Field.eq(field));
+        }
+        if let Some(from) = filter.from {
+            cond = cond.add(instrument_history::Column::ChangedAt.gte(from.fixed_offset()));
+        }
+        if let Some(to) = filter.to {
+            cond = cond.add(instrument_history::Column::ChangedAt.lte(to.fixed_offset()));
+        }
+
+        let page = self.history_repo
+            .paginate(cond, order_by, params.page(), params.per_page())
+            .await
             .map_err(AppError::from)?;
+        Ok(page.into())
+    }
+}
+
+/// 导入时每批解析、写入的行数
+const IMPORT_BATCH: usize = 500;
+/// 导出时每批读取的行数
+const EXPORT_BATCH_SIZE: u64 = 500;
+
+/// 文件内与第 `first` 行重复
+fn duplicate_error(first: u64) -> AppError {
+    let mut errors = FieldErrors::new();
+    errors.add("symbol", "validation-import_duplicate", TranslateArgs::new().add("row", first as i64));
+    errors.into()
+}
+
+/// 按 ID 加载未删除的标的，不存在时返回 `NotFound`；依附于标的的各个服务共用
+pub async fn find_instrument(repo: &InstrumentRepository, id: i32) -> APPResult<instrument::Model> {
+    repo.find_by_id(id).await
+        .map_err(AppError::from)?
+        .ok_or(AppError::NotFound {
+            resource: "Instrument".to_string(),
+            identifier: Some(id.to_string()),
+        })
+}
+
+/// 客户端持有的版本已过期
+fn precondition_failed(current_version: i32) -> AppError {
+    AppError::PreconditionFailed {
+        resource: "Instrument".to_string(),
+        version: current_version.into(),
+    }
+}
+
+/// 将导入的一行转换为待写入的记录，校验规则与创建接口一致
+fn build_import_row(
+    req: CreateInstrumentRequest,
+    exchange: Option<&exchange::Model>,
+    code: &str,
+) -> APPResult<instrument::ActiveModel> {
+    req.validate()?;
+    let exchange = exchange.ok_or_else(|| AppError::NotFound {
+        resource: "Exchange".to_string(),
+        identifier: Some(code.to_string()),
+    })?;
+    let asset_type = parse_asset_type(&req.asset_type)?;
+
+    let mut active: instrument::ActiveModel = req.into();
+    active.exchange_id = Set(exchange.id);
+    active.asset_type = Set(asset_type);
+    normalize_currencies(&mut active);
+    validate_instrument(&active)?;
+    Ok(active)
+}
+
+/// 将唯一索引冲突转换为带 `交易所代码:symbol` 标识的 `Conflict`，其余错误按默认规则转换
+fn conflict_error(err: DbErr, identifier: String) -> AppError {
+    match DbError::from(err) {
+        DbError::UniqueViolation { .. } => AppError::Conflict {
+            resource: "Instrument".to_string(),
+            identifier,
+        },
+        other => other.into(),
+    }
+}
+
+/// 将列表过滤参数转换为查询条件
+fn filter_condition(filter: InstrumentFilter) -> Condition {
+    let mut cond = Condition::all();
+    if let Some(code) = filter.exchange {
+        cond = cond.add(
+            instrument::Column::ExchangeId.in_subquery(
+                Query::select()
+                    .column(exchange::Column::Id)
+                    .from(exchange::Entity)
+                    .and_where(exchange::Column::Code.eq(normalize_code(&code)))
+                    .to_owned(),
+            ),
+        );
+    }
+    if let Some(symbol) = filter.symbol {
+        cond = cond.add(instrument::Column::Symbol.eq(symbol));
+    }
+    if let Some(prefix) = filter.symbol_prefix {
+        // LIKE 在 SQLite 上不区分大小写、在 Postgres 上区分，统一转小写比较
+        let pattern = format!("{}%", escape_like(&prefix.to_lowercase()));
+        cond = cond.add(
+            Expr::expr(Func::lower(Expr::col(instrument::Column::Symbol)))
+                .like(LikeExpr::new(pattern).escape('\\')),
+        );
+    }
+    if let Some(name) = filter.name {
+        let pattern = format!("%{}%", escape_like(&name.to_lowercase()));
+        cond = cond.add(
+            Expr::expr(Func::lower(Expr::col(instrument::Column::Name)))
+                .like(LikeExpr::new(pattern).escape('\\')),
+        );
+    }
+    if let Some(asset_type) = filter.asset_type {
+        cond = cond.add(instrument::Column::AssetType.eq(asset_type));
+    }
+    if let Some(status) = filter.status {
+        cond = cond.add(instrument::Column::Status.eq(status));
+    }
+    cond
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::DbPool;
+
+    async fn service() -> InstrumentService {
+        let db = Arc::new(DbPool::in_memory().await);
+        let exchange_repo = Arc::new(ExchangeRepository::new(db.clone()));
+        exchange_repo
+            .create(exchange::ActiveModel {
+                code: Set("SSE".to_string()),
+                name: Set("Shanghai Stock Exchange".to_string()),
+                timezone: Set("Asia/Shanghai".to_string()),
+                country: Set(None),
+                asset_classes: Set(serde_json::json!([])),
+                trading_hours: Set(serde_json::json!([])),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        InstrumentService::new(
+            Arc::new(InstrumentRepository::new(db.clone())),
+            exchange_repo,
+            Arc::new(InstrumentHistoryRepository::new(db)),
+        )
+    }
 
-        if deleted {
-            tracing::info!(instrument_id = id, "Deleted instrument");
+    fn create_request(symbol: &str, extra: serde_json::Value) -> CreateInstrumentRequest {
+        let mut body = serde_json::json!({
+            "exchange": "sse",
+            "symbol": symbol,
+            "asset_type": "stock",
+            "name": format!("Stock {symbol}"),
+        });
+        body.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(body).unwrap()
+    }
+
+    fn patch_request(body: serde_json::Value) -> UpdateInstrumentRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    fn field_names(result: APPResult<InstrumentResponse>) -> Vec<String> {
+        match result {
+            Err(AppError::Validation { errors: Some(errors) }) => {
+                let mut names: Vec<String> = errors.into_keys().collect();
+                names.sort();
+                names
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_sorts_and_paginates() {
+        let service = service().await;
+        for symbol in ["600000", "600036", "601318", "000001", "btcusdt"] {
+            service.create(create_request(symbol, serde_json::json!({}))).await.unwrap();
         }
-        Ok(deleted)
+        let params = |page: u64, sort: &str| PageParams {
+            page: Some(page),
+            per_page: Some(2),
+            sort: Some(sort.to_string()),
+        };
+        let symbols = |page: &PageResponse<InstrumentResponse>| {
+            page.items.iter().map(|item| item.symbol.clone()).collect::<Vec<_>>()
+        };
+
+        let first = service.list(InstrumentFilter::default(), params(1, "symbol:desc")).await.unwrap();
+        assert_eq!((first.total, first.total_pages), (5, 3));
+        assert_eq!(symbols(&first), vec!["btcusdt", "601318"]);
+        let last = service.list(InstrumentFilter::default(), params(3, "symbol:desc")).await.unwrap();
+        assert_eq!(symbols(&last), vec!["000001"]);
+
+        // 前缀匹配与数据库无关，统一不区分大小写
+        let filter = InstrumentFilter { symbol_prefix: Some("BTC".to_string()), ..Default::default() };
+        let page = service.list(filter, params(1, "")).await.unwrap();
+        assert_eq!(symbols(&page), vec!["btcusdt"]);
+        let filter = InstrumentFilter { symbol_prefix: Some("60".to_string()), ..Default::default() };
+        let page = service.list(filter, params(2, "id:asc")).await.unwrap();
+        assert_eq!((page.total, symbols(&page)), (3, vec!["601318".to_string()]));
+
+        assert!(matches!(
+            service.list(InstrumentFilter::default(), params(1, "exchange_id:up,secret")).await,
+            Err(AppError::Validation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_patch_validates_dates_against_stored_values() {
+        let service = service().await;
+        let created = service
+            .create(create_request("600000", serde_json::json!({ "listing_date": "2020-01-02" })))
+            .await
+            .unwrap();
+        assert_eq!(created.exchange, "SSE");
+
+        let early = patch_request(serde_json::json!({ "delisting_date": "2019-12-31" }));
+        assert_eq!(field_names(service.patch(created.id, early, &ETags::Any).await), vec!["delisting_date"]);
+
+        let delisted = patch_request(serde_json::json!({ "delisting_date": "2025-06-30" }));
+        service.patch(created.id, delisted, &ETags::Any).await.unwrap();
+        let late = patch_request(serde_json::json!({ "listing_date": "2026-01-05" }));
+        assert_eq!(field_names(service.patch(created.id, late, &ETags::Any).await), vec!["delisting_date"]);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_patch_clears_nullable_fields_with_explicit_null() {
+        let service = service().await;
+        let extra = serde_json::json!({ "tick_size": 0.01, "base_currency": "cny", "listing_date": "2020-01-02" });
+        let created = service.create(create_request("600000", extra)).await.unwrap();
+
+        // 缺省的字段保持不变
+        let renamed = service
+            .patch(created.id, patch_request(serde_json::json!({ "name": "浦发银行" })), &ETags::Any)
+            .await
+            .unwrap();
+        assert_eq!(renamed.tick_size, Some(0.01));
+        assert_eq!(renamed.listing_date, created.listing_date);
+
+        let cleared = service
+            .patch(created.id, patch_request(serde_json::json!({ "tick_size": null, "listing_date": null })), &ETags::Any)
+            .await
+            .unwrap();
+        assert_eq!((cleared.tick_size, cleared.listing_date), (None, None));
+        assert_eq!(cleared.base_currency.as_deref(), Some("CNY"));
+
+        let empty = patch_request(serde_json::json!({ "base_currency": "" }));
+        assert!(empty.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_streamed_import_reports_duplicates_and_keeps_creator() {
+        use crate::core::context::{RequestContext, CONTEXT};
+        use std::io::Cursor;
+
+        let service = service().await;
+        let as_actor = |actor: &str| Arc::new(RequestContext::new("test".to_string(), "en".to_string(), Some(actor.to_string())));
+        let ndjson = |rows: &[(&str, &str)]| {
+            let lines: Vec<String> = rows
+                .iter()
+                .map(|(symbol, name)| {
+                    serde_json::json!({"exchange": "sse", "symbol": symbol, "asset_type": "stock", "name": name}).to_string()
+                })
+                .collect();
+            Cursor::new(lines.join("\n").into_bytes())
+        };
+
+        // 超过一批的数据跨批次去重
+        let mut rows: Vec<(String, String)> = (0..IMPORT_BATCH + 1).map(|i| (format!("S{i:04}"), "Stock".to_string())).collect();
+        rows.push(("S0000".to_string(), "Again".to_string()));
+        let rows: Vec<(&str, &str)> = rows.iter().map(|(s, n)| (s.as_str(), n.as_str())).collect();
+        let report = CONTEXT
+            .scope(as_actor("alice"), service.import(DataFormat::Ndjson, ndjson(&rows), false))
+            .await
+            .unwrap();
+        assert_eq!((report.total, report.created, report.failed), (IMPORT_BATCH + 2, IMPORT_BATCH + 1, 1));
+        let error = &report.errors[0];
+        assert_eq!((error.row, error.key.as_deref()), (IMPORT_BATCH as u64 + 2, Some("SSE:S0000")));
+        assert!(error.errors.as_ref().unwrap().contains_key("symbol"));
+
+        // 内容不变的行不受导入者影响；更新不改写 created_by
+        let rows = [("S0000", "Stock"), ("S0001", "Renamed")];
+        let report = CONTEXT
+            .scope(as_actor("bob"), service.import(DataFormat::Ndjson, ndjson(&rows), false))
+            .await
+            .unwrap();
+        assert_eq!((report.unchanged, report.updated), (1, 1));
+        let filter = InstrumentFilter { symbol_prefix: Some("S0001".to_string()), ..Default::default() };
+        let page = service.list(filter, PageParams::default()).await.unwrap();
+        assert_eq!((page.items[0].name.as_str(), page.items[0].created_by.as_deref()), ("Renamed", Some("alice")));
+
+        // dry_run 报告与实际执行一致，但不落库
+        let report = service.import(DataFormat::Ndjson, ndjson(&[("NEW1", "New")]), true).await.unwrap();
+        assert_eq!((report.dry_run, report.created), (true, 1));
+        let filter = InstrumentFilter { symbol_prefix: Some("NEW".to_string()), ..Default::default() };
+        assert_eq!(service.list(filter, PageParams::default()).await.unwrap().total, 0);
+    }
+}
@@ -0,0 +1,256 @@
+//! 交易标的的业务校验规则
+//!
+//! 所有规则都基于 `ActiveModel` 上已赋值（Set / Unchanged）的字段，未赋值（NotSet）的字段直接跳过，
+//! 因此同一套规则同时适用于创建和部分更新。部分更新时须传入与库中记录合并后的模型，
+//! 跨字段规则（如退市日期不早于上市日期）才能与未修改的一侧比较。
+
+use chrono::NaiveDate;
+use sea_orm::{ActiveEnum, Iterable};
+use sea_orm::ActiveValue::Set;
+use entities::instrument;
+use entities::sea_orm_active_enums::AssetType;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use super::APPResult;
+
+/// 将请求中的资产类型字符串解析为 `AssetType`（大小写及首尾空白不敏感）
+pub fn parse_asset_type(value: &str) -> APPResult<AssetType> {
+    let normalized = value.trim().to_lowercase();
+    AssetType::iter()
+        .find(|t| t.to_value() == normalized)
+        .ok_or_else(|| {
+            let mut errors = FieldErrors::new();
+            errors.add("asset_type", "validation-asset_type", asset_type_args(value));
+            errors.into()
+        })
+}
+
+fn asset_type_args(value: &str) -> TranslateArgs {
+    let expected = AssetType::iter()
+        .map(|t| t.to_value())
+        .collect::<Vec<_>>()
+        .join(", ");
+    TranslateArgs::new()
+        .add("value", value)
+        .add("expected", expected)
+}
+
+/// 币种代码统一为去空白的大写形式
+pub fn normalize_currencies(active: &mut instrument::ActiveModel) {
+    for currency in [&mut active.base_currency, &mut active.quote_currency] {
+        if let Some(Some(code)) = currency.try_as_ref() {
+            *currency = Set(Some(code.trim().to_uppercase()));
+        }
+    }
+}
+
+/// 校验一个标的的全部业务规则：通用合约规格 + 资产类型特有规则
+pub fn validate_instrument(active: &instrument::ActiveModel) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    validate_contract_spec(active, &mut errors);
+    if let Some(asset_type) = active.asset_type.try_as_ref() {
+        validate_asset_specific(*asset_type, active, &mut errors);
+    }
+    errors.into_result()
+}
+
+/// 校验合约规格与生命周期字段
+fn validate_contract_spec(active: &instrument::ActiveModel, errors: &mut FieldErrors) {
+    for (field, currency) in [
+        ("base_currency", &active.base_currency),
+        ("quote_currency", &active.quote_currency),
+    ] {
+        if let Some(Some(code)) = currency.try_as_ref()
+            && (code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()))
+        {
+            errors.add(field, "validation-currency_code", TranslateArgs::new());
+        }
+    }
+
+    for (field, value) in [
+        ("tick_size", &active.tick_size),
+        ("lot_size", &active.lot_size),
+        ("contract_multiplier", &active.contract_multiplier),
+    ] {
+        if let Some(Some(v)) = value.try_as_ref()
+            && (!v.is_finite() || *v <= 0.0)
+        {
+            errors.add(field, "validation-positive", TranslateArgs::new());
+        }
+    }
+
+    if let Some(Some(v)) = active.min_notional.try_as_ref()
+        && (!v.is_finite() || *v < 0.0)
+    {
+        errors.add("min_notional", "validation-non_negative", TranslateArgs::new());
+    }
+
+    if let (Some(Some(listing)), Some(Some(delisting))) =
+        (active.listing_date.try_as_ref(), active.delisting_date.try_as_ref())
+        && delisting < listing
+    {
+        errors.add(
+            "delisting_date",
+            "validation-date_order",
+            TranslateArgs::new().add("other", "listing_date"),
+        );
+    }
+
+    if let Some(metadata) = active.metadata.try_as_ref()
+        && !metadata.is_object()
+    {
+        errors.add("metadata", "validation-json_object", TranslateArgs::new());
+    }
+}
+
+/// 资产类型特有规则
+///
+/// 新增资产类型或规则时在此扩展对应分支即可；衍生品的条款（行权价、到期日等）存放在 `metadata` 中。
+fn validate_asset_specific(
+    asset_type: AssetType,
+    active: &instrument::ActiveModel,
+    errors: &mut FieldErrors,
+) {
+    match asset_type {
+        AssetType::CryptoSpot | AssetType::CryptoPerp | AssetType::Forex => {
+            require_field(asset_type, "base_currency", &active.base_currency, errors);
+            require_field(asset_type, "quote_currency", &active.quote_currency, errors);
+        }
+        AssetType::Future => {
+            if let Some(metadata) = active.metadata.try_as_ref() {
+                require_date(asset_type, metadata, "expiry", errors);
+            }
+        }
+        AssetType::Option => {
+            if let Some(metadata) = active.metadata.try_as_ref() {
+                require_date(asset_type, metadata, "expiry", errors);
+
+                match metadata.get("strike") {
+                    None | Some(serde_json::Value::Null) => {
+                        require_missing(asset_type, "metadata.strike", errors)
+                    }
+                    Some(strike) if strike.as_f64().is_none_or(|v| v <= 0.0) => {
+                        errors.add("metadata.strike", "validation-positive", TranslateArgs::new())
+                    }
+                    _ => {}
+                }
+
+                match metadata.get("right") {
+                    None | Some(serde_json::Value::Null) => {
+                        require_missing(asset_type, "metadata.right", errors)
+                    }
+                    Some(right) if matches!(right.as_str(), Some("call" | "put")) => {}
+                    Some(_) => errors.add("metadata.right", "validation-option_right", TranslateArgs::new()),
+                }
+            }
+        }
+        AssetType::Stock | AssetType::Etf | AssetType::Index | AssetType::Bond => {}
+    }
+}
+
+/// 要求可空列已赋非空值；未赋值的字段（部分更新时）跳过
+fn require_field(
+    asset_type: AssetType,
+    field: &str,
+    value: &sea_orm::ActiveValue<Option<String>>,
+    errors: &mut FieldErrors,
+) {
+    if let Some(None) = value.try_as_ref() {
+        require_missing(asset_type, field, errors);
+    }
+}
+
+/// 要求 metadata 中存在 YYYY-MM-DD 格式的日期
+fn require_date(
+    asset_type: AssetType,
+    metadata: &serde_json::Value,
+    key: &str,
+    errors: &mut FieldErrors,
+) {
+    let field = format!("metadata.{key}");
+    match metadata.get(key) {
+        None | Some(serde_json::Value::Null) => require_missing(asset_type, &field, errors),
+        Some(v) => {
+            let valid = v
+                .as_str()
+                .is_some_and(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok());
+            if !valid {
+                errors.add(field, "validation-date", TranslateArgs::new());
+            }
+        }
+    }
+}
+
+fn require_missing(asset_type: AssetType, field: &str, errors: &mut FieldErrors) {
+    errors.add(
+        field,
+        "validation-required_for_asset_type",
+        TranslateArgs::new().add("asset_type", asset_type.to_value()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::code::AppError;
+    use sea_orm::ActiveValue::NotSet;
+
+    fn field_names(result: APPResult<()>) -> Vec<String> {
+        match result {
+            Err(AppError::Validation { errors: Some(errors) }) => {
+                let mut names: Vec<String> = errors.into_keys().collect();
+                names.sort();
+                names
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_asset_type() {
+        assert_eq!(parse_asset_type(" Crypto_Spot ").unwrap(), AssetType::CryptoSpot);
+        assert_eq!(field_names(parse_asset_type("stcok").map(|_| ())), vec!["asset_type"]);
+    }
+
+    #[test]
+    fn test_option_requires_strike_expiry_and_right() {
+        let active = instrument::ActiveModel {
+            asset_type: Set(AssetType::Option),
+            metadata: Set(serde_json::json!({ "strike": -1, "right": "straddle" })),
+            ..Default::default()
+        };
+        assert_eq!(
+            field_names(validate_instrument(&active)),
+            vec!["metadata.expiry", "metadata.right", "metadata.strike"]
+        );
+
+        let active = instrument::ActiveModel {
+            asset_type: Set(AssetType::Option),
+            metadata: Set(serde_json::json!({ "strike": 100.0, "expiry": "2026-12-18", "right": "call" })),
+            ..Default::default()
+        };
+        assert!(validate_instrument(&active).is_ok());
+    }
+
+    #[test]
+    fn test_delisting_not_before_listing() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let active = instrument::ActiveModel {
+            listing_date: sea_orm::ActiveValue::Unchanged(Some(date("2020-01-02"))),
+            delisting_date: Set(Some(date("2019-12-31"))),
+            ..Default::default()
+        };
+        assert_eq!(field_names(validate_instrument(&active)), vec!["delisting_date"]);
+    }
+
+    #[test]
+    fn test_partial_update_skips_unset_fields() {
+        let active = instrument::ActiveModel {
+            asset_type: Set(AssetType::CryptoSpot),
+            base_currency: NotSet,
+            quote_currency: Set(None),
+            ..Default::default()
+        };
+        assert_eq!(field_names(validate_instrument(&active)), vec!["quote_currency"]);
+    }
+}
@@ -0,0 +1,447 @@
+use std::sync::Arc;
+use chrono::{Days, Months, Utc};
+use sea_orm::{ActiveEnum, Condition, DbErr, Order};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use tokio::sync::OnceCell;
+use crate::db::repositories::Repository;
+use crate::db::repositories::corporate_action::CorporateActionRepository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::dto::kline::{KlineAdjust, KlineQuery};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::adjustment::{AdjustMode, AdjustmentCache, AdjustmentFactors};
+use crate::service::calendar::CalendarFiles;
+use crate::service::instrument::find_instrument;
+use crate::service::kline_resample::{interval_minutes, Resampler};
+use crate::service::trading_calendar::TradingCalendar;
+use entities::{exchange, instrument, kline};
+use entities::sea_orm_active_enums::KlineInterval;
+use super::APPResult;
+
+/// 重采样的源周期
+const SOURCE_INTERVAL: KlineInterval = KlineInterval::Minute1;
+/// 应用内重采样时每批读取的源 K 线数
+const SOURCE_BATCH: u64 = 10_000;
+/// 单次请求最多读取的源 K 线数，避免大周期、长区间的请求拖垮服务
+const MAX_SOURCE_BARS: usize = 2_000_000;
+
+pub struct KlineService {
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    corporate_action_repo: Arc<CorporateActionRepository>,
+    calendars: Arc<CalendarFiles>,
+    adjustments: Arc<AdjustmentCache>,
+    /// 首次重采样时检测是否可用 TimescaleDB
+    timescale: OnceCell<bool>,
+}
+
+impl KlineService {
+    pub fn new(
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        corporate_action_repo: Arc<CorporateActionRepository>,
+        calendars: Arc<CalendarFiles>,
+        adjustments: Arc<AdjustmentCache>,
+    ) -> Self {
+        Self {
+            kline_repo,
+            instrument_repo,
+            exchange_repo,
+            corporate_action_repo,
+            calendars,
+            adjustments,
+            timescale: OnceCell::new(),
+        }
+    }
+
+    /// 查询标的的 K 线，按时间升序返回
+    ///
+    /// 优先使用库中所请求周期的数据，库中没有或只覆盖部分区间时由 1m 重采样补齐：TimescaleDB 可用且
+    /// 桶与交易时段对齐时使用 `time_bucket`，其余情况在应用内按交易时段聚合（见 `kline_resample`）。
+    /// 指定 `start` 时返回其后的前 `limit` 根，否则返回 `end` 之前最近的 `limit` 根。
+    /// `adjust` 不为 `none` 时按公司行动计算的复权因子调整价格（见 `adjustment`）。
+    pub async fn query(&self, instrument_id: i32, query: &KlineQuery) -> APPResult<Vec<kline::Model>> {
+        if let (Some(start), Some(end)) = (query.start, query.end)
+            && end <= start
+        {
+            let mut errors = FieldErrors::new();
+            errors.add("end", "validation-date_order", TranslateArgs::new().add("other", "start"));
+            errors.into_result()?;
+        }
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+
+        let mut bars = self.query_unadjusted(&instrument, query).await?;
+        let mode = match query.adjust {
+            KlineAdjust::None => return Ok(bars),
+            KlineAdjust::Forward => AdjustMode::Forward,
+            KlineAdjust::Backward => AdjustMode::Backward,
+        };
+        let actions = self.corporate_action_repo
+            .find_timeline(instrument_id, Condition::all())
+            .await
+            .map_err(AppError::from)?;
+        if !actions.is_empty() {
+            let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+                .map_err(AppError::from)?
+                .ok_or(AppError::Internal)?;
+            let calendar = self.calendars.calendar_for(&exchange)?;
+            let factors = self.adjustments
+                .get(&actions, &self.kline_repo, &calendar, instrument_id)
+                .await
+                .map_err(AppError::from)?;
+            factors.apply(&mut bars, mode);
+            if matches!(query.interval(), KlineInterval::Week1 | KlineInterval::Month1) {
+                self.readjust_straddling(&instrument, &calendar, &factors, mode, &mut bars).await?;
+            }
+        }
+        Ok(bars)
+    }
+
+    /// 跨越除权日的周线、月线按开始时刻的因子整体调整会失真，改由复权后的日线重新聚合
+    async fn readjust_straddling(
+        &self,
+        instrument: &instrument::Model,
+        calendar: &TradingCalendar,
+        factors: &AdjustmentFactors,
+        mode: AdjustMode,
+        bars: &mut [kline::Model],
+    ) -> APPResult<()> {
+        for bar in bars {
+            let start = bar.ts.to_utc();
+            let date = calendar.local_date(start);
+            let next = match bar.interval {
+                KlineInterval::Week1 => date.checked_add_days(Days::new(7)),
+                _ => date.checked_add_months(Months::new(1)),
+            };
+            let Some(end) = next.map(|next| calendar.start_of_day(next)) else { continue };
+            if !factors.has_ex_date_within(start, end) {
+                continue;
+            }
+
+            let daily = KlineQuery {
+                interval: Some(KlineInterval::Day1),
+                start: Some(start.fixed_offset()),
+                end: Some(end.fixed_offset()),
+                limit: Some(31),
+                format: Default::default(),
+                adjust: KlineAdjust::None,
+            };
+            let mut days = self.query_unadjusted(instrument, &daily).await?;
+            factors.apply(&mut days, mode);
+            let mut resampler = Resampler::new(bar.interval, calendar);
+            // 日线都落在同一个桶内，只需取最后输出的桶
+            for day in &days {
+                resampler.push(day);
+            }
+            if let Some(rebuilt) = resampler.finish() {
+                *bar = kline::Model { ts: bar.ts, ..rebuilt };
+            }
+        }
+        Ok(())
+    }
+
+    async fn query_unadjusted(&self, instrument: &instrument::Model, query: &KlineQuery) -> APPResult<Vec<kline::Model>> {
+        let instrument_id = instrument.id;
+        let interval = query.interval();
+        let (start, end) = (query.start, Some(query.end.unwrap_or_else(|| Utc::now().fixed_offset())));
+        let order = if start.is_some() { Order::Asc } else { Order::Desc };
+        let limit = query.limit();
+
+        let stored = self.kline_repo
+            .find_range(instrument_id, interval, start, end, order.clone(), limit)
+            .await
+            .map_err(AppError::from)?;
+        if interval == SOURCE_INTERVAL {
+            return Ok(sorted(stored, &order));
+        }
+
+        let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::Internal)?;
+        let calendar = self.calendars.calendar_for(&exchange)?;
+        let source = Source { instrument_id, interval, exchange: &exchange, calendar: &calendar };
+        let stored = sorted(stored, &order);
+        let (Some(first), Some(last)) = (stored.first().map(|bar| bar.ts), stored.last().map(|bar| bar.ts)) else {
+            return self.aggregate(&source, (start, end), order, limit).await;
+        };
+
+        // 库中的 K 线可能只覆盖请求区间的一部分（如日线只导入到上月），首尾缺口由 1m 重采样补齐；
+        // 已存储的首尾之间视为连续，不再检查中间缺口
+        let full = stored.len() as u64 == limit;
+        let head = if order == Order::Asc || !full {
+            self.aggregate(&source, (start, Some(first)), order.clone(), limit).await?
+        } else {
+            Vec::new()
+        };
+        let tail = if order == Order::Desc || !full {
+            // 与最后一根同桶的 1m 已计入库中的 K 线
+            let mut tail = self.aggregate(&source, (Some(last), end), order.clone(), limit).await?;
+            tail.retain(|bar| bar.ts > last);
+            tail
+        } else {
+            Vec::new()
+        };
+
+        let mut bars = [head, stored, tail].concat();
+        let limit = limit as usize;
+        if order == Order::Desc {
+            bars.drain(..bars.len().saturating_sub(limit));
+        } else {
+            bars.truncate(limit);
+        }
+        Ok(bars)
+    }
+
+    /// 由 1m 聚合 `[from, to)` 内的 K 线：TimescaleDB 可用且桶与交易时段对齐时使用 `time_bucket`，
+    /// 否则在应用内重采样；结果按时间升序
+    async fn aggregate(
+        &self,
+        source: &Source<'_>,
+        (from, to): (Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>),
+        order: Order,
+        limit: u64,
+    ) -> APPResult<Vec<kline::Model>> {
+        let interval = source.interval;
+        let aligned = interval_minutes(interval).is_none_or(|minutes| source.calendar.aligned_to(minutes));
+        if aligned && self.timescale_available().await? {
+            return self.kline_repo
+                .time_bucket(
+                    source.instrument_id,
+                    SOURCE_INTERVAL,
+                    interval,
+                    &bucket_width(interval),
+                    &source.exchange.timezone,
+                    from,
+                    to,
+                    order,
+                    limit,
+                )
+                .await
+                .map_err(AppError::from);
+        }
+        self.resample(source.instrument_id, interval, source.calendar, (from, to), order, limit).await
+    }
+
+    async fn timescale_available(&self) -> APPResult<bool> {
+        self.timescale
+            .get_or_try_init(|| async {
+                let available = self.kline_repo.timescale_available().await?;
+                tracing::info!(available, "Kline resampling checked TimescaleDB");
+                Ok::<_, DbErr>(available)
+            })
+            .await
+            .copied()
+            .map_err(AppError::from)
+    }
+
+    /// 应用内重采样：按 `order` 分批读取 1m K 线，凑满 `limit` 个桶即停止
+    async fn resample(
+        &self,
+        instrument_id: i32,
+        interval: KlineInterval,
+        calendar: &TradingCalendar,
+        (mut from, mut to): (Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>),
+        order: Order,
+        limit: u64,
+    ) -> APPResult<Vec<kline::Model>> {
+        let limit = limit as usize;
+        let mut resampler = Resampler::new(interval, calendar);
+        let mut bars = Vec::new();
+        let mut scanned = 0;
+        loop {
+            let batch = self.kline_repo
+                .find_range(instrument_id, SOURCE_INTERVAL, from, to, order.clone(), SOURCE_BATCH)
+                .await
+                .map_err(AppError::from)?;
+            // 升序翻页时下一批从上一批最后一根（含）开始，跳过已处理的那一根
+            let cursor = from.filter(|_| order == Order::Asc && scanned > 0);
+            for bar in batch.iter().filter(|bar| cursor.is_none_or(|cursor| bar.ts > cursor)) {
+                if let Some(done) = resampler.push(bar) {
+                    bars.push(done);
+                    if bars.len() == limit {
+                        return Ok(sorted(bars, &order));
+                    }
+                }
+            }
+
+            scanned += batch.len();
+            let Some(last) = batch.last().filter(|_| batch.len() as u64 == SOURCE_BATCH) else {
+                bars.extend(resampler.finish());
+                return Ok(sorted(bars, &order));
+            };
+            if scanned >= MAX_SOURCE_BARS {
+                let mut errors = FieldErrors::new();
+                let args = TranslateArgs::new().add("interval", interval.to_value()).add("max", MAX_SOURCE_BARS as i64);
+                errors.add("limit", "validation-resample_scan", args);
+                return Err(errors.into());
+            }
+            match order {
+                Order::Desc => to = Some(last.ts),
+                _ => from = Some(last.ts),
+            }
+        }
+    }
+}
+
+/// 重采样所需的标的与交易所信息
+struct Source<'a> {
+    instrument_id: i32,
+    interval: KlineInterval,
+    exchange: &'a exchange::Model,
+    calendar: &'a TradingCalendar,
+}
+
+/// 降序聚合的结果翻转为升序
+fn sorted(mut bars: Vec<kline::Model>, order: &Order) -> Vec<kline::Model> {
+    if *order == Order::Desc {
+        bars.reverse();
+    }
+    bars
+}
+
+/// 周期对应的 Postgres interval 写法
+fn bucket_width(interval: KlineInterval) -> String {
+    match interval {
+        KlineInterval::Day1 => "1 day".to_string(),
+        KlineInterval::Week1 => "1 week".to_string(),
+        KlineInterval::Month1 => "1 month".to_string(),
+        _ => format!("{} minutes", interval_minutes(interval).unwrap_or(1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ActiveModelTrait, Set};
+    use crate::db::connection::DbPool;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+
+    async fn service() -> KlineService {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(serde_json::json!([])),
+            trading_hours: Set(serde_json::json!([{"open": "09:30", "close": "15:00"}])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        instrument::ActiveModel {
+            id: Set(1),
+            exchange_id: Set(1),
+            symbol: Set("600000".to_string()),
+            asset_type: Set(AssetType::Stock),
+            name: Set("Stock 600000".to_string()),
+            status: Set(InstrumentStatus::Active),
+            metadata: Set(serde_json::json!({})),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        KlineService::new(
+            Arc::new(KlineRepository::new(db.clone())),
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db.clone())),
+            Arc::new(CorporateActionRepository::new(db)),
+            Arc::new(CalendarFiles::default()),
+            Arc::new(AdjustmentCache::default()),
+        )
+    }
+
+    fn bar(interval: KlineInterval, ts: &str, close: f64) -> kline::Model {
+        kline::Model {
+            instrument_id: 1,
+            interval,
+            ts: ts.parse().unwrap(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+            quote_volume: None,
+            trade_count: None,
+        }
+    }
+
+    fn query(start: Option<&str>, end: &str, limit: u64) -> KlineQuery {
+        KlineQuery {
+            interval: Some(KlineInterval::Day1),
+            start: start.map(|start| start.parse().unwrap()),
+            end: Some(end.parse().unwrap()),
+            limit: Some(limit),
+            format: Default::default(),
+            adjust: KlineAdjust::None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_bars_are_extended_by_resampling() {
+        let service = service().await;
+        // 日线只存到 10-13，1m 从 10-09 一直到 10-15
+        service.kline_repo
+            .upsert_batch(vec![
+                bar(KlineInterval::Day1, "2026-10-12T00:00:00+08:00", 12.0),
+                bar(KlineInterval::Day1, "2026-10-13T00:00:00+08:00", 13.0),
+                bar(SOURCE_INTERVAL, "2026-10-09T10:00:00+08:00", 9.0),
+                bar(SOURCE_INTERVAL, "2026-10-13T10:00:00+08:00", 99.0),
+                bar(SOURCE_INTERVAL, "2026-10-14T10:00:00+08:00", 14.0),
+                bar(SOURCE_INTERVAL, "2026-10-15T10:00:00+08:00", 15.5),
+                bar(SOURCE_INTERVAL, "2026-10-15T14:00:00+08:00", 15.0),
+            ])
+            .await
+            .unwrap();
+        let closes = |bars: Vec<kline::Model>| bars.into_iter().map(|bar| bar.close).collect::<Vec<_>>();
+
+        // 最近的 K 线：库中日线之后的交易日由 1m 补齐，与库中最后一根同日的 1m 不重复计入
+        let bars = service.query(1, &query(None, "2026-10-16T00:00:00+08:00", 3)).await.unwrap();
+        assert_eq!(closes(bars), vec![13.0, 14.0, 15.0]);
+        let bars = service.query(1, &query(None, "2026-10-16T00:00:00+08:00", 10)).await.unwrap();
+        assert_eq!(closes(bars), vec![9.0, 12.0, 13.0, 14.0, 15.0]);
+
+        // 指定 start 时库中日线之前的缺口同样补齐
+        let bars = service.query(1, &query(Some("2026-10-09T00:00:00+08:00"), "2026-10-16T00:00:00+08:00", 2)).await.unwrap();
+        assert_eq!(closes(bars), vec![9.0, 12.0]);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_bar_straddling_ex_date_is_rebuilt_from_daily() {
+        let service = service().await;
+        // 10-15（周四）1 拆 2：周一至周三收 20，除权后收 10
+        service.corporate_action_repo
+            .create(entities::corporate_action::ActiveModel {
+                instrument_id: Set(1),
+                kind: Set(entities::sea_orm_active_enums::CorporateActionKind::Split),
+                ex_date: Set(chrono::NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()),
+                ratio: Set(Some(2.0)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let mut bars: Vec<kline::Model> = (12..=16)
+            .map(|day| bar(KlineInterval::Day1, &format!("2026-10-{day}T00:00:00+08:00"), if day < 15 { 20.0 } else { 10.0 }))
+            .collect();
+        bars.push(kline::Model { open: 20.0, high: 20.0, low: 10.0, close: 10.0, ..bar(KlineInterval::Week1, "2026-10-12T00:00:00+08:00", 10.0) });
+        service.kline_repo.upsert_batch(bars).await.unwrap();
+
+        let query = KlineQuery {
+            interval: Some(KlineInterval::Week1),
+            adjust: KlineAdjust::Forward,
+            ..query(Some("2026-10-12T00:00:00+08:00"), "2026-10-19T00:00:00+08:00", 1)
+        };
+        let bars = service.query(1, &query).await.unwrap();
+        let week = &bars[0];
+        assert_eq!((week.open, week.high, week.low, week.close), (10.0, 10.0, 10.0, 10.0));
+        assert_eq!(week.ts, "2026-10-12T00:00:00+08:00".parse::<DateTimeWithTimeZone>().unwrap());
+        assert_eq!(week.volume, 5.0);
+    }
+}
@@ -0,0 +1,193 @@
+//! K 线重采样：将细粒度 K 线（通常为 1m）聚合为更大周期
+//!
+//! 日内周期按交易时段对齐：桶从时段开盘起每 N 分钟一格，且不跨越时段（如 SSE 的 1h 为
+//! 09:30、10:30、13:00、14:00）；不在任何时段内的 K 线按交易所本地 0 点起的网格对齐。
+//! 日线及以上按交易所本地日期归并，周线从周一开始，月线从 1 日开始，`ts` 为本地 0 点。
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use entities::kline;
+use entities::sea_orm_active_enums::KlineInterval;
+use crate::service::trading_calendar::{Session, TradingCalendar};
+
+/// 日内周期的分钟数，日线及以上返回 `None`
+pub fn interval_minutes(interval: KlineInterval) -> Option<u32> {
+    match interval {
+        KlineInterval::Minute1 => Some(1),
+        KlineInterval::Minute3 => Some(3),
+        KlineInterval::Minute5 => Some(5),
+        KlineInterval::Minute15 => Some(15),
+        KlineInterval::Minute30 => Some(30),
+        KlineInterval::Hour1 => Some(60),
+        KlineInterval::Hour2 => Some(120),
+        KlineInterval::Hour4 => Some(240),
+        KlineInterval::Hour6 => Some(360),
+        KlineInterval::Hour12 => Some(720),
+        KlineInterval::Day1 | KlineInterval::Week1 | KlineInterval::Month1 => None,
+    }
+}
+
+/// 计算 K 线所属桶的开始时间，缓存最近一次命中的交易时段
+pub struct Bucketer<'a> {
+    interval: KlineInterval,
+    calendar: &'a TradingCalendar,
+    session: Option<Session>,
+}
+
+impl<'a> Bucketer<'a> {
+    pub fn new(interval: KlineInterval, calendar: &'a TradingCalendar) -> Self {
+        Self { interval, calendar, session: None }
+    }
+
+    pub fn bucket(&mut self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        let Some(minutes) = interval_minutes(self.interval) else {
+            let date = self.calendar.local_date(ts);
+            let date = match self.interval {
+                KlineInterval::Week1 => date - Duration::days(date.weekday().num_days_from_monday().into()),
+                KlineInterval::Month1 => date.with_day(1).unwrap_or(date),
+                _ => date,
+            };
+            return self.calendar.start_of_day(date);
+        };
+
+        if self.session.is_none_or(|session| ts < session.open || ts >= session.close) {
+            self.session = self.calendar.session_at(ts);
+        }
+        let origin = match self.session {
+            Some(session) => session.open,
+            None => self.calendar.start_of_day(self.calendar.local_date(ts)),
+        };
+        let minutes = i64::from(minutes);
+        origin + Duration::minutes((ts - origin).num_minutes().div_euclid(minutes) * minutes)
+    }
+}
+
+/// 正在聚合的桶；开盘 / 收盘取时间最早 / 最晚的 K 线，因此输入可以是升序或降序
+struct Bucket {
+    bar: kline::Model,
+    first: DateTime<Utc>,
+    last: DateTime<Utc>,
+}
+
+impl Bucket {
+    fn new(ts: DateTime<Utc>, interval: KlineInterval, bar: &kline::Model) -> Self {
+        let source = bar.ts.to_utc();
+        Self {
+            bar: kline::Model { interval, ts: ts.fixed_offset(), ..bar.clone() },
+            first: source,
+            last: source,
+        }
+    }
+
+    fn merge(&mut self, bar: &kline::Model) {
+        let ts = bar.ts.to_utc();
+        if ts < self.first {
+            self.first = ts;
+            self.bar.open = bar.open;
+        }
+        if ts > self.last {
+            self.last = ts;
+            self.bar.close = bar.close;
+        }
+        self.bar.high = self.bar.high.max(bar.high);
+        self.bar.low = self.bar.low.min(bar.low);
+        self.bar.volume += bar.volume;
+        self.bar.quote_volume = sum(self.bar.quote_volume, bar.quote_volume);
+        self.bar.trade_count = sum(self.bar.trade_count, bar.trade_count);
+    }
+}
+
+/// 可选值求和：任一方有值即有值
+fn sum<T: std::ops::Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 流式重采样器：按时间顺序（升序或降序均可）逐条输入，桶切换时输出上一个完整的桶
+pub struct Resampler<'a> {
+    interval: KlineInterval,
+    bucketer: Bucketer<'a>,
+    current: Option<Bucket>,
+}
+
+impl<'a> Resampler<'a> {
+    pub fn new(interval: KlineInterval, calendar: &'a TradingCalendar) -> Self {
+        Self { interval, bucketer: Bucketer::new(interval, calendar), current: None }
+    }
+
+    pub fn push(&mut self, bar: &kline::Model) -> Option<kline::Model> {
+        let ts = self.bucketer.bucket(bar.ts.to_utc());
+        match &mut self.current {
+            Some(bucket) if bucket.bar.ts == ts => {
+                bucket.merge(bar);
+                None
+            }
+            current => current.replace(Bucket::new(ts, self.interval, bar)).map(|bucket| bucket.bar),
+        }
+    }
+
+    /// 输出最后一个桶（可能尚不完整）
+    pub fn finish(self) -> Option<kline::Model> {
+        self.current.map(|bucket| bucket.bar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::exchange::TradingSession;
+
+    fn bar(ts: &str, open: f64, close: f64) -> kline::Model {
+        kline::Model {
+            instrument_id: 1,
+            interval: KlineInterval::Minute1,
+            ts: ts.parse().unwrap(),
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+            volume: 1.0,
+            quote_volume: None,
+            trade_count: Some(2),
+        }
+    }
+
+    fn resample(interval: KlineInterval, calendar: &TradingCalendar, bars: &[kline::Model]) -> Vec<kline::Model> {
+        let mut resampler = Resampler::new(interval, calendar);
+        let mut out: Vec<_> = bars.iter().filter_map(|bar| resampler.push(bar)).collect();
+        out.extend(resampler.finish());
+        out
+    }
+
+    #[test]
+    fn test_session_aligned_buckets() {
+        let session = |open: &str, close: &str| TradingSession { open: open.into(), close: close.into() };
+        let sse = TradingCalendar::new("Asia/Shanghai", &[session("09:30", "11:30"), session("13:00", "15:00")], None)
+            .unwrap();
+        // 2026-10-19（周一）10:29、10:30、11:29、13:00（北京时间）
+        let bars = [
+            bar("2026-10-19T02:29:00Z", 1.0, 2.0),
+            bar("2026-10-19T02:30:00Z", 2.0, 3.0),
+            bar("2026-10-19T03:29:00Z", 3.0, 4.0),
+            bar("2026-10-19T05:00:00Z", 4.0, 5.0),
+        ];
+        let hours = resample(KlineInterval::Hour1, &sse, &bars);
+        let starts: Vec<String> = hours.iter().map(|bar| bar.ts.to_rfc3339()).collect();
+        assert_eq!(starts, ["2026-10-19T01:30:00+00:00", "2026-10-19T02:30:00+00:00", "2026-10-19T05:00:00+00:00"]);
+        assert_eq!((hours[1].open, hours[1].close, hours[1].volume, hours[1].trade_count), (2.0, 4.0, 2.0, Some(4)));
+
+        // 降序输入得到相同的桶
+        let mut reversed = bars.to_vec();
+        reversed.reverse();
+        let mut descending = resample(KlineInterval::Hour1, &sse, &reversed);
+        descending.reverse();
+        assert_eq!(descending, hours);
+
+        // 日线 / 周线 / 月线按本地日期归并，ts 为北京时间 0 点
+        let day = resample(KlineInterval::Day1, &sse, &bars);
+        assert_eq!((day.len(), day[0].ts.to_rfc3339(), day[0].open, day[0].close), (1, "2026-10-18T16:00:00+00:00".into(), 1.0, 5.0));
+        assert_eq!(resample(KlineInterval::Week1, &sse, &bars)[0].ts, "2026-10-18T16:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(resample(KlineInterval::Month1, &sse, &bars)[0].ts, "2026-09-30T16:00:00Z".parse::<DateTime<Utc>>().unwrap());
+    }
+}
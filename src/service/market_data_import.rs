@@ -0,0 +1,678 @@
+//! 历史行情文件导入：CSV / Parquet 流式读取，校验后按 (标的, 周期, 时间) 批量 upsert
+//!
+//! 同一导入内重复的 (标的, 时间) 以后出现的行为准：同批内由 `upsert_batch` 去重，
+//! 跨批时后写入的行覆盖先写入的行。命令行导入直接调用 `import_file`，API 通过 `submit`
+//! 创建后台任务，任务状态只保存在内存中。
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use tokio::sync::{mpsc, RwLock, Semaphore};
+use uuid::Uuid;
+use crate::core::context::CONTEXT;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::dto::market_data::{
+    CreateImportJobRequest, ImportJobResponse, ImportJobStatus, KlineImportSpec, MarketDataFormat,
+    MarketDataImportReport,
+};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::exchange::normalize_code;
+use crate::service::instrument::find_instrument;
+use crate::service::market_data_reader::{is_valid_ts_format, read_file, ParsedBar, ReadOptions};
+use crate::service::symbology_rules::SymbologyRegistry;
+use entities::sea_orm_active_enums::KlineInterval;
+use entities::{exchange, kline};
+use super::APPResult;
+
+/// 每次 upsert 的行数
+const WRITE_BATCH: usize = 5_000;
+/// 重复检测记住的最近 (标的, 时间) 数，超出后淘汰最早的键
+const DEDUP_WINDOW: usize = 1_000_000;
+/// 每处理这么多行记录一次进度日志
+const PROGRESS_EVERY: u64 = 1_000_000;
+/// 内存中保留的任务数，超出后淘汰最早结束的任务
+const MAX_JOBS: usize = 100;
+
+/// 行对应的标的来源
+enum Target {
+    /// 整个文件属于同一个标的
+    Instrument(i32),
+    /// 按代码列在交易所下解析
+    Symbol(exchange::Model),
+}
+
+/// 校验通过的导入计划
+struct ImportPlan {
+    path: PathBuf,
+    options: ReadOptions,
+    interval: KlineInterval,
+    target: Target,
+    dry_run: bool,
+}
+
+/// 最近出现过的键，容量固定，用于统计重复行
+struct DedupWindow {
+    keys: HashSet<(i32, DateTime<Utc>)>,
+    order: VecDeque<(i32, DateTime<Utc>)>,
+    capacity: usize,
+}
+
+impl DedupWindow {
+    fn new(capacity: usize) -> Self {
+        Self { keys: HashSet::new(), order: VecDeque::new(), capacity }
+    }
+
+    /// 键首次出现时返回 `true`
+    fn insert(&mut self, key: (i32, DateTime<Utc>)) -> bool {
+        if !self.keys.insert(key) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > self.capacity
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.keys.remove(&oldest);
+        }
+        true
+    }
+}
+
+pub struct MarketDataImportService {
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    symbology: Arc<SymbologyRegistry>,
+    /// API 导入只能读取该目录下的文件
+    import_dir: PathBuf,
+    /// 按创建顺序保存的任务
+    jobs: RwLock<VecDeque<ImportJobResponse>>,
+    permits: Arc<Semaphore>,
+}
+
+impl MarketDataImportService {
+    pub fn new(
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        symbology: Arc<SymbologyRegistry>,
+        import_dir: PathBuf,
+        max_concurrent: usize,
+    ) -> Self {
+        Self {
+            kline_repo,
+            instrument_repo,
+            exchange_repo,
+            symbology,
+            import_dir,
+            jobs: RwLock::new(VecDeque::new()),
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// 同步导入一个文件（命令行使用），文件无法读取或缺少列时返回错误
+    pub async fn import_file(&self, path: &Path, spec: &KlineImportSpec) -> APPResult<MarketDataImportReport> {
+        let plan = self.prepare(path.to_path_buf(), spec).await?;
+        self.run(plan, None).await
+    }
+
+    /// 校验请求并创建后台导入任务，返回排队中的任务
+    pub async fn submit(self: &Arc<Self>, request: CreateImportJobRequest) -> APPResult<ImportJobResponse> {
+        let path = self.resolve_path(&request.path)?;
+        let plan = self.prepare(path, &request.spec).await?;
+        let job = ImportJobResponse {
+            id: Uuid::new_v4(),
+            path: request.path.trim().to_string(),
+            interval: plan.interval,
+            status: ImportJobStatus::Queued,
+            created_at: Utc::now().fixed_offset(),
+            started_at: None,
+            finished_at: None,
+            report: MarketDataImportReport::new(plan.dry_run),
+            error: None,
+        };
+        {
+            let mut jobs = self.jobs.write().await;
+            jobs.push_back(job.clone());
+            while jobs.len() > MAX_JOBS {
+                let Some(index) = jobs.iter().position(|job| job.finished_at.is_some()) else { break };
+                jobs.remove(index);
+            }
+        }
+
+        let service = self.clone();
+        let id = job.id;
+        // 后台任务沿用提交请求的语言翻译行错误
+        let context = CONTEXT.try_with(Arc::clone).ok();
+        let task = async move {
+            let Ok(_permit) = service.permits.clone().acquire_owned().await else { return };
+            service.update_job(id, |job| {
+                job.status = ImportJobStatus::Running;
+                job.started_at = Some(Utc::now().fixed_offset());
+            }).await;
+            let result = service.run(plan, Some(id)).await;
+            service.update_job(id, |job| {
+                job.finished_at = Some(Utc::now().fixed_offset());
+                match result {
+                    Ok(report) => {
+                        job.status = ImportJobStatus::Succeeded;
+                        job.report = report;
+                    }
+                    Err(err) => {
+                        tracing::warn!(%id, error = %err, "Market data import failed");
+                        job.status = ImportJobStatus::Failed;
+                        job.error = Some(match err {
+                            AppError::BadRequest { message } => message,
+                            err => err.localized_message(),
+                        });
+                    }
+                }
+            }).await;
+        };
+        tokio::spawn(async move {
+            match context {
+                Some(context) => CONTEXT.scope(context, task).await,
+                None => task.await,
+            }
+        });
+        Ok(job)
+    }
+
+    /// 所有任务，最新创建的在前
+    pub async fn jobs(&self) -> Vec<ImportJobResponse> {
+        self.jobs.read().await.iter().rev().cloned().collect()
+    }
+
+    pub async fn job(&self, id: Uuid) -> APPResult<ImportJobResponse> {
+        self.jobs.read().await
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or(AppError::NotFound { resource: "ImportJob".to_string(), identifier: Some(id.to_string()) })
+    }
+
+    async fn update_job(&self, id: Uuid, update: impl FnOnce(&mut ImportJobResponse)) {
+        if let Some(job) = self.jobs.write().await.iter_mut().find(|job| job.id == id) {
+            update(job);
+        }
+    }
+
+    /// 将请求中的相对路径解析到导入目录下，拒绝绝对路径、`..` 及指向目录外的符号链接
+    fn resolve_path(&self, raw: &str) -> APPResult<PathBuf> {
+        let relative = Path::new(raw.trim());
+        let escapes = relative.is_absolute()
+            || relative.components().any(|part| !matches!(part, Component::Normal(_) | Component::CurDir));
+        let not_found = || AppError::NotFound { resource: "File".to_string(), identifier: Some(raw.trim().to_string()) };
+        let path = match escapes {
+            true => None,
+            false => {
+                let root = self.import_dir.canonicalize().map_err(|_| not_found())?;
+                let path = root.join(relative).canonicalize().map_err(|_| not_found())?;
+                path.starts_with(&root).then_some(path)
+            }
+        };
+        let Some(path) = path else {
+            let mut errors = FieldErrors::new();
+            errors.add("path", "validation-import_path", TranslateArgs::new());
+            return Err(errors.into());
+        };
+        if !path.is_file() {
+            return Err(not_found());
+        }
+        Ok(path)
+    }
+
+    /// 校验导入规则并查出标的或交易所
+    async fn prepare(&self, path: PathBuf, spec: &KlineImportSpec) -> APPResult<ImportPlan> {
+        let mut errors = FieldErrors::new();
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let format = spec.format.or_else(|| MarketDataFormat::from_filename(file_name));
+        if format.is_none() {
+            errors.add("format", "validation-file_format", TranslateArgs::new());
+        }
+        let tz = match spec.timezone.as_deref().map(str::trim) {
+            None => Some(Tz::UTC),
+            Some(value) => value.parse::<Tz>().ok().or_else(|| {
+                errors.add("timezone", "validation-timezone", TranslateArgs::new().add("value", value));
+                None
+            }),
+        };
+        if let Some(value) = spec.ts_format.as_deref().filter(|value| !is_valid_ts_format(value)) {
+            errors.add("ts_format", "validation-ts_format", TranslateArgs::new().add("value", value));
+        }
+        let by_symbol = spec.exchange.is_some() && spec.columns.symbol.is_some();
+        if spec.instrument_id.is_some() == by_symbol || (!by_symbol && spec.exchange.is_some()) {
+            errors.add("instrument_id", "validation-instrument_source", TranslateArgs::new());
+        }
+        let delimiter = spec.delimiter.unwrap_or(',');
+        if !delimiter.is_ascii() {
+            errors.add("delimiter", "validation-invalid", TranslateArgs::new());
+        }
+        errors.into_result()?;
+
+        let target = match (spec.instrument_id, spec.exchange.as_deref()) {
+            (Some(id), _) => {
+                find_instrument(&self.instrument_repo, id).await?;
+                Target::Instrument(id)
+            }
+            (None, code) => {
+                let code = normalize_code(code.unwrap_or_default());
+                let exchange = self.exchange_repo.find_by_code(&code).await
+                    .map_err(AppError::from)?
+                    .ok_or(AppError::NotFound { resource: "Exchange".to_string(), identifier: Some(code) })?;
+                Target::Symbol(exchange)
+            }
+        };
+
+        Ok(ImportPlan {
+            path,
+            options: ReadOptions {
+                format: format.unwrap_or(MarketDataFormat::Csv),
+                columns: spec.columns.clone(),
+                ts_format: spec.ts_format.clone(),
+                tz: tz.unwrap_or(Tz::UTC),
+                delimiter: delimiter as u8,
+            },
+            interval: spec.interval,
+            target,
+            dry_run: spec.dry_run,
+        })
+    }
+
+    /// 读取文件并写入，`job` 不为空时同步更新任务进度
+    async fn run(&self, plan: ImportPlan, job: Option<Uuid>) -> APPResult<MarketDataImportReport> {
+        let (tx, mut rx) = mpsc::channel(4);
+        let (path, options) = (plan.path.clone(), plan.options.clone());
+        let reader = tokio::task::spawn_blocking(move || read_file(&path, &options, &tx));
+
+        let mut report = MarketDataImportReport::new(plan.dry_run);
+        let mut seen = DedupWindow::new(DEDUP_WINDOW);
+        let mut symbols: HashMap<String, Option<i32>> = HashMap::new();
+        let mut pending = Vec::with_capacity(WRITE_BATCH);
+        let mut next_log = PROGRESS_EVERY;
+        while let Some(rows) = rx.recv().await {
+            for (row, parsed) in rows {
+                report.total += 1;
+                let bar = match parsed {
+                    Ok(bar) => bar,
+                    Err(err) => {
+                        report.reject(row, None, err.into());
+                        continue;
+                    }
+                };
+                let instrument_id = match self.instrument_id(&plan.target, bar.symbol.as_deref(), &mut symbols).await? {
+                    Ok(id) => id,
+                    Err(err) => {
+                        report.reject(row, bar.symbol, err);
+                        continue;
+                    }
+                };
+                if seen.insert((instrument_id, bar.ts)) {
+                    report.accepted += 1;
+                } else {
+                    report.duplicated += 1;
+                }
+                pending.push(to_model(instrument_id, plan.interval, bar));
+            }
+            if pending.len() >= WRITE_BATCH {
+                self.flush(&mut pending, &mut report).await?;
+            }
+            if report.total >= next_log {
+                next_log += PROGRESS_EVERY;
+                tracing::info!(path = %plan.path.display(), total = report.total, rejected = report.rejected, "Importing market data");
+            }
+            if let Some(id) = job {
+                let progress = report.clone();
+                self.update_job(id, |job| job.report = progress).await;
+            }
+        }
+        self.flush(&mut pending, &mut report).await?;
+
+        reader.await.map_err(|_| AppError::Internal)??;
+        tracing::info!(
+            path = %plan.path.display(),
+            total = report.total,
+            accepted = report.accepted,
+            rejected = report.rejected,
+            duplicated = report.duplicated,
+            written = report.written,
+            "Market data import finished"
+        );
+        Ok(report)
+    }
+
+    async fn flush(&self, pending: &mut Vec<kline::Model>, report: &mut MarketDataImportReport) -> APPResult<()> {
+        let batch = std::mem::take(pending);
+        if report.dry_run || batch.is_empty() {
+            return Ok(());
+        }
+        report.written += self.kline_repo.upsert_batch(batch).await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    /// 行所属的标的：外层错误是查询失败，中止导入；内层错误是该行被拒绝的原因
+    async fn instrument_id(
+        &self,
+        target: &Target,
+        symbol: Option<&str>,
+        symbols: &mut HashMap<String, Option<i32>>,
+    ) -> APPResult<APPResult<i32>> {
+        let exchange = match target {
+            Target::Instrument(id) => return Ok(Ok(*id)),
+            Target::Symbol(exchange) => exchange,
+        };
+        let Some(symbol) = symbol else {
+            let mut errors = FieldErrors::new();
+            errors.add("symbol", "validation-required", TranslateArgs::new());
+            return Ok(Err(errors.into()));
+        };
+        if !symbols.contains_key(symbol) {
+            let candidates = self.symbology.candidates(&exchange.code, symbol);
+            let found = self.instrument_repo.find_by_exchange_and_symbols(exchange.id, &candidates).await?;
+            symbols.insert(symbol.to_string(), found.map(|instrument| instrument.id));
+        }
+        Ok(symbols[symbol].ok_or_else(|| AppError::NotFound {
+            resource: "Instrument".to_string(),
+            identifier: Some(format!("{}:{symbol}", exchange.code)),
+        }))
+    }
+}
+
+fn to_model(instrument_id: i32, interval: KlineInterval, bar: ParsedBar) -> kline::Model {
+    kline::Model {
+        instrument_id,
+        interval,
+        ts: bar.ts.fixed_offset(),
+        open: bar.open,
+        high: bar.high,
+        low: bar.low,
+        close: bar.close,
+        volume: bar.volume,
+        quote_volume: bar.quote_volume,
+        trade_count: bar.trade_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+    use std::time::Duration;
+    use chrono::DateTime;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+    use entities::instrument;
+    use parquet::data_type::{DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use sea_orm::{ActiveModelTrait, Order, Set};
+    use serde_json::json;
+    use crate::db::connection::DbPool;
+
+    /// SSE 下的 600000（id 1）和 600519（id 2），`import_dir` 为新建的临时目录
+    async fn service() -> (Arc<MarketDataImportService>, Arc<KlineRepository>) {
+        let db = Arc::new(DbPool::in_memory().await);
+        exchange::ActiveModel {
+            id: Set(1),
+            code: Set("SSE".to_string()),
+            name: Set("Shanghai Stock Exchange".to_string()),
+            timezone: Set("Asia/Shanghai".to_string()),
+            asset_classes: Set(json!([])),
+            trading_hours: Set(json!([])),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        for (id, symbol) in [(1, "600000"), (2, "600519")] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(1),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(AssetType::Stock),
+                name: Set(format!("Stock {symbol}")),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        let import_dir = std::env::temp_dir().join(format!("uniquant-import-{}", Uuid::new_v4()));
+        fs::create_dir_all(&import_dir).unwrap();
+        let kline_repo = Arc::new(KlineRepository::new(db.clone()));
+        let service = MarketDataImportService::new(
+            kline_repo.clone(),
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db)),
+            Arc::new(SymbologyRegistry::default()),
+            import_dir,
+            1,
+        );
+        (Arc::new(service), kline_repo)
+    }
+
+    fn spec(value: serde_json::Value) -> KlineImportSpec {
+        serde_json::from_value(value).unwrap()
+    }
+
+    async fn stored(kline_repo: &KlineRepository, instrument_id: i32) -> Vec<kline::Model> {
+        kline_repo.find_range(instrument_id, KlineInterval::Minute1, None, None, Order::Asc, 10_000).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_import_counts_accepted_duplicated_and_rejected_rows() {
+        let (service, kline_repo) = service().await;
+        let path = service.import_dir.join("bars.csv");
+        fs::write(
+            &path,
+            "ts,open,high,low,close,volume,symbol\n\
+             2026-10-19 09:30:00,1,2,0.5,1.5,10,600000\n\
+             2026-10-19 09:30:00,1,2,0.5,1.6,10,600000.SH\n\
+             2026-10-19 09:31:00,1,0.9,0.5,1.5,10,600000\n\
+             2026-10-19 09:30:00,1,2,0.5,1.5,10,600519\n\
+             2026-10-19 09:30:00,1,2,0.5,1.5,10,999999\n\
+             2026-10-19 09:32:00,1,2,0.5,1.5,10,\n\
+             2026-10-19 09:33:00,1,2,0.5,x,10,600519\n",
+        )
+        .unwrap();
+        let by_symbol = json!({
+            "interval": "1m",
+            "exchange": "sse",
+            "columns": {"symbol": "symbol"},
+            "timezone": "Asia/Shanghai",
+        });
+
+        let mut dry_run = by_symbol.clone();
+        dry_run["dry_run"] = json!(true);
+        let report = service.import_file(&path, &spec(dry_run)).await.unwrap();
+        assert_eq!((report.total, report.accepted, report.duplicated, report.rejected, report.written), (7, 2, 1, 4, 0));
+        assert!(stored(&kline_repo, 1).await.is_empty());
+
+        let report = service.import_file(&path, &spec(by_symbol)).await.unwrap();
+        assert_eq!((report.total, report.accepted, report.duplicated, report.rejected, report.written), (7, 2, 1, 4, 2));
+        let rejected: Vec<(u64, Option<&str>)> = report.errors.iter().map(|e| (e.row, e.key.as_deref())).collect();
+        assert_eq!(rejected, [(4, None), (6, Some("999999")), (7, None), (8, None)]);
+        let fields: Vec<Option<Vec<&String>>> = report.errors.iter().map(|e| e.errors.as_ref().map(|e| e.keys().collect())).collect();
+        assert_eq!(fields, [Some(vec![&"high".to_string()]), None, Some(vec![&"symbol".to_string()]), Some(vec![&"close".to_string()])]);
+        let not_found = AppError::NotFound { resource: "Instrument".to_string(), identifier: None };
+        assert_eq!(report.errors[1].code, not_found.code());
+
+        // 同一 (标的, 时间) 以后出现的行为准
+        let bars = stored(&kline_repo, 1).await;
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].ts, DateTime::parse_from_rfc3339("2026-10-19T01:30:00Z").unwrap());
+        assert_eq!(bars[0].close, 1.6);
+        assert_eq!(stored(&kline_repo, 2).await.len(), 1);
+
+        // 文件级错误中止导入
+        fs::write(&path, "ts,open,high\n").unwrap();
+        let err = service.import_file(&path, &spec(json!({"interval": "1m", "instrument_id": 1}))).await.unwrap_err();
+        assert!(matches!(err, AppError::BadRequest { .. }));
+        fs::remove_dir_all(&service.import_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_are_detected_across_write_batches() {
+        let (service, kline_repo) = service().await;
+        let path = service.import_dir.join("bars.csv");
+        let start = DateTime::parse_from_rfc3339("2026-10-19T00:00:00Z").unwrap().timestamp();
+        let count = WRITE_BATCH as i64 + 10;
+        // 前 10 行在文件末尾重复一次，落在第二个写入批次
+        let mut content = String::from("ts,open,high,low,close,volume\n");
+        for minute in (0..count).chain(0..10) {
+            content.push_str(&format!("{},1,2,0.5,1.5,10\n", start + minute * 60));
+        }
+        fs::write(&path, content).unwrap();
+
+        let report = service.import_file(&path, &spec(json!({"interval": "1m", "instrument_id": 1}))).await.unwrap();
+        assert_eq!((report.total, report.accepted, report.duplicated, report.rejected), (count as u64 + 10, count as u64, 10, 0));
+        assert_eq!(report.written, count as u64 + 10);
+        assert_eq!(stored(&kline_repo, 1).await.len(), count as usize);
+        fs::remove_dir_all(&service.import_dir).unwrap();
+    }
+
+    #[test]
+    fn test_dedup_window_forgets_the_oldest_keys() {
+        let ts = |minute: i64| DateTime::from_timestamp(minute * 60, 0).unwrap();
+        let mut window = DedupWindow::new(2);
+        assert!(window.insert((1, ts(0))));
+        assert!(window.insert((1, ts(1))));
+        assert!(!window.insert((1, ts(0))));
+        assert!(window.insert((2, ts(0))));
+        // 容量为 2，最早的 (1, 0) 已被淘汰
+        assert!(window.insert((1, ts(0))));
+        assert!(!window.insert((2, ts(0))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_resolve_path_stays_inside_the_import_dir() {
+        let (service, _) = service().await;
+        let root = &service.import_dir;
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join("data/bars.csv"), "").unwrap();
+        let outside = std::env::temp_dir().join(format!("uniquant-outside-{}.csv", Uuid::new_v4()));
+        fs::write(&outside, "").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape.csv")).unwrap();
+
+        let expected = root.canonicalize().unwrap().join("data/bars.csv");
+        assert_eq!(service.resolve_path("data/bars.csv").unwrap(), expected);
+        assert_eq!(service.resolve_path(" ./data/bars.csv ").unwrap(), expected);
+        let outside_path = outside.to_string_lossy().to_string();
+        for raw in ["../bars.csv", "data/../data/bars.csv", outside_path.as_str(), "escape.csv"] {
+            let err = service.resolve_path(raw).unwrap_err();
+            assert!(matches!(err, AppError::Validation { errors: Some(ref errors) } if errors.contains_key("path")), "{raw}");
+        }
+        for raw in ["missing.csv", "data"] {
+            assert!(matches!(service.resolve_path(raw), Err(AppError::NotFound { .. })), "{raw}");
+        }
+        fs::remove_file(outside).unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_parquet_file() {
+        let (service, kline_repo) = service().await;
+        let path = service.import_dir.join("bars.parquet");
+        let schema = parse_message_type(
+            "message bars {
+                REQUIRED INT64 time (TIMESTAMP_MILLIS);
+                REQUIRED DOUBLE open;
+                REQUIRED DOUBLE high;
+                REQUIRED DOUBLE low;
+                REQUIRED DOUBLE close;
+                REQUIRED DOUBLE volume;
+                OPTIONAL INT64 trades;
+            }",
+        )
+        .unwrap();
+        let start = DateTime::parse_from_rfc3339("2026-10-19T01:30:00Z").unwrap();
+        let millis = [start.timestamp_millis(), start.timestamp_millis() + 60_000];
+        let mut writer = SerializedFileWriter::new(
+            File::create(&path).unwrap(),
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column().unwrap() {
+            match index {
+                0 => column.typed::<Int64Type>().write_batch(&millis, None, None).unwrap(),
+                // 第二行的成交笔数为空
+                6 => column.typed::<Int64Type>().write_batch(&[42], Some(&[1, 0]), None).unwrap(),
+                _ => column.typed::<DoubleType>().write_batch(&[1.0, 1.0 + index as f64], None, None).unwrap(),
+            };
+            column.close().unwrap();
+            index += 1;
+        }
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let spec = spec(json!({
+            "interval": "1m",
+            "instrument_id": 2,
+            "columns": {"ts": "time", "trade_count": "trades"},
+        }));
+        let report = service.import_file(&path, &spec).await.unwrap();
+        assert_eq!((report.total, report.accepted, report.rejected, report.written), (2, 1, 1, 1));
+        // 第二行最高价 3 低于收盘价 5，被拒绝
+        assert_eq!(report.errors[0].row, 2);
+        assert!(report.errors[0].errors.as_ref().unwrap().contains_key("high"));
+        let bars = stored(&kline_repo, 2).await;
+        assert_eq!(bars.len(), 1);
+        assert_eq!((bars[0].ts, bars[0].trade_count), (start, Some(42)));
+        fs::remove_dir_all(&service.import_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_submitted_jobs_run_in_the_background() {
+        let (service, kline_repo) = service().await;
+        fs::write(service.import_dir.join("good.csv"), "ts,open,high,low,close,volume\n2026-10-19T01:30:00Z,1,2,0.5,1.5,10\n").unwrap();
+        fs::write(service.import_dir.join("bad.csv"), "ts,open\n2026-10-19T01:30:00Z,1\n").unwrap();
+        let request = |path: &str| CreateImportJobRequest {
+            path: path.to_string(),
+            spec: spec(json!({"interval": "1m", "instrument_id": 1})),
+        };
+
+        let good = service.submit(request("good.csv")).await.unwrap();
+        let bad = service.submit(request("bad.csv")).await.unwrap();
+        assert_eq!((good.status, good.started_at), (ImportJobStatus::Queued, None));
+        let ids: Vec<Uuid> = service.jobs().await.iter().map(|job| job.id).collect();
+        assert_eq!(ids, [bad.id, good.id]);
+
+        let finished = |id: Uuid| {
+            let service = service.clone();
+            async move {
+                for _ in 0..500 {
+                    let job = service.job(id).await.unwrap();
+                    if job.finished_at.is_some() {
+                        return job;
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                panic!("import job {id} did not finish");
+            }
+        };
+        let good = finished(good.id).await;
+        assert_eq!(good.status, ImportJobStatus::Succeeded);
+        assert!(good.started_at.is_some() && good.error.is_none());
+        assert_eq!((good.report.accepted, good.report.written), (1, 1));
+        assert_eq!(stored(&kline_repo, 1).await.len(), 1);
+
+        let bad = finished(bad.id).await;
+        assert_eq!(bad.status, ImportJobStatus::Failed);
+        assert!(bad.error.is_some());
+
+        assert!(matches!(service.job(Uuid::new_v4()).await, Err(AppError::NotFound { .. })));
+        assert!(service.submit(request("../good.csv")).await.is_err());
+        fs::remove_dir_all(&service.import_dir).unwrap();
+    }
+}
@@ -0,0 +1,505 @@
+//! 行情文件的流式读取与逐行解析
+//!
+//! CSV / Parquet 在阻塞线程中逐行读取，按列映射取出各字段并解析时间，成批发送给写入端，
+//! 因此多 GB 的文件也只占用固定的内存。
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use tokio::sync::mpsc::Sender;
+use crate::dto::market_data::{ColumnMapping, MarketDataFormat};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+
+/// 每次发送给写入端的行数
+const SEND_BATCH: usize = 5_000;
+
+/// `auto` 模式下尝试的无时区时间格式
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y%m%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M",
+];
+/// `auto` 模式下尝试的日期格式，时间取当天 0 点
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"];
+
+/// 单元格的原始值
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Time(DateTime<Utc>),
+    Date(NaiveDate),
+}
+
+/// 解析后的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedBar {
+    pub symbol: Option<String>,
+    pub ts: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub quote_volume: Option<f64>,
+    pub trade_count: Option<i64>,
+}
+
+/// `(行号, 解析结果)`
+pub type ParsedRow = (u64, Result<ParsedBar, ReadError>);
+
+/// 读取或解析失败的原因：消息 key 和参数，由调用方按请求语言翻译
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadError {
+    /// 出错的字段，为空时是整个文件或整行的问题
+    pub field: Option<&'static str>,
+    /// `validation-*` 消息 key
+    pub key: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl ReadError {
+    fn file(key: &'static str) -> Self {
+        Self { field: None, key, args: Vec::new() }
+    }
+
+    fn field(field: &'static str, key: &'static str) -> Self {
+        Self { field: Some(field), key, args: Vec::new() }
+    }
+
+    fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+}
+
+/// 字段错误转换为 `Validation`，文件或整行的错误转换为 `BadRequest`
+impl From<ReadError> for AppError {
+    fn from(err: ReadError) -> Self {
+        let args = err.args.into_iter().fold(TranslateArgs::new(), |args, (name, value)| args.add(name, value));
+        match err.field {
+            Some(field) => {
+                let mut errors = FieldErrors::new();
+                errors.add(field, err.key, args);
+                errors.into()
+            }
+            None => AppError::bad_request_with(err.key, args),
+        }
+    }
+}
+
+/// 时间列格式
+#[derive(Debug, Clone, PartialEq)]
+enum TsFormat {
+    Auto,
+    Rfc3339,
+    /// 整数时间戳，值为每秒的单位数（秒 1、毫秒 1e3……）
+    Unix(i64),
+    Pattern(String),
+}
+
+/// 行解析规则：列位置、时间格式及无时区时间所用的时区
+#[derive(Debug, Clone)]
+pub struct RowParser {
+    /// 依次为 ts、open、high、low、close、volume、quote_volume、trade_count、symbol 的列位置
+    indices: [Option<usize>; 9],
+    ts_format: TsFormat,
+    tz: Tz,
+}
+
+impl RowParser {
+    /// 按表头定位各列，必需的列缺失时返回错误
+    pub fn new(columns: &ColumnMapping, headers: &[String], ts_format: Option<&str>, tz: Tz) -> Result<Self, ReadError> {
+        let find = |name: &str| headers.iter().position(|header| header.trim() == name.trim());
+        let required = [&columns.ts, &columns.open, &columns.high, &columns.low, &columns.close, &columns.volume];
+        let missing: Vec<&str> = required.iter().filter(|name| find(name).is_none()).map(|name| name.as_str()).collect();
+        if !missing.is_empty() {
+            return Err(ReadError::file("validation-import_columns").arg("columns", missing.join(", ")));
+        }
+        let optional = [&columns.quote_volume, &columns.trade_count, &columns.symbol];
+        for name in optional.iter().copied().flatten() {
+            if find(name).is_none() {
+                return Err(ReadError::file("validation-import_columns").arg("columns", name));
+            }
+        }
+
+        let mut indices = [None; 9];
+        for (slot, name) in indices.iter_mut().zip(required.into_iter().map(Some).chain(optional.map(Option::as_ref))) {
+            *slot = name.and_then(|name| find(name));
+        }
+        Ok(Self { indices, ts_format: parse_ts_format(ts_format)?, tz })
+    }
+
+    fn parse(&self, cells: &[Cell]) -> Result<ParsedBar, ReadError> {
+        let cell = |slot: usize| self.indices[slot].and_then(|index| cells.get(index)).unwrap_or(&Cell::Null);
+        let number = |slot: usize, name: &'static str| {
+            to_f64(cell(slot), name)?.ok_or_else(|| ReadError::field(name, "validation-required"))
+        };
+        let bar = ParsedBar {
+            ts: self.timestamp(cell(0))?,
+            open: number(1, "open")?,
+            high: number(2, "high")?,
+            low: number(3, "low")?,
+            close: number(4, "close")?,
+            volume: number(5, "volume")?,
+            quote_volume: to_f64(cell(6), "quote_volume")?,
+            trade_count: to_i64(cell(7), "trade_count")?,
+            symbol: match cell(8) {
+                Cell::Null => None,
+                Cell::Text(text) if text.trim().is_empty() => None,
+                Cell::Text(text) => Some(text.trim().to_string()),
+                other => Some(display(other)),
+            },
+        };
+        check_bar(&bar)?;
+        Ok(bar)
+    }
+
+    fn timestamp(&self, cell: &Cell) -> Result<DateTime<Utc>, ReadError> {
+        match (cell, &self.ts_format) {
+            (Cell::Null, _) => Err(ReadError::field("ts", "validation-required")),
+            (Cell::Time(ts), _) => Ok(*ts),
+            (Cell::Date(date), _) => self.local(date.and_hms_opt(0, 0, 0).unwrap_or_default()),
+            (Cell::Int(value), TsFormat::Auto) => from_unix(*value, auto_unit(*value)),
+            (Cell::Int(value), TsFormat::Unix(unit)) => from_unix(*value, *unit),
+            (Cell::Float(value), TsFormat::Auto | TsFormat::Unix(_)) => {
+                let unit = match self.ts_format {
+                    TsFormat::Unix(unit) => unit,
+                    _ => auto_unit(*value as i64),
+                };
+                from_unix_float(*value, unit)
+            }
+            (Cell::Text(text), format) => self.parse_text(text.trim(), format),
+            (other, format) => self.parse_text(&display(other), format),
+        }
+    }
+
+    fn parse_text(&self, text: &str, format: &TsFormat) -> Result<DateTime<Utc>, ReadError> {
+        let invalid = || ReadError::field("ts", "validation-timestamp").arg("value", text);
+        match format {
+            TsFormat::Rfc3339 => DateTime::parse_from_rfc3339(text).map(|ts| ts.to_utc()).map_err(|_| invalid()),
+            TsFormat::Unix(unit) => text.parse::<i64>().map_err(|_| invalid()).and_then(|value| from_unix(value, *unit)),
+            TsFormat::Pattern(pattern) => {
+                if let Ok(ts) = DateTime::parse_from_str(text, pattern) {
+                    return Ok(ts.to_utc());
+                }
+                if let Ok(naive) = NaiveDateTime::parse_from_str(text, pattern) {
+                    return self.local(naive);
+                }
+                let date = NaiveDate::parse_from_str(text, pattern).map_err(|_| invalid())?;
+                self.local(date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            }
+            TsFormat::Auto => {
+                if let Ok(value) = text.parse::<i64>() {
+                    return from_unix(value, auto_unit(value));
+                }
+                if let Ok(value) = text.parse::<f64>() {
+                    return from_unix_float(value, auto_unit(value as i64));
+                }
+                if let Ok(ts) = DateTime::parse_from_rfc3339(text) {
+                    return Ok(ts.to_utc());
+                }
+                if let Some(naive) = NAIVE_FORMATS.iter().find_map(|f| NaiveDateTime::parse_from_str(text, f).ok()) {
+                    return self.local(naive);
+                }
+                let date = DATE_FORMATS
+                    .iter()
+                    .find_map(|f| NaiveDate::parse_from_str(text, f).ok())
+                    .ok_or_else(invalid)?;
+                self.local(date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            }
+        }
+    }
+
+    /// 无时区时间按配置的时区解析；夏令时重叠取较早者，跳过的时刻视为错误
+    fn local(&self, naive: NaiveDateTime) -> Result<DateTime<Utc>, ReadError> {
+        self.tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|ts| ts.to_utc())
+            .ok_or_else(|| ReadError::field("ts", "validation-local_time").arg("value", naive).arg("timezone", self.tz))
+    }
+}
+
+/// 时间列格式是否可识别
+pub fn is_valid_ts_format(format: &str) -> bool {
+    parse_ts_format(Some(format)).is_ok()
+}
+
+fn parse_ts_format(format: Option<&str>) -> Result<TsFormat, ReadError> {
+    Ok(match format.map(str::trim).unwrap_or("auto") {
+        "auto" => TsFormat::Auto,
+        "rfc3339" => TsFormat::Rfc3339,
+        "unix_s" => TsFormat::Unix(1),
+        "unix_ms" => TsFormat::Unix(1_000),
+        "unix_us" => TsFormat::Unix(1_000_000),
+        "unix_ns" => TsFormat::Unix(1_000_000_000),
+        pattern if pattern.contains('%') => TsFormat::Pattern(pattern.to_string()),
+        other => return Err(ReadError::file("validation-ts_format").arg("value", other)),
+    })
+}
+
+/// 按数量级判断整数时间戳的单位：秒 / 毫秒 / 微秒 / 纳秒
+fn auto_unit(value: i64) -> i64 {
+    match value.unsigned_abs() {
+        0..100_000_000_000 => 1,
+        100_000_000_000..100_000_000_000_000 => 1_000,
+        100_000_000_000_000..100_000_000_000_000_000 => 1_000_000,
+        _ => 1_000_000_000,
+    }
+}
+
+fn from_unix(value: i64, unit: i64) -> Result<DateTime<Utc>, ReadError> {
+    let nanos = i128::from(value) * i128::from(1_000_000_000 / unit);
+    i64::try_from(nanos)
+        .map(DateTime::from_timestamp_nanos)
+        .map_err(|_| ReadError::field("ts", "validation-timestamp_range").arg("value", value))
+}
+
+fn from_unix_float(value: f64, unit: i64) -> Result<DateTime<Utc>, ReadError> {
+    let nanos = value * (1_000_000_000 / unit) as f64;
+    if !nanos.is_finite() || nanos.abs() >= i64::MAX as f64 {
+        return Err(ReadError::field("ts", "validation-timestamp_range").arg("value", value));
+    }
+    Ok(DateTime::from_timestamp_nanos(nanos.round() as i64))
+}
+
+fn display(cell: &Cell) -> String {
+    match cell {
+        Cell::Null => String::new(),
+        Cell::Int(value) => value.to_string(),
+        Cell::Float(value) => value.to_string(),
+        Cell::Text(text) => text.clone(),
+        Cell::Time(ts) => ts.to_rfc3339(),
+        Cell::Date(date) => date.to_string(),
+    }
+}
+
+fn to_f64(cell: &Cell, field: &'static str) -> Result<Option<f64>, ReadError> {
+    let not_number = |value: &dyn ToString| ReadError::field(field, "validation-number").arg("value", value.to_string());
+    let value = match cell {
+        Cell::Null => return Ok(None),
+        Cell::Int(value) => *value as f64,
+        Cell::Float(value) => *value,
+        Cell::Text(text) if text.trim().is_empty() => return Ok(None),
+        Cell::Text(text) => text.trim().parse().map_err(|_| not_number(text))?,
+        other => return Err(not_number(&display(other))),
+    };
+    if value.is_finite() {
+        Ok(Some(value))
+    } else {
+        Err(not_number(&value))
+    }
+}
+
+fn to_i64(cell: &Cell, field: &'static str) -> Result<Option<i64>, ReadError> {
+    match cell {
+        Cell::Int(value) => Ok(Some(*value)),
+        Cell::Float(value) if value.fract() == 0.0 && value.is_finite() => Ok(Some(*value as i64)),
+        Cell::Text(text) if text.trim().parse::<i64>().is_ok() => Ok(text.trim().parse().ok()),
+        other => to_f64(other, field).and_then(|value| match value {
+            None => Ok(None),
+            Some(value) => Err(ReadError::field(field, "validation-integer").arg("value", value)),
+        }),
+    }
+}
+
+/// 基本合理性校验：最高价、最低价包住开收盘价，成交量非负
+fn check_bar(bar: &ParsedBar) -> Result<(), ReadError> {
+    if bar.high < bar.open.max(bar.close).max(bar.low) {
+        return Err(ReadError::field("high", "validation-bar_high"));
+    }
+    if bar.low > bar.open.min(bar.close) {
+        return Err(ReadError::field("low", "validation-bar_low"));
+    }
+    let negative = [
+        ("volume", bar.volume < 0.0),
+        ("quote_volume", bar.quote_volume.is_some_and(|v| v < 0.0)),
+        ("trade_count", bar.trade_count.is_some_and(|c| c < 0)),
+    ];
+    match negative.into_iter().find(|(_, negative)| *negative) {
+        Some((field, _)) => Err(ReadError::field(field, "validation-non_negative")),
+        None => Ok(()),
+    }
+}
+
+/// 文件读取选项
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    pub format: MarketDataFormat,
+    pub columns: ColumnMapping,
+    pub ts_format: Option<String>,
+    pub tz: Tz,
+    pub delimiter: u8,
+}
+
+/// 读取整个文件，解析结果按批发送到 `tx`（阻塞调用，需在 `spawn_blocking` 中运行）
+///
+/// 文件无法打开、表头缺列或读取中途出错时返回 `Err`；写入端关闭时提前结束。
+pub fn read_file(path: &Path, options: &ReadOptions, tx: &Sender<Vec<ParsedRow>>) -> Result<(), ReadError> {
+    let failed = |key: &'static str, err: &dyn std::fmt::Display| ReadError::file(key).arg("error", err);
+    let file = File::open(path).map_err(|err| failed("validation-import_open", &err))?;
+    let mut batch = Vec::with_capacity(SEND_BATCH);
+    let mut emit = |row: ParsedRow| -> bool {
+        batch.push(row);
+        if batch.len() < SEND_BATCH {
+            return true;
+        }
+        tx.blocking_send(std::mem::replace(&mut batch, Vec::with_capacity(SEND_BATCH))).is_ok()
+    };
+
+    match options.format {
+        MarketDataFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .delimiter(options.delimiter)
+                .trim(csv::Trim::All)
+                .flexible(true)
+                .from_reader(BufReader::with_capacity(1 << 20, file));
+            let headers: Vec<String> = reader
+                .headers()
+                .map_err(|err| failed("validation-import_csv_header", &err))?
+                .iter()
+                .map(|header| header.trim_start_matches('\u{feff}').to_string())
+                .collect();
+            let parser = RowParser::new(&options.columns, &headers, options.ts_format.as_deref(), options.tz)?;
+            let mut record = csv::StringRecord::new();
+            loop {
+                let line = reader.position().line();
+                let row = match reader.read_record(&mut record) {
+                    Ok(false) => break,
+                    Ok(true) => {
+                        let line = record.position().map_or(line, |p| p.line());
+                        let cells: Vec<Cell> = record
+                            .iter()
+                            .map(|value| if value.is_empty() { Cell::Null } else { Cell::Text(value.to_string()) })
+                            .collect();
+                        (line, parser.parse(&cells))
+                    }
+                    // 读取错误（如非 UTF-8）只影响当前行，I/O 错误则中止
+                    Err(err) if matches!(err.kind(), csv::ErrorKind::Io(_)) => {
+                        return Err(failed("validation-import_read", &err));
+                    }
+                    Err(err) => (line, Err(failed("validation-import_record", &err))),
+                };
+                if !emit(row) {
+                    return Ok(());
+                }
+            }
+        }
+        MarketDataFormat::Parquet => {
+            let reader = SerializedFileReader::new(file).map_err(|err| failed("validation-import_parquet", &err))?;
+            let headers: Vec<String> = reader
+                .metadata()
+                .file_metadata()
+                .schema_descr()
+                .root_schema()
+                .get_fields()
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect();
+            let parser = RowParser::new(&options.columns, &headers, options.ts_format.as_deref(), options.tz)?;
+            let rows = reader.get_row_iter(None).map_err(|err| failed("validation-import_parquet", &err))?;
+            for (index, row) in rows.enumerate() {
+                let row = row.map_err(|err| failed("validation-import_read", &err))?;
+                let cells: Vec<Cell> = row.get_column_iter().map(|(_, field)| to_cell(field)).collect();
+                if !emit((index as u64 + 1, parser.parse(&cells))) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    if !batch.is_empty() {
+        let _ = tx.blocking_send(batch);
+    }
+    Ok(())
+}
+
+fn to_cell(field: &Field) -> Cell {
+    match field {
+        Field::Null => Cell::Null,
+        Field::Bool(value) => Cell::Int(i64::from(*value)),
+        Field::Byte(value) => Cell::Int((*value).into()),
+        Field::Short(value) => Cell::Int((*value).into()),
+        Field::Int(value) => Cell::Int((*value).into()),
+        Field::Long(value) => Cell::Int(*value),
+        Field::UByte(value) => Cell::Int((*value).into()),
+        Field::UShort(value) => Cell::Int((*value).into()),
+        Field::UInt(value) => Cell::Int((*value).into()),
+        Field::ULong(value) => i64::try_from(*value).map_or(Cell::Float(*value as f64), Cell::Int),
+        Field::Float(value) => Cell::Float((*value).into()),
+        Field::Double(value) => Cell::Float(*value),
+        Field::Str(value) => Cell::Text(value.clone()),
+        Field::TimestampMillis(value) => DateTime::from_timestamp_millis(*value).map_or(Cell::Null, Cell::Time),
+        Field::TimestampMicros(value) => DateTime::from_timestamp_micros(*value).map_or(Cell::Null, Cell::Time),
+        Field::Date(days) => NaiveDate::from_num_days_from_ce_opt(*days + 719_163).map_or(Cell::Null, Cell::Date),
+        // Decimal 等其余类型按文本处理
+        other => Cell::Text(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(columns: ColumnMapping, headers: &[&str], ts_format: Option<&str>, tz: Tz) -> RowParser {
+        let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+        RowParser::new(&columns, &headers, ts_format, tz).unwrap()
+    }
+
+    fn text(values: &[&str]) -> Vec<Cell> {
+        values.iter().map(|v| Cell::Text(v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_column_mapping_and_timestamps() {
+        let columns = ColumnMapping {
+            ts: "time".into(),
+            open: "o".into(),
+            high: "h".into(),
+            low: "l".into(),
+            close: "c".into(),
+            volume: "vol".into(),
+            ..Default::default()
+        };
+        let parser = parser(columns.clone(), &["time", "o", "h", "l", "c", "vol"], None, chrono_tz::Asia::Shanghai);
+        let expected: DateTime<Utc> = "2026-10-15T01:30:00Z".parse().unwrap();
+        for ts in ["2026-10-15 09:30:00", "2026-10-15T09:30:00+08:00", "1792027800", "1792027800000"] {
+            let bar = parser.parse(&text(&[ts, "1", "2", "0.5", "1.5", "10"])).unwrap();
+            assert_eq!(bar.ts, expected, "{ts}");
+        }
+        // 日期按本地 0 点
+        let bar = parser.parse(&text(&["2026/10/15", "1", "2", "0.5", "1.5", "10"])).unwrap();
+        assert_eq!(bar.ts, "2026-10-14T16:00:00Z".parse::<DateTime<Utc>>().unwrap());
+
+        let err = parser.parse(&text(&["2026-10-15", "1", "0.9", "0.5", "1.5", "10"])).unwrap_err();
+        assert_eq!(err, ReadError::field("high", "validation-bar_high"));
+        let err = parser.parse(&text(&["2026-10-15", "1", "2", "1.2", "1.5", "10"])).unwrap_err();
+        assert_eq!(err, ReadError::field("low", "validation-bar_low"));
+        let err = parser.parse(&text(&["2026-10-15", "1", "2", "0.5", "1.5", "-1"])).unwrap_err();
+        assert_eq!(err, ReadError::field("volume", "validation-non_negative"));
+        let err = parser.parse(&text(&["yesterday", "1", "2", "0.5", "1.5", "10"])).unwrap_err();
+        assert_eq!(err, ReadError::field("ts", "validation-timestamp").arg("value", "yesterday"));
+        let err = parser.parse(&text(&["2026-10-15", "x", "2", "0.5", "1.5", "10"])).unwrap_err();
+        assert_eq!(err, ReadError::field("open", "validation-number").arg("value", "x"));
+
+        let pattern = RowParser::new(&columns, &["time", "o", "h", "l", "c", "vol"].map(String::from), Some("%Y%m%d"), Tz::UTC)
+            .unwrap();
+        let bar = pattern.parse(&[Cell::Int(20261015), Cell::Float(1.0), Cell::Int(2), Cell::Float(0.5), Cell::Int(1), Cell::Null]);
+        assert_eq!(bar.unwrap_err(), ReadError::field("volume", "validation-required"));
+
+        let missing = RowParser::new(&columns, &["time".to_string()], None, Tz::UTC).unwrap_err();
+        assert_eq!(missing, ReadError::file("validation-import_columns").arg("columns", "o, h, l, c, vol"));
+        assert!(RowParser::new(&columns, &["time".to_string()], Some("unix_days"), Tz::UTC).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use sea_orm::{ColumnTrait, Condition};
+use sea_orm::ActiveValue::Set;
+use crate::db::repositories::Repository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::feature_metric::FeatureMetricRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::metric_definition::MetricDefinitionRepository;
+use crate::dto::metric::{
+    MetricBatchRequest, MetricCrossSectionItem, MetricCrossSectionQuery, MetricDefinitionRequest,
+    MetricDefinitionResponse, MetricSeriesQuery, MetricValue, MetricWriteResponse,
+};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::calendar::CalendarFiles;
+use crate::service::instrument::find_instrument;
+use crate::service::trading_calendar::TradingCalendar;
+use entities::{feature_metric, instrument, metric_definition};
+use super::APPResult;
+
+/// 单次时间序列查询最多的指标数
+const MAX_SERIES_NAMES: usize = 50;
+
+pub struct MetricService {
+    repo: Arc<FeatureMetricRepository>,
+    definition_repo: Arc<MetricDefinitionRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    calendars: Arc<CalendarFiles>,
+}
+
+impl MetricService {
+    pub fn new(
+        repo: Arc<FeatureMetricRepository>,
+        definition_repo: Arc<MetricDefinitionRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        calendars: Arc<CalendarFiles>,
+    ) -> Self {
+        Self { repo, definition_repo, instrument_repo, exchange_repo, calendars }
+    }
+
+    /// 指标目录，按名称升序
+    pub async fn definitions(&self) -> APPResult<Vec<MetricDefinitionResponse>> {
+        let definitions = self.definition_repo.list().await.map_err(AppError::from)?;
+        Ok(definitions.into_iter().map(MetricDefinitionResponse::from).collect())
+    }
+
+    /// 登记或更新指标，返回是否为新登记
+    pub async fn put_definition(
+        &self,
+        name: String,
+        req: MetricDefinitionRequest,
+    ) -> APPResult<(bool, MetricDefinitionResponse)> {
+        if !is_metric_name(&name) {
+            let mut errors = FieldErrors::new();
+            errors.add("name", "validation-metric_name", TranslateArgs::new());
+            errors.into_result()?;
+        }
+        let existing = self.definition_repo.find_by_id(name.clone()).await.map_err(AppError::from)?;
+        let created = existing.is_none();
+        let mut active = match existing {
+            Some(model) => model.into(),
+            None => metric_definition::ActiveModel { name: Set(name.clone()), ..Default::default() },
+        };
+        active.unit = Set(req.unit);
+        active.frequency = Set(req.frequency);
+        active.source = Set(req.source);
+        active.description = Set(req.description);
+        let model = if created {
+            self.definition_repo.create(active).await
+        } else {
+            self.definition_repo.update(active).await
+        }
+        .map_err(AppError::from)?;
+
+        tracing::info!(name = %model.name, created, "Saved metric definition");
+        Ok((created, model.into()))
+    }
+
+    /// 批量写入某个标的的指标值，指标须已登记
+    pub async fn write(&self, instrument_id: i32, request: MetricBatchRequest) -> APPResult<MetricWriteResponse> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let names: Vec<String> = request.metrics
+            .iter()
+            .map(|metric| metric.name.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let known: HashSet<String> = self.definition_repo
+            .existing_names(&names)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .collect();
+        let mut errors = FieldErrors::new();
+        for (index, metric) in request.metrics.iter().enumerate() {
+            if !known.contains(&metric.name) {
+                errors.add(
+                    format!("metrics[{index}].name"),
+                    "validation-unknown_metric",
+                    TranslateArgs::new().add("value", metric.name.as_str()),
+                );
+            }
+        }
+        errors.into_result()?;
+
+        let received = request.metrics.len();
+        let metrics = request.metrics
+            .into_iter()
+            .map(|input| feature_metric::Model {
+                instrument_id,
+                metric_name: input.name,
+                ts: input.ts,
+                known_at: input.known_at,
+                value: input.value,
+                detail: input.detail,
+            })
+            .collect();
+        let written = self.repo.upsert_batch(metrics).await.map_err(AppError::from)?;
+        Ok(MetricWriteResponse { received, written })
+    }
+
+    /// 某个标的若干指标的时间序列，`as_of` 不为空时按时点读取，日期按标的所属交易所的时区划分
+    pub async fn series(&self, instrument_id: i32, query: MetricSeriesQuery) -> APPResult<Vec<MetricValue>> {
+        let instrument = find_instrument(&self.instrument_repo, instrument_id).await?;
+        let names = parse_names(&query.names);
+        let mut errors = FieldErrors::new();
+        if names.is_empty() {
+            errors.add("names", "validation-required", TranslateArgs::new());
+        } else if names.len() > MAX_SERIES_NAMES {
+            errors.add("names", "validation-length_max", TranslateArgs::new().add("max", MAX_SERIES_NAMES as i64));
+        }
+        if let (Some(start), Some(end)) = (query.start, query.end)
+            && end <= start
+        {
+            errors.add("end", "validation-date_order", TranslateArgs::new().add("other", "start"));
+        }
+        errors.into_result()?;
+
+        let known_before = match query.as_of {
+            Some(date) => {
+                let exchange = self.exchange_repo.find_by_id(instrument.exchange_id).await
+                    .map_err(AppError::from)?
+                    .ok_or(AppError::Internal)?;
+                let calendar = self.calendars.calendar_for(&exchange)?;
+                Some(end_of_day(&calendar, date).fixed_offset())
+            }
+            None => None,
+        };
+        let values = self.repo
+            .find_series(instrument_id, &names, query.start, query.end, known_before)
+            .await
+            .map_err(AppError::from)?;
+        Ok(values.into_iter().map(MetricValue::from).collect())
+    }
+
+    /// 指标在某日的截面：各标的截至该日（所属交易所的时区）结束前已公布的最新值
+    pub async fn cross_section(
+        &self,
+        name: String,
+        query: MetricCrossSectionQuery,
+    ) -> APPResult<Vec<MetricCrossSectionItem>> {
+        self.definition_repo.find_by_id(name.clone()).await
+            .map_err(AppError::from)?
+            .ok_or(AppError::NotFound {
+                resource: "Metric".to_string(),
+                identifier: Some(name.clone()),
+            })?;
+        // 同一时刻结束当天的交易所合并为一次查询
+        let mut cutoffs: BTreeMap<DateTime<Utc>, Vec<i32>> = BTreeMap::new();
+        for exchange in self.exchange_repo.find_all().await.map_err(AppError::from)? {
+            let calendar = self.calendars.calendar_for(&exchange)?;
+            cutoffs.entry(end_of_day(&calendar, query.date)).or_default().push(exchange.id);
+        }
+        let mut values = Vec::new();
+        for (before, exchange_ids) in cutoffs {
+            let found = self.repo
+                .find_cross_section(&name, &exchange_ids, before.fixed_offset())
+                .await
+                .map_err(AppError::from)?;
+            values.extend(found);
+        }
+        values.sort_by_key(|value| value.instrument_id);
+        let symbols: HashMap<i32, String> = self.instrument_repo
+            .find_by_condition(
+                Condition::all().add(instrument::Column::Id.is_in(values.iter().map(|value| value.instrument_id))),
+            )
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|instrument| (instrument.id, instrument.symbol))
+            .collect();
+
+        Ok(values
+            .into_iter()
+            .filter_map(|value| {
+                let symbol = symbols.get(&value.instrument_id)?.clone();
+                Some(MetricCrossSectionItem {
+                    instrument_id: value.instrument_id,
+                    symbol,
+                    ts: value.ts,
+                    known_at: value.known_at,
+                    value: value.value,
+                    detail: value.detail,
+                })
+            })
+            .collect())
+    }
+
+}
+
+/// 指标名：小写字母开头，只含小写字母、数字和下划线，最长 100
+fn is_metric_name(name: &str) -> bool {
+    name.len() <= 100
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// 解析逗号分隔的指标名，去除空白与重复，保持原有顺序
+fn parse_names(names: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && seen.insert(*name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 交易所本地日期 `date` 结束的时刻，即次日本地 0 点
+fn end_of_day(calendar: &TradingCalendar, date: NaiveDate) -> DateTime<Utc> {
+    calendar.start_of_day(date.checked_add_days(Days::new(1)).unwrap_or(date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveModelTrait;
+    use serde_json::json;
+    use crate::db::connection::DbPool;
+    use entities::exchange;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus, MetricFrequency};
+
+    /// SSE（Asia/Shanghai）下的 600000（id 1）与 NYSE（America/New_York）下的 AAPL（id 2）
+    async fn service() -> (MetricService, Arc<FeatureMetricRepository>) {
+        let db = Arc::new(DbPool::in_memory().await);
+        for (id, code, timezone) in [(1, "SSE", "Asia/Shanghai"), (2, "NYSE", "America/New_York")] {
+            exchange::ActiveModel {
+                id: Set(id),
+                code: Set(code.to_string()),
+                name: Set(code.to_string()),
+                timezone: Set(timezone.to_string()),
+                asset_classes: Set(json!([])),
+                trading_hours: Set(json!([])),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        for (id, symbol) in [(1, "600000"), (2, "AAPL")] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(id),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(AssetType::Stock),
+                name: Set(symbol.to_string()),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        metric_definition::ActiveModel {
+            name: Set("revenue".to_string()),
+            frequency: Set(MetricFrequency::Quarterly),
+            ..Default::default()
+        }
+        .insert(&**db)
+        .await
+        .unwrap();
+        let repo = Arc::new(FeatureMetricRepository::new(db.clone()));
+        let service = MetricService::new(
+            repo.clone(),
+            Arc::new(MetricDefinitionRepository::new(db.clone())),
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db)),
+            Arc::new(CalendarFiles::default()),
+        );
+        (service, repo)
+    }
+
+    #[tokio::test]
+    async fn test_point_in_time_dates_follow_the_exchange_timezone() {
+        let (service, repo) = service().await;
+        let metric = |instrument_id: i32, known_at: &str, value: f64| feature_metric::Model {
+            instrument_id,
+            metric_name: "revenue".to_string(),
+            ts: "2024-03-31T00:00:00Z".parse().unwrap(),
+            known_at: known_at.parse().unwrap(),
+            value: Some(value),
+            detail: None,
+        };
+        repo.upsert_batch(vec![
+            // 上海时间 04-20 23:00 公布，04-21 00:30 重述
+            metric(1, "2024-04-20T15:00:00Z", 100.0),
+            metric(1, "2024-04-20T16:30:00Z", 90.0),
+            // 纽约时间 04-20 22:00 公布
+            metric(2, "2024-04-21T02:00:00Z", 50.0),
+        ])
+        .await
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 4, 20).unwrap();
+
+        let query = MetricSeriesQuery { names: "revenue".to_string(), start: None, end: None, as_of: Some(date) };
+        let series = service.series(1, query).await.unwrap();
+        assert_eq!(series.iter().map(|value| value.value).collect::<Vec<_>>(), [Some(100.0)]);
+
+        let section = service.cross_section("revenue".to_string(), MetricCrossSectionQuery { date }).await.unwrap();
+        let values: Vec<(i32, Option<f64>)> = section.iter().map(|item| (item.instrument_id, item.value)).collect();
+        assert_eq!(values, [(1, Some(100.0)), (2, Some(50.0))]);
+
+        let next_day = MetricCrossSectionQuery { date: date.succ_opt().unwrap() };
+        let section = service.cross_section("revenue".to_string(), next_day).await.unwrap();
+        let values: Vec<(i32, Option<f64>)> = section.iter().map(|item| (item.instrument_id, item.value)).collect();
+        assert_eq!(values, [(1, Some(90.0)), (2, Some(50.0))]);
+    }
+
+    #[test]
+    fn test_metric_names() {
+        assert_eq!(parse_names(" pe_ttm, revenue,,pe_ttm "), vec!["pe_ttm", "revenue"]);
+        assert!(is_metric_name("roe_3y"));
+        assert!(!is_metric_name("3y_roe"));
+        assert!(!is_metric_name("PE"));
+        assert!(!is_metric_name(""));
+    }
+}
@@ -1,5 +1,29 @@
+pub mod adjustment;
+pub mod calendar;
+pub mod corporate_action;
+pub mod data_quality;
+pub mod data_quality_checks;
+pub mod exchange;
+pub mod financial_statement;
+pub mod financial_taxonomy;
+pub mod health;
+pub mod health_scores;
 pub mod factory;
 pub mod instrument;
+pub mod instrument_rules;
+pub mod kline;
+pub mod kline_resample;
+pub mod market_data_import;
+pub mod market_data_reader;
+pub mod metric;
+pub mod search;
+pub mod search_index;
+pub mod symbology;
+pub mod symbology_rules;
+pub mod tabular;
+pub mod tick;
+pub mod trade_replay;
+pub mod trading_calendar;
 use crate::error::code::AppError;
 
 type APPResult<T> = Result<T, AppError>;
\ No newline at end of file
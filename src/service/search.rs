@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{ColumnTrait, Condition, DbErr};
+use tokio::sync::{OnceCell, RwLock};
+use crate::db::repositories::Repository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::instrument_alias::InstrumentAliasRepository;
+use crate::dto::instrument::InstrumentResponse;
+use crate::dto::search::{SearchHit, SearchQuery};
+use crate::error::code::AppError;
+use crate::service::exchange::normalize_code;
+use crate::service::search_index::{rank, IndexEntry};
+use entities::{instrument, instrument_alias};
+use super::APPResult;
+
+/// Postgres 粗筛时每条结果对应的候选数，候选再由应用内排序截断
+const CANDIDATES_PER_HIT: u64 = 10;
+
+/// 候选检索方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchBackend {
+    /// Postgres `pg_trgm` 索引粗筛
+    Trigram,
+    /// 全量加载到应用内存（SQLite 或未启用 `pg_trgm`）
+    InMemory,
+}
+
+/// 应用内索引的数据指纹：标的的 (记录数, 最近更新时间, 最近删除时间) 与别名的 (记录数, 最大 ID)
+type Fingerprint = (i64, Option<DateTimeWithTimeZone>, Option<DateTimeWithTimeZone>, i64, Option<i32>);
+
+/// 应用内索引及其对应的数据指纹
+struct MemoryIndex {
+    fingerprint: Fingerprint,
+    entries: Vec<IndexEntry>,
+}
+
+pub struct SearchService {
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    alias_repo: Arc<InstrumentAliasRepository>,
+    backend: OnceCell<SearchBackend>,
+    index: RwLock<Option<Arc<MemoryIndex>>>,
+}
+
+impl SearchService {
+    pub fn new(
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        alias_repo: Arc<InstrumentAliasRepository>,
+    ) -> Self {
+        Self {
+            instrument_repo,
+            exchange_repo,
+            alias_repo,
+            backend: OnceCell::new(),
+            index: RwLock::new(None),
+        }
+    }
+
+    /// 按代码、名称、拼音和别名搜索标的，结果按匹配度排序
+    ///
+    /// 匹配依次为完全匹配、前缀、子串和编辑距离模糊匹配，详见 `search_index`。
+    pub async fn search(&self, query: SearchQuery) -> APPResult<Vec<SearchHit>> {
+        let exchange_id = match &query.exchange {
+            Some(code) => match self.exchange_repo.find_by_code(&normalize_code(code)).await.map_err(AppError::from)? {
+                Some(exchange) => Some(exchange.id),
+                None => return Ok(Vec::new()),
+            },
+            None => None,
+        };
+
+        let exchanges = self.exchange_repo.codes_by_id().await.map_err(AppError::from)?;
+        let hits = match self.backend().await? {
+            SearchBackend::Trigram => {
+                let mut condition = Condition::all();
+                if let Some(exchange_id) = exchange_id {
+                    condition = condition.add(instrument::Column::ExchangeId.eq(exchange_id));
+                }
+                if let Some(asset_type) = query.asset_type {
+                    condition = condition.add(instrument::Column::AssetType.eq(asset_type));
+                }
+                let limit = query.limit() as u64 * CANDIDATES_PER_HIT;
+                let candidates = self.instrument_repo
+                    .trigram_candidates(&query.q, condition, limit)
+                    .await
+                    .map_err(AppError::from)?;
+                let ids: Vec<i32> = candidates.iter().map(|model| model.id).collect();
+                let aliases = self.alias_repo.find_by_instruments(&ids).await.map_err(AppError::from)?;
+                to_hits(&query, &index_entries(candidates, aliases), &exchanges)
+            }
+            SearchBackend::InMemory => {
+                let index = self.memory_index().await?;
+                let entries = index.entries.iter().filter(|entry| {
+                    exchange_id.is_none_or(|id| entry.instrument.exchange_id == id)
+                        && query.asset_type.is_none_or(|t| entry.instrument.asset_type == t)
+                });
+                to_hits(&query, entries, &exchanges)
+            }
+        };
+        Ok(hits)
+    }
+
+    /// 首次搜索时确定检索方式；使用 `pg_trgm` 时顺带为历史数据补齐拼音
+    async fn backend(&self) -> APPResult<SearchBackend> {
+        self.backend
+            .get_or_try_init(|| async {
+                if !self.instrument_repo.trigram_available().await? {
+                    tracing::info!("pg_trgm unavailable, instrument search uses the in-process index");
+                    return Ok::<_, DbErr>(SearchBackend::InMemory);
+                }
+                let filled = self.instrument_repo.backfill_name_pinyin().await?;
+                tracing::info!(filled, "Instrument search uses pg_trgm");
+                Ok(SearchBackend::Trigram)
+            })
+            .await
+            .copied()
+            .map_err(AppError::from)
+    }
+
+    /// 获取应用内索引，数据指纹变化时重建
+    async fn memory_index(&self) -> APPResult<Arc<MemoryIndex>> {
+        let (count, updated_at, deleted_at) = self.instrument_repo.fingerprint().await.map_err(AppError::from)?;
+        let (alias_count, alias_max_id) = self.alias_repo.fingerprint().await.map_err(AppError::from)?;
+        let fingerprint = (count, updated_at, deleted_at, alias_count, alias_max_id);
+
+        if let Some(index) = self.index.read().await.as_ref().filter(|i| i.fingerprint == fingerprint) {
+            return Ok(index.clone());
+        }
+        let mut guard = self.index.write().await;
+        if let Some(index) = guard.as_ref().filter(|i| i.fingerprint == fingerprint) {
+            return Ok(index.clone());
+        }
+        let instruments = self.instrument_repo.find_all().await.map_err(AppError::from)?;
+        let aliases = self.alias_repo.find_all().await.map_err(AppError::from)?;
+        let index = Arc::new(MemoryIndex { fingerprint, entries: index_entries(instruments, aliases) });
+        tracing::debug!(entries = index.entries.len(), "Rebuilt instrument search index");
+        *guard = Some(index.clone());
+        Ok(index)
+    }
+}
+
+/// 将标的与别名组装为索引条目（不属于这批标的的别名被忽略）
+fn index_entries(instruments: Vec<instrument::Model>, aliases: Vec<instrument_alias::Model>) -> Vec<IndexEntry> {
+    let mut by_instrument: HashMap<i32, Vec<instrument_alias::Model>> = HashMap::new();
+    for alias in aliases {
+        by_instrument.entry(alias.instrument_id).or_default().push(alias);
+    }
+    instruments
+        .into_iter()
+        .map(|model| {
+            let aliases = by_instrument.remove(&model.id).unwrap_or_default();
+            IndexEntry::new(model, &aliases)
+        })
+        .collect()
+}
+
+/// 排序并转换为响应，得分保留三位小数
+fn to_hits<'a>(
+    query: &SearchQuery,
+    entries: impl IntoIterator<Item = &'a IndexEntry>,
+    exchanges: &HashMap<i32, String>,
+) -> Vec<SearchHit> {
+    rank(&query.q, entries, query.limit())
+        .into_iter()
+        .map(|hit| SearchHit {
+            score: (hit.score * 1000.0).round() / 1000.0,
+            matched_field: hit.field,
+            matched_text: hit.text.to_string(),
+            instrument: InstrumentResponse::new(
+                hit.entry.instrument.clone(),
+                exchanges.get(&hit.entry.instrument.exchange_id).cloned().unwrap_or_default(),
+            ),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue::Set;
+    use crate::db::connection::DbPool;
+    use crate::dto::search::MatchField;
+    use entities::exchange;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+
+    struct Fixture {
+        service: SearchService,
+        instrument_repo: Arc<InstrumentRepository>,
+        alias_repo: Arc<InstrumentAliasRepository>,
+    }
+
+    async fn fixture() -> Fixture {
+        let db = Arc::new(DbPool::in_memory().await);
+        for (id, code) in [(1, "SSE"), (2, "NASDAQ")] {
+            exchange::ActiveModel {
+                id: Set(id),
+                code: Set(code.to_string()),
+                name: Set(code.to_string()),
+                timezone: Set("UTC".to_string()),
+                asset_classes: Set(serde_json::json!([])),
+                trading_hours: Set(serde_json::json!([])),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        for (id, exchange_id, symbol, name) in [
+            (1, 1, "600000", "浦发银行"),
+            (2, 1, "600519", "贵州茅台"),
+            (3, 2, "AAPL", "Apple Inc."),
+        ] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(exchange_id),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(AssetType::Stock),
+                name: Set(name.to_string()),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(serde_json::json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        let instrument_repo = Arc::new(InstrumentRepository::new(db.clone()));
+        let alias_repo = Arc::new(InstrumentAliasRepository::new(db.clone()));
+        let service = SearchService::new(
+            instrument_repo.clone(),
+            Arc::new(ExchangeRepository::new(db)),
+            alias_repo.clone(),
+        );
+        Fixture { service, instrument_repo, alias_repo }
+    }
+
+    async fn search(service: &SearchService, q: &str) -> Vec<(i32, MatchField)> {
+        let query = SearchQuery { q: q.to_string(), limit: None, exchange: None, asset_type: None };
+        let hits = service.search(query).await.unwrap();
+        hits.into_iter().map(|hit| (hit.instrument.id, hit.matched_field)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_index_matches_symbols_names_and_pinyin() {
+        let Fixture { service, .. } = fixture().await;
+        assert_eq!(search(&service, "600519").await, vec![(2, MatchField::Symbol)]);
+        // 前缀命中排在一字之差的模糊命中之前
+        assert_eq!(search(&service, "6000").await, vec![(1, MatchField::Symbol), (2, MatchField::Symbol)]);
+        assert_eq!(search(&service, "茅台").await, vec![(2, MatchField::Name)]);
+        assert_eq!(search(&service, "gzmt").await, vec![(2, MatchField::Pinyin)]);
+        assert_eq!(search(&service, "pufa").await, vec![(1, MatchField::Pinyin)]);
+        assert_eq!(search(&service, "aple").await, vec![(3, MatchField::Name)]);
+        assert_eq!(search(&service, "ple inc").await, vec![(3, MatchField::Name)]);
+
+        let query = SearchQuery { q: "6".to_string(), limit: None, exchange: Some("nasdaq".to_string()), asset_type: None };
+        assert!(service.search(query).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_index_follows_aliases_and_deletes() {
+        let Fixture { service, instrument_repo, alias_repo } = fixture().await;
+        assert!(search(&service, "maotai").await.iter().any(|(id, _)| *id == 2));
+
+        alias_repo
+            .create(instrument_alias::ActiveModel {
+                instrument_id: Set(2),
+                source: Set("vendor".to_string()),
+                value: Set("KWEICHOW".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(search(&service, "kweichow").await, vec![(2, MatchField::Alias)]);
+
+        // 通用软删除只写入 deleted_at，索引也要随之重建
+        instrument_repo.delete(2).await.unwrap();
+        assert!(search(&service, "kweichow").await.is_empty());
+        assert!(search(&service, "maotai").await.is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+//! 标的搜索的匹配与排序
+//!
+//! 查询词与候选词统一规范化（小写、去除空白及符号）后，依次尝试完全匹配、前缀、子串与编辑距离模糊匹配，
+//! 再乘以字段权重得到得分。Postgres 与应用内索引两种检索方式共用这里的排序，保证结果一致。
+
+use crate::dto::search::MatchField;
+use entities::sea_orm_active_enums::InstrumentStatus;
+use entities::{instrument, instrument_alias};
+
+/// 字段权重：代码最可信，拼音最模糊
+fn weight(field: MatchField) -> f64 {
+    match field {
+        MatchField::Symbol => 1.0,
+        MatchField::Alias => 0.95,
+        MatchField::Name => 0.9,
+        MatchField::Pinyin => 0.8,
+    }
+}
+
+/// 规范化：转小写并只保留字母数字（含汉字）
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 单个可匹配的词
+#[derive(Debug, Clone)]
+struct Term {
+    field: MatchField,
+    /// 规范化后的字符
+    chars: Vec<char>,
+    /// 原始文本，作为命中说明返回
+    text: String,
+}
+
+/// 一个标的及其全部可匹配的词
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub instrument: instrument::Model,
+    terms: Vec<Term>,
+}
+
+impl IndexEntry {
+    pub fn new(instrument: instrument::Model, aliases: &[instrument_alias::Model]) -> Self {
+        let mut terms = Vec::new();
+        let mut push = |field, text: &str| {
+            let chars: Vec<char> = normalize(text).chars().collect();
+            if !chars.is_empty() {
+                terms.push(Term { field, chars, text: text.to_string() });
+            }
+        };
+        push(MatchField::Symbol, &instrument.symbol);
+        push(MatchField::Name, &instrument.name);
+        // 拼音在保存时生成，历史数据尚未回填时现场计算
+        let pinyin = instrument.name_pinyin.clone().or_else(|| instrument::name_pinyin(&instrument.name));
+        for part in pinyin.iter().flat_map(|p| p.split_whitespace()) {
+            push(MatchField::Pinyin, part);
+        }
+        for alias in aliases {
+            push(MatchField::Alias, &alias.value);
+        }
+        Self { instrument, terms }
+    }
+}
+
+/// 一次命中：得分在 0 到 1 之间
+#[derive(Debug)]
+pub struct Scored<'a> {
+    pub entry: &'a IndexEntry,
+    pub score: f64,
+    pub field: MatchField,
+    pub text: &'a str,
+}
+
+/// 允许的编辑距离：查询词越长越宽松，过短的查询词不做模糊匹配
+fn max_typos(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// 查询词与候选词任一前缀的最小编辑距离（允许候选词更长，如 `aple` 对 `appleinc` 为 1）
+///
+/// 相邻字符互换计为一次编辑（OSA 距离），如 `appel` 对 `apple` 为 1。
+fn prefix_distance(query: &[char], term: &[char]) -> usize {
+    let mut before: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=term.len()).collect();
+    for (i, q) in query.iter().enumerate() {
+        let mut row = vec![i + 1; term.len() + 1];
+        for (j, t) in term.iter().enumerate() {
+            let cost = usize::from(q != t);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+            if i > 0 && j > 0 && *q == term[j - 1] && query[i - 1] == *t {
+                row[j + 1] = row[j + 1].min(before[j - 1] + 1);
+            }
+        }
+        before = std::mem::replace(&mut prev, row);
+    }
+    prev.into_iter().min().unwrap_or(query.len())
+}
+
+/// 单个词的匹配得分（未乘字段权重）
+fn match_score(query: &[char], term: &[char]) -> Option<f64> {
+    let coverage = query.len() as f64 / term.len().max(query.len()) as f64;
+    if term == query {
+        return Some(1.0);
+    }
+    if term.starts_with(query) {
+        return Some(0.8 + 0.1 * coverage);
+    }
+    if term.windows(query.len()).any(|window| window == query) {
+        return Some(0.6 + 0.1 * coverage);
+    }
+    let typos = max_typos(query.len());
+    if typos == 0 {
+        return None;
+    }
+    let distance = prefix_distance(query, term);
+    (distance <= typos).then_some(0.5 - 0.1 * distance as f64)
+}
+
+/// 计算一个标的的最高得分，不匹配时返回 `None`
+pub fn score<'a>(query: &str, entry: &'a IndexEntry) -> Option<Scored<'a>> {
+    let query: Vec<char> = normalize(query).chars().collect();
+    if query.is_empty() {
+        return None;
+    }
+    entry
+        .terms
+        .iter()
+        .filter_map(|term| {
+            match_score(&query, &term.chars).map(|s| Scored {
+                entry,
+                score: s * weight(term.field),
+                field: term.field,
+                text: &term.text,
+            })
+        })
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+/// 对候选标的打分排序，取前 `limit` 个
+///
+/// 同分时正常交易的标的优先，其次 symbol 较短者、ID 较小者优先。
+pub fn rank<'a>(query: &str, entries: impl IntoIterator<Item = &'a IndexEntry>, limit: usize) -> Vec<Scored<'a>> {
+    let mut hits: Vec<Scored> = entries.into_iter().filter_map(|entry| score(query, entry)).collect();
+    hits.sort_by(|a, b| {
+        let key = |hit: &Scored| {
+            let instrument = &hit.entry.instrument;
+            (instrument.status != InstrumentStatus::Active, instrument.symbol.len(), instrument.id)
+        };
+        b.score.total_cmp(&a.score).then_with(|| key(a).cmp(&key(b)))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::sea_orm_active_enums::AssetType;
+
+    fn entry(id: i32, symbol: &str, name: &str) -> IndexEntry {
+        let now = chrono::Utc::now().fixed_offset();
+        let model = instrument::Model {
+            id,
+            exchange_id: 1,
+            symbol: symbol.to_string(),
+            asset_type: AssetType::Stock,
+            name: name.to_string(),
+            base_currency: None,
+            quote_currency: None,
+            tick_size: None,
+            lot_size: None,
+            min_notional: None,
+            contract_multiplier: None,
+            listing_date: None,
+            delisting_date: None,
+            status: InstrumentStatus::Active,
+            metadata: serde_json::json!({}),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            created_by: None,
+            version: 1,
+            name_pinyin: None,
+        };
+        IndexEntry::new(model, &[])
+    }
+
+    #[test]
+    fn test_rank_prefix_substring_fuzzy_and_pinyin() {
+        let entries = [
+            entry(1, "AAPL", "Apple Inc."),
+            entry(2, "600519", "贵州茅台"),
+            entry(3, "BTCUSDT", "BTC/USDT"),
+            entry(4, "ETHBTC", "ETH/BTC"),
+            entry(5, "PINEAPPLE", "Pineapple Corp"),
+        ];
+
+        let hits = rank("apple", &entries, 10);
+        assert_eq!(hits[0].entry.instrument.id, 1);
+        assert_eq!(hits[0].field, MatchField::Name);
+        assert_eq!(hits[1].entry.instrument.id, 5);
+
+        let hits = rank("btc", &entries, 10);
+        assert_eq!(hits.iter().map(|h| h.entry.instrument.id).collect::<Vec<_>>(), vec![3, 4]);
+
+        assert_eq!(rank("茅台", &entries, 10)[0].entry.instrument.id, 2);
+        let hits = rank("gzmt", &entries, 10);
+        assert_eq!((hits[0].entry.instrument.id, hits[0].field), (2, MatchField::Pinyin));
+        assert_eq!(rank("maotai", &entries, 10)[0].entry.instrument.id, 2);
+
+        // 一处拼写错误
+        assert_eq!(rank("aple", &entries, 10)[0].entry.instrument.id, 1);
+        assert_eq!(rank("appel", &entries, 10)[0].entry.instrument.id, 1);
+        assert!(rank("xyz", &entries, 10).is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use std::sync::Arc;
+use sea_orm::ActiveValue::Set;
+use sea_orm::DbErr;
+use crate::core::context::current_actor;
+use crate::db::error::DbError;
+use crate::db::repositories::Repository;
+use crate::db::repositories::exchange::ExchangeRepository;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::instrument_alias::InstrumentAliasRepository;
+use crate::dto::instrument::InstrumentResponse;
+use crate::dto::symbology::{AliasResponse, CreateAliasRequest, MatchKind, ResolveQuery, ResolvedInstrument};
+use crate::error::code::AppError;
+use crate::service::exchange::normalize_code;
+use crate::service::instrument::find_instrument;
+use crate::service::symbology_rules::{
+    normalize_alias_value, normalize_source, validate_alias, SymbologyRegistry,
+};
+use entities::{instrument, instrument_alias};
+use super::APPResult;
+
+/// `source` 取该值时，`symbol` 为规范 ID（`交易所:规范写法`）
+const CANONICAL_SOURCE: &str = "canonical";
+
+pub struct SymbologyService {
+    instrument_repo: Arc<InstrumentRepository>,
+    exchange_repo: Arc<ExchangeRepository>,
+    alias_repo: Arc<InstrumentAliasRepository>,
+    registry: Arc<SymbologyRegistry>,
+}
+
+impl SymbologyService {
+    pub fn new(
+        instrument_repo: Arc<InstrumentRepository>,
+        exchange_repo: Arc<ExchangeRepository>,
+        alias_repo: Arc<InstrumentAliasRepository>,
+        registry: Arc<SymbologyRegistry>,
+    ) -> Self {
+        Self { instrument_repo, exchange_repo, alias_repo, registry }
+    }
+
+    /// 将某个来源下的代码解析为标的
+    ///
+    /// 依次尝试：别名表精确匹配；`source` 为交易所代码时按该交易所的规则规范化后匹配 symbol；
+    /// `source=canonical` 时按规范 ID 匹配。已删除的标的不会被解析到。
+    pub async fn resolve(&self, query: ResolveQuery) -> APPResult<ResolvedInstrument> {
+        let source = normalize_source(&query.source);
+        let not_found = || AppError::NotFound {
+            resource: "Instrument".to_string(),
+            identifier: Some(format!("{source}:{}", query.symbol.trim())),
+        };
+
+        let alias = self.alias_repo
+            .find_by_source_value(&source, &normalize_alias_value(&source, &query.symbol))
+            .await
+            .map_err(AppError::from)?;
+        if let Some(alias) = alias {
+            let model = self.instrument_repo.find_by_id(alias.instrument_id).await
+                .map_err(AppError::from)?
+                .ok_or_else(not_found)?;
+            return self.resolved(model, MatchKind::Alias).await;
+        }
+
+        let (exchange_code, symbol) = if source == CANONICAL_SOURCE {
+            query.symbol.split_once(':').ok_or_else(|| AppError::BadRequest {
+                message: format!("Canonical id must look like EXCHANGE:SYMBOL, got \"{}\"", query.symbol),
+            })?
+        } else {
+            (source.as_str(), query.symbol.as_str())
+        };
+        let Some(exchange) = self.exchange_repo.find_by_code(&normalize_code(exchange_code)).await
+            .map_err(AppError::from)?
+        else {
+            return Err(not_found());
+        };
+        let candidates = self.registry.candidates(&exchange.code, symbol);
+        let model = self.instrument_repo.find_by_exchange_and_symbols(exchange.id, &candidates).await
+            .map_err(AppError::from)?
+            .ok_or_else(not_found)?;
+        self.resolved(model, MatchKind::Exchange).await
+    }
+
+    /// 组装解析结果：规范 ID、原生写法及全部别名
+    async fn resolved(&self, model: instrument::Model, matched_by: MatchKind) -> APPResult<ResolvedInstrument> {
+        let exchange_code = self.exchange_repo.find_by_id(model.exchange_id).await
+            .map_err(AppError::from)?
+            .map(|exchange| exchange.code)
+            .ok_or(AppError::Internal)?;
+        let rule = self.registry.rule(&exchange_code);
+        let native_symbol = rule.canonical(&model.symbol)
+            .map_or_else(|| model.symbol.clone(), |canonical| rule.native(&canonical));
+        let aliases = self.alias_repo.find_by_instrument(model.id).await.map_err(AppError::from)?;
+
+        Ok(ResolvedInstrument {
+            canonical_id: self.registry.canonical_id(&exchange_code, &model.symbol),
+            native_symbol,
+            matched_by,
+            instrument: InstrumentResponse::new(model, exchange_code),
+            aliases: aliases.into_iter().map(AliasResponse::from).collect(),
+        })
+    }
+
+    pub async fn list_aliases(&self, instrument_id: i32) -> APPResult<Vec<AliasResponse>> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let aliases = self.alias_repo.find_by_instrument(instrument_id).await.map_err(AppError::from)?;
+        Ok(aliases.into_iter().map(AliasResponse::from).collect())
+    }
+
+    /// 为标的添加别名
+    /// 业务规则：同一来源下的代码只能指向一个标的，冲突时返回 `Conflict`
+    pub async fn add_alias(&self, instrument_id: i32, req: CreateAliasRequest) -> APPResult<AliasResponse> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let source = normalize_source(&req.source);
+        let value = normalize_alias_value(&source, &req.value);
+        validate_alias(&source, &value)?;
+        let identifier = format!("{source}:{value}");
+
+        let active = instrument_alias::ActiveModel {
+            instrument_id: Set(instrument_id),
+            source: Set(source),
+            value: Set(value),
+            created_by: Set(current_actor()),
+            ..Default::default()
+        };
+        let model = self.alias_repo.create(active).await
+            .map_err(|err| conflict_error(err, identifier))?;
+
+        tracing::info!(instrument_id, alias_id = model.id, "Added instrument alias");
+        Ok(model.into())
+    }
+
+    /// 删除标的的某个别名
+    pub async fn delete_alias(&self, instrument_id: i32, alias_id: i32) -> APPResult<()> {
+        let not_found = AppError::NotFound {
+            resource: "InstrumentAlias".to_string(),
+            identifier: Some(alias_id.to_string()),
+        };
+        let alias = self.alias_repo.find_by_id(alias_id).await.map_err(AppError::from)?;
+        if alias.is_none_or(|alias| alias.instrument_id != instrument_id) {
+            return Err(not_found);
+        }
+        self.alias_repo.delete(alias_id).await.map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+/// 唯一索引冲突转换为 `Conflict`，其余错误按数据库错误处理
+fn conflict_error(err: DbErr, identifier: String) -> AppError {
+    match DbError::from(err) {
+        DbError::UniqueViolation { .. } => AppError::Conflict {
+            resource: "InstrumentAlias".to_string(),
+            identifier,
+        },
+        other => other.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveModelTrait;
+    use crate::db::connection::DbPool;
+    use entities::exchange;
+    use entities::sea_orm_active_enums::{AssetType, InstrumentStatus};
+
+    async fn service() -> SymbologyService {
+        let db = Arc::new(DbPool::in_memory().await);
+        for (id, code) in [(1, "BINANCE"), (2, "KRAKEN"), (3, "SSE")] {
+            exchange::ActiveModel {
+                id: Set(id),
+                code: Set(code.to_string()),
+                name: Set(code.to_string()),
+                timezone: Set("UTC".to_string()),
+                asset_classes: Set(serde_json::json!([])),
+                trading_hours: Set(serde_json::json!([])),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        for (id, exchange_id, symbol, asset_type) in [
+            (1, 1, "BTCUSDT", AssetType::CryptoSpot),
+            (2, 2, "XBT/USD", AssetType::CryptoSpot),
+            (3, 3, "600519", AssetType::Stock),
+        ] {
+            instrument::ActiveModel {
+                id: Set(id),
+                exchange_id: Set(exchange_id),
+                symbol: Set(symbol.to_string()),
+                asset_type: Set(asset_type),
+                name: Set(symbol.to_string()),
+                status: Set(InstrumentStatus::Active),
+                metadata: Set(serde_json::json!({})),
+                ..Default::default()
+            }
+            .insert(&**db)
+            .await
+            .unwrap();
+        }
+        SymbologyService::new(
+            Arc::new(InstrumentRepository::new(db.clone())),
+            Arc::new(ExchangeRepository::new(db.clone())),
+            Arc::new(InstrumentAliasRepository::new(db)),
+            Arc::new(SymbologyRegistry::default()),
+        )
+    }
+
+    async fn resolve(service: &SymbologyService, source: &str, symbol: &str) -> APPResult<ResolvedInstrument> {
+        service.resolve(ResolveQuery { source: source.to_string(), symbol: symbol.to_string() }).await
+    }
+
+    fn alias(source: &str, value: &str) -> CreateAliasRequest {
+        CreateAliasRequest { source: source.to_string(), value: value.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_normalizes_exchange_symbols() {
+        let service = service().await;
+        for symbol in ["BTCUSDT", "BTC-USDT", "btc/usdt"] {
+            let resolved = resolve(&service, "binance", symbol).await.unwrap();
+            assert_eq!(resolved.instrument.id, 1);
+            assert_eq!(resolved.canonical_id, "BINANCE:BTC-USDT");
+            assert!(matches!(resolved.matched_by, MatchKind::Exchange));
+        }
+
+        // Kraken 的 XBT 与通用代码 BTC 等价
+        for symbol in ["XBT/USD", "BTC-USD", "btcusd"] {
+            let resolved = resolve(&service, "KRAKEN", symbol).await.unwrap();
+            assert_eq!(resolved.instrument.id, 2);
+            assert_eq!((resolved.canonical_id.as_str(), resolved.native_symbol.as_str()), ("KRAKEN:BTC-USD", "XBT/USD"));
+        }
+
+        for symbol in ["600519.SH", "SH600519", "600519"] {
+            let resolved = resolve(&service, "sse", symbol).await.unwrap();
+            assert_eq!(resolved.instrument.id, 3);
+            assert_eq!(resolved.canonical_id, "SSE:600519.SH");
+        }
+        assert_eq!(resolve(&service, "canonical", "SSE:600519.SH").await.unwrap().instrument.id, 3);
+
+        assert!(matches!(resolve(&service, "sse", "600519.SZ").await, Err(AppError::NotFound { .. })));
+        assert!(matches!(resolve(&service, "canonical", "600519.SH").await, Err(AppError::BadRequest { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_aliases_resolve_and_stay_unique() {
+        let service = service().await;
+        let created = service.add_alias(3, alias("ISIN", "cne0000018r8")).await.unwrap();
+        assert_eq!((created.source.as_str(), created.value.as_str()), ("isin", "CNE0000018R8"));
+        service.add_alias(1, alias("vendor_x", "XBTUSDT")).await.unwrap();
+
+        let resolved = resolve(&service, "isin", "CNE0000018R8").await.unwrap();
+        assert_eq!(resolved.instrument.id, 3);
+        assert!(matches!(resolved.matched_by, MatchKind::Alias));
+        assert_eq!(resolved.aliases.len(), 1);
+        assert_eq!(resolve(&service, "VENDOR_X", " XBTUSDT ").await.unwrap().instrument.id, 1);
+
+        // 同一来源下的代码只能指向一个标的
+        assert!(matches!(
+            service.add_alias(1, alias("isin", "CNE0000018R8")).await,
+            Err(AppError::Conflict { .. })
+        ));
+        // 校验位错误与非法来源
+        assert!(matches!(service.add_alias(1, alias("isin", "CNE0000018R9")).await, Err(AppError::Validation { .. })));
+        assert!(matches!(service.add_alias(1, alias("my source", "X")).await, Err(AppError::Validation { .. })));
+
+        // 既不是别名来源也不是交易所代码
+        assert!(matches!(resolve(&service, "bloomberg", "IBM US Equity").await, Err(AppError::NotFound { .. })));
+
+        assert!(matches!(service.delete_alias(1, created.id).await, Err(AppError::NotFound { .. })));
+        service.delete_alias(3, created.id).await.unwrap();
+        assert!(matches!(resolve(&service, "isin", "CNE0000018R8").await, Err(AppError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,332 @@
+//! 标的代码的规范化规则
+//!
+//! 每个交易所注册一条 [`SymbolRule`]，负责在交易所原生写法与规范写法之间转换；
+//! 未注册的交易所使用 [`PlainRule`]（去空白转大写）。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use super::APPResult;
+
+/// 交易所代码规则：原生写法 <-> 规范写法
+pub trait SymbolRule: Send + Sync {
+    /// 将任意常见写法解析为规范写法，无法识别时返回 `None`
+    fn canonical(&self, raw: &str) -> Option<String>;
+
+    /// 将规范写法转换为交易所原生写法
+    fn native(&self, canonical: &str) -> String;
+}
+
+/// 默认规则：去空白转大写，原生写法与规范写法相同
+pub struct PlainRule;
+
+impl SymbolRule for PlainRule {
+    fn canonical(&self, raw: &str) -> Option<String> {
+        let symbol = raw.trim().to_uppercase();
+        (!symbol.is_empty()).then_some(symbol)
+    }
+
+    fn native(&self, canonical: &str) -> String {
+        canonical.to_string()
+    }
+}
+
+/// 拆分无分隔符交易对时识别的计价资产，按长度从长到短匹配
+const QUOTE_ASSETS: &[&str] = &[
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USD", "EUR", "GBP", "JPY", "TRY", "DAI", "BTC", "ETH", "BNB",
+];
+
+/// 加密货币交易对：规范写法为 `BASE-QUOTE`，如 `BTC-USDT`
+///
+/// 可识别 `BTCUSDT`、`BTC-USDT`、`BTC/USDT`、`btc_usdt` 等写法，
+/// 交易所特有的资产代码（如 Kraken 的 `XBT`）通过 `with_asset` 映射为通用代码。
+pub struct CryptoPairRule {
+    separator: &'static str,
+    /// (交易所资产代码, 通用资产代码)
+    assets: Vec<(&'static str, &'static str)>,
+}
+
+impl CryptoPairRule {
+    pub fn new(separator: &'static str) -> Self {
+        Self { separator, assets: Vec::new() }
+    }
+
+    pub fn with_asset(mut self, venue: &'static str, common: &'static str) -> Self {
+        self.assets.push((venue, common));
+        self
+    }
+
+    fn to_common<'a>(&self, asset: &'a str) -> &'a str {
+        self.assets.iter().find(|(venue, _)| *venue == asset).map_or(asset, |(_, common)| common)
+    }
+
+    fn to_venue<'a>(&self, asset: &'a str) -> &'a str {
+        self.assets.iter().find(|(_, common)| *common == asset).map_or(asset, |(venue, _)| venue)
+    }
+
+    fn split_quote(symbol: &str) -> Option<(&str, &str)> {
+        let mut quotes = QUOTE_ASSETS.to_vec();
+        quotes.sort_by_key(|q| std::cmp::Reverse(q.len()));
+        quotes
+            .into_iter()
+            .find(|q| symbol.len() > q.len() && symbol.ends_with(q))
+            .map(|q| symbol.split_at(symbol.len() - q.len()))
+    }
+}
+
+impl SymbolRule for CryptoPairRule {
+    fn canonical(&self, raw: &str) -> Option<String> {
+        let symbol = raw.trim().to_uppercase();
+        let parts: Vec<&str> = symbol
+            .split(['-', '/', '_', ':'])
+            .filter(|part| !part.is_empty())
+            .collect();
+        let (base, quote) = match parts.as_slice() {
+            [base, quote] => (*base, *quote),
+            [pair] => Self::split_quote(pair)?,
+            _ => return None,
+        };
+        if !(base.chars().all(|c| c.is_ascii_alphanumeric()) && quote.chars().all(|c| c.is_ascii_alphanumeric())) {
+            return None;
+        }
+        Some(format!("{}-{}", self.to_common(base), self.to_common(quote)))
+    }
+
+    fn native(&self, canonical: &str) -> String {
+        match canonical.split_once('-') {
+            Some((base, quote)) => format!("{}{}{}", self.to_venue(base), self.separator, self.to_venue(quote)),
+            None => canonical.to_string(),
+        }
+    }
+}
+
+/// A 股 / 北交所代码：规范写法为 `600519.SH`，原生写法为 6 位数字
+///
+/// 可识别 `600519`、`600519.SH`、`SH600519`、`sh.600519` 等写法。
+pub struct ChinaEquityRule {
+    suffix: &'static str,
+}
+
+impl ChinaEquityRule {
+    pub fn new(suffix: &'static str) -> Self {
+        Self { suffix }
+    }
+}
+
+impl SymbolRule for ChinaEquityRule {
+    fn canonical(&self, raw: &str) -> Option<String> {
+        let symbol = raw.trim().to_uppercase();
+        let code = symbol
+            .strip_prefix(self.suffix)
+            .map(|rest| rest.trim_start_matches('.'))
+            .or_else(|| symbol.strip_suffix(self.suffix).map(|rest| rest.trim_end_matches('.')))
+            .unwrap_or(&symbol);
+        (code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()))
+            .then(|| format!("{code}.{}", self.suffix))
+    }
+
+    fn native(&self, canonical: &str) -> String {
+        canonical.split('.').next().unwrap_or(canonical).to_string()
+    }
+}
+
+/// 按交易所代码查找规范化规则
+pub struct SymbologyRegistry {
+    rules: HashMap<String, Arc<dyn SymbolRule>>,
+    fallback: Arc<dyn SymbolRule>,
+}
+
+impl SymbologyRegistry {
+    /// 不含任何交易所规则的空注册表
+    pub fn empty() -> Self {
+        Self { rules: HashMap::new(), fallback: Arc::new(PlainRule) }
+    }
+
+    /// 注册（或覆盖）某个交易所的规则，交易所代码大小写不敏感
+    pub fn register(&mut self, exchange_code: &str, rule: impl SymbolRule + 'static) -> &mut Self {
+        self.rules.insert(exchange_code.trim().to_uppercase(), Arc::new(rule));
+        self
+    }
+
+    pub fn rule(&self, exchange_code: &str) -> &dyn SymbolRule {
+        self.rules
+            .get(&exchange_code.trim().to_uppercase())
+            .unwrap_or(&self.fallback)
+            .as_ref()
+    }
+
+    /// 标的的规范 ID：`交易所代码:规范写法`，如 `BINANCE:BTC-USDT`
+    pub fn canonical_id(&self, exchange_code: &str, symbol: &str) -> String {
+        let rule = self.rule(exchange_code);
+        let canonical = rule.canonical(symbol).unwrap_or_else(|| symbol.to_string());
+        format!("{exchange_code}:{canonical}")
+    }
+
+    /// 某个交易所下与 `raw` 等价的候选 symbol（原生写法、规范写法、原样大写），已去重
+    pub fn candidates(&self, exchange_code: &str, raw: &str) -> Vec<String> {
+        let rule = self.rule(exchange_code);
+        let mut candidates: Vec<String> = Vec::new();
+        let canonical = rule.canonical(raw);
+        let native = canonical.as_deref().map(|c| rule.native(c));
+        for candidate in [native, canonical, Some(raw.trim().to_uppercase())].into_iter().flatten() {
+            if !candidate.is_empty() && !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl Default for SymbologyRegistry {
+    /// 内置常见交易所的规则
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("BINANCE", CryptoPairRule::new(""))
+            .register("BYBIT", CryptoPairRule::new(""))
+            .register("OKX", CryptoPairRule::new("-"))
+            .register("COINBASE", CryptoPairRule::new("-"))
+            .register("KRAKEN", CryptoPairRule::new("/").with_asset("XBT", "BTC").with_asset("XDG", "DOGE"))
+            .register("SSE", ChinaEquityRule::new("SH"))
+            .register("SZSE", ChinaEquityRule::new("SZ"))
+            .register("BSE", ChinaEquityRule::new("BJ"));
+        registry
+    }
+}
+
+/// 别名来源统一为去空白的小写形式
+pub fn normalize_source(source: &str) -> String {
+    source.trim().to_lowercase()
+}
+
+/// 标准证券标识符统一为大写，其他来源（供应商代码）仅去空白
+pub fn normalize_alias_value(source: &str, value: &str) -> String {
+    match source {
+        "isin" | "cusip" | "figi" => value.trim().to_uppercase(),
+        _ => value.trim().to_string(),
+    }
+}
+
+/// 校验别名：来源只能包含小写字母、数字和下划线，ISIN / CUSIP / FIGI 需通过格式与校验位检查
+pub fn validate_alias(source: &str, value: &str) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    if source.is_empty() || !source.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        errors.add("source", "validation-alias_source", TranslateArgs::new());
+    }
+    let valid = match source {
+        "isin" => is_isin(value),
+        "cusip" => is_cusip(value),
+        "figi" => is_figi(value),
+        _ => !value.is_empty(),
+    };
+    if !valid {
+        errors.add("value", "validation-identifier", TranslateArgs::new().add("kind", source.to_uppercase()));
+    }
+    errors.into_result()
+}
+
+/// 字母数字字符的数值：0-9 为本身，A-Z 为 10-35
+fn char_value(c: char) -> Option<u32> {
+    c.to_digit(36).filter(|_| c.is_ascii_digit() || c.is_ascii_uppercase())
+}
+
+/// ISIN：2 位国家代码 + 9 位字母数字 + 1 位 Luhn 校验位
+fn is_isin(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if value.len() != 12
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+        || !bytes[11].is_ascii_digit()
+    {
+        return false;
+    }
+    let Some(digits) = value
+        .chars()
+        .map(|c| char_value(c).map(|v| v.to_string()))
+        .collect::<Option<String>>()
+    else {
+        return false;
+    };
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 1 { let d = d * 2; d / 10 + d % 10 } else { d })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// CUSIP 与 FIGI 共用的校验位算法：偶数位乘 2 后逐位求和
+fn modulus_10_check(body: &str, check: char) -> bool {
+    let Some(values) = body.chars().map(char_value).collect::<Option<Vec<_>>>() else {
+        return false;
+    };
+    let sum: u32 = values
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| if i % 2 == 1 { v * 2 } else { v })
+        .map(|v| v / 10 + v % 10)
+        .sum();
+    check.to_digit(10) == Some((10 - sum % 10) % 10)
+}
+
+/// CUSIP：8 位字母数字 + 1 位校验位
+fn is_cusip(value: &str) -> bool {
+    value.len() == 9 && value.is_ascii() && modulus_10_check(&value[..8], value.as_bytes()[8] as char)
+}
+
+/// FIGI：2 位辅音字母 + `G` + 8 位辅音字母或数字 + 1 位校验位，如 `BBG000BLNNH6`
+fn is_figi(value: &str) -> bool {
+    let consonant_or_digit = |c: char| c.is_ascii_digit() || (c.is_ascii_uppercase() && !"AEIOU".contains(c));
+    value.len() == 12
+        && value.is_ascii()
+        && value[..2].chars().all(|c| c.is_ascii_uppercase() && consonant_or_digit(c))
+        && value.as_bytes()[2] == b'G'
+        && value[3..11].chars().all(consonant_or_digit)
+        && modulus_10_check(&value[..11], value.as_bytes()[11] as char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crypto_pair_normalization() {
+        let registry = SymbologyRegistry::default();
+        let binance = registry.rule("binance");
+        assert_eq!(binance.canonical("btcusdt").as_deref(), Some("BTC-USDT"));
+        assert_eq!(binance.canonical("ETH/BTC").as_deref(), Some("ETH-BTC"));
+        assert_eq!(binance.native("BTC-USDT"), "BTCUSDT");
+        assert_eq!(registry.rule("OKX").native("BTC-USDT"), "BTC-USDT");
+
+        let kraken = registry.rule("KRAKEN");
+        assert_eq!(kraken.canonical("XBT/USD").as_deref(), Some("BTC-USD"));
+        assert_eq!(kraken.native("BTC-USD"), "XBT/USD");
+        assert_eq!(registry.candidates("BINANCE", "BTC-USDT"), vec!["BTCUSDT", "BTC-USDT"]);
+    }
+
+    #[test]
+    fn test_china_equity_normalization() {
+        let registry = SymbologyRegistry::default();
+        let sse = registry.rule("SSE");
+        for raw in ["600519", "600519.SH", "SH600519", "sh.600519"] {
+            assert_eq!(sse.canonical(raw).as_deref(), Some("600519.SH"));
+        }
+        assert_eq!(sse.canonical("600519.SZ"), None);
+        assert_eq!(sse.native("600519.SH"), "600519");
+        assert_eq!(registry.canonical_id("NYSE", " ibm "), "NYSE:IBM");
+    }
+
+    #[test]
+    fn test_identifier_checksums() {
+        assert!(is_isin("US0378331005"));
+        assert!(!is_isin("US0378331006"));
+        assert!(is_cusip("037833100"));
+        assert!(!is_cusip("037833101"));
+        assert!(is_figi("BBG000BLNNH6"));
+        assert!(!is_figi("BBG000BLNNH7"));
+        assert!(validate_alias("Bloomberg", "X").is_err());
+        assert!(validate_alias("vendor_ticker", "AAPL US Equity").is_ok());
+    }
+}
@@ -0,0 +1,323 @@
+//! CSV / NDJSON 记录的编解码，供批量导入导出复用
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::dto::import::DataFormat;
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use super::APPResult;
+
+/// 单次导入允许的最大数据行数
+pub const MAX_IMPORT_ROWS: usize = 50_000;
+
+/// 数据行数超过 [`MAX_IMPORT_ROWS`] 时返回记在 `body` 上的字段错误
+pub fn check_row_count(rows: usize) -> APPResult<()> {
+    if rows <= MAX_IMPORT_ROWS {
+        return Ok(());
+    }
+    let mut errors = FieldErrors::new();
+    errors.add("body", "validation-import_rows", TranslateArgs::new().add("max", MAX_IMPORT_ROWS as i64));
+    Err(errors.into())
+}
+
+/// 取回流式读取请求体失败的原因：读取器内含的 `AppError`，其余按请求体无法读取处理
+pub fn read_failure(err: io::Error) -> AppError {
+    match err.into_inner().map(|err| err.downcast::<AppError>()) {
+        Some(Ok(err)) => *err,
+        _ => {
+            let mut errors = FieldErrors::new();
+            errors.add("body", "validation-body", TranslateArgs::new());
+            errors.into()
+        }
+    }
+}
+
+/// 解析得到的一行：`(行号, 反序列化结果)`，失败时为可读的错误描述
+pub type DecodedRow<T> = (u64, Result<T, String>);
+
+/// 流式解析中止的原因
+#[derive(Debug)]
+pub enum DecodeError {
+    /// CSV 表头无法解析
+    Header(String),
+    /// 读取数据失败
+    Read(io::Error),
+}
+
+/// 将整段数据解析为记录，单行失败不影响其他行
+///
+/// CSV 需包含表头，列名与字段名一致，未知列被忽略；NDJSON 跳过空行。
+/// CSV 表头本身无法解析时返回 `Err`。
+pub fn decode_records<T: DeserializeOwned>(format: DataFormat, data: &[u8]) -> Result<Vec<DecodedRow<T>>, String> {
+    let mut rows = Vec::new();
+    decode_reader(format, data, usize::MAX, |batch| {
+        rows.extend(batch);
+        true
+    })
+    .map_err(|err| match err {
+        DecodeError::Header(message) => message,
+        DecodeError::Read(err) => err.to_string(),
+    })?;
+    Ok(rows)
+}
+
+/// 从 `reader` 流式解析记录，规则同 [`decode_records`]
+///
+/// 每解析 `batch` 行交给 `emit` 一次，`emit` 返回 `false` 时停止读取；内存占用与数据总量无关。
+pub fn decode_reader<T, R>(
+    format: DataFormat,
+    reader: R,
+    batch: usize,
+    emit: impl FnMut(Vec<DecodedRow<T>>) -> bool,
+) -> Result<(), DecodeError>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let mut rows = Batcher { rows: Vec::new(), size: batch, emit };
+    match format {
+        DataFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .flexible(true)
+                .from_reader(LineTracker::new(reader));
+            let headers = match reader.headers() {
+                Ok(headers) => headers.clone(),
+                Err(err) if err.is_io_error() => return Err(DecodeError::Read(io_error(err))),
+                Err(err) => return Err(DecodeError::Header(format!("Invalid CSV header: {err}"))),
+            };
+
+            let mut record = csv::StringRecord::new();
+            loop {
+                let start = reader.position().byte();
+                let row = match reader.read_record(&mut record) {
+                    Ok(false) => break,
+                    Ok(true) => {
+                        let start = record.position().map_or(start, |p| p.byte());
+                        let row = record.deserialize(Some(&headers)).map_err(|err| err.to_string());
+                        (reader.get_mut().line_of(start), row)
+                    }
+                    Err(err) if err.is_io_error() => return Err(DecodeError::Read(io_error(err))),
+                    Err(err) => (reader.get_mut().line_of(start), Err(err.to_string())),
+                };
+                if !rows.push(row) {
+                    return Ok(());
+                }
+            }
+        }
+        DataFormat::Ndjson => {
+            let mut reader = BufReader::new(reader);
+            let mut line = Vec::new();
+            let mut index = 0;
+            loop {
+                line.clear();
+                if reader.read_until(b'\n', &mut line).map_err(DecodeError::Read)? == 0 {
+                    break;
+                }
+                index += 1;
+                let line = line.trim_ascii();
+                if line.is_empty() {
+                    continue;
+                }
+                if !rows.push((index, serde_json::from_slice(line).map_err(|err| err.to_string()))) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    rows.finish();
+    Ok(())
+}
+
+/// 按批收集解析结果
+struct Batcher<T, F> {
+    rows: Vec<DecodedRow<T>>,
+    size: usize,
+    emit: F,
+}
+
+impl<T, F: FnMut(Vec<DecodedRow<T>>) -> bool> Batcher<T, F> {
+    /// 追加一行，凑满一批时交出；返回 `false` 表示接收方要求停止
+    fn push(&mut self, row: DecodedRow<T>) -> bool {
+        self.rows.push(row);
+        self.rows.len() < self.size || (self.emit)(std::mem::take(&mut self.rows))
+    }
+
+    fn finish(mut self) {
+        if !self.rows.is_empty() {
+            (self.emit)(self.rows);
+        }
+    }
+}
+
+/// 记录已读取数据中换行符的位置，把 CSV 记录的字节偏移换算为实际行号
+///
+/// csv 的行号不计空行；只保留尚未换算的换行符，占用与预读的缓冲区大小相当。
+struct LineTracker<R> {
+    inner: R,
+    /// 已读取的字节数
+    read: u64,
+    /// 尚未换算的 `\r` / `\n` 的偏移，`true` 为 `\n`
+    breaks: VecDeque<(u64, bool)>,
+    /// 已换算到的偏移及该处的行号
+    counted: (u64, u64),
+}
+
+impl<R> LineTracker<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, read: 0, breaks: VecDeque::new(), counted: (0, 1) }
+    }
+
+    /// 从 `byte` 开始的记录所在的行号（跳过记录前的空行）
+    fn line_of(&mut self, byte: u64) -> u64 {
+        let (from, mut line) = self.counted;
+        let mut start = byte.max(from);
+        while let Some(&(offset, newline)) = self.breaks.front() {
+            if offset > start {
+                break;
+            }
+            if offset == start {
+                start += 1;
+            }
+            if newline {
+                line += 1;
+            }
+            self.breaks.pop_front();
+        }
+        self.counted = (start, line);
+        line
+    }
+}
+
+impl<R: Read> Read for LineTracker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for (i, b) in buf[..n].iter().enumerate() {
+            if *b == b'\n' || *b == b'\r' {
+                self.breaks.push_back((self.read + i as u64, *b == b'\n'));
+            }
+        }
+        self.read += n as u64;
+        Ok(n)
+    }
+}
+
+fn io_error(err: csv::Error) -> io::Error {
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => err,
+        kind => io::Error::other(format!("{kind:?}")),
+    }
+}
+
+/// 将一批记录编码为字节；`header` 不为空时先写入 CSV 表头（NDJSON 忽略）
+pub fn encode_records<T: Serialize>(format: DataFormat, records: &[T], header: Option<&[&str]>) -> Result<Vec<u8>, String> {
+    match format {
+        DataFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+            if let Some(header) = header {
+                writer.write_record(header).map_err(|err| err.to_string())?;
+            }
+            for record in records {
+                writer.serialize(record).map_err(|err| err.to_string())?;
+            }
+            writer.into_inner().map_err(|err| err.to_string())
+        }
+        DataFormat::Ndjson => {
+            let mut buf = Vec::new();
+            for record in records {
+                serde_json::to_writer(&mut buf, record).map_err(|err| err.to_string())?;
+                buf.push(b'\n');
+            }
+            Ok(buf)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::import::json_or_text;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Row {
+        symbol: String,
+        tick_size: Option<f64>,
+        #[serde(default, deserialize_with = "json_or_text")]
+        metadata: Option<serde_json::Value>,
+    }
+
+    #[test]
+    fn test_csv_roundtrip_and_row_errors() {
+        let rows = vec![
+            Row { symbol: "A".into(), tick_size: Some(0.01), metadata: Some(serde_json::json!("{\"k\":1}")) },
+            Row { symbol: "B".into(), tick_size: None, metadata: None },
+        ];
+        let data = encode_records(DataFormat::Csv, &rows, Some(&["symbol", "tick_size", "metadata"])).unwrap();
+        let decoded = decode_records::<Row>(DataFormat::Csv, &data).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].0, 2);
+        assert_eq!(decoded[0].1.as_ref().unwrap().metadata, Some(serde_json::json!({"k": 1})));
+        assert_eq!(decoded[1].1.as_ref().unwrap().tick_size, None);
+
+        let data = b"symbol,tick_size\nA,abc\n\nB,0.5\n";
+        let decoded = decode_records::<Row>(DataFormat::Csv, data).unwrap();
+        assert!(decoded[0].1.is_err());
+        assert_eq!(decoded[1].0, 4);
+
+        let data = b"{\"symbol\":\"A\",\"metadata\":{\"k\":1}}\n\nnot json\n";
+        let decoded = decode_records::<Row>(DataFormat::Ndjson, data).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded[0].1.is_ok());
+        assert_eq!(decoded[1].0, 3);
+        assert!(decoded[1].1.is_err());
+    }
+
+    /// 每次最多返回 3 字节，模拟分块到达的请求体
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(3).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("connection reset"))
+        }
+    }
+
+    #[test]
+    fn test_decode_reader_batches_and_stops() {
+        let data = b"symbol,tick_size\nA,abc\n\nB,0.5\nC,1\n";
+        let mut batches = Vec::new();
+        decode_reader::<Row, _>(DataFormat::Csv, Trickle(data), 2, |batch| {
+            batches.push(batch.iter().map(|(line, _)| *line).collect::<Vec<_>>());
+            true
+        })
+        .unwrap();
+        assert_eq!(batches, vec![vec![2, 4], vec![5]]);
+
+        let mut seen = 0;
+        decode_reader::<Row, _>(DataFormat::Ndjson, Trickle(b"{\"symbol\":\"A\"}\n{\"symbol\":\"B\"}\n"), 1, |_| {
+            seen += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(seen, 1);
+
+        let failing = Trickle(b"symbol,tick_size\nA,1\n").chain(Failing);
+        let result = decode_reader::<Row, _>(DataFormat::Csv, failing, 10, |_| true);
+        assert!(matches!(result, Err(DecodeError::Read(_))));
+    }
+}
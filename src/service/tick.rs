@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, FixedOffset};
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::Serialize;
+use crate::db::repositories::instrument::InstrumentRepository;
+use crate::db::repositories::kline::KlineRepository;
+use crate::db::repositories::order_book::OrderBookRepository;
+use crate::db::repositories::trade::TradeRepository;
+use crate::dto::import::DataFormat;
+use crate::dto::tick::{
+    KlineRebuildReport, OrderBookBatchRequest, OrderBookRecord, TickRangeQuery, TickWriteResponse, TradeBatchRequest,
+    TradeRecord,
+};
+use crate::error::code::AppError;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use crate::service::instrument::find_instrument;
+use crate::service::tabular::encode_records;
+use crate::service::trade_replay::{minute_of, TradeReplayer};
+use entities::{order_book, trade};
+use entities::sea_orm_active_enums::KlineInterval;
+use super::APPResult;
+
+/// 流式读取时每批的条数
+const STREAM_BATCH: u64 = 10_000;
+/// 单次重建的最大跨度（天）
+const MAX_REBUILD_DAYS: i64 = 31;
+
+pub struct TickService {
+    trade_repo: Arc<TradeRepository>,
+    order_book_repo: Arc<OrderBookRepository>,
+    kline_repo: Arc<KlineRepository>,
+    instrument_repo: Arc<InstrumentRepository>,
+}
+
+impl TickService {
+    pub fn new(
+        trade_repo: Arc<TradeRepository>,
+        order_book_repo: Arc<OrderBookRepository>,
+        kline_repo: Arc<KlineRepository>,
+        instrument_repo: Arc<InstrumentRepository>,
+    ) -> Self {
+        Self { trade_repo, order_book_repo, kline_repo, instrument_repo }
+    }
+
+    /// 批量写入成交，重复的成交被跳过，因此可以安全地重复提交同一批数据
+    pub async fn write_trades(&self, instrument_id: i32, request: TradeBatchRequest) -> APPResult<TickWriteResponse> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let received = request.trades.len();
+        let trades = request.trades
+            .into_iter()
+            .map(|input| trade::Model {
+                instrument_id,
+                ts: input.ts,
+                trade_id: input.trade_id,
+                price: input.price,
+                qty: input.qty,
+                side: input.side,
+            })
+            .collect();
+        let inserted = self.trade_repo.insert_batch(trades).await.map_err(AppError::from)?;
+        Ok(TickWriteResponse { received, inserted })
+    }
+
+    /// 批量写入盘口快照和增量，重复的记录被跳过
+    pub async fn write_order_book(
+        &self,
+        instrument_id: i32,
+        request: OrderBookBatchRequest,
+    ) -> APPResult<TickWriteResponse> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        let received = request.updates.len();
+        let updates = request.updates
+            .into_iter()
+            .map(|input| order_book::Model {
+                instrument_id,
+                ts: input.ts,
+                sequence: input.sequence,
+                kind: input.kind,
+                bids: serde_json::json!(input.bids),
+                asks: serde_json::json!(input.asks),
+            })
+            .collect();
+        let inserted = self.order_book_repo.insert_batch(updates).await.map_err(AppError::from)?;
+        Ok(TickWriteResponse { received, inserted })
+    }
+
+    /// 按成交顺序流式导出区间内的成交（NDJSON）
+    pub async fn export_trades(
+        &self,
+        instrument_id: i32,
+        query: TickRangeQuery,
+    ) -> APPResult<impl Stream<Item = APPResult<Vec<u8>>> + Send + 'static> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        validate_range(&query, None)?;
+        let stream = self.trade_repo.stream_range(instrument_id, query.start, query.end, STREAM_BATCH);
+        Ok(stream.map(|batch| encode_batch(batch?.into_iter().map(TradeRecord::from).collect())))
+    }
+
+    /// 按时间和序号流式导出区间内的盘口记录（NDJSON）
+    pub async fn export_order_book(
+        &self,
+        instrument_id: i32,
+        query: TickRangeQuery,
+    ) -> APPResult<impl Stream<Item = APPResult<Vec<u8>>> + Send + 'static> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        validate_range(&query, None)?;
+        let stream = self.order_book_repo.stream_range(instrument_id, query.start, query.end, STREAM_BATCH);
+        Ok(stream.map(|batch| encode_batch(batch?.into_iter().map(OrderBookRecord::from).collect())))
+    }
+
+    /// 由逐笔成交重建区间内的 1m K 线
+    ///
+    /// 区间向外对齐到整分钟，区间内原有的 1m K 线整体替换为回放结果，因此结果只取决于库中的成交。
+    /// 替换在同一事务内完成，中途失败时保留原有的 K 线。
+    pub async fn rebuild_klines(&self, instrument_id: i32, query: TickRangeQuery) -> APPResult<KlineRebuildReport> {
+        find_instrument(&self.instrument_repo, instrument_id).await?;
+        validate_range(&query, Some(MAX_REBUILD_DAYS))?;
+        let start = align_minute(query.start, false);
+        let end = align_minute(query.end, true);
+
+        // 先回放成交得到全部 1m K 线（跨度受 MAX_REBUILD_DAYS 限制，最多约 4.5 万根），
+        // 再在同一事务内删除旧 K 线并写入，失败时不会留下被删空的区间
+        let mut replayer = TradeReplayer::default();
+        let mut rebuilt = Vec::new();
+        let mut trades = 0;
+        let mut stream = std::pin::pin!(self.trade_repo.stream_range(instrument_id, start, end, STREAM_BATCH));
+        while let Some(batch) = stream.try_next().await.map_err(AppError::from)? {
+            trades += batch.len() as u64;
+            rebuilt.extend(batch.iter().filter_map(|trade| replayer.push(trade)));
+        }
+        rebuilt.extend(replayer.finish());
+        let (replaced, bars) = self.kline_repo
+            .replace_range(instrument_id, KlineInterval::Minute1, start, end, rebuilt)
+            .await
+            .map_err(AppError::from)?;
+
+        tracing::info!(instrument_id, trades, replaced, bars, "Rebuilt 1m klines from trades");
+        Ok(KlineRebuildReport { instrument_id, start, end, trades, replaced, bars })
+    }
+
+}
+
+/// 校验 `end` 晚于 `start`，`max_days` 不为空时限制跨度
+fn validate_range(query: &TickRangeQuery, max_days: Option<i64>) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    if query.end <= query.start {
+        errors.add("end", "validation-date_order", TranslateArgs::new().add("other", "start"));
+    } else if let Some(max_days) = max_days.filter(|&days| query.end - query.start > Duration::days(days)) {
+        errors.add("end", "validation-date_span", TranslateArgs::new().add("max", max_days));
+    }
+    errors.into_result()
+}
+
+/// 对齐到所在分钟的开始，`ceil` 时不在整分钟上的时间对齐到下一分钟
+fn align_minute(ts: DateTime<FixedOffset>, ceil: bool) -> DateTime<FixedOffset> {
+    let minute = minute_of(ts.to_utc());
+    let minute = if ceil && minute < ts.to_utc() { minute + Duration::minutes(1) } else { minute };
+    minute.with_timezone(ts.offset())
+}
+
+fn encode_batch<T: Serialize>(records: Vec<T>) -> APPResult<Vec<u8>> {
+    encode_records(DataFormat::Ndjson, &records, None).map_err(|message| {
+        tracing::error!(%message, "Failed to encode exported ticks");
+        AppError::Internal
+    })
+}
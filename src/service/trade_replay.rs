@@ -0,0 +1,95 @@
+//! 逐笔成交回放为 1m K 线
+//!
+//! 输入须按 (ts, trade_id) 升序（即 `TradeRepository::stream_range` 的顺序），同样的成交总是得到同样的 K 线：
+//! 开盘 / 收盘为分钟内第一笔 / 最后一笔的价格，成交量为数量之和，成交额为价格 × 数量之和。
+//! 分钟按 UTC 对齐，没有成交的分钟不生成 K 线。
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use entities::{kline, trade};
+use entities::sea_orm_active_enums::KlineInterval;
+
+/// 成交所属分钟的开始时间
+pub fn minute_of(ts: DateTime<Utc>) -> DateTime<Utc> {
+    ts.duration_trunc(TimeDelta::minutes(1)).unwrap_or(ts)
+}
+
+/// 流式回放器：逐笔输入，跨入新的分钟时输出上一分钟的 K 线
+#[derive(Default)]
+pub struct TradeReplayer {
+    current: Option<kline::Model>,
+}
+
+impl TradeReplayer {
+    pub fn push(&mut self, trade: &trade::Model) -> Option<kline::Model> {
+        let minute = minute_of(trade.ts.to_utc()).fixed_offset();
+        let notional = trade.price * trade.qty;
+        match &mut self.current {
+            Some(bar) if bar.instrument_id == trade.instrument_id && bar.ts == minute => {
+                bar.high = bar.high.max(trade.price);
+                bar.low = bar.low.min(trade.price);
+                bar.close = trade.price;
+                bar.volume += trade.qty;
+                bar.quote_volume = bar.quote_volume.map(|value| value + notional);
+                bar.trade_count = bar.trade_count.map(|count| count + 1);
+                None
+            }
+            current => current.replace(kline::Model {
+                instrument_id: trade.instrument_id,
+                interval: KlineInterval::Minute1,
+                ts: minute,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: trade.qty,
+                quote_volume: Some(notional),
+                trade_count: Some(1),
+            }),
+        }
+    }
+
+    /// 输出最后一分钟的 K 线
+    pub fn finish(self) -> Option<kline::Model> {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entities::sea_orm_active_enums::TradeSide;
+
+    fn trade(ts: &str, trade_id: i64, price: f64, qty: f64) -> trade::Model {
+        trade::Model { instrument_id: 1, ts: ts.parse().unwrap(), trade_id, price, qty, side: Some(TradeSide::Buy) }
+    }
+
+    fn replay(trades: &[trade::Model]) -> Vec<kline::Model> {
+        let mut replayer = TradeReplayer::default();
+        let mut bars: Vec<_> = trades.iter().filter_map(|trade| replayer.push(trade)).collect();
+        bars.extend(replayer.finish());
+        bars
+    }
+
+    #[test]
+    fn test_replay_trades_into_minutes() {
+        let trades = [
+            trade("2026-10-19T09:30:00.250+08:00", 7, 10.0, 2.0),
+            trade("2026-10-19T01:30:00.250Z", 8, 12.0, 1.0),
+            trade("2026-10-19T01:30:59.999Z", 9, 9.0, 1.0),
+            // 01:31 没有成交
+            trade("2026-10-19T01:32:00Z", 10, 11.0, 4.0),
+        ];
+        let bars = replay(&trades);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].ts, "2026-10-19T01:30:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(
+            (bars[0].open, bars[0].high, bars[0].low, bars[0].close, bars[0].volume),
+            (10.0, 12.0, 9.0, 9.0, 4.0)
+        );
+        assert_eq!((bars[0].quote_volume, bars[0].trade_count), (Some(41.0), Some(3)));
+        assert_eq!((bars[1].open, bars[1].close, bars[1].trade_count), (11.0, 11.0, Some(1)));
+
+        // 同样的输入得到同样的结果
+        assert_eq!(replay(&trades), bars);
+    }
+}
@@ -0,0 +1,394 @@
+//! 交易日历：按交易所的时区、常规交易时段及日历文件（交易日、休市日、特殊交易日）计算交易时段
+//!
+//! 时段以交易所本地时间描述，换算为 UTC 时按时区规则处理夏令时；`24:00` 表示当天结束，
+//! 全天交易（如加密货币）配置为每天 `00:00`-`24:00`。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use crate::dto::exchange::TradingSession;
+use crate::error::validation::FieldErrors;
+use crate::i18n::TranslateArgs;
+use super::APPResult;
+
+/// 向后查找下一个交易时段的最大天数
+const MAX_LOOKAHEAD_DAYS: u64 = 366 * 2;
+
+/// 日历文件（`configs/calendars/<交易所代码>.toml`）
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CalendarFile {
+    /// 交易日（星期），如 `["mon", "tue"]`，默认周一至周五
+    pub weekdays: Option<Vec<String>>,
+    /// 常规交易时段，交易所记录中未配置 `trading_hours` 时使用
+    #[serde(default)]
+    pub sessions: Vec<TradingSession>,
+    /// 已收录休市安排的年份；未配置时不限年份，只适用于没有休市日的日历（如 7x24 交易）
+    pub years: Option<Vec<i32>>,
+    /// 休市日
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    /// 特殊交易日（如半天交易），当天使用这里的时段替代常规时段
+    #[serde(default)]
+    pub special_days: Vec<SpecialDay>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpecialDay {
+    pub date: NaiveDate,
+    pub sessions: Vec<TradingSession>,
+}
+
+/// 一个交易时段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Session {
+    /// 所属交易日（交易所本地日期）
+    pub date: NaiveDate,
+    pub open: DateTime<Utc>,
+    pub close: DateTime<Utc>,
+}
+
+/// 本地时段：距当天 0 点的分钟数，`close` 最大为 1440（24:00）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LocalSession {
+    open: u32,
+    close: u32,
+}
+
+/// 解析 `HH:MM`，允许 `24:00`
+fn parse_minutes(value: &str) -> Option<u32> {
+    let (hour, minute) = value.split_once(':')?;
+    if hour.len() != 2 || minute.len() != 2 {
+        return None;
+    }
+    let (hour, minute): (u32, u32) = (hour.parse().ok()?, minute.parse().ok()?);
+    let minutes = hour * 60 + minute;
+    (minute < 60 && minutes <= 24 * 60).then_some(minutes)
+}
+
+fn parse_sessions(sessions: &[TradingSession]) -> Result<Vec<LocalSession>, String> {
+    let mut parsed = sessions
+        .iter()
+        .map(|session| match (parse_minutes(&session.open), parse_minutes(&session.close)) {
+            (Some(open), Some(close)) if open < close => Ok(LocalSession { open, close }),
+            _ => Err(format!("invalid session {}-{}", session.open, session.close)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    parsed.sort_by_key(|session| session.open);
+    if parsed.windows(2).any(|pair| pair[0].close > pair[1].open) {
+        return Err("sessions overlap".to_string());
+    }
+    Ok(parsed)
+}
+
+/// 校验交易所的时区与交易时段，供创建 / 更新交易所时使用
+pub fn validate_exchange_schedule(timezone: Option<&str>, sessions: Option<&[TradingSession]>) -> APPResult<()> {
+    let mut errors = FieldErrors::new();
+    if let Some(timezone) = timezone
+        && Tz::from_str(timezone).is_err()
+    {
+        errors.add("timezone", "validation-timezone", TranslateArgs::new().add("value", timezone));
+    }
+    if let Some(sessions) = sessions
+        && parse_sessions(sessions).is_err()
+    {
+        errors.add("trading_hours", "validation-trading_hours", TranslateArgs::new());
+    }
+    errors.into_result()
+}
+
+/// 某个交易所的交易日历
+#[derive(Debug, Clone)]
+pub struct TradingCalendar {
+    tz: Tz,
+    weekdays: Vec<Weekday>,
+    sessions: Vec<LocalSession>,
+    holidays: BTreeSet<NaiveDate>,
+    special_days: BTreeMap<NaiveDate, Vec<LocalSession>>,
+    /// 已收录休市安排的年份，`None` 表示不限
+    years: Option<BTreeSet<i32>>,
+}
+
+impl TradingCalendar {
+    /// 由交易所的时区、常规时段及可选的日历文件构建；常规时段为空时使用日历文件中的时段
+    pub fn new(timezone: &str, trading_hours: &[TradingSession], file: Option<&CalendarFile>) -> Result<Self, String> {
+        let tz = Tz::from_str(timezone).map_err(|_| format!("unknown timezone {timezone}"))?;
+        let file = file.cloned().unwrap_or_default();
+        let weekdays = match &file.weekdays {
+            Some(days) => days
+                .iter()
+                .map(|day| Weekday::from_str(day).map_err(|_| format!("invalid weekday {day}")))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+        };
+        let sessions = if trading_hours.is_empty() { &file.sessions } else { trading_hours };
+        let special_days = file
+            .special_days
+            .iter()
+            .map(|day| parse_sessions(&day.sessions).map(|sessions| (day.date, sessions)))
+            .collect::<Result<_, _>>()?;
+        let years: Option<BTreeSet<i32>> = file.years.map(|years| years.into_iter().collect());
+        // 休市日与特殊交易日须落在已收录的年份内，避免漏配 `years` 导致覆盖范围失真
+        let mut dates = file.holidays.iter().chain(file.special_days.iter().map(|day| &day.date));
+        if let Some(date) = dates.find(|date| !years.as_ref().is_some_and(|years| years.contains(&date.year()))) {
+            return Err(format!("{date} is outside the covered `years`"));
+        }
+
+        Ok(Self {
+            tz,
+            weekdays,
+            sessions: parse_sessions(sessions)?,
+            holidays: file.holidays.into_iter().collect(),
+            special_days,
+            years,
+        })
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.tz
+    }
+
+    /// 已收录休市安排的年份，不限年份时为 `None`
+    pub fn covered_years(&self) -> Option<Vec<i32>> {
+        self.years.as_ref().map(|years| years.iter().copied().collect())
+    }
+
+    /// `[from, to]` 内的每一年是否都已收录休市安排；未收录的年份会把休市日当作交易日
+    pub fn covers(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        self.years
+            .as_ref()
+            .is_none_or(|years| (from.year()..=to.year()).all(|year| years.contains(&year)))
+    }
+
+    /// 本地日期当天的时段（休市日为空）
+    fn local_sessions(&self, date: NaiveDate) -> &[LocalSession] {
+        if self.holidays.contains(&date) {
+            return &[];
+        }
+        if let Some(sessions) = self.special_days.get(&date) {
+            return sessions;
+        }
+        if self.weekdays.contains(&date.weekday()) {
+            &self.sessions
+        } else {
+            &[]
+        }
+    }
+
+    /// 本地时间换算为 UTC：夏令时重叠取较早者，跳过的时刻顺延一小时
+    fn to_utc(&self, date: NaiveDate, minutes: u32) -> DateTime<Utc> {
+        let local = date.and_time(NaiveTime::MIN) + TimeDelta::minutes(minutes.into());
+        self.tz
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| self.tz.from_local_datetime(&(local + TimeDelta::hours(1))).earliest())
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+    }
+
+    /// 某个交易日的全部时段
+    pub fn sessions_on(&self, date: NaiveDate) -> Vec<Session> {
+        self.local_sessions(date)
+            .iter()
+            .map(|session| Session {
+                date,
+                open: self.to_utc(date, session.open),
+                close: self.to_utc(date, session.close),
+            })
+            .collect()
+    }
+
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !self.local_sessions(date).is_empty()
+    }
+
+    /// `ts` 对应的交易所本地日期
+    pub fn local_date(&self, ts: DateTime<Utc>) -> NaiveDate {
+        ts.with_timezone(&self.tz).date_naive()
+    }
+
+    /// 本地日期 0 点对应的 UTC 时刻
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        self.to_utc(date, 0)
+    }
+
+    /// 全部时段（含特殊交易日）的开盘、收盘是否都落在从 0 点起、每 `minutes` 分钟一格的网格上
+    pub fn aligned_to(&self, minutes: u32) -> bool {
+        self.special_days
+            .values()
+            .flatten()
+            .chain(&self.sessions)
+            .all(|session| session.open % minutes == 0 && session.close % minutes == 0)
+    }
+
+    /// `ts` 所在的时段（左闭右开）
+    pub fn session_at(&self, ts: DateTime<Utc>) -> Option<Session> {
+        let date = self.local_date(ts);
+        // 时段可能因夏令时换算落在相邻日期，前后各多看一天
+        [date.pred_opt(), Some(date), date.succ_opt()]
+            .into_iter()
+            .flatten()
+            .flat_map(|date| self.sessions_on(date))
+            .find(|session| session.open <= ts && ts < session.close)
+    }
+
+    pub fn is_open(&self, ts: DateTime<Utc>) -> bool {
+        self.session_at(ts).is_some()
+    }
+
+    /// `ts` 之后（含）的连续交易区间：首尾相接的时段（如 7x24 交易的相邻两天）合并为一个区间
+    fn periods_from(&self, ts: DateTime<Utc>) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> + '_ {
+        let start = self.local_date(ts).pred_opt().unwrap_or(NaiveDate::MIN);
+        let mut sessions = (0..MAX_LOOKAHEAD_DAYS)
+            .filter_map(move |offset| start.checked_add_days(Days::new(offset)))
+            .flat_map(|date| self.sessions_on(date))
+            .filter(move |session| session.close > ts)
+            .peekable();
+        std::iter::from_fn(move || {
+            let first = sessions.next()?;
+            let mut close = first.close;
+            while let Some(next) = sessions.next_if(|next| next.open <= close) {
+                close = close.max(next.close);
+            }
+            Some((first.open, close))
+        })
+    }
+
+    /// `ts` 之后的下一次开盘（不含当前所在区间）
+    pub fn next_open(&self, ts: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.periods_from(ts).map(|(open, _)| open).find(|open| *open > ts)
+    }
+
+    /// `ts` 之后的下一次收盘；当前处于交易中时为当前区间的收盘，查找范围内不收盘时为空
+    pub fn next_close(&self, ts: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.periods_from(ts)
+            .map(|(_, close)| close)
+            .next()
+            .filter(|close| self.local_date(*close) < self.lookahead_end(ts))
+    }
+
+    /// 查找范围的结束日期（不含）
+    fn lookahead_end(&self, ts: DateTime<Utc>) -> NaiveDate {
+        let start = self.local_date(ts).pred_opt().unwrap_or(NaiveDate::MIN);
+        start.checked_add_days(Days::new(MAX_LOOKAHEAD_DAYS - 1)).unwrap_or(NaiveDate::MAX)
+    }
+
+    /// 与 `[from, to)` 有交集的全部时段
+    pub fn sessions_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Session> {
+        let (first, last) = (self.local_date(from).pred_opt(), self.local_date(to).succ_opt());
+        let (Some(first), Some(last)) = (first, last) else {
+            return Vec::new();
+        };
+        first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .flat_map(|date| self.sessions_on(date))
+            .filter(|session| session.open < to && session.close > from)
+            .collect()
+    }
+
+    /// `[from, to]` 内的交易日
+    pub fn trading_days(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .filter(|date| self.is_trading_day(*date))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(open: &str, close: &str) -> TradingSession {
+        TradingSession { open: open.to_string(), close: close.to_string() }
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_lunch_break_holidays_and_special_days() {
+        let file = CalendarFile {
+            years: Some(vec![2026]),
+            holidays: vec![NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()],
+            special_days: vec![SpecialDay {
+                date: NaiveDate::from_ymd_opt(2026, 10, 10).unwrap(),
+                sessions: vec![session("09:30", "11:30")],
+            }],
+            ..Default::default()
+        };
+        let hours = [session("13:00", "15:00"), session("09:30", "11:30")];
+        let calendar = TradingCalendar::new("Asia/Shanghai", &hours, Some(&file)).unwrap();
+
+        // 2026-09-30 周三：10:00 开盘，12:00 午休
+        assert!(calendar.is_open(utc("2026-09-30T02:00:00Z")));
+        assert!(!calendar.is_open(utc("2026-09-30T04:00:00Z")));
+        assert_eq!(calendar.next_open(utc("2026-09-30T04:00:00Z")), Some(utc("2026-09-30T05:00:00Z")));
+        assert_eq!(calendar.next_close(utc("2026-09-30T02:00:00Z")), Some(utc("2026-09-30T03:30:00Z")));
+        // 收盘后的下一次开盘跳过休市日 10-01 及周末，落在 10-02（周五）
+        assert_eq!(calendar.next_open(utc("2026-09-30T08:00:00Z")), Some(utc("2026-10-02T01:30:00Z")));
+
+        let days = calendar.trading_days(
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 10).unwrap(),
+        );
+        assert_eq!(days.len(), 8);
+        // 10-10 为周六，按特殊交易日只交易上午
+        assert_eq!(calendar.sessions_on(NaiveDate::from_ymd_opt(2026, 10, 10).unwrap()).len(), 1);
+        assert_eq!(
+            calendar.sessions_between(utc("2026-09-30T03:00:00Z"), utc("2026-09-30T06:00:00Z")).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_covered_years() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let file = CalendarFile {
+            years: Some(vec![2025, 2026]),
+            holidays: vec![date(2026, 10, 1)],
+            ..Default::default()
+        };
+        let calendar = TradingCalendar::new("Asia/Shanghai", &[session("09:30", "15:00")], Some(&file)).unwrap();
+        assert_eq!(calendar.covered_years(), Some(vec![2025, 2026]));
+        assert!(calendar.covers(date(2025, 1, 1), date(2026, 12, 31)));
+        assert!(!calendar.covers(date(2024, 12, 31), date(2025, 1, 2)));
+        assert!(!calendar.covers(date(2026, 12, 31), date(2027, 1, 1)));
+
+        // 休市日不在 years 内或缺少 years 均视为配置错误
+        let file = CalendarFile { years: Some(vec![2025]), ..file };
+        assert!(TradingCalendar::new("UTC", &[], Some(&file)).is_err());
+        let file = CalendarFile { years: None, ..file };
+        assert!(TradingCalendar::new("UTC", &[], Some(&file)).is_err());
+
+        // 未配置休市日的日历不限年份
+        let calendar = TradingCalendar::new("UTC", &[session("00:00", "24:00")], None).unwrap();
+        assert_eq!(calendar.covered_years(), None);
+        assert!(calendar.covers(date(2000, 1, 1), date(2100, 1, 1)));
+    }
+
+    #[test]
+    fn test_dst_and_continuous_trading() {
+        let calendar = TradingCalendar::new("America/New_York", &[session("09:30", "16:00")], None).unwrap();
+        // 夏令时（UTC-4）与冬令时（UTC-5）下的开盘时间
+        assert_eq!(calendar.sessions_on(NaiveDate::from_ymd_opt(2026, 7, 1).unwrap())[0].open, utc("2026-07-01T13:30:00Z"));
+        assert_eq!(calendar.sessions_on(NaiveDate::from_ymd_opt(2026, 12, 1).unwrap())[0].open, utc("2026-12-01T14:30:00Z"));
+
+        let file = CalendarFile {
+            weekdays: Some(["mon", "tue", "wed", "thu", "fri", "sat", "sun"].map(String::from).to_vec()),
+            sessions: vec![session("00:00", "24:00")],
+            ..Default::default()
+        };
+        let crypto = TradingCalendar::new("UTC", &[], Some(&file)).unwrap();
+        assert!(crypto.is_open(utc("2026-10-17T23:59:59Z")));
+        // 相邻两天的时段首尾相接，视为连续交易，不会收盘
+        assert_eq!(crypto.next_close(utc("2026-10-17T12:00:00Z")), None);
+        assert_eq!(crypto.next_open(utc("2026-10-17T12:00:00Z")), None);
+
+        assert!(TradingCalendar::new("Mars/Olympus", &[], None).is_err());
+        assert!(TradingCalendar::new("UTC", &[session("10:00", "09:00")], None).is_err());
+    }
+}
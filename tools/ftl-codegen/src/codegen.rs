@@ -141,6 +141,11 @@ fn generate_app_error(codes: &CodesYaml, output_file: &Path) -> anyhow::Result<(
                 for (arg_name, arg_def) in &entry.args {
                     let ty = match arg_def.arg_type.as_str() {
                         "string" => "String",
+                        "int" | "i32" => "i32",
+                        "i64" => "i64",
+                        "u32" => "u32",
+                        "u64" => "u64",
+                        "usize" => "usize",
                         "map" => "HashMap<String, String>",
                         "error" => "anyhow::Error",
                         _ => "String",
@@ -198,7 +203,7 @@ fn generate_app_error(codes: &CodesYaml, output_file: &Path) -> anyhow::Result<(
     desc_fn.vis("pub").arg_ref_self().ret("&'static str").line("match self {");
     for (_cat_name, items) in &codes.categories {
         for (key, entry) in items {
-            desc_fn.line(&format!("    Self::{} {{ .. }} => \"{}\",", to_pascal_case(key), entry.description));
+            desc_fn.line(&format!("    Self::{} {{ .. }} => {:?},", to_pascal_case(key), entry.description));
         }
     }
     desc_fn.line("}");
@@ -241,7 +246,7 @@ fn generate_app_error(codes: &CodesYaml, output_file: &Path) -> anyhow::Result<(
                 // 根据类型生成 to_string 表达式
                 let value_expr = match ty {
                     "string" | "str" => "v.to_string()",
-                    "int" | "i32" | "i64" | "usize" => "v.to_string()",
+                    "int" | "i32" | "i64" | "u32" | "u64" | "usize" => "v.to_string()",
                     "float" | "f32" | "f64" => "v.to_string()",
                     "bool" => "v.to_string()",
                     _ => "format!(\"{:?}\", v)",
@@ -271,6 +276,20 @@ fn generate_app_error(codes: &CodesYaml, output_file: &Path) -> anyhow::Result<(
 
     scope.push_impl(impl_block);
 
+    // ---------- 4️⃣ 返回码清单（供 OpenAPI 文档等使用） ----------
+    scope.raw("/// 返回码元信息\n#[derive(Debug, Clone, Copy)]\npub struct CodeInfo {\n    pub code: i32,\n    pub name: &'static str,\n    pub key: &'static str,\n    pub description: &'static str,\n    pub http_status: u16,\n}");
+    let mut all_codes = String::from("/// codes.yaml 中定义的全部返回码\npub const ALL_CODES: &[CodeInfo] = &[\n");
+    for (cat_name, items) in &codes.categories {
+        for (key, entry) in items {
+            // `{:?}` 输出带转义的字符串字面量，描述中的引号和反斜杠不会破坏生成的代码
+            all_codes.push_str(&format!(
+                "    CodeInfo {{ code: {}, name: \"{}\", key: \"{}-{}\", description: {:?}, http_status: {} }},\n",
+                entry.code, to_pascal_case(key), cat_name, key, entry.description, entry.http_status
+            ));
+        }
+    }
+    all_codes.push_str("];");
+    scope.raw(&all_codes);
 
     // ------------------------------
     // 3️⃣ 写文件
@@ -6,7 +6,7 @@ use std::collections::{HashMap, HashSet};
 use walkdir::WalkDir;
 
 pub fn parse_ftl_keys(dir: &str) -> Result<HashMap<String, HashSet<String>>> {
-    let mut lang_keys = HashMap::new();
+    let mut lang_keys: HashMap<String, HashSet<String>> = HashMap::new();
 
     for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
         let path = entry.path();
@@ -27,7 +27,8 @@ pub fn parse_ftl_keys(dir: &str) -> Result<HashMap<String, HashSet<String>>> {
                     keys.insert(msg.id.name.to_string());
                 }
             }
-            lang_keys.insert(lang, keys);
+            // 同一语言可能分布在多个目录（如 locales/validation/<lang>.ftl），合并其 key
+            lang_keys.entry(lang).or_default().extend(keys);
         }
     }
 